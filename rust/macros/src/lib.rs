@@ -0,0 +1,377 @@
+/// lib.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Data, DeriveInput, Error, Fields, GenericParam, Ident, Lit, Token, parse_macro_input};
+use std::f64::consts::{FRAC_1_SQRT_2, PI};
+
+/// Designs a biquad at compile time and expands to a `Coefficients<f64>` literal, which can
+/// initialize a `const` so no design work happens at runtime:
+///
+/// ```ignore
+/// use biquad_filters::{Coefficients, biquad};
+///
+/// const LOW_PASS: Coefficients<f64> = biquad!(lowpass, 1000.0 Hz, fs = 48000, q = 0.707);
+/// const PRESENCE: Coefficients<f64> = biquad!(peaking, 3.5 kHz, fs = 48000, q = 1.2, gain = -3.0 dB);
+/// ```
+///
+/// The filter type is one of `lowpass`, `highpass`, `bandpass`, `notch`, `allpass`, `peaking`,
+/// `lowshelf` and `highshelf`, followed by the cutoff in `Hz` or `kHz` and the sample rate. `q`
+/// defaults to 0.707 and `gain`, in dB, to zero. The coefficients are exactly those the matching
+/// filter type computes at runtime, before normalization.
+#[proc_macro]
+pub fn biquad(input: TokenStream) -> TokenStream {
+    let design = parse_macro_input!(input as Design);
+    match design.coefficients() {
+        Ok([b0, b1, b2, a0, a1, a2]) => quote! {
+            ::biquad_filters::Coefficients::<f64> {
+                b0: #b0,
+                b1: #b1,
+                b2: #b2,
+                a0: #a0,
+                a1: #a1,
+                a2: #a2,
+            }
+        }
+        .into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// The filter types accepted by `biquad!`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    LowPass,
+    HighPass,
+    BandPass,
+    Notch,
+    AllPass,
+    Peaking,
+    LowShelf,
+    HighShelf,
+}
+
+/// A parsed `biquad!` invocation.
+struct Design {
+    kind: Kind,
+    cutoff: f64,
+    sample_rate: f64,
+    q_factor: f64,
+    gain: f64,
+    span: Span,
+}
+
+impl Parse for Design {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let span = input.span();
+        let kind_ident: Ident = input.parse()?;
+        let kind = match kind_ident.to_string().as_str() {
+            "lowpass" => Kind::LowPass,
+            "highpass" => Kind::HighPass,
+            "bandpass" => Kind::BandPass,
+            "notch" => Kind::Notch,
+            "allpass" => Kind::AllPass,
+            "peaking" => Kind::Peaking,
+            "lowshelf" => Kind::LowShelf,
+            "highshelf" => Kind::HighShelf,
+            _ => {
+                return Err(Error::new(
+                    kind_ident.span(),
+                    "expected one of lowpass, highpass, bandpass, notch, allpass, peaking, \
+                     lowshelf or highshelf",
+                ));
+            }
+        };
+        input.parse::<Token![,]>()?;
+        let mut cutoff = parse_number(input)?;
+        let unit: Ident = input.parse()?;
+        match unit.to_string().as_str() {
+            "Hz" => {}
+            "kHz" => cutoff *= 1000.0,
+            _ => return Err(Error::new(unit.span(), "expected Hz or kHz")),
+        }
+
+        let mut sample_rate = None;
+        let mut q_factor = None;
+        let mut gain = None;
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let name: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let value = parse_number(input)?;
+            let slot = match name.to_string().as_str() {
+                "fs" => &mut sample_rate,
+                "q" => &mut q_factor,
+                "gain" => {
+                    if input.peek(Ident) {
+                        let unit: Ident = input.parse()?;
+                        if unit != "dB" {
+                            return Err(Error::new(unit.span(), "expected dB"));
+                        }
+                    }
+                    &mut gain
+                }
+                _ => return Err(Error::new(name.span(), "expected fs, q or gain")),
+            };
+            if slot.replace(value).is_some() {
+                return Err(Error::new(name.span(), "parameter given more than once"));
+            }
+        }
+        let sample_rate =
+            sample_rate.ok_or_else(|| Error::new(span, "missing sample rate, such as fs = 48000"))?;
+        Ok(Design {
+            kind,
+            cutoff,
+            sample_rate,
+            q_factor: q_factor.unwrap_or(FRAC_1_SQRT_2),
+            gain: gain.unwrap_or(0.0),
+            span,
+        })
+    }
+}
+
+/// Parses an integer or float literal with an optional leading minus sign.
+fn parse_number(input: ParseStream) -> syn::Result<f64> {
+    let negative = input.parse::<Option<Token![-]>>()?.is_some();
+    let literal: Lit = input.parse()?;
+    let value = match &literal {
+        Lit::Int(int) => int.base10_parse::<f64>()?,
+        Lit::Float(float) => float.base10_parse::<f64>()?,
+        _ => return Err(Error::new(literal.span(), "expected a number")),
+    };
+    Ok(if negative { -value } else { value })
+}
+
+impl Design {
+    /// Computes `[b0, b1, b2, a0, a1, a2]` with the same formulas as the runtime filters.
+    fn coefficients(&self) -> syn::Result<[f64; 6]> {
+        let in_range = self.sample_rate.fract() == 0.0 && self.sample_rate >= 1.0;
+        if !in_range {
+            return Err(Error::new(self.span, "the sample rate must be a positive integer"));
+        }
+        let in_range = self.cutoff > 0.0 && self.cutoff < self.sample_rate / 2.0;
+        if !in_range {
+            return Err(Error::new(self.span, "the cutoff must be between 0 Hz and the Nyquist frequency"));
+        }
+        let in_range = self.q_factor > 0.0 && self.q_factor.is_finite();
+        if !in_range {
+            return Err(Error::new(self.span, "the Q factor must be positive"));
+        }
+        let in_range = self.gain.abs() <= 60.0;
+        if !in_range {
+            return Err(Error::new(self.span, "the gain must be within 60 dB"));
+        }
+
+        let w0 = 2.0 * PI * self.cutoff / self.sample_rate;
+        let cos_w0 = w0.cos();
+        let alpha = w0.sin() / (2.0 * self.q_factor);
+        let a = 10.0_f64.powf(self.gain / 40.0);
+        let shelf = 2.0 * a.sqrt() * alpha;
+        Ok(match self.kind {
+            Kind::LowPass => {
+                let b1 = 1.0 - cos_w0;
+                [b1 / 2.0, b1, b1 / 2.0, 1.0 + alpha, -2.0 * cos_w0, 1.0 - alpha]
+            }
+            Kind::HighPass => {
+                let b1 = -(1.0 + cos_w0);
+                [-b1 / 2.0, b1, -b1 / 2.0, 1.0 + alpha, -2.0 * cos_w0, 1.0 - alpha]
+            }
+            Kind::BandPass => [alpha, 0.0, -alpha, 1.0 + alpha, -2.0 * cos_w0, 1.0 - alpha],
+            Kind::Notch => [1.0, -2.0 * cos_w0, 1.0, 1.0 + alpha, -2.0 * cos_w0, 1.0 - alpha],
+            Kind::AllPass => [
+                1.0 - alpha,
+                -2.0 * cos_w0,
+                1.0 + alpha,
+                1.0 + alpha,
+                -2.0 * cos_w0,
+                1.0 - alpha,
+            ],
+            Kind::Peaking => [
+                1.0 + alpha * a,
+                -2.0 * cos_w0,
+                1.0 - alpha * a,
+                1.0 + alpha / a,
+                -2.0 * cos_w0,
+                1.0 - alpha / a,
+            ],
+            Kind::LowShelf => [
+                a * ((a + 1.0) - (a - 1.0) * cos_w0 + shelf),
+                2.0 * a * ((a - 1.0) - (a + 1.0) * cos_w0),
+                a * ((a + 1.0) - (a - 1.0) * cos_w0 - shelf),
+                (a + 1.0) + (a - 1.0) * cos_w0 + shelf,
+                -2.0 * ((a - 1.0) + (a + 1.0) * cos_w0),
+                (a + 1.0) + (a - 1.0) * cos_w0 - shelf,
+            ],
+            Kind::HighShelf => [
+                a * ((a + 1.0) + (a - 1.0) * cos_w0 + shelf),
+                -2.0 * a * ((a - 1.0) + (a + 1.0) * cos_w0),
+                a * ((a + 1.0) + (a - 1.0) * cos_w0 - shelf),
+                (a + 1.0) - (a - 1.0) * cos_w0 + shelf,
+                2.0 * ((a - 1.0) - (a + 1.0) * cos_w0),
+                (a + 1.0) - (a - 1.0) * cos_w0 - shelf,
+            ],
+        })
+    }
+}
+
+/// Derives `BiquadFilterWrapper` for a filter type that holds a `DigitalBiquadFilter<T>` and a
+/// `FilterConfiguration<T>`, along with the `Filter`, `FrequencyResponse` and, with the
+/// `metering` feature, `Metered` implementations the filters in the crate have. The derived type
+/// can be processed, boxed as a `dyn Filter`, pushed into a `FilterChain` and analyzed like any
+/// built-in filter:
+///
+/// ```ignore
+/// use biquad_filters::{BiquadFilterWrapper, Coefficients, DigitalBiquadFilter, FilterConfiguration};
+///
+/// #[derive(BiquadFilterWrapper)]
+/// struct OnePole<T: Float> {
+///     #[biquad(filter)]
+///     filter: DigitalBiquadFilter<T>,
+///     #[biquad(config)]
+///     config: FilterConfiguration<T>,
+/// }
+///
+/// impl<T: Float> OnePole<T> {
+///     fn design_coefficients(config: &FilterConfiguration<T>) -> Option<Coefficients<T>> {
+///         // ...
+///     }
+/// }
+/// ```
+///
+/// The field attributes mark the filter and its configuration, and the first type parameter is
+/// taken as the sample type. The type must have an inherent function
+/// `fn design_coefficients(config: &FilterConfiguration<T>) -> Option<Coefficients<T>>`, which
+/// the derived `calculate_coefficients` calls; it is the only part a new filter type writes by
+/// hand. A design that follows a built-in response type can name it with
+/// `#[biquad(filter_type = LowPass)]`, and `filter_type` reports `None` without it.
+#[proc_macro_derive(BiquadFilterWrapper, attributes(biquad))]
+pub fn derive_biquad_filter_wrapper(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match wrapper_impl(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Generates the `BiquadFilterWrapper` implementation for a derive input, followed by the
+/// `Filter` and `FrequencyResponse` implementations shared with the filters in the crate.
+fn wrapper_impl(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let sample = input
+        .generics
+        .params
+        .iter()
+        .find_map(|param| match param {
+            GenericParam::Type(param) => Some(&param.ident),
+            _ => None,
+        })
+        .ok_or_else(|| Error::new(name.span(), "expected a type parameter for the sample type"))?;
+
+    let mut filter_type = None;
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("biquad")) {
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("filter_type") {
+                return Err(meta.error("expected filter_type"));
+            }
+            filter_type = Some(meta.value()?.parse::<Ident>()?);
+            Ok(())
+        })?;
+    }
+    let filter_type = match filter_type {
+        Some(variant) => quote! { Some(::biquad_filters::FilterType::#variant) },
+        None => quote! { None },
+    };
+
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(name.span(), "BiquadFilterWrapper can only be derived for structs"));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new(name.span(), "expected a struct with named fields"));
+    };
+    let mut filter = None;
+    let mut config = None;
+    for field in fields.named.iter() {
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("biquad")) {
+            attr.parse_nested_meta(|meta| {
+                let slot = if meta.path.is_ident("filter") {
+                    &mut filter
+                } else if meta.path.is_ident("config") {
+                    &mut config
+                } else {
+                    return Err(meta.error("expected filter or config"));
+                };
+                if slot.is_some() {
+                    return Err(meta.error("field marked more than once"));
+                }
+                *slot = field.ident.clone();
+                Ok(())
+            })?;
+        }
+    }
+    let filter = filter
+        .ok_or_else(|| Error::new(name.span(), "missing a field marked #[biquad(filter)]"))?;
+    let config = config
+        .ok_or_else(|| Error::new(name.span(), "missing a field marked #[biquad(config)]"))?;
+
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::biquad_filters::BiquadFilterWrapper<#sample>
+            for #name #type_generics #where_clause
+        {
+            fn get_filter(&mut self) -> &mut ::biquad_filters::DigitalBiquadFilter<#sample> {
+                &mut self.#filter
+            }
+
+            fn get_filter_ref(&self) -> &::biquad_filters::DigitalBiquadFilter<#sample> {
+                &self.#filter
+            }
+
+            fn get_config(&self) -> &::biquad_filters::FilterConfiguration<#sample> {
+                &self.#config
+            }
+
+            fn get_config_mut(&mut self) -> &mut ::biquad_filters::FilterConfiguration<#sample> {
+                &mut self.#config
+            }
+
+            fn filter_type(&self) -> Option<::biquad_filters::FilterType> {
+                #filter_type
+            }
+
+            fn calculate_coefficients(
+                config: &::biquad_filters::FilterConfiguration<#sample>,
+            ) -> Option<::biquad_filters::Coefficients<#sample>> {
+                Self::design_coefficients(config)
+            }
+        }
+
+        ::biquad_filters::__impl_biquad_filter!(
+            [#impl_generics] #name #type_generics, #sample, [#where_clause]
+        );
+    })
+}
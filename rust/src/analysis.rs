@@ -0,0 +1,197 @@
+/// analysis.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::{Coefficients, DigitalBiquadFilter};
+use crate::filters::filter::{BiquadFilterWrapper, FilterType};
+use crate::filters::filter_configuration::FilterConfiguration;
+use crate::filters::response::FrequencyResponse;
+use num_complex::Complex;
+use num_traits::Float;
+use core::f64::consts::PI;
+
+/// Number of points in the logarithmic frequency grid used to search the response.
+const GRID_POINTS: usize = 2048;
+/// Lowest grid frequency, as a fraction of the Nyquist frequency.
+const GRID_FLOOR: f64 = 1e-5;
+/// Number of bisection steps used to refine crossings found on the grid.
+const REFINE_ITERATIONS: usize = 64;
+
+/// Machine-readable summary of a filter design.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DesignReport<T: Float> {
+    /// The built-in response type of the filter, or `None` for a custom design.
+    pub filter_type: Option<FilterType>,
+    /// The parameters the filter was designed with.
+    pub configuration: FilterConfiguration<T>,
+    /// The coefficients of the filter, normalized so that `a0` is one.
+    pub coefficients: Coefficients<T>,
+    /// The poles of the transfer function in the z-plane.
+    pub poles: Vec<Complex<T>>,
+    /// The zeros of the transfer function in the z-plane.
+    pub zeros: Vec<Complex<T>>,
+    /// One minus the largest pole radius. The filter is stable when this is positive.
+    pub stability_margin: T,
+    /// Linear magnitude of the response at DC.
+    pub dc_gain: T,
+    /// Linear magnitude of the response at the Nyquist frequency.
+    pub nyquist_gain: T,
+    /// Frequencies in Hz where the response crosses 3 dB below its peak.
+    pub cutoff_points: Vec<T>,
+    /// Largest linear magnitude of the response.
+    pub peak_gain: T,
+    /// Frequency in Hz at which the peak gain occurs.
+    pub peak_frequency: T,
+}
+
+/// Produces a design report for the given filter, or `None` if its configuration is invalid.
+pub fn report<T, F>(filter: &F) -> Option<DesignReport<T>>
+where
+    T: Float,
+    F: BiquadFilterWrapper<T>,
+{
+    let configuration = *filter.get_config();
+    let coefficients = F::calculate_coefficients(&configuration)?;
+    let coefficients = DigitalBiquadFilter::new(coefficients)?.get_coefficients();
+    let sample_rate = configuration.get_sample_rate();
+    let nyquist = T::from(sample_rate)? / T::from(2.0)?;
+
+    let poles = roots(T::one(), coefficients.a1, coefficients.a2)?;
+    let zeros = roots(coefficients.b0, coefficients.b1, coefficients.b2)?;
+    let max_radius = poles
+        .iter()
+        .map(|pole| pole.norm())
+        .fold(T::zero(), T::max);
+
+    let ((peak_frequency, peak_gain), cutoff_points) =
+        half_power_points(&coefficients, sample_rate)?;
+
+    Some(DesignReport {
+        filter_type: filter.filter_type(),
+        configuration,
+        coefficients,
+        poles,
+        zeros,
+        stability_margin: T::one() - max_radius,
+        dc_gain: coefficients.magnitude_at(T::zero(), sample_rate),
+        nyquist_gain: coefficients.magnitude_at(nyquist, sample_rate),
+        cutoff_points,
+        peak_gain,
+        peak_frequency,
+    })
+}
+
+/// Returns the frequencies in Hz where the response of the coefficients crosses 3 dB below its
+/// peak, in ascending order. These are the realized edges of the design, which drift away from the
+/// requested cutoff as it approaches the Nyquist frequency. Returns `None` if the sample rate is
+/// zero or the response cannot be evaluated.
+pub fn cutoff_points<T: Float>(coefficients: &Coefficients<T>, sample_rate: u32) -> Option<Vec<T>> {
+    if sample_rate == 0 {
+        return None;
+    }
+    let (_, cutoff_points) = half_power_points(coefficients, sample_rate)?;
+    Some(cutoff_points)
+}
+
+/// Returns the peak of the response, as a frequency and linear gain, and the frequencies where it
+/// crosses 3 dB below the peak. The peak is the closed-form `Coefficients::max_gain`, so it agrees
+/// with `FrequencyResponse::max_gain`.
+fn half_power_points<T: Float>(
+    coefficients: &Coefficients<T>,
+    sample_rate: u32,
+) -> Option<((T, T), Vec<T>)> {
+    let nyquist = T::from(sample_rate)? / T::from(2.0)?;
+    let (peak_omega, peak_gain) = coefficients.peak();
+    let peak_frequency = peak_omega / T::from(PI)? * nyquist;
+    let grid = frequency_grid(nyquist)?;
+    let magnitudes = grid
+        .iter()
+        .map(|&frequency| coefficients.magnitude_at(frequency, sample_rate))
+        .collect::<Vec<T>>();
+    let level = peak_gain / T::from(2.0)?.sqrt();
+    let crossings = find_crossings(coefficients, &grid, &magnitudes, level, sample_rate)?;
+    Some(((peak_frequency, peak_gain), crossings))
+}
+
+/// Returns the roots of `c0 * z^2 + c1 * z + c2`, dropping those at infinity.
+fn roots<T: Float>(c0: T, c1: T, c2: T) -> Option<Vec<Complex<T>>> {
+    if c0.is_zero() {
+        if c1.is_zero() {
+            return Some(Vec::new());
+        }
+        return Some(vec![Complex::new(-c2 / c1, T::zero())]);
+    }
+    let two = T::from(2.0)?;
+    let discriminant = c1 * c1 - T::from(4.0)? * c0 * c2;
+    let real = -c1 / (two * c0);
+    let offset = discriminant.abs().sqrt() / (two * c0);
+    if discriminant >= T::zero() {
+        Some(vec![
+            Complex::new(real + offset, T::zero()),
+            Complex::new(real - offset, T::zero()),
+        ])
+    } else {
+        Some(vec![Complex::new(real, offset), Complex::new(real, -offset)])
+    }
+}
+
+/// Builds a logarithmically spaced frequency grid from DC up to the Nyquist frequency.
+fn frequency_grid<T: Float>(nyquist: T) -> Option<Vec<T>> {
+    let floor = T::from(GRID_FLOOR)?.ln();
+    let steps = T::from(GRID_POINTS - 1)?;
+    let mut grid = Vec::with_capacity(GRID_POINTS + 1);
+    grid.push(T::zero());
+    for index in 0..GRID_POINTS {
+        let fraction = T::from(index)? / steps;
+        grid.push(nyquist * (floor * (T::one() - fraction)).exp());
+    }
+    Some(grid)
+}
+
+/// Locates every frequency where the magnitude crosses `level`, refined by bisection.
+fn find_crossings<T: Float>(
+    coefficients: &Coefficients<T>,
+    grid: &[T],
+    magnitudes: &[T],
+    level: T,
+    sample_rate: u32,
+) -> Option<Vec<T>> {
+    let mut crossings = Vec::new();
+    for index in 1..grid.len() {
+        let below = magnitudes[index - 1] < level;
+        if below == (magnitudes[index] < level) {
+            continue;
+        }
+        let mut low = grid[index - 1];
+        let mut high = grid[index];
+        for _ in 0..REFINE_ITERATIONS {
+            let middle = (low + high) / T::from(2.0)?;
+            if (coefficients.magnitude_at(middle, sample_rate) < level) == below {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        crossings.push((low + high) / T::from(2.0)?);
+    }
+    Some(crossings)
+}
@@ -0,0 +1,368 @@
+/// convert.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use num_traits::Float;
+
+/// The dither added before float samples are rounded to integers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Dither {
+    /// Samples are rounded without dither, which leaves truncation distortion on quiet signals.
+    None,
+    /// Triangular dither spanning one least significant bit either side, which makes the rounding
+    /// error independent of the signal.
+    #[default]
+    Triangular,
+    /// Triangular dither with first-order error feedback, which moves the rounding noise towards
+    /// high frequencies where it is less audible.
+    Shaped,
+}
+
+/// What happens to samples that do not fit in the output bit depth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OverflowPolicy {
+    /// Out-of-range samples are clipped to the largest value of the same sign.
+    #[default]
+    Saturate,
+    /// Out-of-range samples wrap around in two's complement, as on hardware without saturating
+    /// arithmetic.
+    Wrap,
+    /// Out-of-range samples are clipped, and block conversions stop at the first one and report
+    /// failure.
+    Error,
+}
+
+/// Converts float samples in `[-1, 1)` to integers of a given bit depth, with optional dither.
+/// Out-of-range samples are handled by the `OverflowPolicy`, clipping by default, and every
+/// overflow is recorded so it can be told apart from correct output. NaN samples become zero.
+#[derive(Debug, Clone)]
+pub struct Quantizer<T: Float> {
+    dither: Dither,
+    bits: u32,
+    scale: T,
+    /// The rounding error of the previous sample, fed back for noise shaping.
+    error: T,
+    random: u32,
+    overflow_policy: OverflowPolicy,
+    /// Whether a sample has overflowed since the flag was last taken.
+    overflowed: bool,
+}
+
+impl<T: Float> Quantizer<T> {
+    /// The seed used by `new`.
+    pub const DEFAULT_SEED: u32 = 0x9E37_79B9;
+
+    /// Creates a quantizer producing `bits`-bit integers. Returns `None` if `bits` is not between
+    /// 2 and 32.
+    pub fn new(bits: u32, dither: Dither) -> Option<Self> {
+        Self::with_seed(bits, dither, Self::DEFAULT_SEED)
+    }
+
+    /// Creates a quantizer whose dither sequence starts from `seed`, so separate channels can use
+    /// uncorrelated dither. Returns `None` if `bits` is not between 2 and 32.
+    pub fn with_seed(bits: u32, dither: Dither, seed: u32) -> Option<Self> {
+        if !(2..=32).contains(&bits) {
+            return None;
+        }
+        Some(Self {
+            dither,
+            bits,
+            scale: T::from(1_u64 << (bits - 1))?,
+            error: T::zero(),
+            random: seed.max(1),
+            overflow_policy: OverflowPolicy::Saturate,
+            overflowed: false,
+        })
+    }
+
+    /// Returns the dither mode.
+    pub fn get_dither(&self) -> Dither {
+        self.dither
+    }
+
+    /// Returns the output bit depth.
+    pub fn get_bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Returns the overflow policy.
+    pub fn get_overflow_policy(&self) -> OverflowPolicy {
+        self.overflow_policy
+    }
+
+    /// Sets what happens to samples that do not fit in the output bit depth.
+    pub fn set_overflow_policy(&mut self, overflow_policy: OverflowPolicy) {
+        self.overflow_policy = overflow_policy;
+    }
+
+    /// Returns whether any sample has overflowed since the flag was last taken, and clears it.
+    pub fn take_overflow(&mut self) -> bool {
+        core::mem::take(&mut self.overflowed)
+    }
+
+    /// Converts one sample to an integer of the configured bit depth. Overflows are handled by
+    /// the overflow policy and recorded for `take_overflow`.
+    pub fn quantize(&mut self, sample: T) -> i32 {
+        if sample.is_nan() {
+            return 0;
+        }
+        let mut value = sample * self.scale;
+        if self.dither == Dither::Shaped {
+            value = value - self.error;
+        }
+        let dithered = match self.dither {
+            Dither::None => value,
+            Dither::Triangular | Dither::Shaped => value + self.next_uniform() - self.next_uniform(),
+        };
+        let largest = self.scale - T::one();
+        let unclipped = dithered.round();
+        let rounded = unclipped.max(-self.scale).min(largest);
+        if self.dither == Dither::Shaped {
+            // Clipped samples would feed back the clipping error, so limit what is carried over.
+            self.error = (rounded - value).max(-T::one()).min(T::one());
+        }
+        if rounded == unclipped {
+            return rounded.to_i32().unwrap_or(0);
+        }
+        self.overflowed = true;
+        match self.overflow_policy {
+            OverflowPolicy::Saturate | OverflowPolicy::Error => rounded.to_i32().unwrap_or(0),
+            OverflowPolicy::Wrap => self.wrap(unclipped),
+        }
+    }
+
+    /// Wraps an out-of-range integer value into the bit depth in two's complement.
+    fn wrap(&self, value: T) -> i32 {
+        let Some(value) = value.to_i128() else {
+            return 0;
+        };
+        let shift = 128 - self.bits;
+        ((value << shift) >> shift) as i32
+    }
+
+    /// Converts a block of samples with `convert`. Returns `false` if the block is empty, the
+    /// slices differ in length, or a sample overflows under `OverflowPolicy::Error`, in which
+    /// case the outputs from that sample on are left unchanged.
+    fn quantize_block<I>(&mut self, input: &[T], output: &mut [I], convert: impl Fn(i32) -> I) -> bool {
+        if input.is_empty() || input.len() != output.len() {
+            return false;
+        }
+        for (sample, output) in input.iter().zip(output.iter_mut()) {
+            let overflowed = self.overflowed;
+            self.overflowed = false;
+            let value = self.quantize(*sample);
+            let failed = self.overflowed && self.overflow_policy == OverflowPolicy::Error;
+            self.overflowed |= overflowed;
+            if failed {
+                return false;
+            }
+            *output = convert(value);
+        }
+        true
+    }
+
+    /// Converts a block of samples to 16-bit integers. Returns `false` if the bit depth is above
+    /// 16, the block is empty, the slices differ in length, or a sample overflows under
+    /// `OverflowPolicy::Error`.
+    pub fn quantize_block_i16(&mut self, input: &[T], output: &mut [i16]) -> bool {
+        if self.bits > 16 {
+            return false;
+        }
+        self.quantize_block(input, output, |value| value as i16)
+    }
+
+    /// Converts a block of samples to integers of the configured bit depth stored in `i32`, as
+    /// used for 24-bit audio. Returns `false` if the block is empty, the slices differ in length,
+    /// or a sample overflows under `OverflowPolicy::Error`.
+    pub fn quantize_block_i32(&mut self, input: &[T], output: &mut [i32]) -> bool {
+        self.quantize_block(input, output, |value| value)
+    }
+
+    /// Clears the noise-shaping error.
+    pub fn reset(&mut self) {
+        self.error = T::zero();
+    }
+
+    /// Returns a uniform value in `[0, 1)` from a xorshift generator.
+    fn next_uniform(&mut self) -> T {
+        self.random ^= self.random << 13;
+        self.random ^= self.random >> 17;
+        self.random ^= self.random << 5;
+        let fraction = (self.random >> 8) as f64 / (1_u32 << 24) as f64;
+        T::from(fraction).unwrap_or_else(T::zero)
+    }
+}
+
+/// The scale between 16-bit integers and floats in `[-1, 1)`.
+const I16_SCALE: f32 = 32768.0;
+/// The scale between 32-bit integers and floats in `[-1, 1)`.
+const I32_SCALE: f64 = 2_147_483_648.0;
+
+/// Converts 16-bit integer samples to floats in `[-1, 1)`. Returns `false` if the slices differ in
+/// length.
+pub fn i16_to_f32(input: &[i16], output: &mut [f32]) -> bool {
+    if input.len() != output.len() {
+        return false;
+    }
+    for (sample, output) in input.iter().zip(output.iter_mut()) {
+        *output = *sample as f32 / I16_SCALE;
+    }
+    true
+}
+
+/// Converts float samples to 16-bit integers by rounding, clipping samples outside `[-1, 1)` and
+/// mapping NaN to zero. Use a `Quantizer` to add dither. Returns `false` if the slices differ in
+/// length.
+pub fn f32_to_i16(input: &[f32], output: &mut [i16]) -> bool {
+    if input.len() != output.len() {
+        return false;
+    }
+    for (sample, output) in input.iter().zip(output.iter_mut()) {
+        // Float to integer casts saturate and map NaN to zero.
+        *output = (*sample * I16_SCALE).round() as i16;
+    }
+    true
+}
+
+/// Converts 32-bit integer samples to floats in `[-1, 1)`. Returns `false` if the slices differ in
+/// length.
+pub fn i32_to_f64(input: &[i32], output: &mut [f64]) -> bool {
+    if input.len() != output.len() {
+        return false;
+    }
+    for (sample, output) in input.iter().zip(output.iter_mut()) {
+        *output = *sample as f64 / I32_SCALE;
+    }
+    true
+}
+
+/// Converts float samples to 32-bit integers by rounding, clipping samples outside `[-1, 1)` and
+/// mapping NaN to zero. Returns `false` if the slices differ in length.
+pub fn f64_to_i32(input: &[f64], output: &mut [i32]) -> bool {
+    if input.len() != output.len() {
+        return false;
+    }
+    for (sample, output) in input.iter().zip(output.iter_mut()) {
+        *output = (*sample * I32_SCALE).round() as i32;
+    }
+    true
+}
+
+/// Interleaves one slice per channel into frames. Returns `false` if there are no channels, the
+/// channels differ in length, or `output` does not hold exactly one frame per sample.
+pub fn interleave<T: Copy>(channels: &[&[T]], output: &mut [T]) -> bool {
+    let Some(length) = channels.first().map(|channel| channel.len()) else {
+        return false;
+    };
+    if channels.iter().any(|channel| channel.len() != length)
+        || output.len() != length * channels.len()
+    {
+        return false;
+    }
+    for (index, frame) in output.chunks_exact_mut(channels.len()).enumerate() {
+        for (output, channel) in frame.iter_mut().zip(channels.iter()) {
+            *output = channel[index];
+        }
+    }
+    true
+}
+
+/// Splits interleaved frames into one slice per channel. Returns `false` if there are no
+/// channels, the channels differ in length, or `input` does not hold exactly one frame per sample.
+pub fn deinterleave<T: Copy>(input: &[T], channels: &mut [&mut [T]]) -> bool {
+    let Some(length) = channels.first().map(|channel| channel.len()) else {
+        return false;
+    };
+    if channels.iter().any(|channel| channel.len() != length)
+        || input.len() != length * channels.len()
+    {
+        return false;
+    }
+    let count = channels.len();
+    for (index, frame) in input.chunks_exact(count).enumerate() {
+        for (sample, channel) in frame.iter().zip(channels.iter_mut()) {
+            channel[index] = *sample;
+        }
+    }
+    true
+}
+
+/// A sample format that filters can process directly, converting each sample to their float type
+/// and back as it is processed. Integer formats map full scale to `[-1, 1)`, and results are
+/// rounded and clipped, with NaN becoming zero.
+pub trait Sample: Copy {
+    /// Converts the sample to a float.
+    fn to_float<T: Float>(self) -> T;
+    /// Converts a float back to the sample format.
+    fn from_float<T: Float>(value: T) -> Self;
+}
+
+impl Sample for f32 {
+    fn to_float<T: Float>(self) -> T {
+        T::from(self).unwrap_or_else(T::nan)
+    }
+
+    fn from_float<T: Float>(value: T) -> Self {
+        value.to_f32().unwrap_or(f32::NAN)
+    }
+}
+
+impl Sample for f64 {
+    fn to_float<T: Float>(self) -> T {
+        T::from(self).unwrap_or_else(T::nan)
+    }
+
+    fn from_float<T: Float>(value: T) -> Self {
+        value.to_f64().unwrap_or(f64::NAN)
+    }
+}
+
+impl Sample for i16 {
+    fn to_float<T: Float>(self) -> T {
+        T::from(self as f32 / I16_SCALE).unwrap_or_else(T::zero)
+    }
+
+    fn from_float<T: Float>(value: T) -> Self {
+        let scaled = (value * T::from(I16_SCALE).unwrap_or_else(T::one)).round();
+        match scaled.to_i16() {
+            Some(sample) => sample,
+            None if scaled.is_nan() => 0,
+            None if scaled > T::zero() => i16::MAX,
+            None => i16::MIN,
+        }
+    }
+}
+
+impl Sample for i32 {
+    fn to_float<T: Float>(self) -> T {
+        T::from(self as f64 / I32_SCALE).unwrap_or_else(T::zero)
+    }
+
+    fn from_float<T: Float>(value: T) -> Self {
+        let scaled = (value * T::from(I32_SCALE).unwrap_or_else(T::one)).round();
+        match scaled.to_i32() {
+            Some(sample) => sample,
+            None if scaled.is_nan() => 0,
+            None if scaled > T::zero() => i32::MAX,
+            None => i32::MIN,
+        }
+    }
+}
@@ -0,0 +1,295 @@
+/// design.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::Coefficients;
+use crate::filters::crossover::butterworth_section;
+use num_complex::Complex;
+use num_traits::Float;
+use core::f64::consts::PI;
+
+/// Returns the Q factor of each second-order section of an `order`-pole Butterworth filter built
+/// from biquads that share the same cutoff, from the lowest Q to the highest. Odd orders also need
+/// one first-order section, which has no Q and is not included, so orders 4 and 5 both return two
+/// values. Orders below two return no values.
+pub fn butterworth_q_values<T: Float>(order: usize) -> Vec<T> {
+    (0..order / 2)
+        .map(|pair| {
+            // The poles of the pair sit at this angle from the negative real axis. Odd orders have
+            // a real pole on the axis itself, which shifts every pair by half a step.
+            let angle = PI * (2 * pair + 1 + order % 2) as f64 / (2 * order) as f64;
+            T::from(1.0 / (2.0 * angle.cos())).unwrap_or_else(T::nan)
+        })
+        .collect()
+}
+
+/// Returns the sections of an `order`-pole Butterworth low-pass filter with the given cutoff, in
+/// processing order from the lowest Q to the highest, which keeps the internal levels of the
+/// cascade low. Odd orders start with a first-order section. Returns `None` if the order is zero
+/// or the cutoff is not between zero and the Nyquist frequency.
+pub fn butterworth_low_pass<T: Float>(
+    order: usize,
+    cutoff: T,
+    sample_rate: u32,
+) -> Option<Vec<Coefficients<T>>> {
+    butterworth_sections(order, cutoff, sample_rate, false)
+}
+
+/// Returns the sections of an `order`-pole Butterworth high-pass filter with the given cutoff, in
+/// the same arrangement as `butterworth_low_pass`.
+pub fn butterworth_high_pass<T: Float>(
+    order: usize,
+    cutoff: T,
+    sample_rate: u32,
+) -> Option<Vec<Coefficients<T>>> {
+    butterworth_sections(order, cutoff, sample_rate, true)
+}
+
+/// Returns the sections of a Butterworth band-pass filter made from an `order`-pole low-pass
+/// prototype, so it has `order` sections and falls off at `order` poles on each side. The response
+/// is 3 dB down at both edges and has unity gain at their geometric mean. Returns `None` if the
+/// order is zero or the edges are not increasing and between zero and the Nyquist frequency.
+pub fn butterworth_band_pass<T: Float>(
+    order: usize,
+    low_cutoff: T,
+    high_cutoff: T,
+    sample_rate: u32,
+) -> Option<Vec<Coefficients<T>>> {
+    let low = prewarp(low_cutoff.to_f64()?, sample_rate)?;
+    let high = prewarp(high_cutoff.to_f64()?, sample_rate)?;
+    if order == 0 || low >= high {
+        return None;
+    }
+    let bandwidth = high - low;
+    let center = low * high;
+    // Each prototype pole p becomes the roots of s^2 - p B s + w0^2. A conjugate pair of
+    // prototype poles gives two conjugate pairs of band-pass poles, and the real pole of odd
+    // orders gives that quadratic itself. Each section is kept as the sum and product of its
+    // poles.
+    let mut denominators = Vec::with_capacity(order);
+    for index in 0..order.div_ceil(2) {
+        let pole = butterworth_pole(order, index) * bandwidth;
+        if index == order / 2 {
+            denominators.push((pole.re, center));
+            continue;
+        }
+        let root = (pole * pole - 4.0 * center).sqrt();
+        for pole in [(pole + root) / 2.0, (pole - root) / 2.0] {
+            denominators.push((2.0 * pole.re, pole.norm_sqr()));
+        }
+    }
+    denominators
+        .iter()
+        .map(|&(sum, product)| {
+            // The section is B s / (s^2 - sum s + product) through the bilinear transform.
+            Some(Coefficients {
+                b0: T::from(bandwidth)?,
+                b1: T::zero(),
+                b2: T::from(-bandwidth)?,
+                a0: T::from(1.0 - sum + product)?,
+                a1: T::from(2.0 * product - 2.0)?,
+                a2: T::from(1.0 + sum + product)?,
+            })
+        })
+        .collect()
+}
+
+/// Returns the low-pass or high-pass sections of a Butterworth filter.
+fn butterworth_sections<T: Float>(
+    order: usize,
+    cutoff: T,
+    sample_rate: u32,
+    high_pass: bool,
+) -> Option<Vec<Coefficients<T>>> {
+    let k = prewarp(cutoff.to_f64()?, sample_rate)?;
+    if order == 0 {
+        return None;
+    }
+    let mut sections = Vec::with_capacity(order.div_ceil(2));
+    if order % 2 == 1 {
+        let (b0, b1) = if high_pass { (1.0, -1.0) } else { (k, k) };
+        sections.push(Coefficients {
+            b0,
+            b1,
+            b2: 0.0,
+            a0: 1.0 + k,
+            a1: k - 1.0,
+            a2: 0.0,
+        });
+    }
+    for q in butterworth_q_values::<f64>(order) {
+        sections.push(butterworth_section(k, q, high_pass)?);
+    }
+    Some(sections.iter().map(Coefficients::cast).collect())
+}
+
+/// Returns the prewarped frequency `tan(pi * frequency / sample_rate)`, or `None` if the frequency
+/// is not between zero and the Nyquist frequency.
+fn prewarp(frequency: f64, sample_rate: u32) -> Option<f64> {
+    let nyquist = sample_rate as f64 / 2.0;
+    if !(frequency > 0.0 && frequency < nyquist) {
+        return None;
+    }
+    Some((PI * frequency / sample_rate as f64).tan())
+}
+
+/// Returns pole `index` of an `order`-pole Butterworth prototype with a cutoff of one, counting
+/// from the pole nearest the imaginary axis in the upper half plane. The last pole of odd orders
+/// is the real pole.
+fn butterworth_pole(order: usize, index: usize) -> Complex<f64> {
+    let angle = PI * (2 * index + 1 + order) as f64 / (2 * order) as f64;
+    Complex::from_polar(1.0, angle)
+}
+
+/// The highest order accepted by `bessel_sections`.
+pub const MAX_BESSEL_ORDER: usize = 12;
+/// Number of iterations used to find the roots of the Bessel polynomial.
+const ROOT_ITERATIONS: usize = 500;
+/// Number of bisection steps used to find the -3 dB frequency.
+const BISECTION_STEPS: usize = 100;
+
+/// Where the cutoff of a Bessel filter is defined. The two conventions differ by a factor of 1.27
+/// at second order and by more at higher orders, so designs meant to match must use the same one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BesselNormalization {
+    /// The response is 3 dB down at the cutoff, like other filter families. Used when swapping a
+    /// Bessel filter for a Butterworth filter with the same bandwidth.
+    #[default]
+    Magnitude,
+    /// The group delay at DC is `1 / (2 * pi * cutoff)` seconds, so the cutoff sets the delay.
+    /// Used by crossover designs that align the delays of their bands.
+    Delay,
+}
+
+/// One section of a Bessel filter, relative to a cutoff of 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BesselSection<T: Float> {
+    /// The natural frequency of the section as a multiple of the cutoff.
+    pub frequency: T,
+    /// The Q factor of a second-order section, or `None` for the single first-order section of an
+    /// odd-order filter.
+    pub q: Option<T>,
+}
+
+/// Returns the sections of an `order`-pole Bessel low-pass filter, starting with the first-order
+/// section of odd orders and followed by second-order sections from the lowest Q to the highest.
+/// Multiply each section's frequency by the cutoff to design it. Returns `None` if the order is
+/// zero or above `MAX_BESSEL_ORDER`.
+pub fn bessel_sections<T: Float>(
+    order: usize,
+    normalization: BesselNormalization,
+) -> Option<Vec<BesselSection<T>>> {
+    if order == 0 || order > MAX_BESSEL_ORDER {
+        return None;
+    }
+    let mut poles = bessel_poles(order);
+    if normalization == BesselNormalization::Magnitude {
+        let cutoff = half_power_frequency(&poles);
+        for pole in poles.iter_mut() {
+            *pole /= cutoff;
+        }
+    }
+    let mut sections: Vec<BesselSection<T>> = poles
+        .iter()
+        // Keep one pole of each conjugate pair, and the real pole.
+        .filter(|pole| pole.im >= 0.0)
+        .map(|pole| {
+            let frequency = T::from(pole.norm())?;
+            let q = if pole.im > 0.0 {
+                Some(T::from(pole.norm() / (-2.0 * pole.re))?)
+            } else {
+                None
+            };
+            Some(BesselSection { frequency, q })
+        })
+        .collect::<Option<_>>()?;
+    sections.sort_by(|first, second| match (first.q, second.q) {
+        (None, _) => core::cmp::Ordering::Less,
+        (_, None) => core::cmp::Ordering::Greater,
+        (Some(first), Some(second)) => first.partial_cmp(&second).unwrap_or(core::cmp::Ordering::Equal),
+    });
+    Some(sections)
+}
+
+/// Returns the poles of the delay-normalized Bessel filter: the roots of the reverse Bessel
+/// polynomial, found with the Durand-Kerner method. Real roots are returned with an imaginary part
+/// of exactly zero.
+fn bessel_poles(order: usize) -> Vec<Complex<f64>> {
+    // The coefficients of s^k are (2n - k)! / (2^(n - k) k! (n - k)!), and the leading one is 1.
+    let mut coefficients = vec![1.0; order + 1];
+    for k in (0..order).rev() {
+        let n = order as f64;
+        let k_next = (k + 1) as f64;
+        coefficients[k] = coefficients[k + 1] * (2.0 * n - k as f64) * k_next / (2.0 * (n - k as f64));
+    }
+    let evaluate = |s: Complex<f64>| {
+        coefficients
+            .iter()
+            .rev()
+            .fold(Complex::new(0.0, 0.0), |sum, coefficient| sum * s + coefficient)
+    };
+    let radius = coefficients[0].powf(1.0 / order as f64);
+    let mut roots: Vec<Complex<f64>> = (0..order)
+        .map(|index| Complex::from_polar(radius, 2.0 * PI * index as f64 / order as f64 + 0.4))
+        .collect();
+    for _ in 0..ROOT_ITERATIONS {
+        for index in 0..order {
+            let root = roots[index];
+            let denominator = roots
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != index)
+                .fold(Complex::new(1.0, 0.0), |product, (_, other)| product * (root - other));
+            roots[index] = root - evaluate(root) / denominator;
+        }
+    }
+    for root in roots.iter_mut() {
+        if root.im.abs() < 1e-9 * root.norm() {
+            root.im = 0.0;
+        }
+    }
+    roots
+}
+
+/// Returns the frequency in radians per second where the all-pole response with these poles and
+/// unity DC gain falls to half power.
+fn half_power_frequency(poles: &[Complex<f64>]) -> f64 {
+    let power = |omega: f64| {
+        poles
+            .iter()
+            .map(|pole| pole.norm_sqr() / (Complex::new(0.0, omega) - pole).norm_sqr())
+            .product::<f64>()
+    };
+    let mut upper = 1.0;
+    while power(upper) > 0.5 {
+        upper *= 2.0;
+    }
+    let mut lower = 0.0;
+    for _ in 0..BISECTION_STEPS {
+        let middle = 0.5 * (lower + upper);
+        if power(middle) > 0.5 {
+            lower = middle;
+        } else {
+            upper = middle;
+        }
+    }
+    0.5 * (lower + upper)
+}
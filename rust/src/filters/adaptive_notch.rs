@@ -0,0 +1,199 @@
+/// adaptive_notch.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use num_traits::Float;
+use core::f64::consts::PI;
+
+/// A notch filter that tracks a drifting interference frequency, such as mains hum, within a
+/// tolerance around its nominal value.
+///
+/// The filter is a constrained second-order notch whose poles sit just inside its zeros on the
+/// unit circle. The zero position is adapted every sample with a normalized gradient step that
+/// minimizes the output power, and is clamped to the tolerance range so broadband content can
+/// never pull the notch away from the hum.
+#[derive(Debug, Clone)]
+pub struct AdaptiveNotch<T: Float> {
+    nominal_frequency: T,
+    tolerance: T,
+    bandwidth: T,
+    adaptation_rate: T,
+    sample_rate: u32,
+    /// The notch position as `2 - 2cos(w)`, which keeps low frequencies well resolved in `f32`
+    /// where `cos(w)` would round to one.
+    k: T,
+    k_min: T,
+    k_max: T,
+    pole_radius: T,
+    smoothing: T,
+    power: T,
+    s1: T,
+    s2: T,
+}
+
+impl<T: Float> AdaptiveNotch<T> {
+    /// The default notch bandwidth in Hz.
+    pub const DEFAULT_BANDWIDTH: f64 = 2.0;
+    /// The default normalized adaptation step size.
+    pub const DEFAULT_ADAPTATION_RATE: f64 = 0.001;
+
+    /// Creates an adaptive notch centered on `nominal_frequency` that tracks drift of up to
+    /// `tolerance` Hz in either direction. Returns `None` if the tracking range is not between zero
+    /// and the Nyquist frequency.
+    pub fn new(nominal_frequency: T, tolerance: T, sample_rate: u32) -> Option<Self> {
+        let nyquist = T::from(sample_rate)? / T::from(2.0)?;
+        let low = nominal_frequency - tolerance;
+        let high = nominal_frequency + tolerance;
+        if !(tolerance >= T::zero() && low > T::zero() && high < nyquist) {
+            return None;
+        }
+        let mut notch = Self {
+            nominal_frequency,
+            tolerance,
+            bandwidth: T::zero(),
+            adaptation_rate: T::from(Self::DEFAULT_ADAPTATION_RATE)?,
+            sample_rate,
+            k: Self::frequency_to_k(nominal_frequency, sample_rate)?,
+            k_min: Self::frequency_to_k(low, sample_rate)?,
+            k_max: Self::frequency_to_k(high, sample_rate)?,
+            pole_radius: T::zero(),
+            smoothing: T::zero(),
+            power: T::zero(),
+            s1: T::zero(),
+            s2: T::zero(),
+        };
+        if !notch.set_bandwidth(T::from(Self::DEFAULT_BANDWIDTH)?) {
+            return None;
+        }
+        Some(notch)
+    }
+
+    /// Processes a single sample in-place.
+    pub fn process(&mut self, sample: &mut T) -> bool {
+        let two = T::one() + T::one();
+        let s0 = *sample + self.pole_radius * (two - self.k) * self.s1
+            - self.pole_radius * self.pole_radius * self.s2;
+        let output = s0 - two * self.s1 + self.s2 + self.k * self.s1;
+
+        self.power = self.power + self.smoothing * (self.s1 * self.s1 - self.power);
+        if self.power > T::zero() {
+            let step = self.adaptation_rate * output * self.s1 / self.power;
+            self.k = (self.k - step).max(self.k_min).min(self.k_max);
+        }
+
+        self.s2 = self.s1;
+        self.s1 = s0;
+        *sample = output;
+        true
+    }
+
+    /// Processes a block of samples in-place.
+    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
+        if samples.is_empty() {
+            return false;
+        }
+        for sample in samples.iter_mut() {
+            self.process(sample);
+        }
+        true
+    }
+
+    /// Returns the current estimate of the interference frequency in Hz.
+    pub fn get_frequency(&self) -> T {
+        let two = T::one() + T::one();
+        let omega = two * (self.k.sqrt() / two).asin();
+        omega * T::from(self.sample_rate).unwrap_or_else(T::zero) / (two * Self::pi())
+    }
+
+    /// Returns the nominal frequency the notch starts from.
+    pub fn get_nominal_frequency(&self) -> T {
+        self.nominal_frequency
+    }
+
+    /// Returns the largest drift from the nominal frequency the notch will follow.
+    pub fn get_tolerance(&self) -> T {
+        self.tolerance
+    }
+
+    /// Returns the sample rate of the filter.
+    pub fn get_sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Returns the -3 dB bandwidth of the notch in Hz.
+    pub fn get_bandwidth(&self) -> T {
+        self.bandwidth
+    }
+
+    /// Sets the -3 dB bandwidth of the notch in Hz. A narrower notch removes less of the wanted
+    /// signal but locks on more slowly. Returns `false` and keeps the current bandwidth if it is
+    /// not positive and finite.
+    pub fn set_bandwidth(&mut self, bandwidth: T) -> bool {
+        if !(bandwidth > T::zero() && bandwidth.is_finite()) {
+            return false;
+        }
+        let Some(sample_rate) = T::from(self.sample_rate) else {
+            return false;
+        };
+        let pole_radius = (-Self::pi() * bandwidth / sample_rate).exp();
+        self.bandwidth = bandwidth;
+        self.pole_radius = pole_radius;
+        self.smoothing = T::one() - pole_radius;
+        true
+    }
+
+    /// Returns the normalized adaptation step size.
+    pub fn get_adaptation_rate(&self) -> T {
+        self.adaptation_rate
+    }
+
+    /// Sets the normalized adaptation step size. Larger values follow drift faster but leave more
+    /// jitter on the estimate. Returns `false` and keeps the current rate if it is not between zero
+    /// and one.
+    pub fn set_adaptation_rate(&mut self, adaptation_rate: T) -> bool {
+        if !(adaptation_rate > T::zero() && adaptation_rate <= T::one()) {
+            return false;
+        }
+        self.adaptation_rate = adaptation_rate;
+        true
+    }
+
+    /// Resets the filter state and returns the estimate to the nominal frequency.
+    pub fn reset(&mut self) {
+        if let Some(k) = Self::frequency_to_k(self.nominal_frequency, self.sample_rate) {
+            self.k = k;
+        }
+        self.power = T::zero();
+        self.s1 = T::zero();
+        self.s2 = T::zero();
+    }
+
+    /// Converts a frequency in Hz to the notch position `2 - 2cos(w)`, computed as `4sin²(w/2)`.
+    fn frequency_to_k(frequency: T, sample_rate: u32) -> Option<T> {
+        let half_omega = Self::pi() * frequency / T::from(sample_rate)?;
+        let sine = half_omega.sin();
+        Some(T::from(4.0)? * sine * sine)
+    }
+
+    fn pi() -> T {
+        T::from(PI).unwrap_or_else(T::zero)
+    }
+}
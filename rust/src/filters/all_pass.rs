@@ -1,5 +1,4 @@
 /// all_pass.rs
-
 /**
 Copyright © 2025 Alex Parisi
 
@@ -22,22 +21,25 @@ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
 use crate::filters::biquad::{Coefficients, DigitalBiquadFilter};
-use crate::filters::filter::BiquadFilterWrapper;
+use crate::filters::filter::{
+    BiquadFilterWrapper, impl_biquad_filter, impl_biquad_filter_wrapper,
+    impl_precision_conversions,
+};
 use crate::filters::filter_configuration::FilterConfiguration;
 use num_traits::Float;
-use std::f64::consts::PI;
+use core::f64::consts::PI;
 
 
 /// All-pass filter implementation using a digital biquad filter.
 #[derive(Debug, Clone)]
-pub struct AllPassFilter<T: Float + Default + Copy> {
+pub struct AllPassFilter<T: Float> {
     /// The digital biquad filter used for processing.
     filter: DigitalBiquadFilter<T>,
     /// The configuration for the filter, including cutoff frequency, sample rate, and Q factor.
     config: FilterConfiguration<T>,
 }
 
-impl<T: Float + Default + Copy + std::ops::MulAssign> AllPassFilter<T> {
+impl<T: Float> AllPassFilter<T> {
     /// Creates a new all-pass filter with the given cutoff frequency, sample rate, and Q factor.
     pub fn new(cutoff: T, sample_rate: u32, q_factor: T) -> Option<Self> {
         let config =
@@ -55,26 +57,19 @@ impl<T: Float + Default + Copy + std::ops::MulAssign> AllPassFilter<T> {
     }
 }
 
-/// Provide internal access and coefficient logic via BiquadFilterWrapper.
-impl<T: Float + Default + Copy + std::ops::MulAssign> BiquadFilterWrapper<T> for AllPassFilter<T> {
-    fn get_filter(&mut self) -> &mut DigitalBiquadFilter<T> {
-        &mut self.filter
-    }
-
-    fn get_config(&self) -> &FilterConfiguration<T> {
-        &self.config
-    }
-
-    fn get_config_mut(&mut self) -> &mut FilterConfiguration<T> {
-        &mut self.config
-    }
+impl_biquad_filter!(AllPassFilter);
+impl_precision_conversions!(AllPassFilter);
+impl_biquad_filter_wrapper!(AllPassFilter, AllPass);
 
-    fn calculate_coefficients(config: &FilterConfiguration<T>) -> Option<Coefficients<T>> {
+impl<T: Float> AllPassFilter<T> {
+    /// Designs the all-pass coefficients for a configuration. Returns `None` if the configuration
+    /// is invalid.
+    fn design_coefficients(config: &FilterConfiguration<T>) -> Option<Coefficients<T>> {
         let cutoff = config.get_cutoff();
         let sample_rate = config.get_sample_rate();
         let q = config.get_q_factor();
 
-        if cutoff <= T::zero() || sample_rate == 0 || q <= T::zero() {
+        if !config.is_valid() {
             return None;
         }
 
@@ -0,0 +1,445 @@
+/// automation.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::filter::Filter;
+use num_traits::Float;
+
+/// The default number of samples between coefficient updates while a ramp is active.
+const DEFAULT_CONTROL_INTERVAL: usize = 16;
+
+/// A continuously variable filter parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Parameter {
+    Cutoff,
+    QFactor,
+    Gain,
+}
+
+impl Parameter {
+    /// Returns the current value of the parameter on the given filter.
+    pub fn get<T: Float, F: Filter<T> + ?Sized>(self, filter: &F) -> T {
+        match self {
+            Parameter::Cutoff => filter.get_cutoff(),
+            Parameter::QFactor => filter.get_q_factor(),
+            Parameter::Gain => filter.get_gain(),
+        }
+    }
+
+    /// Returns a change that sets the parameter to the given value.
+    pub fn change<T>(self, value: T) -> ParameterChange<T> {
+        match self {
+            Parameter::Cutoff => ParameterChange::Cutoff(value),
+            Parameter::QFactor => ParameterChange::QFactor(value),
+            Parameter::Gain => ParameterChange::Gain(value),
+        }
+    }
+}
+
+/// A change to a single filter parameter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParameterChange<T> {
+    Cutoff(T),
+    SampleRate(u32),
+    QFactor(T),
+    Gain(T),
+    ConstantSkirtGain(bool),
+    Bypass(bool),
+}
+
+impl<T: Float> ParameterChange<T> {
+    /// Applies the change through the corresponding setter and returns its result.
+    pub fn apply<F: Filter<T> + ?Sized>(self, filter: &mut F) -> bool {
+        match self {
+            ParameterChange::Cutoff(cutoff) => filter.set_cutoff(cutoff),
+            ParameterChange::SampleRate(sample_rate) => filter.set_sample_rate(sample_rate),
+            ParameterChange::QFactor(q_factor) => filter.set_q_factor(q_factor),
+            ParameterChange::Gain(gain) => filter.set_gain(gain),
+            ParameterChange::ConstantSkirtGain(value) => filter.set_constant_skirt_gain(value),
+            ParameterChange::Bypass(bypass) => filter.set_bypass(bypass),
+        }
+    }
+
+    /// Returns the continuously variable parameter this change sets, if any.
+    pub fn parameter(&self) -> Option<Parameter> {
+        match self {
+            ParameterChange::Cutoff(_) => Some(Parameter::Cutoff),
+            ParameterChange::QFactor(_) => Some(Parameter::QFactor),
+            ParameterChange::Gain(_) => Some(Parameter::Gain),
+            _ => None,
+        }
+    }
+}
+
+/// A parameter change scheduled at a sample offset from the start of the next processed block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AutomationEvent<T> {
+    /// The index of the first sample processed with the new value.
+    pub offset: usize,
+    /// The change to apply.
+    pub change: ParameterChange<T>,
+}
+
+/// The interpolation curve followed by a parameter ramp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RampShape {
+    /// Linear in the physical quantity: hertz for the cutoff, linear amplitude for the gain, and
+    /// the raw value for the Q factor.
+    Linear,
+    /// Linear in octaves, so equal times cover equal frequency ratios. This is the natural shape
+    /// for frequency sweeps. Gains are interpolated in decibels.
+    #[default]
+    Octaves,
+    /// Linear in decibels. Gains, which are already expressed in decibels, move at a constant
+    /// rate; positive quantities such as the cutoff follow the same curve as `Octaves`.
+    Decibels,
+    /// An exponential approach in the linear domain that moves quickly at first and settles into
+    /// the target, like an analog RC control voltage.
+    Exponential,
+}
+
+impl RampShape {
+    /// Returns the value of a ramp of the given parameter from `start` to `target` at the
+    /// fraction `progress` of its length.
+    pub fn interpolate<T: Float>(self, parameter: Parameter, start: T, target: T, progress: T) -> T {
+        let progress = progress.max(T::zero()).min(T::one());
+        let logarithmic = matches!(self, RampShape::Octaves | RampShape::Decibels);
+        match parameter {
+            Parameter::Gain if !logarithmic => {
+                let weight = self.weight(progress);
+                let start = decibels_to_amplitude(start);
+                let target = decibels_to_amplitude(target);
+                amplitude_to_decibels(start + (target - start) * weight)
+            }
+            Parameter::Gain => start + (target - start) * progress,
+            _ if logarithmic && start > T::zero() && target > T::zero() => {
+                start * (target / start).powf(progress)
+            }
+            _ => start + (target - start) * self.weight(progress),
+        }
+    }
+
+    /// Maps linear progress onto the curve used in the linear domain.
+    fn weight<T: Float>(self, progress: T) -> T {
+        match self {
+            RampShape::Exponential => match T::from(5.0) {
+                Some(rate) => (T::one() - (-rate * progress).exp()) / (T::one() - (-rate).exp()),
+                None => progress,
+            },
+            _ => progress,
+        }
+    }
+}
+
+/// A ramp of one parameter that is in progress.
+#[derive(Debug, Clone, Copy)]
+struct Ramp<T> {
+    parameter: Parameter,
+    start: T,
+    target: T,
+    length: usize,
+    elapsed: usize,
+    shape: RampShape,
+}
+
+/// Wraps a filter and applies scheduled parameter changes at exact sample offsets while
+/// processing, the way plugin hosts deliver automation. Parameters can also be ramped smoothly
+/// across any number of blocks.
+#[derive(Debug, Clone)]
+pub struct AutomatedFilter<T, F> {
+    /// The filter being automated.
+    filter: F,
+    /// Pending events, ordered by offset and then by scheduling order.
+    events: Vec<AutomationEvent<T>>,
+    /// Ramps in progress, at most one per parameter.
+    ramps: Vec<Ramp<T>>,
+    /// The number of samples between coefficient updates while a ramp is active.
+    control_interval: usize,
+}
+
+impl<T, F> AutomatedFilter<T, F>
+where
+    T: Float,
+    F: Filter<T>,
+{
+    /// Wraps the given filter with an empty event list.
+    pub fn new(filter: F) -> Self {
+        Self::with_capacity(filter, 0)
+    }
+
+    /// Wraps the given filter, reserving room for `capacity` events so scheduling does not
+    /// allocate on the audio thread.
+    pub fn with_capacity(filter: F, capacity: usize) -> Self {
+        Self {
+            filter,
+            events: Vec::with_capacity(capacity),
+            ramps: Vec::with_capacity(3),
+            control_interval: DEFAULT_CONTROL_INTERVAL,
+        }
+    }
+
+    /// Schedules a change at `offset` samples from the start of the next processed block. Changes
+    /// scheduled at the same offset are applied in the order they were scheduled. Offsets beyond
+    /// the next block carry over to following blocks. A change to a parameter that is ramping
+    /// cancels the ramp.
+    pub fn schedule(&mut self, offset: usize, change: ParameterChange<T>) {
+        let index = self.events.partition_point(|event| event.offset <= offset);
+        self.events.insert(index, AutomationEvent { offset, change });
+    }
+
+    /// Starts ramping a parameter from its current value to `target` over the next `length`
+    /// samples, following the given shape. Replaces any ramp already running on the parameter.
+    pub fn ramp(&mut self, parameter: Parameter, target: T, length: usize, shape: RampShape) {
+        self.cancel_ramp(parameter);
+        self.ramps.push(Ramp {
+            parameter,
+            start: parameter.get(&self.filter),
+            target,
+            length,
+            elapsed: 0,
+            shape,
+        });
+    }
+
+    /// Stops ramping a parameter, leaving it at its current value.
+    pub fn cancel_ramp(&mut self, parameter: Parameter) {
+        self.ramps.retain(|ramp| ramp.parameter != parameter);
+    }
+
+    /// Returns whether a ramp is running on the given parameter.
+    pub fn is_ramping(&self, parameter: Parameter) -> bool {
+        self.ramps.iter().any(|ramp| ramp.parameter == parameter)
+    }
+
+    /// Returns the number of samples between coefficient updates while a ramp is active.
+    pub fn get_control_interval(&self) -> usize {
+        self.control_interval
+    }
+
+    /// Sets the number of samples between coefficient updates while a ramp is active. Smaller
+    /// intervals give smoother ramps at a higher cost. Returns `false` if the interval is zero.
+    pub fn set_control_interval(&mut self, control_interval: usize) -> bool {
+        if control_interval == 0 {
+            return false;
+        }
+        self.control_interval = control_interval;
+        true
+    }
+
+    /// Returns the events that have not been applied yet.
+    pub fn get_pending_events(&self) -> &[AutomationEvent<T>] {
+        &self.events
+    }
+
+    /// Discards every pending event.
+    pub fn clear_events(&mut self) {
+        self.events.clear();
+    }
+
+    /// Returns the wrapped filter.
+    pub fn get_filter(&self) -> &F {
+        &self.filter
+    }
+
+    /// Returns the wrapped filter mutably.
+    pub fn get_filter_mut(&mut self) -> &mut F {
+        &mut self.filter
+    }
+
+    /// Consumes the wrapper and returns the wrapped filter, discarding pending events and ramps.
+    pub fn into_inner(self) -> F {
+        self.filter
+    }
+
+    /// Processes a single sample in-place, applying any events scheduled at offset zero first.
+    pub fn process(&mut self, sample: &mut T) -> bool {
+        self.process_block(core::slice::from_mut(sample))
+    }
+
+    /// Processes a block of samples in-place, splitting it at every scheduled event that falls
+    /// inside the block and at every control interval while a ramp is active. Returns `false` if
+    /// processing or any of the applied changes failed.
+    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
+        if samples.is_empty() {
+            return false;
+        }
+        let length = samples.len();
+        let mut success = true;
+        let mut position = 0;
+        let mut applied = 0;
+        while position < length {
+            while let Some(event) = self.events.get(applied).filter(|event| event.offset <= position)
+            {
+                let change = event.change;
+                if let Some(parameter) = change.parameter() {
+                    self.cancel_ramp(parameter);
+                }
+                success &= change.apply(&mut self.filter);
+                applied += 1;
+            }
+            success &= self.update_ramps();
+
+            let mut end = length;
+            if let Some(event) = self.events.get(applied) {
+                end = end.min(event.offset);
+            }
+            for ramp in self.ramps.iter() {
+                let remaining = ramp.length - ramp.elapsed;
+                end = end.min(position + self.control_interval.min(remaining));
+            }
+            success &= self.filter.process_block(&mut samples[position..end]);
+            for ramp in self.ramps.iter_mut() {
+                ramp.elapsed += end - position;
+            }
+            position = end;
+        }
+        self.events.drain(..applied);
+        for event in self.events.iter_mut() {
+            event.offset -= length;
+        }
+        success
+    }
+
+    /// Moves every ramp to its value at the current position, removing those that have finished.
+    fn update_ramps(&mut self) -> bool {
+        let mut success = true;
+        for ramp in self.ramps.iter() {
+            let value = if ramp.elapsed >= ramp.length {
+                ramp.target
+            } else {
+                match (T::from(ramp.elapsed), T::from(ramp.length)) {
+                    (Some(elapsed), Some(length)) => ramp.shape.interpolate(
+                        ramp.parameter,
+                        ramp.start,
+                        ramp.target,
+                        elapsed / length,
+                    ),
+                    _ => ramp.target,
+                }
+            };
+            success &= ramp.parameter.change(value).apply(&mut self.filter);
+        }
+        self.ramps.retain(|ramp| ramp.elapsed < ramp.length);
+        success
+    }
+}
+
+/// Binds a plugin host parameter to one parameter of an `AutomatedFilter`, so that once per block
+/// the latest host value is ramped in across the block. Hosts report either plain values or
+/// normalized values between zero and one; normalized values are mapped onto the range with the
+/// binding's ramp shape, so `RampShape::Octaves` gives a logarithmic frequency knob.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParameterBinding<T> {
+    parameter: Parameter,
+    min: T,
+    max: T,
+    shape: RampShape,
+    /// The latest value reported by the host.
+    target: T,
+    /// The value last handed to the filter, if any.
+    applied: Option<T>,
+}
+
+impl<T: Float> ParameterBinding<T> {
+    /// Binds `parameter` over the range `min` to `max`, ramping with the given shape. The target
+    /// starts at `min`. Returns `None` unless `min < max` and both are finite, and positive for
+    /// the cutoff and Q factor.
+    pub fn new(parameter: Parameter, min: T, max: T, shape: RampShape) -> Option<Self> {
+        let positive = matches!(parameter, Parameter::Cutoff | Parameter::QFactor);
+        let valid = min.is_finite() && max.is_finite() && min < max && (!positive || min > T::zero());
+        if !valid {
+            return None;
+        }
+        Some(Self {
+            parameter,
+            min,
+            max,
+            shape,
+            target: min,
+            applied: None,
+        })
+    }
+
+    /// Returns the bound parameter.
+    pub fn get_parameter(&self) -> Parameter {
+        self.parameter
+    }
+
+    /// Returns the lowest and highest value of the parameter.
+    pub fn get_range(&self) -> (T, T) {
+        (self.min, self.max)
+    }
+
+    /// Returns the shape of the ramps and of the normalized mapping.
+    pub fn get_shape(&self) -> RampShape {
+        self.shape
+    }
+
+    /// Returns the latest value reported by the host.
+    pub fn get_value(&self) -> T {
+        self.target
+    }
+
+    /// Sets the value reported by the host, limited to the range. Returns `false` and keeps the
+    /// current value if it is NaN.
+    pub fn set_value(&mut self, value: T) -> bool {
+        if value.is_nan() {
+            return false;
+        }
+        self.target = value.max(self.min).min(self.max);
+        true
+    }
+
+    /// Sets the value from a normalized host value between zero and one, mapped onto the range
+    /// with the binding's shape. Returns `false` and keeps the current value if it is NaN.
+    pub fn set_normalized(&mut self, normalized: T) -> bool {
+        if normalized.is_nan() {
+            return false;
+        }
+        let value = self.shape.interpolate(self.parameter, self.min, self.max, normalized);
+        self.set_value(value)
+    }
+
+    /// Ramps the filter's parameter to the latest host value across the next `length` samples,
+    /// if it changed since the last call. Call this once per block, before processing it. Returns
+    /// whether a ramp was started.
+    pub fn apply<F: Filter<T>>(&mut self, filter: &mut AutomatedFilter<T, F>, length: usize) -> bool {
+        if self.applied == Some(self.target) {
+            return false;
+        }
+        filter.ramp(self.parameter, self.target, length, self.shape);
+        self.applied = Some(self.target);
+        true
+    }
+}
+
+/// Converts a gain in decibels to a linear amplitude.
+fn decibels_to_amplitude<T: Float>(decibels: T) -> T {
+    match T::from(20.0) {
+        Some(twenty) => T::from(10.0).map_or(T::one(), |ten| ten.powf(decibels / twenty)),
+        None => T::one(),
+    }
+}
+
+/// Converts a linear amplitude to a gain in decibels.
+fn amplitude_to_decibels<T: Float>(amplitude: T) -> T {
+    T::from(20.0).map_or(T::zero(), |twenty| twenty * amplitude.log10())
+}
@@ -1,5 +1,4 @@
 /// band_pass.rs
-
 /**
 Copyright © 2025 Alex Parisi
 
@@ -22,22 +21,26 @@ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
 use crate::filters::biquad::{Coefficients, DigitalBiquadFilter};
-use crate::filters::filter::BiquadFilterWrapper;
+use crate::filters::filter::{
+    BiquadFilterWrapper, impl_biquad_filter, impl_biquad_filter_wrapper,
+    impl_precision_conversions,
+};
+use crate::filters::design_method::{DesignMethod, matched_band_pass};
 use crate::filters::filter_configuration::FilterConfiguration;
 use num_traits::Float;
-use std::f64::consts::PI;
+use core::f64::consts::PI;
 
 
 /// Band-pass filter implementation using a digital biquad filter.
 #[derive(Debug, Clone)]
-pub struct BandPassFilter<T: Float + Default + Copy> {
+pub struct BandPassFilter<T: Float> {
     /// The digital biquad filter used for processing.
     filter: DigitalBiquadFilter<T>,
     /// The configuration for the filter, including cutoff frequency, sample rate, and Q factor.
     config: FilterConfiguration<T>,
 }
 
-impl<T: Float + Default + Copy + std::ops::MulAssign> BandPassFilter<T> {
+impl<T: Float> BandPassFilter<T> {
     /// Creates a new band-pass filter with the given cutoff frequency, sample rate, and Q factor.
     pub fn new(cutoff: T, sample_rate: u32, q_factor: T, constant_skirt: bool) -> Option<Self> {
         let config = FilterConfiguration::new(
@@ -54,31 +57,27 @@ impl<T: Float + Default + Copy + std::ops::MulAssign> BandPassFilter<T> {
     }
 }
 
-/// Provide internal access and coefficient logic via BiquadFilterWrapper.
-impl<T: Float + Default + Copy + std::ops::MulAssign> BiquadFilterWrapper<T> for BandPassFilter<T> {
-    fn get_filter(&mut self) -> &mut DigitalBiquadFilter<T> {
-        &mut self.filter
-    }
-
-    fn get_config(&self) -> &FilterConfiguration<T> {
-        &self.config
-    }
+impl_biquad_filter!(BandPassFilter);
+impl_precision_conversions!(BandPassFilter);
+impl_biquad_filter_wrapper!(BandPassFilter, BandPass);
 
-    fn get_config_mut(&mut self) -> &mut FilterConfiguration<T> {
-        &mut self.config
-    }
-
-    fn calculate_coefficients(config: &FilterConfiguration<T>) -> Option<Coefficients<T>> {
+impl<T: Float> BandPassFilter<T> {
+    /// Designs the band-pass coefficients for a configuration. Returns `None` if the configuration
+    /// is invalid.
+    fn design_coefficients(config: &FilterConfiguration<T>) -> Option<Coefficients<T>> {
+        if config.get_design_method() == DesignMethod::Matched {
+            return matched_band_pass(config);
+        }
         let cutoff = config.get_cutoff();
         let sample_rate = config.get_sample_rate();
         let q = config.get_q_factor();
 
-        if cutoff <= T::zero() || sample_rate == 0 || q <= T::zero() {
+        if !config.is_valid() {
             return None;
         }
 
-        let two = T::from(2.0).unwrap();
-        let pi = T::from(PI).unwrap();
+        let two = T::from(2.0)?;
+        let pi = T::from(PI)?;
         let one = T::one();
 
         let w0 = two * pi * cutoff / T::from(sample_rate)?;
@@ -1,5 +1,4 @@
 /// biquad.rs
-
 /**
 Copyright © 2025 Alex Parisi
 
@@ -21,12 +20,38 @@ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
 OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
+use crate::convert::Sample;
+use crate::filters::fault::{Fault, FaultHandler, Faults};
+#[cfg(feature = "std")]
+use crate::filters::fir::{self, FirApproximation, Window};
+#[cfg(feature = "std")]
+use crate::filters::notation::{self, Notation};
+#[cfg(feature = "state-history")]
+use crate::filters::history::{HistoryEntry, StateHistory};
+#[cfg(feature = "metering")]
+use crate::filters::metering::{Metering, Meters};
+use crate::filters::limiter::OutputLimit;
+use crate::filters::saturation::Saturation;
+use crate::filters::stats::{self, BlockStats};
+use crate::filters::topology::{self, Topology};
+use crate::filters::update::UpdatePolicy;
+use num_complex::Complex;
 use num_traits::Float;
-use std::ops::MulAssign;
 
+/// The most bisection steps used to find the steady state of a saturated filter, enough to narrow
+/// any `f64` interval to adjacent values.
+const STEADY_STATE_ITERATIONS: usize = 2200;
+
+/// Converts between float types. Conversions between floats always succeed, rounding to the
+/// nearest representable value and saturating to infinity.
+#[inline]
+pub(crate) fn cast<T: Float, U: Float>(value: T) -> U {
+    U::from(value).unwrap_or_else(U::nan)
+}
 
 /// Coefficients struct for the digital biquad filter.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Coefficients<T: Float> {
     pub b0: T,
     pub b1: T,
@@ -36,100 +61,997 @@ pub struct Coefficients<T: Float> {
     pub a2: T,
 }
 
+impl<T: Float> Coefficients<T> {
+    /// Returns whether the coefficients can be used by a filter: every coefficient must be finite
+    /// and `a0` must be non-zero.
+    pub fn is_valid(&self) -> bool {
+        [self.b0, self.b1, self.b2, self.a0, self.a1, self.a2]
+            .iter()
+            .all(|coefficient| coefficient.is_finite())
+            && !self.a0.is_zero()
+    }
+
+    /// Returns the coefficients divided by `a0`, as expected by `tick`. Returns `None` if the
+    /// coefficients are invalid before or after normalization.
+    pub fn normalized(&self) -> Option<Coefficients<T>> {
+        if !self.is_valid() {
+            return None;
+        }
+        let a0_inv = T::one() / self.a0;
+        let coefficients = Coefficients {
+            b0: self.b0 * a0_inv,
+            b1: self.b1 * a0_inv,
+            b2: self.b2 * a0_inv,
+            a0: T::one(),
+            a1: self.a1 * a0_inv,
+            a2: self.a2 * a0_inv,
+        };
+        coefficients.is_valid().then_some(coefficients)
+    }
+
+    /// Returns the normalized transfer function as plain text, such as
+    /// `H(z) = (0.2 + 0.4 z^-1 + 0.2 z^-2) / (1 - 0.37 z^-1 + 0.2 z^-2)`. Coefficients are printed
+    /// to six significant digits and zero terms are left out.
+    #[cfg(feature = "std")]
+    pub fn to_transfer_function_string(&self) -> String {
+        notation::transfer_function(core::slice::from_ref(self), Notation::Plain)
+    }
+
+    /// Returns the normalized transfer function as a LaTeX math expression, such as
+    /// `H(z) = \frac{0.2 + 0.4 z^{-1} + 0.2 z^{-2}}{1 - 0.37 z^{-1} + 0.2 z^{-2}}`.
+    #[cfg(feature = "std")]
+    pub fn to_latex(&self) -> String {
+        notation::transfer_function(core::slice::from_ref(self), Notation::Latex)
+    }
+
+    /// Converts the coefficients to another float type.
+    pub(crate) fn cast<U: Float>(&self) -> Coefficients<U> {
+        Coefficients {
+            b0: cast(self.b0),
+            b1: cast(self.b1),
+            b2: cast(self.b2),
+            a0: cast(self.a0),
+            a1: cast(self.a1),
+            a2: cast(self.a2),
+        }
+    }
+
+    /// Returns the coefficients a fraction `position` of the way from these to `target`.
+    pub(crate) fn interpolate(&self, target: &Coefficients<T>, position: T) -> Coefficients<T> {
+        let lerp = |from: T, to: T| from + (to - from) * position;
+        Coefficients {
+            b0: lerp(self.b0, target.b0),
+            b1: lerp(self.b1, target.b1),
+            b2: lerp(self.b2, target.b2),
+            a0: lerp(self.a0, target.a0),
+            a1: lerp(self.a1, target.a1),
+            a2: lerp(self.a2, target.a2),
+        }
+    }
+
+    /// Returns whether both poles lie strictly inside the unit circle, so the filter's response
+    /// decays instead of growing without bound.
+    pub fn is_stable(&self) -> bool {
+        let a1 = self.a1 / self.a0;
+        let a2 = self.a2 / self.a0;
+        a2.abs() < T::one() && a1.abs() < T::one() + a2
+    }
+
+    /// Returns the distance of the outermost pole from the unit circle, `1 - max pole radius`.
+    /// The margin is positive for stable coefficients, shrinks as the response rings for longer,
+    /// and is negative for unstable ones. Returns NaN if the coefficients are invalid.
+    pub fn stability_margin(&self) -> T {
+        let Some(coefficients) = self.normalized() else {
+            return T::nan();
+        };
+        let (a1, a2) = (coefficients.a1, coefficients.a2);
+        let half = -a1 / (T::one() + T::one());
+        let discriminant = half * half - a2;
+        let radius = if discriminant < T::zero() {
+            // Complex conjugate poles share a radius of sqrt(a2).
+            a2.sqrt()
+        } else {
+            half.abs() + discriminant.sqrt()
+        };
+        T::one() - radius
+    }
+
+    /// Returns whether a pole lies within `margin` of the unit circle, or outside it. Such
+    /// coefficients ring for a long time and can become unstable once they are quantized.
+    pub fn has_poles_near_unit_circle(&self, margin: T) -> bool {
+        let stability_margin = self.stability_margin();
+        stability_margin.is_nan() || stability_margin < margin
+    }
+
+    /// Returns the complex frequency response at the normalized angular frequency `omega`, in
+    /// radians per sample.
+    pub(crate) fn response(&self, omega: T) -> Complex<T> {
+        let z1 = Complex::from_polar(T::one(), -omega);
+        let z2 = z1 * z1;
+        let numerator = z2.scale(self.b2) + z1.scale(self.b1) + Complex::from(self.b0);
+        let denominator = z2.scale(self.a2) + z1.scale(self.a1) + Complex::from(self.a0);
+        numerator / denominator
+    }
+
+    /// Returns the group delay in samples at the given frequency in Hz: how long the envelope of
+    /// a narrow-band signal at that frequency is delayed. Returns NaN at a zero of the response.
+    pub fn group_delay(&self, frequency: T, sample_rate: u32) -> T {
+        let omega = T::from(2.0 * core::f64::consts::PI).unwrap_or_else(T::nan) * frequency
+            / T::from(sample_rate).unwrap_or_else(T::nan);
+        // The delay of a polynomial in z^-1 is Re(sum k c_k z^-k / sum c_k z^-k).
+        let delay = |c0: T, c1: T, c2: T| {
+            let z1 = Complex::from_polar(T::one(), -omega);
+            let z2 = z1 * z1;
+            let value = z2.scale(c2) + z1.scale(c1) + Complex::from(c0);
+            let weighted = z2.scale(c2 + c2) + z1.scale(c1);
+            (weighted / value).re
+        };
+        delay(self.b0, self.b1, self.b2) - delay(self.a0, self.a1, self.a2)
+    }
+
+    /// Returns the largest magnitude of the frequency response between DC and Nyquist, the
+    /// worst-case gain for any input.
+    pub fn max_gain(&self) -> T {
+        self.peak().1
+    }
+
+    /// Returns the normalized angular frequency in radians per sample at which the magnitude of
+    /// the response peaks, and the peak magnitude. Both squared magnitudes are quadratics in
+    /// `cos(w)`, so the peak is at an end of the band or at a root of the derivative of their
+    /// ratio.
+    pub(crate) fn peak(&self) -> (T, T) {
+        let two = T::one() + T::one();
+        let four = two * two;
+        let squared = |c0: T, c1: T, c2: T| {
+            (
+                c0 * c0 + c1 * c1 + c2 * c2 - two * c0 * c2,
+                two * (c0 * c1 + c1 * c2),
+                four * c0 * c2,
+            )
+        };
+        let (n0, n1, n2) = squared(self.b0, self.b1, self.b2);
+        let (d0, d1, d2) = squared(self.a0, self.a1, self.a2);
+        let gain = |c: T| {
+            let denominator = d0 + d1 * c + d2 * c * c;
+            if denominator > T::zero() {
+                // Rounding can leave the numerator slightly negative at a zero of the response.
+                ((n0 + n1 * c + n2 * c * c) / denominator).max(T::zero()).sqrt()
+            } else {
+                T::infinity()
+            }
+        };
+        let (mut peak_cos, mut peak) = (T::one(), gain(T::one()));
+        if gain(-T::one()) > peak {
+            (peak_cos, peak) = (-T::one(), gain(-T::one()));
+        }
+        let a = n2 * d1 - n1 * d2;
+        let b = two * (n2 * d0 - n0 * d2);
+        let c = n1 * d0 - n0 * d1;
+        let mut candidates = [None, None];
+        if a.is_zero() {
+            if !b.is_zero() {
+                candidates[0] = Some(-c / b);
+            }
+        } else {
+            let discriminant = b * b - four * a * c;
+            if discriminant >= T::zero() {
+                let root = discriminant.sqrt();
+                candidates = [Some((-b + root) / (two * a)), Some((-b - root) / (two * a))];
+            }
+        }
+        for c in candidates.into_iter().flatten() {
+            if c.abs() <= T::one() && gain(c) > peak {
+                (peak_cos, peak) = (c, gain(c));
+            }
+        }
+        (peak_cos.acos(), peak)
+    }
+}
+
+/// Rounds double-precision coefficients to single precision, for deploying designs computed in
+/// `f64` to `f32` filters.
+impl From<Coefficients<f64>> for Coefficients<f32> {
+    fn from(coefficients: Coefficients<f64>) -> Self {
+        coefficients.cast()
+    }
+}
+
+/// Widens single-precision coefficients to double precision without loss.
+impl From<Coefficients<f32>> for Coefficients<f64> {
+    fn from(coefficients: Coefficients<f32>) -> Self {
+        coefficients.cast()
+    }
+}
+
 /// State struct for storing the filter's internal state.
 #[derive(Debug, Clone, Copy)]
-pub struct State<T: Float + Default> {
+pub struct State<T: Float> {
     pub x1: T,
     pub x2: T,
     pub y1: T,
     pub y2: T,
 }
 
+impl<T: Float> State<T> {
+    /// Converts the state to another float type.
+    pub(crate) fn cast<U: Float>(&self) -> State<U> {
+        State {
+            x1: cast(self.x1),
+            x2: cast(self.x2),
+            y1: cast(self.y1),
+            y2: cast(self.y2),
+        }
+    }
+}
+
+/// Runs the Direct Form I difference equation for one sample, for processing many channels with
+/// one shared set of coefficients. Each channel keeps only its own `State`, so channels stay in
+/// sync when the shared coefficients change and no coefficients are duplicated. The coefficients
+/// must be normalized so `a0` is one; see `Coefficients::normalized`.
+#[inline(always)]
+pub fn tick<T: Float>(coefficients: &Coefficients<T>, state: &mut State<T>, sample: T) -> T {
+    let output = coefficients.b0 * sample + coefficients.b1 * state.x1 + coefficients.b2 * state.x2
+        - coefficients.a1 * state.y1
+        - coefficients.a2 * state.y2;
+    state.x2 = state.x1;
+    state.x1 = sample;
+    state.y2 = state.y1;
+    state.y1 = output;
+    output
+}
+
+/// Runs `tick` over a block of samples in place.
+pub fn tick_block<T: Float>(
+    coefficients: &Coefficients<T>,
+    state: &mut State<T>,
+    samples: &mut [T],
+) {
+    for sample in samples.iter_mut() {
+        *sample = tick(coefficients, state, *sample);
+    }
+}
+
+/// Multiplies two 2x2 matrices.
+fn multiply<T: Float>(left: &[[T; 2]; 2], right: &[[T; 2]; 2]) -> [[T; 2]; 2] {
+    core::array::from_fn(|row| {
+        core::array::from_fn(|column| left[row][0] * right[0][column] + left[row][1] * right[1][column])
+    })
+}
+
 /// Digital Biquad Filter implementation.
 #[derive(Debug, Clone)]
-pub struct DigitalBiquadFilter<T: Float + Default> {
+pub struct DigitalBiquadFilter<T: Float> {
+    /// The coefficients used for processing.
     coefficients: Coefficients<T>,
+    /// The coefficients exactly as they were given.
+    raw: Coefficients<T>,
+    /// The reciprocal of `coefficients.a0`, which is one unless normalization is disabled.
+    a0_inverse: T,
+    normalize: bool,
     state: State<T>,
+    saturation: Saturation<T>,
+    output_limit: OutputLimit<T>,
     iter: u64,
+    faults: Faults,
+    fault_handler: Option<FaultHandler>,
+    #[cfg(feature = "metering")]
+    metering: Option<Metering>,
+    #[cfg(feature = "state-history")]
+    history: Option<StateHistory<T>>,
+    update_policy: UpdatePolicy,
+    /// Coefficients waiting for the next block under `UpdatePolicy::NextBlock`.
+    pending: Option<PendingCoefficients<T>>,
+    /// Whether a wrapping filter defers recomputing coefficients until it next processes.
+    lazy: bool,
+    /// Whether the wrapping filter's configuration changed since the coefficients were computed.
+    stale: bool,
+    topology: Topology,
+    /// The rounding error of the last output, fed back under `Topology::DirectForm1ErrorFeedback`.
+    feedback_error: T,
+}
+
+/// Validated coefficients that have not been adopted yet.
+#[derive(Debug, Clone, Copy)]
+struct PendingCoefficients<T: Float> {
+    raw: Coefficients<T>,
+    coefficients: Coefficients<T>,
+    /// Whether the state is reset when the coefficients are adopted.
+    reset: bool,
 }
 
 impl<T> DigitalBiquadFilter<T>
 where
-    T: Float + Default + MulAssign + Copy,
+    T: Float,
 {
-    /// Creates a new filter instance with the given coefficients.
+    /// Creates a new filter instance with the given coefficients. Returns `None` if `a0` is zero
+    /// or any coefficient is not finite. Unstable coefficients are accepted but recorded as a
+    /// fault.
+    ///
+    /// The coefficients are normalized by dividing every one of them by `a0`, so
+    /// `get_coefficients` reports `a0` as one. The values as given are available from
+    /// `coefficients_raw`. Use `new_raw` to keep the coefficients unnormalized.
     pub fn new(coefficients: Coefficients<T>) -> Option<Self> {
-        if coefficients.a0.is_zero() {
-            return None;
-        }
-        let mut filter = Self {
+        Self::with_normalization(coefficients, true)
+    }
+
+    /// Creates a new filter that keeps the coefficients exactly as given and divides each output
+    /// by `a0` as it is computed, for comparing against designs that are not normalized. Returns
+    /// `None` if `a0` is zero or any coefficient is not finite.
+    pub fn new_raw(coefficients: Coefficients<T>) -> Option<Self> {
+        Self::with_normalization(coefficients, false)
+    }
+
+    /// Creates a new filter, normalizing the coefficients if `normalize` is set.
+    fn with_normalization(raw: Coefficients<T>, normalize: bool) -> Option<Self> {
+        let coefficients = Self::prepare_coefficients(raw, normalize)?;
+        Some(Self {
             coefficients,
+            raw,
+            a0_inverse: T::one() / coefficients.a0,
+            normalize,
             state: State::default(),
+            saturation: Saturation::Linear,
+            output_limit: OutputLimit::Unlimited,
             iter: 0,
-        };
-        filter.normalize_coefficients();
-        Some(filter)
+            faults: Faults {
+                unstable_coefficients: !coefficients.is_stable(),
+                ..Faults::default()
+            },
+            fault_handler: None,
+            #[cfg(feature = "metering")]
+            metering: None,
+            #[cfg(feature = "state-history")]
+            history: None,
+            update_policy: UpdatePolicy::Immediate,
+            pending: None,
+            lazy: false,
+            stale: false,
+            topology: Topology::DirectForm1,
+            feedback_error: T::zero(),
+        })
     }
 
     /// Processes a single sample.
     pub fn process(&mut self, sample: &mut T) -> bool {
-        let output = self.coefficients.b0 * *sample
-            + self.coefficients.b1 * self.state.x1
-            + self.coefficients.b2 * self.state.x2
-            - self.coefficients.a1 * self.state.y1
-            - self.coefficients.a2 * self.state.y2;
+        self.begin_block();
+        self.tick(sample);
+        self.finish_block();
+        true
+    }
+
+    /// Processes a block of samples.
+    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
+        self.run_block(samples, |sample| sample, |sample| sample)
+    }
+
+    /// Processes a block of samples of another format, such as 16-bit integers, converting each
+    /// sample to `T` and back as it is processed so no separate conversion pass is needed.
+    /// Integer results are rounded and clipped to full scale.
+    pub fn process_samples<S: Sample>(&mut self, samples: &mut [S]) -> bool {
+        self.run_block(samples, S::to_float, S::from_float)
+    }
+
+    /// Processes a block, converting each sample with `widen` before filtering and `narrow`
+    /// after.
+    #[inline(always)]
+    fn run_block<S: Copy>(
+        &mut self,
+        samples: &mut [S],
+        widen: impl Fn(S) -> T,
+        narrow: impl Fn(T) -> S,
+    ) -> bool {
+        if samples.is_empty() {
+            return false;
+        }
+        let smooth = self.update_policy == UpdatePolicy::Smoothed
+            && samples.len() > 1
+            && self.pending.is_some_and(|pending| !pending.reset);
+        match self.pending.take() {
+            Some(pending) if smooth => {
+                // Interpolate from the current coefficients to the pending ones across the block.
+                let start = self.coefficients;
+                let steps = T::from(samples.len()).unwrap_or_else(T::one);
+                for (n, sample) in samples.iter_mut().enumerate() {
+                    let position = T::from(n + 1).unwrap_or_else(T::one) / steps;
+                    self.coefficients = start.interpolate(&pending.coefficients, position);
+                    self.a0_inverse = T::one() / self.coefficients.a0;
+                    let mut value = widen(*sample);
+                    self.tick(&mut value);
+                    *sample = narrow(value);
+                }
+                self.adopt_coefficients(pending);
+            }
+            pending => {
+                if let Some(pending) = pending {
+                    self.adopt_coefficients(pending);
+                }
+                for sample in samples.iter_mut() {
+                    let mut value = widen(*sample);
+                    self.tick(&mut value);
+                    *sample = narrow(value);
+                }
+            }
+        }
+        self.finish_block();
+        true
+    }
+
+    /// Processes a block of samples and returns statistics about it, or `None` if the block is
+    /// empty. Subnormal outputs and filter state are flushed to zero.
+    pub fn process_block_with_stats(&mut self, samples: &mut [T]) -> Option<BlockStats<T>> {
+        let mut stats = BlockStats::measure(samples, |samples| self.process_block(samples))?;
+        let mut flushed = false;
+        for value in [
+            &mut self.state.x1,
+            &mut self.state.x2,
+            &mut self.state.y1,
+            &mut self.state.y2,
+        ] {
+            flushed |= stats::flush_denormal(value);
+        }
+        stats.denormals_flushed |= flushed;
+        Some(stats)
+    }
+
+    /// Processes `input` into `output`, leaving the input untouched. Returns `false` if the
+    /// slices differ in length or are empty.
+    pub fn process_block_to(&mut self, input: &[T], output: &mut [T]) -> bool {
+        if input.len() != output.len() {
+            return false;
+        }
+        output.copy_from_slice(input);
+        self.process_block(output)
+    }
+
+    /// Adopts coefficients held back by `UpdatePolicy::NextBlock`.
+    #[inline]
+    pub(crate) fn begin_block(&mut self) {
+        if let Some(pending) = self.pending.take() {
+            self.adopt_coefficients(pending);
+        }
+    }
+
+    /// Publishes the meter levels accumulated by `tick` since the last call.
+    #[inline]
+    pub(crate) fn finish_block(&mut self) {
+        #[cfg(feature = "metering")]
+        if let Some(metering) = self.metering.as_mut() {
+            metering.publish();
+        }
+    }
+
+    /// Runs the difference equation for one sample without publishing meter levels.
+    #[inline(always)]
+    pub(crate) fn tick(&mut self, sample: &mut T) {
+        let output = if self.topology == Topology::DirectForm1ErrorFeedback {
+            self.error_feedback_output(*sample)
+        } else {
+            (self.coefficients.b0 * *sample
+                + self.coefficients.b1 * self.state.x1
+                + self.coefficients.b2 * self.state.x2
+                - self.coefficients.a1 * self.state.y1
+                - self.coefficients.a2 * self.state.y2)
+                * self.a0_inverse
+        };
+
+        #[cfg(feature = "metering")]
+        if let Some(metering) = self.metering.as_mut() {
+            metering.record(*sample, output);
+        }
+        #[cfg(feature = "state-history")]
+        if let Some(history) = self.history.as_mut() {
+            history.record(HistoryEntry {
+                sample_index: self.iter,
+                input: *sample,
+                state: self.state,
+                output,
+            });
+        }
 
         self.state.x2 = self.state.x1;
         self.state.x1 = *sample;
         self.state.y2 = self.state.y1;
-        self.state.y1 = output;
-        *sample = output;
+        self.state.y1 = self.saturation.apply(output);
+        *sample = self.output_limit.apply(output);
 
-        self.iter += 1;
-        true
+        self.iter = self.iter.wrapping_add(1);
+        if !output.is_finite() {
+            self.report(Fault::NonFiniteOutput);
+        }
     }
 
-    /// Processes a block of samples.
-    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
-        if samples.is_empty() {
+    /// Computes the next output with the rounding error of the previous one added back, and
+    /// stores the new rounding error.
+    #[inline(always)]
+    fn error_feedback_output(&mut self, sample: T) -> T {
+        let c = &self.coefficients;
+        let (sum, error) = topology::error_feedback_sum(
+            [
+                (c.b0, sample),
+                (c.b1, self.state.x1),
+                (c.b2, self.state.x2),
+                (-c.a1, self.state.y1),
+                (-c.a2, self.state.y2),
+            ],
+            self.feedback_error,
+        );
+        let (output, error) = (sum * self.a0_inverse, error * self.a0_inverse);
+        // A non-finite output would poison every later sample through the feedback.
+        self.feedback_error = if error.is_finite() { error } else { T::zero() };
+        output
+    }
+
+    /// Records a fault, calling the fault handler the first time it is seen.
+    #[cold]
+    fn report(&mut self, fault: Fault) {
+        if self.faults.mark(fault) {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(instance = self as *const Self as usize, fault = ?fault, "filter fault");
+            if let Some(handler) = self.fault_handler {
+                handler(fault);
+            }
+        }
+    }
+
+    /// Returns the limit applied to the output.
+    pub fn get_output_limit(&self) -> OutputLimit<T> {
+        self.output_limit
+    }
+
+    /// Sets the limit applied to the output. The limit does not affect the fed-back signal, so
+    /// the response below the limit is unchanged. Returns `false` and keeps the current limit if
+    /// it is not positive and finite.
+    pub fn set_output_limit(&mut self, output_limit: OutputLimit<T>) -> bool {
+        if !output_limit.is_valid() {
             return false;
         }
-        for sample in samples.iter_mut() {
-            self.process(sample);
+        self.output_limit = output_limit;
+        true
+    }
+
+    /// Returns the faults detected since they were last taken.
+    pub fn get_faults(&self) -> Faults {
+        self.faults
+    }
+
+    /// Returns the faults detected since they were last taken and clears them, so the handler is
+    /// called again the next time each fault occurs.
+    pub fn take_faults(&mut self) -> Faults {
+        core::mem::take(&mut self.faults)
+    }
+
+    /// Sets the function called the first time each fault is detected, or removes it.
+    pub fn set_fault_handler(&mut self, handler: Option<FaultHandler>) {
+        self.fault_handler = handler;
+    }
+
+    /// Returns the number of samples processed since the filter was created or last reset. The
+    /// count wraps around to zero after `u64::MAX` samples, which takes millions of years at audio
+    /// rates. It is only cleared by `reset`; coefficient changes, including `set_coefficients`,
+    /// keep it running.
+    pub fn samples_processed(&self) -> u64 {
+        self.iter
+    }
+
+    /// Advances the sample count without processing, for samples that bypass the filter.
+    pub(crate) fn advance(&mut self, count: u64) {
+        self.iter = self.iter.wrapping_add(count);
+    }
+
+    /// Returns the coefficients used for processing. They are normalized so `a0` is one, unless
+    /// the filter was created with `new_raw`.
+    pub fn get_coefficients(&self) -> Coefficients<T> {
+        self.coefficients
+    }
+
+    /// Returns the coefficients exactly as they were last given, before any normalization.
+    pub fn coefficients_raw(&self) -> Coefficients<T> {
+        self.raw
+    }
+
+    /// Returns whether the filter normalizes its coefficients by `a0`.
+    pub fn is_normalized(&self) -> bool {
+        self.normalize
+    }
+
+    /// Sets new coefficients for the filter and resets its state. Returns `false` and keeps the
+    /// current coefficients if `a0` is zero or any coefficient is not finite. Under
+    /// `UpdatePolicy::NextBlock` the change is held until the next block.
+    pub fn set_coefficients(&mut self, raw: Coefficients<T>) -> bool {
+        self.change_coefficients(raw, true)
+    }
+
+    /// Sets new coefficients for the filter without resetting its state, so parameters can change
+    /// while audio is running. Returns `false` and keeps the current coefficients if `a0` is zero
+    /// or any coefficient is not finite. Under `UpdatePolicy::NextBlock` the change is held until
+    /// the next block.
+    pub fn update_coefficients(&mut self, raw: Coefficients<T>) -> bool {
+        self.change_coefficients(raw, false)
+    }
+
+    /// Returns when new coefficients take effect.
+    pub fn get_update_policy(&self) -> UpdatePolicy {
+        self.update_policy
+    }
+
+    /// Sets when new coefficients take effect. Coefficients held back by the previous policy are
+    /// adopted immediately.
+    pub fn set_update_policy(&mut self, update_policy: UpdatePolicy) {
+        self.update_policy = update_policy;
+        self.begin_block();
+    }
+
+    /// Returns the structure used to evaluate the difference equation.
+    pub fn get_topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Sets the structure used to evaluate the difference equation. The state carries over, so
+    /// the topology can change while processing.
+    pub fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+        self.feedback_error = T::zero();
+    }
+
+    /// Returns whether new coefficients are waiting for the next block.
+    pub fn has_pending_coefficients(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Returns whether the wrapping filter defers recomputing coefficients.
+    pub(crate) fn is_lazy(&self) -> bool {
+        self.lazy
+    }
+
+    /// Sets whether the wrapping filter defers recomputing coefficients.
+    pub(crate) fn set_lazy(&mut self, lazy: bool) {
+        self.lazy = lazy;
+    }
+
+    /// Records that the wrapping filter's configuration changed without recomputing the
+    /// coefficients.
+    pub(crate) fn mark_stale(&mut self) {
+        self.stale = true;
+    }
+
+    /// Returns whether the coefficients were marked stale, clearing the mark.
+    pub(crate) fn take_stale(&mut self) -> bool {
+        core::mem::take(&mut self.stale)
+    }
+
+    /// Validates new coefficients and adopts them or holds them for the next block, according to
+    /// the update policy.
+    fn change_coefficients(&mut self, raw: Coefficients<T>, reset: bool) -> bool {
+        let Some(coefficients) = Self::prepare_coefficients(raw, self.normalize) else {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(instance = self as *const Self as usize, "rejected coefficients");
+            return false;
+        };
+        let pending = PendingCoefficients {
+            raw,
+            coefficients,
+            reset,
+        };
+        match self.update_policy {
+            UpdatePolicy::Immediate => self.adopt_coefficients(pending),
+            UpdatePolicy::NextBlock | UpdatePolicy::Smoothed => {
+                // A reset requested by an earlier change still applies.
+                let reset = reset || self.pending.is_some_and(|earlier| earlier.reset);
+                self.pending = Some(PendingCoefficients { reset, ..pending });
+            }
         }
         true
     }
 
-    /// Sets new coefficients for the filter.
-    pub fn set_coefficients(&mut self, coefficients: Coefficients<T>) -> bool {
-        if coefficients.a0.is_zero() {
+    /// Starts using validated coefficients.
+    fn adopt_coefficients(&mut self, pending: PendingCoefficients<T>) {
+        self.store_coefficients(pending.raw, pending.coefficients);
+        if pending.reset {
+            self.clear_state();
+        }
+        if !pending.coefficients.is_stable() {
+            self.report(Fault::UnstableCoefficients);
+        }
+    }
+
+    /// Returns the saturation applied to the feedback path.
+    pub fn get_saturation(&self) -> Saturation<T> {
+        self.saturation
+    }
+
+    /// Sets the saturation applied to the output before it is fed back into the filter, which
+    /// bounds the resonance and adds harmonics at high levels. The saturation is memoryless, so the
+    /// filter can be run at an oversampled rate to keep those harmonics from aliasing. Returns
+    /// `false` and keeps the current saturation if its drive is not positive and finite.
+    pub fn set_saturation(&mut self, saturation: Saturation<T>) -> bool {
+        if !saturation.is_valid() {
             return false;
         }
-        self.coefficients = coefficients;
-        self.normalize_coefficients();
-        self.reset();
+        self.saturation = saturation;
         true
     }
 
+    /// Starts metering the input and output levels, with RMS levels averaged over roughly
+    /// `window` samples, and returns handles to the meters. Levels are published once per call to
+    /// `process` or `process_block`. Clones of the filter share its meters.
+    #[cfg(feature = "metering")]
+    pub fn enable_metering(&mut self, window: u32) -> Meters {
+        let metering = Metering::new(window);
+        let meters = metering.get_meters();
+        self.metering = Some(metering);
+        meters
+    }
+
+    /// Stops metering. Existing meter handles keep their last levels.
+    #[cfg(feature = "metering")]
+    pub fn disable_metering(&mut self) {
+        self.metering = None;
+    }
+
+    /// Returns handles to the meters, if metering is enabled.
+    #[cfg(feature = "metering")]
+    pub fn get_meters(&self) -> Option<Meters> {
+        self.metering.as_ref().map(Metering::get_meters)
+    }
+
+    /// Starts recording the last `capacity` processed samples together with the state each
+    /// output was computed from. The buffer is allocated here, so recording on the audio thread
+    /// never allocates.
+    #[cfg(feature = "state-history")]
+    pub fn enable_history(&mut self, capacity: usize) {
+        self.history = Some(StateHistory::new(capacity));
+    }
+
+    /// Stops recording and discards the history.
+    #[cfg(feature = "state-history")]
+    pub fn disable_history(&mut self) {
+        self.history = None;
+    }
+
+    /// Returns the recorded history, oldest first. Returns an empty history if recording is not
+    /// enabled.
+    #[cfg(feature = "state-history")]
+    pub fn get_history(&self) -> Vec<HistoryEntry<T>> {
+        self.history.as_ref().map(StateHistory::entries).unwrap_or_default()
+    }
+
     /// Resets the filter state.
     pub fn reset(&mut self) {
-        self.state = State::default();
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            instance = self as *const Self as usize,
+            samples_processed = self.iter,
+            "reset filter"
+        );
+        self.clear_state();
         self.iter = u64::default();
     }
 
-    /// Normalizes the coefficients by dividing all by a0.
-    fn normalize_coefficients(&mut self) {
-        let a0_inv = T::one() / self.coefficients.a0;
-        self.coefficients.b0 *= a0_inv;
-        self.coefficients.b1 *= a0_inv;
-        self.coefficients.b2 *= a0_inv;
-        self.coefficients.a1 *= a0_inv;
-        self.coefficients.a2 *= a0_inv;
-        self.coefficients.a0 = T::one();
+    /// Clears the state, the rounding error and the recorded history, keeping the sample count.
+    fn clear_state(&mut self) {
+        self.state = State::default();
+        self.feedback_error = T::zero();
+        #[cfg(feature = "state-history")]
+        if let Some(history) = self.history.as_mut() {
+            history.clear();
+        }
+    }
+
+    /// Advances the state as if `count` zero samples had been processed, for skipping silence or
+    /// seeking in offline processing. Once the input delay line is empty the outputs follow a
+    /// linear recurrence, so it is applied by raising its 2x2 matrix to the power `count` in
+    /// `O(log count)` steps. Saturation is not applied to the skipped outputs, and the rounding
+    /// error carried by `Topology::DirectForm1ErrorFeedback` is cleared, since it belongs to the
+    /// output before the jump.
+    pub fn skip(&mut self, count: u64) {
+        let b1 = self.coefficients.b1 * self.a0_inverse;
+        let b2 = self.coefficients.b2 * self.a0_inverse;
+        let a1 = self.coefficients.a1 * self.a0_inverse;
+        let a2 = self.coefficients.a2 * self.a0_inverse;
+        let state = &mut self.state;
+        let mut remaining = count;
+        while remaining > 0 && !(state.x1.is_zero() && state.x2.is_zero()) {
+            let output = b1 * state.x1 + b2 * state.x2 - a1 * state.y1 - a2 * state.y2;
+            *state = State {
+                x1: T::zero(),
+                x2: state.x1,
+                y1: output,
+                y2: state.y1,
+            };
+            remaining -= 1;
+        }
+        // Each zero sample maps (y1, y2) to (-a1 * y1 - a2 * y2, y1).
+        let mut step = [[-a1, -a2], [T::one(), T::zero()]];
+        let mut transition = [[T::one(), T::zero()], [T::zero(), T::one()]];
+        while remaining > 0 {
+            if remaining & 1 == 1 {
+                transition = multiply(&step, &transition);
+            }
+            step = multiply(&step, &step);
+            remaining >>= 1;
+        }
+        let (y1, y2) = (state.y1, state.y2);
+        state.y1 = transition[0][0] * y1 + transition[0][1] * y2;
+        state.y2 = transition[1][0] * y1 + transition[1][1] * y2;
+        if count > 0 {
+            self.feedback_error = T::zero();
+        }
+        self.advance(count);
+    }
+
+    /// Sets the state to the steady state reached after a constant input of `level`, so the
+    /// filter continues without a start-up transient when the signal resumes at that level. The
+    /// steady state includes the feedback saturation, and the rounding error carried by
+    /// `Topology::DirectForm1ErrorFeedback` is cleared. Returns the output it settles at, or
+    /// `None` and leaves the state unchanged if the DC gain is not finite.
+    pub fn settle(&mut self, level: T) -> Option<T> {
+        let c = self.coefficients;
+        let forward = level * (c.b0 + c.b1 + c.b2) / c.a0;
+        let feedback = (c.a1 + c.a2) / c.a0;
+        let output = match self.saturation {
+            Saturation::Linear => level * (c.b0 + c.b1 + c.b2) / (c.a0 + c.a1 + c.a2),
+            _ => self.saturated_steady_state(forward, feedback)?,
+        };
+        if !output.is_finite() {
+            return None;
+        }
+        let fed_back = self.saturation.apply(output);
+        self.state = State {
+            x1: level,
+            x2: level,
+            y1: fed_back,
+            y2: fed_back,
+        };
+        self.feedback_error = T::zero();
+        Some(output)
+    }
+
+    /// Solves `output = forward - feedback * saturation(output)` by bisection. The saturation is
+    /// bounded, so the output lies within `feedback` times that bound of `forward`.
+    fn saturated_steady_state(&self, forward: T, feedback: T) -> Option<T> {
+        if !forward.is_finite() || !feedback.is_finite() {
+            return None;
+        }
+        let bound = feedback.abs() * self.saturation.apply(T::max_value()).abs();
+        let (mut low, mut high) = (forward - bound, forward + bound);
+        for _ in 0..STEADY_STATE_ITERATIONS {
+            let middle = (low + high) / (T::one() + T::one());
+            if middle <= low || middle >= high {
+                break;
+            }
+            if middle + feedback * self.saturation.apply(middle) < forward {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        Some((low + high) / (T::one() + T::one()))
+    }
+
+    /// Approximates the filter with `len` FIR taps taken from its impulse response and tapered by
+    /// `window`, for targets that can only run FIR filters. Saturation and the output limit are
+    /// ignored. Returns `None` if `len` is zero or the impulse response does not stay finite.
+    #[cfg(feature = "std")]
+    pub fn to_fir(&self, len: usize, window: Window) -> Option<FirApproximation<T>> {
+        fir::approximate(&[self.coefficients], len, window)
+    }
+
+    /// Stores validated coefficients along with the values they were prepared from.
+    fn store_coefficients(&mut self, raw: Coefficients<T>, coefficients: Coefficients<T>) {
+        self.coefficients = coefficients;
+        self.raw = raw;
+        self.a0_inverse = T::one() / coefficients.a0;
+    }
+
+    /// Validates the coefficients, normalizing them if `normalize` is set. Returns `None` if the
+    /// coefficients are invalid.
+    fn prepare_coefficients(coefficients: Coefficients<T>, normalize: bool) -> Option<Coefficients<T>> {
+        if normalize {
+            Self::normalize_coefficients(coefficients)
+        } else {
+            coefficients.is_valid().then_some(coefficients)
+        }
+    }
+
+    /// Normalizes the coefficients by dividing all by a0. Returns `None` if the coefficients are
+    /// invalid before or after normalization.
+    fn normalize_coefficients(coefficients: Coefficients<T>) -> Option<Coefficients<T>> {
+        coefficients.normalized()
+    }
+}
+
+impl<T: Float> DigitalBiquadFilter<T> {
+    /// Converts the filter, including its state, to another float type. Meters are shared with
+    /// the original filter, and the state history is not carried over.
+    fn cast<U: Float>(&self) -> DigitalBiquadFilter<U> {
+        DigitalBiquadFilter {
+            coefficients: self.coefficients.cast(),
+            raw: self.raw.cast(),
+            a0_inverse: cast(self.a0_inverse),
+            normalize: self.normalize,
+            state: self.state.cast(),
+            saturation: self.saturation.cast(),
+            output_limit: self.output_limit.cast(),
+            iter: self.iter,
+            faults: self.faults,
+            fault_handler: self.fault_handler,
+            #[cfg(feature = "metering")]
+            metering: self.metering.clone(),
+            #[cfg(feature = "state-history")]
+            history: None,
+            update_policy: self.update_policy,
+            pending: self.pending.map(|pending| PendingCoefficients {
+                raw: pending.raw.cast(),
+                coefficients: pending.coefficients.cast(),
+                reset: pending.reset,
+            }),
+            lazy: self.lazy,
+            stale: self.stale,
+            topology: self.topology,
+            feedback_error: cast(self.feedback_error),
+        }
+    }
+}
+
+impl DigitalBiquadFilter<f64> {
+    /// Converts the filter, including its state, to single precision so it can continue
+    /// processing without a discontinuity.
+    pub fn to_f32(&self) -> DigitalBiquadFilter<f32> {
+        self.cast()
+    }
+}
+
+impl DigitalBiquadFilter<f32> {
+    /// Converts the filter, including its state, to double precision so it can continue
+    /// processing without a discontinuity.
+    pub fn to_f64(&self) -> DigitalBiquadFilter<f64> {
+        self.cast()
+    }
+}
+
+/// The default filter passes samples through unchanged.
+impl<T: Float> Default for DigitalBiquadFilter<T> {
+    fn default() -> Self {
+        Self {
+            coefficients: Coefficients {
+                b0: T::one(),
+                b1: T::zero(),
+                b2: T::zero(),
+                a0: T::one(),
+                a1: T::zero(),
+                a2: T::zero(),
+            },
+            raw: Coefficients {
+                b0: T::one(),
+                b1: T::zero(),
+                b2: T::zero(),
+                a0: T::one(),
+                a1: T::zero(),
+                a2: T::zero(),
+            },
+            a0_inverse: T::one(),
+            normalize: true,
+            state: State::default(),
+            saturation: Saturation::Linear,
+            output_limit: OutputLimit::Unlimited,
+            iter: 0,
+            faults: Faults::default(),
+            fault_handler: None,
+            #[cfg(feature = "metering")]
+            metering: None,
+            #[cfg(feature = "state-history")]
+            history: None,
+            update_policy: UpdatePolicy::Immediate,
+            pending: None,
+            lazy: false,
+            stale: false,
+            topology: Topology::DirectForm1,
+            feedback_error: T::zero(),
+        }
     }
 }
 
-impl<T: Float + Default> Default for State<T> {
+impl<T: Float> Default for State<T> {
     fn default() -> Self {
         Self {
             x1: T::zero(),
@@ -0,0 +1,122 @@
+/// butterworth.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::design::{butterworth_band_pass, butterworth_high_pass, butterworth_low_pass};
+use crate::filters::biquad::{Coefficients, DigitalBiquadFilter};
+use crate::filters::filter::FilterType;
+use crate::filters::response::{FrequencyResponse, series_response};
+use num_complex::Complex;
+use num_traits::Float;
+
+/// A Butterworth low-pass, high-pass or band-pass filter of any order, run as a cascade of the
+/// biquads designed by `design::butterworth_low_pass`, `butterworth_high_pass` and
+/// `butterworth_band_pass`.
+#[derive(Debug, Clone)]
+pub struct ButterworthFilter<T: Float> {
+    filter_type: FilterType,
+    order: usize,
+    sample_rate: u32,
+    sections: Vec<DigitalBiquadFilter<T>>,
+}
+
+impl<T: Float> ButterworthFilter<T> {
+    /// Creates an `order`-pole low-pass filter. Returns `None` if the order is zero or the cutoff
+    /// is not between zero and the Nyquist frequency.
+    pub fn low_pass(cutoff: T, sample_rate: u32, order: usize) -> Option<Self> {
+        let sections = butterworth_low_pass(order, cutoff, sample_rate)?;
+        Self::new(FilterType::LowPass, order, sample_rate, sections)
+    }
+
+    /// Creates an `order`-pole high-pass filter. Returns `None` if the order is zero or the cutoff
+    /// is not between zero and the Nyquist frequency.
+    pub fn high_pass(cutoff: T, sample_rate: u32, order: usize) -> Option<Self> {
+        let sections = butterworth_high_pass(order, cutoff, sample_rate)?;
+        Self::new(FilterType::HighPass, order, sample_rate, sections)
+    }
+
+    /// Creates a band-pass filter from an `order`-pole prototype, 3 dB down at both edges.
+    /// Returns `None` if the order is zero or the edges are not increasing and between zero and
+    /// the Nyquist frequency.
+    pub fn band_pass(low_cutoff: T, high_cutoff: T, sample_rate: u32, order: usize) -> Option<Self> {
+        let sections = butterworth_band_pass(order, low_cutoff, high_cutoff, sample_rate)?;
+        Self::new(FilterType::BandPass, order, sample_rate, sections)
+    }
+
+    fn new(
+        filter_type: FilterType,
+        order: usize,
+        sample_rate: u32,
+        sections: Vec<Coefficients<T>>,
+    ) -> Option<Self> {
+        Some(Self {
+            filter_type,
+            order,
+            sample_rate,
+            sections: sections
+                .into_iter()
+                .map(DigitalBiquadFilter::new)
+                .collect::<Option<Vec<_>>>()?,
+        })
+    }
+
+    /// Processes a single sample.
+    pub fn process(&mut self, sample: &mut T) -> bool {
+        self.sections.iter_mut().all(|section| section.process(sample))
+    }
+
+    /// Processes a block of samples. Returns `false` if the block is empty.
+    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
+        self.sections.iter_mut().all(|section| section.process_block(samples))
+    }
+
+    /// Returns whether the filter is a low-pass, high-pass or band-pass.
+    pub fn get_filter_type(&self) -> FilterType {
+        self.filter_type
+    }
+
+    /// Returns the order of the prototype, which is the number of poles of a low-pass or high-pass
+    /// and half the number of poles of a band-pass.
+    pub fn get_order(&self) -> usize {
+        self.order
+    }
+
+    /// Returns the sample rate.
+    pub fn get_sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Returns the biquad sections, in processing order.
+    pub fn get_sections(&self) -> &[DigitalBiquadFilter<T>] {
+        &self.sections
+    }
+
+    /// Resets the state of every section.
+    pub fn reset(&mut self) {
+        self.sections.iter_mut().for_each(DigitalBiquadFilter::reset);
+    }
+}
+
+impl<T: Float> FrequencyResponse<T> for ButterworthFilter<T> {
+    fn response_at(&self, frequency: T, sample_rate: u32) -> Complex<T> {
+        series_response(&self.sections, frequency, sample_rate)
+    }
+}
@@ -0,0 +1,503 @@
+/// cascade.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::{Coefficients, DigitalBiquadFilter};
+#[cfg(feature = "std")]
+use crate::filters::convolution::PartitionedConvolver;
+#[cfg(feature = "std")]
+use crate::filters::fir::{self, FirApproximation, Window};
+use crate::filters::limiter::OutputLimit;
+use crate::filters::response::{FrequencyResponse, series_response};
+#[cfg(feature = "std")]
+use crate::filters::notation::{self, Notation};
+use crate::filters::saturation::Saturation;
+use num_complex::Complex;
+use num_traits::Float;
+
+/// Number of samples of the impulse response measured by the `L1` and `L2` scaling norms.
+const SCALING_IMPULSE_LENGTH: usize = 1 << 16;
+
+/// The norm used to scale the internal nodes of a cascade, from the most conservative to the most
+/// aggressive. Each bounds the peak of a section's output for a different class of input whose
+/// peak is at most one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ScalingNorm {
+    /// The sum of the absolute impulse response. No input can overflow, but quiet signals lose
+    /// the most resolution.
+    L1,
+    /// The square root of the impulse response energy, which bounds the output for white noise
+    /// and other broadband inputs.
+    L2,
+    /// The peak of the magnitude response, which bounds the output for sinusoids at any
+    /// frequency.
+    #[default]
+    LInfinity,
+}
+
+/// A fixed series of `N` biquad sections. The section count is part of the type and the sections
+/// are stored inline, so no allocator is required.
+///
+/// An optional saturation can be applied after every section. The input of each saturating
+/// section is scaled down by its peak gain and the total is restored at the output, so every stage
+/// saturates at the same level relative to the input no matter how resonant it is.
+#[derive(Debug, Clone)]
+pub struct Cascade<T: Float, const N: usize> {
+    sections: [DigitalBiquadFilter<T>; N],
+    saturation: Saturation<T>,
+    /// The inverse peak gain of each section.
+    staging: [T; N],
+    /// The inverse of the scale factors folded into the sections by `apply_scaling`.
+    scaling: T,
+    /// The gain restored at the output of a saturating cascade: `scaling` times the product of
+    /// the peak gains of all sections.
+    makeup: T,
+    output_limit: OutputLimit<T>,
+}
+
+impl<T, const N: usize> Cascade<T, N>
+where
+    T: Float,
+{
+    /// The number of sections in the cascade.
+    pub const SECTIONS: usize = N;
+
+    /// Creates a new cascade with one section per set of coefficients. Returns `None` if any set
+    /// of coefficients is invalid.
+    pub fn new(coefficients: [Coefficients<T>; N]) -> Option<Self> {
+        let mut cascade = Self::default();
+        for (section, coefficients) in cascade.sections.iter_mut().zip(coefficients) {
+            if !section.set_coefficients(coefficients) {
+                return None;
+            }
+        }
+        cascade.update_staging();
+        Some(cascade)
+    }
+
+    /// Processes a single sample through every section.
+    pub fn process(&mut self, sample: &mut T) -> bool {
+        if self.saturation == Saturation::Linear {
+            for section in self.sections.iter_mut() {
+                section.process(sample);
+            }
+            *sample = self.output_limit.apply(*sample * self.scaling);
+            return true;
+        }
+        for (section, staging) in self.sections.iter_mut().zip(self.staging.iter()) {
+            *sample = *sample * *staging;
+            section.process(sample);
+            *sample = self.saturation.apply(*sample);
+        }
+        *sample = self.output_limit.apply(*sample * self.makeup);
+        true
+    }
+
+    /// Processes a block of samples through every section. Without saturation each sample runs
+    /// through all `N` sections before the next one starts. `N` is a constant, so the loop over
+    /// the sections is unrolled and the processor can overlap the work of neighbouring sections
+    /// instead of waiting on each section's feedback in turn.
+    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
+        if samples.is_empty() {
+            return false;
+        }
+        if self.saturation == Saturation::Linear {
+            for section in self.sections.iter_mut() {
+                section.begin_block();
+            }
+            for sample in samples.iter_mut() {
+                for section in self.sections.iter_mut() {
+                    section.tick(sample);
+                }
+            }
+            for section in self.sections.iter_mut() {
+                section.finish_block();
+            }
+            if self.scaling != T::one() {
+                for sample in samples.iter_mut() {
+                    *sample = *sample * self.scaling;
+                }
+            }
+            self.limit_block(samples);
+            return true;
+        }
+        for (section, staging) in self.sections.iter_mut().zip(self.staging.iter()) {
+            for sample in samples.iter_mut() {
+                *sample = *sample * *staging;
+            }
+            section.process_block(samples);
+            for sample in samples.iter_mut() {
+                *sample = self.saturation.apply(*sample);
+            }
+        }
+        for sample in samples.iter_mut() {
+            *sample = *sample * self.makeup;
+        }
+        self.limit_block(samples);
+        true
+    }
+
+    /// Returns the limit applied to the output of the cascade.
+    pub fn get_output_limit(&self) -> OutputLimit<T> {
+        self.output_limit
+    }
+
+    /// Sets the limit applied to the output of the last section. Returns `false` and keeps the
+    /// current limit if it is not positive and finite.
+    pub fn set_output_limit(&mut self, output_limit: OutputLimit<T>) -> bool {
+        if !output_limit.is_valid() {
+            return false;
+        }
+        self.output_limit = output_limit;
+        true
+    }
+
+    /// Applies the output limit to a processed block.
+    fn limit_block(&self, samples: &mut [T]) {
+        if self.output_limit != OutputLimit::Unlimited {
+            for sample in samples.iter_mut() {
+                *sample = self.output_limit.apply(*sample);
+            }
+        }
+    }
+
+    /// Returns the saturation applied after every section.
+    pub fn get_saturation(&self) -> Saturation<T> {
+        self.saturation
+    }
+
+    /// Sets the saturation applied after every section. A single drive controls all stages, and
+    /// signals at the input level pass through every stage below the point where it starts to
+    /// clip hard. Returns `false` and keeps the current saturation if its drive is not positive
+    /// and finite.
+    pub fn set_saturation(&mut self, saturation: Saturation<T>) -> bool {
+        if !saturation.is_valid() {
+            return false;
+        }
+        self.saturation = saturation;
+        true
+    }
+
+    /// Returns the gain applied at the output to undo the scaling folded into the sections by
+    /// `apply_scaling`, or one if the cascade has not been scaled.
+    pub fn get_output_gain(&self) -> T {
+        self.scaling
+    }
+
+    /// Returns the sections of the cascade.
+    pub fn get_sections(&self) -> &[DigitalBiquadFilter<T>; N] {
+        &self.sections
+    }
+
+    /// Sets new coefficients for the section at `index`. Returns `false` if the index is out of
+    /// range or the coefficients are invalid.
+    pub fn set_section_coefficients(&mut self, index: usize, coefficients: Coefficients<T>) -> bool {
+        let updated = match self.sections.get_mut(index) {
+            Some(section) => section.set_coefficients(coefficients),
+            None => false,
+        };
+        if updated {
+            self.update_staging();
+        }
+        updated
+    }
+
+    /// Resets the state of every section.
+    pub fn reset(&mut self) {
+        for section in self.sections.iter_mut() {
+            section.reset();
+        }
+    }
+
+    /// Returns a scale factor for the input of each section, computed from the coefficients so
+    /// that the transfer function from the cascade input to every section's output has a `norm`
+    /// of one. Applying the factors keeps a fixed-point implementation from overflowing between
+    /// sections; multiplying the output by the inverse of their product restores the overall gain.
+    /// Returns `None` if any norm is not positive and finite, such as for an unstable section.
+    ///
+    /// The `L1` and `L2` norms are measured over the first 65536 samples of the impulse response.
+    pub fn scale_factors(&self, norm: ScalingNorm) -> Option<[T; N]> {
+        let norms = match norm {
+            ScalingNorm::L1 | ScalingNorm::L2 => self.impulse_norms(norm == ScalingNorm::L2),
+            ScalingNorm::LInfinity => {
+                let mut norms = [T::one(); N];
+                for (count, norm) in norms.iter_mut().enumerate() {
+                    *norm = Series(&self.sections[..=count]).max_gain();
+                }
+                norms
+            }
+        };
+        let mut factors = [T::one(); N];
+        let mut applied = T::one();
+        for (factor, norm) in factors.iter_mut().zip(norms) {
+            let in_range = norm.is_finite() && norm > T::zero();
+            if !in_range {
+                return None;
+            }
+            *factor = (norm * applied).recip();
+            applied = applied * *factor;
+        }
+        Some(factors)
+    }
+
+    /// Scales the cascade for fixed-point processing by folding the `scale_factors` for `norm`
+    /// into the numerator of each section, so the signal between sections stays within the bound
+    /// of the norm for inputs within full scale. The inverse of their product is restored at the
+    /// output, so the overall response is unchanged. Coefficients set on a section afterwards are
+    /// used as given, so scale again after redesigning a section. Returns `false` and leaves the
+    /// cascade unchanged if the factors cannot be computed.
+    pub fn apply_scaling(&mut self, norm: ScalingNorm) -> bool {
+        let Some(factors) = self.scale_factors(norm) else {
+            return false;
+        };
+        for (section, factor) in self.sections.iter_mut().zip(factors) {
+            let mut coefficients = section.get_coefficients();
+            coefficients.b0 = coefficients.b0 * factor;
+            coefficients.b1 = coefficients.b1 * factor;
+            coefficients.b2 = coefficients.b2 * factor;
+            section.set_coefficients(coefficients);
+            self.scaling = self.scaling / factor;
+        }
+        self.update_staging();
+        true
+    }
+
+    /// Returns the L1 or L2 norm of the impulse response at the output of every section.
+    fn impulse_norms(&self, squared: bool) -> [T; N] {
+        let mut sections = self.sections.clone();
+        for section in sections.iter_mut() {
+            section.reset();
+        }
+        let mut norms = [T::zero(); N];
+        for index in 0..SCALING_IMPULSE_LENGTH {
+            let mut sample = if index == 0 { T::one() } else { T::zero() };
+            for (section, norm) in sections.iter_mut().zip(norms.iter_mut()) {
+                section.tick(&mut sample);
+                *norm = *norm + if squared { sample * sample } else { sample.abs() };
+            }
+        }
+        if squared {
+            for norm in norms.iter_mut() {
+                *norm = norm.sqrt();
+            }
+        }
+        norms
+    }
+
+    /// Returns the smallest stability margin of any section, `1 - max pole radius` over the whole
+    /// cascade. A cascade without sections has no poles and a margin of one.
+    pub fn stability_margin(&self) -> T {
+        self.sections
+            .iter()
+            .map(|section| section.get_coefficients().stability_margin())
+            .fold(T::one(), T::min)
+    }
+
+    /// Returns whether any section has a pole within `margin` of the unit circle, or outside it.
+    pub fn has_poles_near_unit_circle(&self, margin: T) -> bool {
+        self.sections
+            .iter()
+            .any(|section| section.get_coefficients().has_poles_near_unit_circle(margin))
+    }
+
+    /// Approximates the cascade with `len` FIR taps taken from its impulse response and tapered by
+    /// `window`. Saturation and the output limit are ignored, so this describes the linear
+    /// response. Returns `None` if `len` is zero or the impulse response does not stay finite.
+    #[cfg(feature = "std")]
+    pub fn to_fir(&self, len: usize, window: Window) -> Option<FirApproximation<T>> {
+        fir::approximate(&self.get_coefficients(), len, window)
+    }
+
+    /// Renders the first `len` samples of the impulse response into a convolver processing
+    /// blocks of `block_len` samples, which is much faster than the recursive sections for long
+    /// cascades over large offline buffers. `len` should cover the time the response takes to
+    /// decay, since the remainder is cut off. Saturation and the output limit are ignored. Returns
+    /// `None` if `len` is zero, `block_len` is not a power of two, or the impulse response does not
+    /// stay finite.
+    #[cfg(feature = "std")]
+    pub fn to_convolver(&self, len: usize, block_len: usize) -> Option<PartitionedConvolver<T>> {
+        let fir = self.to_fir(len, Window::Rectangular)?;
+        PartitionedConvolver::new(&fir.taps, block_len)
+    }
+
+    /// Returns the transfer function of the cascade as plain text, in product form with one
+    /// normalized factor per section.
+    #[cfg(feature = "std")]
+    pub fn to_transfer_function_string(&self) -> String {
+        notation::transfer_function(&self.get_coefficients(), Notation::Plain)
+    }
+
+    /// Returns the transfer function of the cascade as a LaTeX math expression, in product form
+    /// with one `\frac` per section.
+    #[cfg(feature = "std")]
+    pub fn to_latex(&self) -> String {
+        notation::transfer_function(&self.get_coefficients(), Notation::Latex)
+    }
+
+    /// Returns the coefficients of every section, with the output gain folded into the first.
+    #[cfg(feature = "std")]
+    fn get_coefficients(&self) -> Vec<Coefficients<T>> {
+        let mut coefficients: Vec<Coefficients<T>> =
+            self.sections.iter().map(DigitalBiquadFilter::get_coefficients).collect();
+        if let Some(first) = coefficients.first_mut() {
+            first.b0 = first.b0 * self.scaling;
+            first.b1 = first.b1 * self.scaling;
+            first.b2 = first.b2 * self.scaling;
+        }
+        coefficients
+    }
+
+    /// Recomputes the gain staging from the peak gain of every section. Sections without a finite,
+    /// non-zero peak are left unscaled.
+    fn update_staging(&mut self) {
+        self.makeup = self.scaling;
+        for (section, staging) in self.sections.iter().zip(self.staging.iter_mut()) {
+            let peak = section.get_coefficients().max_gain();
+            if peak.is_finite() && peak > T::zero() {
+                *staging = peak.recip();
+                self.makeup = self.makeup * peak;
+            } else {
+                *staging = T::one();
+            }
+        }
+    }
+}
+
+/// A borrowed series of sections, for searching the response of the first sections of a cascade.
+struct Series<'a, T: Float>(&'a [DigitalBiquadFilter<T>]);
+
+impl<T: Float> FrequencyResponse<T> for Series<'_, T> {
+    fn response_at(&self, frequency: T, sample_rate: u32) -> Complex<T> {
+        series_response(self.0, frequency, sample_rate)
+    }
+}
+
+/// The default cascade passes samples through unchanged.
+impl<T: Float, const N: usize> Default for Cascade<T, N> {
+    fn default() -> Self {
+        Self {
+            sections: core::array::from_fn(|_| DigitalBiquadFilter::default()),
+            saturation: Saturation::Linear,
+            staging: [T::one(); N],
+            scaling: T::one(),
+            makeup: T::one(),
+            output_limit: OutputLimit::Unlimited,
+        }
+    }
+}
+
+/// A series of up to `N` biquad sections backed by a `heapless::Vec`, so sections can be added
+/// and removed at runtime without an allocator.
+#[cfg(feature = "heapless")]
+#[derive(Debug, Clone)]
+pub struct BoundedCascade<T: Float, const N: usize> {
+    sections: heapless::Vec<DigitalBiquadFilter<T>, N>,
+}
+
+#[cfg(feature = "heapless")]
+impl<T, const N: usize> BoundedCascade<T, N>
+where
+    T: Float,
+{
+    /// The maximum number of sections in the cascade.
+    pub const CAPACITY: usize = N;
+
+    /// Creates a new cascade with no sections.
+    pub fn new() -> Self {
+        Self {
+            sections: heapless::Vec::new(),
+        }
+    }
+
+    /// Appends a section with the given coefficients. Returns `false` if the cascade is full or
+    /// the coefficients are invalid.
+    pub fn push_section(&mut self, coefficients: Coefficients<T>) -> bool {
+        match DigitalBiquadFilter::new(coefficients) {
+            Some(section) => self.sections.push(section).is_ok(),
+            None => false,
+        }
+    }
+
+    /// Removes and returns the section at `index`, if it exists.
+    pub fn remove_section(&mut self, index: usize) -> Option<DigitalBiquadFilter<T>> {
+        if index < self.sections.len() {
+            Some(self.sections.remove(index))
+        } else {
+            None
+        }
+    }
+
+    /// Removes every section.
+    pub fn clear(&mut self) {
+        self.sections.clear();
+    }
+
+    /// Returns the number of sections currently in the cascade.
+    pub fn len(&self) -> usize {
+        self.sections.len()
+    }
+
+    /// Returns whether the cascade has no sections.
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+
+    /// Returns the sections of the cascade.
+    pub fn get_sections(&self) -> &[DigitalBiquadFilter<T>] {
+        &self.sections
+    }
+
+    /// Processes a single sample through every section.
+    pub fn process(&mut self, sample: &mut T) -> bool {
+        for section in self.sections.iter_mut() {
+            section.process(sample);
+        }
+        true
+    }
+
+    /// Processes a block of samples through every section, one section at a time.
+    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
+        if samples.is_empty() {
+            return false;
+        }
+        for section in self.sections.iter_mut() {
+            section.process_block(samples);
+        }
+        true
+    }
+
+    /// Resets the state of every section.
+    pub fn reset(&mut self) {
+        for section in self.sections.iter_mut() {
+            section.reset();
+        }
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<T, const N: usize> Default for BoundedCascade<T, N>
+where
+    T: Float,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
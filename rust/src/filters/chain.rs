@@ -0,0 +1,441 @@
+/// chain.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::fault::{FaultHandler, Faults};
+use crate::filters::filter::Filter;
+use crate::filters::filter_configuration::FilterConfiguration;
+use crate::filters::limiter::OutputLimit;
+#[cfg(feature = "metering")]
+use crate::filters::metering::{Metered, Metering, Meters};
+use crate::filters::response::FrequencyResponse;
+use crate::filters::spec::{FilterSpec, SpecError};
+use crate::filters::update::UpdatePolicy;
+use num_complex::Complex;
+use num_traits::Float;
+
+/// A filter that can also report its frequency response, which is what a `FilterChain` or
+/// `ParallelBank` needs from each stage to report a response of its own. Every type that
+/// implements both traits implements this one.
+pub trait FilterStage<T: Float>: Filter<T> + FrequencyResponse<T> {}
+
+impl<T: Float, F: Filter<T> + FrequencyResponse<T> + ?Sized> FilterStage<T> for F {}
+
+/// A stage of a `FilterChain`.
+pub type ChainStage<T> = Box<dyn FilterStage<T> + Send>;
+
+/// Filters in series, run in order by a single `process_block` call. The chain is itself a
+/// `Filter`, so chains can be bypassed, reset, metered and nested like any other filter.
+///
+/// Every stage runs at the chain's sample rate: stages are retuned to it when they are added, and
+/// changing the chain's sample rate changes every stage. The chain's update policy and fault
+/// handler are applied to every stage in the same way. The cutoff, Q factor, gain and constant
+/// skirt gain of the `Filter` trait belong to the stages, so on the chain they always read as zero
+/// and cannot be set.
+pub struct FilterChain<T: Float> {
+    stages: Vec<ChainStage<T>>,
+    sample_rate: u32,
+    bypass: bool,
+    output_limit: OutputLimit<T>,
+    iter: u64,
+    fault_handler: Option<FaultHandler>,
+    #[cfg(feature = "metering")]
+    metering: Option<Metering>,
+    update_policy: UpdatePolicy,
+}
+
+impl<T: Float> FilterChain<T> {
+    /// Creates an empty chain, which passes samples through unchanged. Returns `None` if the
+    /// sample rate is zero.
+    pub fn new(sample_rate: u32) -> Option<Self> {
+        if sample_rate == 0 {
+            return None;
+        }
+        Some(Self {
+            stages: Vec::new(),
+            sample_rate,
+            bypass: false,
+            output_limit: OutputLimit::default(),
+            iter: 0,
+            fault_handler: None,
+            #[cfg(feature = "metering")]
+            metering: None,
+            update_policy: UpdatePolicy::Immediate,
+        })
+    }
+
+    /// Returns the number of stages.
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Returns whether the chain has no stages.
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Adds a stage to the end of the chain. Returns `false` and drops the stage if it cannot run
+    /// at the chain's sample rate.
+    pub fn push(&mut self, stage: ChainStage<T>) -> bool {
+        self.insert(self.stages.len(), stage)
+    }
+
+    /// Inserts a stage before the stage at `index`, or at the end if `index` equals the length.
+    /// Returns `false` and drops the stage if the index is past the end or the stage cannot run at
+    /// the chain's sample rate.
+    pub fn insert(&mut self, index: usize, mut stage: ChainStage<T>) -> bool {
+        if index > self.stages.len() || !stage.set_sample_rate(self.sample_rate) {
+            return false;
+        }
+        stage.set_update_policy(self.update_policy);
+        stage.set_fault_handler(self.fault_handler);
+        self.stages.insert(index, stage);
+        true
+    }
+
+    /// Removes and returns the stage at `index`, if it exists.
+    pub fn remove(&mut self, index: usize) -> Option<ChainStage<T>> {
+        (index < self.stages.len()).then(|| self.stages.remove(index))
+    }
+
+    /// Removes every stage.
+    pub fn clear(&mut self) {
+        self.stages.clear();
+    }
+
+    /// Returns the stage at `index`, if it exists.
+    pub fn get_stage(&self, index: usize) -> Option<&(dyn FilterStage<T> + Send)> {
+        self.stages.get(index).map(|stage| stage.as_ref())
+    }
+
+    /// Returns the stage at `index` for retuning, if it exists. Changing its sample rate leaves it
+    /// out of step with the rest of the chain.
+    pub fn get_stage_mut(&mut self, index: usize) -> Option<&mut (dyn FilterStage<T> + Send)> {
+        match self.stages.get_mut(index) {
+            Some(stage) => Some(stage.as_mut()),
+            None => None,
+        }
+    }
+
+    /// Returns an iterator over the stages, in processing order.
+    pub fn stages(&self) -> impl Iterator<Item = &(dyn FilterStage<T> + Send)> {
+        self.stages.iter().map(|stage| stage.as_ref())
+    }
+
+    /// Sets the sample rate of every stage. Returns `false` and restores the previous sample rate
+    /// of every stage if any stage rejects the new one.
+    fn retune(&mut self, sample_rate: u32) -> bool {
+        for index in 0..self.stages.len() {
+            if !self.stages[index].set_sample_rate(sample_rate) {
+                for stage in &mut self.stages[..index] {
+                    stage.set_sample_rate(self.sample_rate);
+                }
+                return false;
+            }
+        }
+        self.sample_rate = sample_rate;
+        true
+    }
+
+    /// Processes a single sample through every stage.
+    fn tick(&mut self, sample: &mut T) -> bool {
+        #[cfg(feature = "metering")]
+        let input = *sample;
+        let mut processed = true;
+        for stage in &mut self.stages {
+            processed &= stage.process(sample);
+        }
+        #[cfg(feature = "metering")]
+        if let Some(metering) = self.metering.as_mut() {
+            metering.record(input, *sample);
+        }
+        *sample = self.output_limit.apply(*sample);
+        processed
+    }
+
+    /// Publishes the meter levels accumulated since the last call.
+    fn finish_block(&mut self) {
+        #[cfg(feature = "metering")]
+        if let Some(metering) = self.metering.as_mut() {
+            metering.publish();
+        }
+    }
+
+    /// Returns whether the chain has to run sample by sample, for metering.
+    fn is_metered(&self) -> bool {
+        #[cfg(feature = "metering")]
+        if self.metering.is_some() {
+            return true;
+        }
+        false
+    }
+}
+
+impl<T: Float + Send + 'static> FilterChain<T> {
+    /// Builds a chain with one stage per spec, such as the specs returned by `parse_chain`.
+    /// Returns the first error if a spec cannot be built, or `SpecError::InvalidDesign` if the
+    /// sample rate is zero.
+    pub fn from_specs(specs: &[FilterSpec<T>], sample_rate: u32) -> Result<Self, SpecError> {
+        let mut chain = Self::new(sample_rate).ok_or(SpecError::InvalidDesign)?;
+        for spec in specs {
+            if !chain.push(spec.build(sample_rate)?) {
+                return Err(SpecError::InvalidDesign);
+            }
+        }
+        Ok(chain)
+    }
+}
+
+impl<T: Float> Filter<T> for FilterChain<T> {
+    /// Processes a single sample through every stage. Returns `false` if any stage failed.
+    fn process(&mut self, sample: &mut T) -> bool {
+        if self.bypass {
+            self.iter = self.iter.wrapping_add(1);
+            return true;
+        }
+        let processed = self.tick(sample);
+        self.iter = self.iter.wrapping_add(1);
+        self.finish_block();
+        processed
+    }
+
+    /// Processes a block of samples through every stage in turn. Returns `false` if the block is
+    /// empty or any stage failed.
+    fn process_block(&mut self, samples: &mut [T]) -> bool {
+        if samples.is_empty() {
+            return false;
+        }
+        self.iter = self.iter.wrapping_add(samples.len() as u64);
+        if self.bypass {
+            return true;
+        }
+        let mut processed = true;
+        if self.is_metered() {
+            for sample in samples.iter_mut() {
+                processed &= self.tick(sample);
+            }
+            self.finish_block();
+        } else {
+            for stage in &mut self.stages {
+                processed &= stage.process_block(samples);
+            }
+            if self.output_limit != OutputLimit::Unlimited {
+                for sample in samples.iter_mut() {
+                    *sample = self.output_limit.apply(*sample);
+                }
+            }
+        }
+        processed
+    }
+
+    /// Resets every stage and the sample count.
+    fn reset(&mut self) {
+        for stage in &mut self.stages {
+            stage.reset();
+        }
+        self.iter = 0;
+    }
+
+    /// Returns a configuration holding the sample rate and bypass state.
+    fn get_configuration(&self) -> FilterConfiguration<T> {
+        FilterConfiguration::new(
+            T::zero(),
+            self.sample_rate,
+            T::zero(),
+            T::zero(),
+            false,
+            self.bypass,
+        )
+    }
+
+    /// Sets the sample rate and bypass state. Returns `false` if the configuration also changes a
+    /// design parameter or the sample rate is rejected.
+    fn set_configuration(&mut self, configuration: FilterConfiguration<T>) -> bool {
+        let design_unchanged = configuration.get_cutoff() == T::zero()
+            && configuration.get_q_factor() == T::zero()
+            && configuration.get_gain() == T::zero()
+            && !configuration.get_constant_skirt_gain();
+        if !design_unchanged || !self.set_sample_rate(configuration.get_sample_rate()) {
+            return false;
+        }
+        self.bypass = configuration.get_bypass();
+        true
+    }
+
+    /// Returns zero, since the cutoff belongs to the stages.
+    fn get_cutoff(&self) -> T {
+        T::zero()
+    }
+
+    /// Returns `false`, since the cutoff belongs to the stages.
+    fn set_cutoff(&mut self, _cutoff: T) -> bool {
+        false
+    }
+
+    /// Returns the sample rate of the chain.
+    fn get_sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Sets the sample rate of the chain and every stage. Returns `false` and keeps the current
+    /// sample rate if it is zero or any stage rejects it.
+    fn set_sample_rate(&mut self, sample_rate: u32) -> bool {
+        sample_rate != 0 && self.retune(sample_rate)
+    }
+
+    /// Returns zero, since the Q factor belongs to the stages.
+    fn get_q_factor(&self) -> T {
+        T::zero()
+    }
+
+    /// Returns `false`, since the Q factor belongs to the stages.
+    fn set_q_factor(&mut self, _q_factor: T) -> bool {
+        false
+    }
+
+    /// Returns zero, since the gain belongs to the stages.
+    fn get_gain(&self) -> T {
+        T::zero()
+    }
+
+    /// Returns `false`, since the gain belongs to the stages.
+    fn set_gain(&mut self, _gain: T) -> bool {
+        false
+    }
+
+    /// Returns `false`, since the constant skirt gain belongs to the stages.
+    fn get_constant_skirt_gain(&self) -> bool {
+        false
+    }
+
+    /// Returns `false`, since the constant skirt gain belongs to the stages.
+    fn set_constant_skirt_gain(&mut self, _constant_skirt_gain: bool) -> bool {
+        false
+    }
+
+    /// Returns whether the whole chain is bypassed.
+    fn get_bypass(&self) -> bool {
+        self.bypass
+    }
+
+    /// Sets whether the whole chain is bypassed. The bypass state of each stage is kept.
+    fn set_bypass(&mut self, bypass: bool) -> bool {
+        self.bypass = bypass;
+        true
+    }
+
+    /// Returns the number of samples processed by the chain, including bypassed samples.
+    fn samples_processed(&self) -> u64 {
+        self.iter
+    }
+
+    /// Returns the limit applied to the output of the last stage.
+    fn get_output_limit(&self) -> OutputLimit<T> {
+        self.output_limit
+    }
+
+    /// Sets the limit applied to the output of the last stage.
+    fn set_output_limit(&mut self, output_limit: OutputLimit<T>) -> bool {
+        if !output_limit.is_valid() {
+            return false;
+        }
+        self.output_limit = output_limit;
+        true
+    }
+
+    /// Returns the faults any stage detected since they were last taken.
+    fn get_faults(&self) -> Faults {
+        self.stages.iter().fold(Faults::default(), |faults, stage| {
+            let stage_faults = stage.get_faults();
+            Faults {
+                non_finite_output: faults.non_finite_output || stage_faults.non_finite_output,
+                unstable_coefficients: faults.unstable_coefficients
+                    || stage_faults.unstable_coefficients,
+            }
+        })
+    }
+
+    /// Returns the faults any stage detected since they were last taken and clears them.
+    fn take_faults(&mut self) -> Faults {
+        let faults = self.get_faults();
+        for stage in &mut self.stages {
+            stage.take_faults();
+        }
+        faults
+    }
+
+    /// Sets the function called the first time each stage detects each fault, or removes it.
+    fn set_fault_handler(&mut self, handler: Option<FaultHandler>) {
+        self.fault_handler = handler;
+        for stage in &mut self.stages {
+            stage.set_fault_handler(handler);
+        }
+    }
+
+    /// Returns when parameter changes to the stages take effect.
+    fn get_update_policy(&self) -> UpdatePolicy {
+        self.update_policy
+    }
+
+    /// Sets when parameter changes to every stage take effect.
+    fn set_update_policy(&mut self, update_policy: UpdatePolicy) {
+        self.update_policy = update_policy;
+        for stage in &mut self.stages {
+            stage.set_update_policy(update_policy);
+        }
+    }
+}
+
+impl<T: Float> FrequencyResponse<T> for FilterChain<T> {
+    /// Returns the product of the stage responses, or a flat response while the chain is
+    /// bypassed.
+    fn response_at(&self, frequency: T, sample_rate: u32) -> Complex<T> {
+        if self.bypass {
+            return Complex::from(T::one());
+        }
+        self.stages
+            .iter()
+            .map(|stage| stage.response_at(frequency, sample_rate))
+            .fold(Complex::from(T::one()), |product, response| product * response)
+    }
+}
+
+#[cfg(feature = "metering")]
+impl<T: Float> Metered<T> for FilterChain<T> {
+    /// Starts metering the input and output levels of the whole chain. While metering, blocks
+    /// are run through the chain one sample at a time.
+    fn enable_metering(&mut self, window: u32) -> Meters {
+        let metering = Metering::new(window);
+        let meters = metering.get_meters();
+        self.metering = Some(metering);
+        meters
+    }
+
+    /// Stops metering.
+    fn disable_metering(&mut self) {
+        self.metering = None;
+    }
+
+    /// Returns handles to the meters, if metering is enabled.
+    fn get_meters(&self) -> Option<Meters> {
+        self.metering.as_ref().map(Metering::get_meters)
+    }
+}
@@ -0,0 +1,62 @@
+/// complementary.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::band_pass::BandPassFilter;
+use crate::filters::filter::Filter;
+use crate::filters::high_pass::HighPassFilter;
+use crate::filters::low_pass::LowPassFilter;
+use num_traits::Float;
+
+/// Filters that can also output the spectral complement of their response, the input minus the
+/// filtered signal. The biquad has no processing latency, so the complement is formed from the
+/// same input sample without any delay compensation and the two outputs always sum back to the
+/// input exactly. For a band-pass filter the complement is the matching notch.
+pub trait ComplementaryFilter<T: Float>: Filter<T> {
+    /// Processes a single sample in-place and returns its complement.
+    fn process_with_complement(&mut self, sample: &mut T) -> T {
+        let input = *sample;
+        self.process(sample);
+        input - *sample
+    }
+
+    /// Processes a block of samples in-place, writing the complement of each sample to
+    /// `complement`. Returns `false` if the block is empty or the slices differ in length.
+    fn process_block_with_complement(&mut self, samples: &mut [T], complement: &mut [T]) -> bool {
+        if samples.is_empty() || samples.len() != complement.len() {
+            return false;
+        }
+        complement.copy_from_slice(samples);
+        if !self.process_block(samples) {
+            return false;
+        }
+        for (complement, filtered) in complement.iter_mut().zip(samples.iter()) {
+            *complement = *complement - *filtered;
+        }
+        true
+    }
+}
+
+impl<T: Float> ComplementaryFilter<T> for LowPassFilter<T> {}
+
+impl<T: Float> ComplementaryFilter<T> for HighPassFilter<T> {}
+
+impl<T: Float> ComplementaryFilter<T> for BandPassFilter<T> {}
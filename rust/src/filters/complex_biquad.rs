@@ -0,0 +1,185 @@
+/// complex_biquad.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::Coefficients;
+use num_complex::Complex;
+use num_traits::Float;
+use core::f64::consts::PI;
+
+/// Complex coefficients for a biquad filter processing complex samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComplexCoefficients<T: Float> {
+    pub b0: Complex<T>,
+    pub b1: Complex<T>,
+    pub b2: Complex<T>,
+    pub a0: Complex<T>,
+    pub a1: Complex<T>,
+    pub a2: Complex<T>,
+}
+
+impl<T: Float> ComplexCoefficients<T> {
+    /// Returns whether the coefficients can be used by a filter: every coefficient must be finite
+    /// and `a0` must be non-zero.
+    pub fn is_valid(&self) -> bool {
+        [self.b0, self.b1, self.b2, self.a0, self.a1, self.a2]
+            .iter()
+            .all(|coefficient| coefficient.re.is_finite() && coefficient.im.is_finite())
+            && !(self.a0.re.is_zero() && self.a0.im.is_zero())
+    }
+
+    /// Moves the response of a real filter by `shift` Hz, so a low-pass becomes a band-pass
+    /// centered on `shift` that passes only positive (or negative) frequencies. Returns `None` if
+    /// the sample rate is zero.
+    pub fn frequency_shifted(coefficients: &Coefficients<T>, shift: T, sample_rate: u32) -> Option<Self> {
+        if sample_rate == 0 {
+            return None;
+        }
+        let omega = T::from(2.0 * PI)? * shift / T::from(sample_rate)?;
+        let rotation = Complex::from_polar(T::one(), omega);
+        let rotation2 = rotation * rotation;
+        Some(Self {
+            b0: Complex::from(coefficients.b0),
+            b1: rotation * coefficients.b1,
+            b2: rotation2 * coefficients.b2,
+            a0: Complex::from(coefficients.a0),
+            a1: rotation * coefficients.a1,
+            a2: rotation2 * coefficients.a2,
+        })
+    }
+}
+
+impl<T: Float> From<Coefficients<T>> for ComplexCoefficients<T> {
+    fn from(coefficients: Coefficients<T>) -> Self {
+        Self {
+            b0: Complex::from(coefficients.b0),
+            b1: Complex::from(coefficients.b1),
+            b2: Complex::from(coefficients.b2),
+            a0: Complex::from(coefficients.a0),
+            a1: Complex::from(coefficients.a1),
+            a2: Complex::from(coefficients.a2),
+        }
+    }
+}
+
+/// A biquad filter with complex coefficients that processes complex samples, such as I/Q data in
+/// software-defined radio or analytic signals. It runs the same Direct Form I recursion as
+/// `DigitalBiquadFilter`.
+#[derive(Debug, Clone)]
+pub struct ComplexBiquad<T: Float> {
+    coefficients: ComplexCoefficients<T>,
+    x1: Complex<T>,
+    x2: Complex<T>,
+    y1: Complex<T>,
+    y2: Complex<T>,
+}
+
+impl<T: Float> ComplexBiquad<T> {
+    /// Creates a new filter with the given coefficients. Returns `None` if `a0` is zero or any
+    /// coefficient is not finite.
+    pub fn new(coefficients: ComplexCoefficients<T>) -> Option<Self> {
+        Some(Self {
+            coefficients: Self::normalize_coefficients(coefficients)?,
+            x1: Complex::new(T::zero(), T::zero()),
+            x2: Complex::new(T::zero(), T::zero()),
+            y1: Complex::new(T::zero(), T::zero()),
+            y2: Complex::new(T::zero(), T::zero()),
+        })
+    }
+
+    /// Processes a single sample.
+    pub fn process(&mut self, sample: &mut Complex<T>) -> bool {
+        let c = &self.coefficients;
+        let output = c.b0 * *sample + c.b1 * self.x1 + c.b2 * self.x2 - c.a1 * self.y1 - c.a2 * self.y2;
+        self.x2 = self.x1;
+        self.x1 = *sample;
+        self.y2 = self.y1;
+        self.y1 = output;
+        *sample = output;
+        true
+    }
+
+    /// Processes a block of samples.
+    pub fn process_block(&mut self, samples: &mut [Complex<T>]) -> bool {
+        if samples.is_empty() {
+            return false;
+        }
+        for sample in samples.iter_mut() {
+            self.process(sample);
+        }
+        true
+    }
+
+    /// Returns the normalized coefficients of the filter.
+    pub fn get_coefficients(&self) -> ComplexCoefficients<T> {
+        self.coefficients
+    }
+
+    /// Sets new coefficients and resets the filter state. Returns `false` and keeps the current
+    /// coefficients if `a0` is zero or any coefficient is not finite.
+    pub fn set_coefficients(&mut self, coefficients: ComplexCoefficients<T>) -> bool {
+        if !self.update_coefficients(coefficients) {
+            return false;
+        }
+        self.reset();
+        true
+    }
+
+    /// Sets new coefficients without resetting the filter state. Returns `false` and keeps the
+    /// current coefficients if `a0` is zero or any coefficient is not finite.
+    pub fn update_coefficients(&mut self, coefficients: ComplexCoefficients<T>) -> bool {
+        match Self::normalize_coefficients(coefficients) {
+            Some(coefficients) => {
+                self.coefficients = coefficients;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Resets the filter state.
+    pub fn reset(&mut self) {
+        let zero = Complex::new(T::zero(), T::zero());
+        self.x1 = zero;
+        self.x2 = zero;
+        self.y1 = zero;
+        self.y2 = zero;
+    }
+
+    /// Normalizes the coefficients by dividing all by a0. Returns `None` if the coefficients are
+    /// invalid before or after normalization.
+    fn normalize_coefficients(coefficients: ComplexCoefficients<T>) -> Option<ComplexCoefficients<T>> {
+        if !coefficients.is_valid() {
+            return None;
+        }
+        let a0 = coefficients.a0;
+        let normalized = ComplexCoefficients {
+            b0: coefficients.b0 / a0,
+            b1: coefficients.b1 / a0,
+            b2: coefficients.b2 / a0,
+            a0: Complex::new(T::one(), T::zero()),
+            a1: coefficients.a1 / a0,
+            a2: coefficients.a2 / a0,
+        };
+        normalized.is_valid().then_some(normalized)
+    }
+}
@@ -0,0 +1,242 @@
+/// convolution.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use num_complex::Complex;
+use num_traits::Float;
+use core::f64::consts::PI;
+
+/// An FIR filter applied with uniformly partitioned FFT convolution. The taps are split into
+/// partitions of `block_len` samples whose spectra are computed once, so each block costs one pair
+/// of FFTs plus a multiply per partition instead of a multiply per tap and sample. This is the
+/// fast path for long, static chains: render their impulse response once with `to_convolver` and
+/// run it over hours of audio.
+///
+/// There is no added latency. Calls that end partway through a block are filtered from the part
+/// of the block received so far, at the cost of an extra FFT pair, so blocks of `block_len`
+/// samples are the most efficient.
+#[derive(Debug, Clone)]
+pub struct PartitionedConvolver<T: Float> {
+    block_len: usize,
+    tap_count: usize,
+    fft: Fft<T>,
+    /// The spectrum of each partition of the taps, zero-padded to twice the block length.
+    partitions: Vec<Vec<Complex<T>>>,
+    /// The spectra of the most recent complete input blocks, one fewer than the partitions.
+    history: Vec<Vec<Complex<T>>>,
+    /// The index in `history` of the most recent complete block.
+    newest: usize,
+    /// The contribution of the complete blocks to the output of the current block.
+    tail: Vec<Complex<T>>,
+    /// The previous input block followed by the current one.
+    input: Vec<T>,
+    /// How many samples of the current block have been received.
+    fill: usize,
+    spectrum: Vec<Complex<T>>,
+    output: Vec<Complex<T>>,
+}
+
+impl<T: Float> PartitionedConvolver<T> {
+    /// Creates a convolver for `taps`, processed in blocks of `block_len` samples. Returns `None`
+    /// if there are no taps, any tap is not finite, or `block_len` is not a power of two.
+    pub fn new(taps: &[T], block_len: usize) -> Option<Self> {
+        if taps.is_empty() || !block_len.is_power_of_two() || taps.iter().any(|tap| !tap.is_finite()) {
+            return None;
+        }
+        let fft = Fft::new(2 * block_len)?;
+        let partitions: Vec<Vec<Complex<T>>> = taps
+            .chunks(block_len)
+            .map(|chunk| {
+                let mut spectrum = vec![Complex::new(T::zero(), T::zero()); 2 * block_len];
+                for (bin, tap) in spectrum.iter_mut().zip(chunk) {
+                    bin.re = *tap;
+                }
+                fft.forward(&mut spectrum);
+                spectrum
+            })
+            .collect();
+        let silence = vec![Complex::new(T::zero(), T::zero()); 2 * block_len];
+        Some(Self {
+            block_len,
+            tap_count: taps.len(),
+            fft,
+            history: vec![silence.clone(); partitions.len() - 1],
+            partitions,
+            newest: 0,
+            tail: silence.clone(),
+            input: vec![T::zero(); 2 * block_len],
+            fill: 0,
+            spectrum: silence.clone(),
+            output: silence,
+        })
+    }
+
+    /// Returns the number of samples in each partition.
+    pub fn get_block_len(&self) -> usize {
+        self.block_len
+    }
+
+    /// Returns the number of FIR taps.
+    pub fn get_tap_count(&self) -> usize {
+        self.tap_count
+    }
+
+    /// Processes a single sample. Prefer `process_block`, since every call performs an FFT pair.
+    pub fn process(&mut self, sample: &mut T) -> bool {
+        self.process_block(core::slice::from_mut(sample))
+    }
+
+    /// Processes a block of samples of any length in place. Returns `false` if the block is empty.
+    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
+        if samples.is_empty() {
+            return false;
+        }
+        let mut remaining = samples;
+        while !remaining.is_empty() {
+            let count = remaining.len().min(self.block_len - self.fill);
+            let (current, rest) = remaining.split_at_mut(count);
+            self.filter(current);
+            remaining = rest;
+        }
+        true
+    }
+
+    /// Filters samples that fit in the remainder of the current block.
+    fn filter(&mut self, samples: &mut [T]) {
+        let start = self.block_len + self.fill;
+        self.input[start..start + samples.len()].copy_from_slice(samples);
+        for (bin, sample) in self.spectrum.iter_mut().zip(self.input.iter()) {
+            *bin = Complex::new(*sample, T::zero());
+        }
+        self.fft.forward(&mut self.spectrum);
+        for ((output, spectrum), (partition, tail)) in self
+            .output
+            .iter_mut()
+            .zip(self.spectrum.iter())
+            .zip(self.partitions[0].iter().zip(self.tail.iter()))
+        {
+            *output = *spectrum * *partition + *tail;
+        }
+        self.fft.inverse(&mut self.output);
+        for (sample, output) in samples.iter_mut().zip(self.output[start..].iter()) {
+            *sample = output.re;
+        }
+        self.fill += samples.len();
+        if self.fill == self.block_len {
+            self.finish_block();
+        }
+    }
+
+    /// Stores the spectrum of the completed block and precomputes the contribution of every
+    /// complete block to the next one.
+    fn finish_block(&mut self) {
+        let count = self.history.len();
+        if count > 0 {
+            self.newest = (self.newest + 1) % count;
+            self.history[self.newest].copy_from_slice(&self.spectrum);
+        }
+        self.tail.fill(Complex::new(T::zero(), T::zero()));
+        for (lag, partition) in self.partitions.iter().enumerate().skip(1) {
+            let spectrum = &self.history[(self.newest + count + 1 - lag) % count];
+            for ((tail, partition), spectrum) in self.tail.iter_mut().zip(partition).zip(spectrum) {
+                *tail = *tail + *partition * *spectrum;
+            }
+        }
+        self.input.copy_within(self.block_len.., 0);
+        self.input[self.block_len..].fill(T::zero());
+        self.fill = 0;
+    }
+
+    /// Clears the input history.
+    pub fn reset(&mut self) {
+        let silence = Complex::new(T::zero(), T::zero());
+        for spectrum in self.history.iter_mut() {
+            spectrum.fill(silence);
+        }
+        self.tail.fill(silence);
+        self.input.fill(T::zero());
+        self.newest = 0;
+        self.fill = 0;
+    }
+}
+
+/// An iterative radix-2 FFT of a fixed power-of-two size.
+#[derive(Debug, Clone)]
+struct Fft<T: Float> {
+    /// `exp(-2 pi i k / size)` for the first half of the size.
+    twiddles: Vec<Complex<T>>,
+    scale: T,
+}
+
+impl<T: Float> Fft<T> {
+    /// Creates an FFT of `size` points, which must be a power of two.
+    fn new(size: usize) -> Option<Self> {
+        let twiddles = (0..size / 2)
+            .map(|index| {
+                let angle = -2.0 * PI * index as f64 / size as f64;
+                Some(Complex::new(T::from(angle.cos())?, T::from(angle.sin())?))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self {
+            twiddles,
+            scale: T::from(size)?.recip(),
+        })
+    }
+
+    /// Replaces `data` with its discrete Fourier transform.
+    fn forward(&self, data: &mut [Complex<T>]) {
+        let size = data.len();
+        let bits = size.trailing_zeros();
+        if bits == 0 {
+            return;
+        }
+        for index in 0..size {
+            let reversed = index.reverse_bits() >> (usize::BITS - bits);
+            if index < reversed {
+                data.swap(index, reversed);
+            }
+        }
+        let mut len = 2;
+        while len <= size {
+            let stride = size / len;
+            for block in data.chunks_exact_mut(len) {
+                let (low, high) = block.split_at_mut(len / 2);
+                for (index, (low, high)) in low.iter_mut().zip(high.iter_mut()).enumerate() {
+                    let product = *high * self.twiddles[index * stride];
+                    *high = *low - product;
+                    *low = *low + product;
+                }
+            }
+            len *= 2;
+        }
+    }
+
+    /// Replaces `data` with its inverse discrete Fourier transform.
+    fn inverse(&self, data: &mut [Complex<T>]) {
+        for value in data.iter_mut() {
+            *value = value.conj();
+        }
+        self.forward(data);
+        for value in data.iter_mut() {
+            *value = value.conj().scale(self.scale);
+        }
+    }
+}
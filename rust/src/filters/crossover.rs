@@ -0,0 +1,423 @@
+/// crossover.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::{Coefficients, DigitalBiquadFilter};
+use crate::filters::cascade::Cascade;
+use crate::filters::thiran::ThiranDelay;
+use num_complex::Complex;
+use num_traits::Float;
+use core::f64::consts::{FRAC_1_SQRT_2, PI};
+
+/// The filter design used to split a signal into low and high bands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CrossoverMode {
+    /// Fourth-order Linkwitz-Riley: each band is a pair of cascaded second-order Butterworth
+    /// sections. Both bands are -6 dB at the crossover frequency.
+    LinkwitzRiley4,
+    /// An odd-order Butterworth low-pass split into the sum of two all-pass branches, with the
+    /// high band taken as their difference. The bands sum to an all-pass response, so the summed
+    /// magnitude is exactly flat, and their powers sum to one, so both bands are -3 dB at the
+    /// crossover frequency. `order` must be odd.
+    AllpassComplementary { order: usize },
+}
+
+/// The internal filter structure of a crossover.
+#[derive(Debug, Clone)]
+enum Topology<T: Float> {
+    LinkwitzRiley {
+        low: Box<Cascade<T, 2>>,
+        high: Box<Cascade<T, 2>>,
+    },
+    AllpassComplementary {
+        /// The branch holding the real pole. The low band is the average of the branches.
+        first: Vec<DigitalBiquadFilter<T>>,
+        /// The other branch. The high band is half of this branch minus the first.
+        second: Vec<DigitalBiquadFilter<T>>,
+    },
+}
+
+/// A two-way crossover that splits one input into low and high bands.
+#[derive(Debug, Clone)]
+pub struct Crossover<T: Float> {
+    mode: CrossoverMode,
+    frequency: T,
+    sample_rate: u32,
+    topology: Topology<T>,
+}
+
+impl<T: Float> Crossover<T> {
+    /// Creates a crossover at the given frequency. Returns `None` if the frequency is not between
+    /// zero and the Nyquist frequency or the mode's order is invalid.
+    pub fn new(mode: CrossoverMode, frequency: T, sample_rate: u32) -> Option<Self> {
+        Some(Self {
+            mode,
+            frequency,
+            sample_rate,
+            topology: Self::design(mode, frequency, sample_rate)?,
+        })
+    }
+
+    /// Designs the filters for the given mode.
+    fn design(mode: CrossoverMode, frequency: T, sample_rate: u32) -> Option<Topology<T>> {
+        let nyquist = T::from(sample_rate)? / T::from(2.0)?;
+        if !(frequency > T::zero() && frequency < nyquist) {
+            return None;
+        }
+        let k = (T::from(PI)? * frequency / T::from(sample_rate)?).tan();
+        match mode {
+            CrossoverMode::LinkwitzRiley4 => {
+                let q = T::from(FRAC_1_SQRT_2)?;
+                let low = butterworth_section(k, q, false)?;
+                let high = butterworth_section(k, q, true)?;
+                Some(Topology::LinkwitzRiley {
+                    low: Box::new(Cascade::new([low, low])?),
+                    high: Box::new(Cascade::new([high, high])?),
+                })
+            }
+            CrossoverMode::AllpassComplementary { order } => {
+                if order % 2 == 0 {
+                    return None;
+                }
+                let pole = (T::one() - k) / (T::one() + k);
+                let mut first = vec![DigitalBiquadFilter::new(Coefficients {
+                    b0: -pole,
+                    b1: T::one(),
+                    b2: T::zero(),
+                    a0: T::one(),
+                    a1: -pole,
+                    a2: T::zero(),
+                })?];
+                let mut second = Vec::new();
+                for pair in 1..=order / 2 {
+                    let angle = T::from(PI * pair as f64 / order as f64)?;
+                    let q = T::one() / (T::from(2.0)? * angle.cos());
+                    let section = DigitalBiquadFilter::new(allpass_section(k, q)?)?;
+                    if pair % 2 == 1 {
+                        second.push(section);
+                    } else {
+                        first.push(section);
+                    }
+                }
+                Some(Topology::AllpassComplementary { first, second })
+            }
+        }
+    }
+
+    /// Processes one sample and returns the low and high band outputs.
+    pub fn process(&mut self, sample: T) -> (T, T) {
+        match &mut self.topology {
+            Topology::LinkwitzRiley { low, high } => {
+                let mut low_sample = sample;
+                let mut high_sample = sample;
+                low.process(&mut low_sample);
+                high.process(&mut high_sample);
+                (low_sample, high_sample)
+            }
+            Topology::AllpassComplementary { first, second } => {
+                let mut first_sample = sample;
+                let mut second_sample = sample;
+                for section in first.iter_mut() {
+                    section.process(&mut first_sample);
+                }
+                for section in second.iter_mut() {
+                    section.process(&mut second_sample);
+                }
+                let half = (T::one() + T::one()).recip();
+                (
+                    (first_sample + second_sample) * half,
+                    (second_sample - first_sample) * half,
+                )
+            }
+        }
+    }
+
+    /// Processes a block of samples, writing the bands to `low` and `high`. Returns `false` if the
+    /// input is empty or the outputs are not as long as the input.
+    pub fn process_block(&mut self, input: &[T], low: &mut [T], high: &mut [T]) -> bool {
+        if input.is_empty() || low.len() != input.len() || high.len() != input.len() {
+            return false;
+        }
+        for ((sample, low), high) in input.iter().zip(low.iter_mut()).zip(high.iter_mut()) {
+            (*low, *high) = self.process(*sample);
+        }
+        true
+    }
+
+    /// Returns the crossover mode.
+    pub fn get_mode(&self) -> CrossoverMode {
+        self.mode
+    }
+
+    /// Returns the crossover frequency.
+    pub fn get_frequency(&self) -> T {
+        self.frequency
+    }
+
+    /// Sets the crossover frequency, redesigning the filters and resetting their state. Returns
+    /// `false` and keeps the current design if the frequency is invalid.
+    pub fn set_frequency(&mut self, frequency: T) -> bool {
+        match Self::design(self.mode, frequency, self.sample_rate) {
+            Some(topology) => {
+                self.frequency = frequency;
+                self.topology = topology;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the sample rate.
+    pub fn get_sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Returns the group delays of the low and high bands in samples at the given frequency in
+    /// Hz, or `None` if the frequency is not between zero and the Nyquist frequency.
+    pub fn group_delays(&self, frequency: T) -> Option<(T, T)> {
+        let omega = T::from(2.0 * PI)? * frequency / T::from(self.sample_rate)?;
+        let in_range = omega > T::zero() && omega < T::from(PI)?;
+        if !in_range {
+            return None;
+        }
+        // The phase slope is estimated from the responses just either side of the frequency.
+        let step = T::from(1e-4)?;
+        let (low_below, high_below) = self.band_responses(omega - step);
+        let (low_above, high_above) = self.band_responses(omega + step);
+        let delay = |below: Complex<T>, above: Complex<T>| -(above / below).arg() / (step + step);
+        Some((delay(low_below, low_above), delay(high_below, high_above)))
+    }
+
+    /// Returns the group delay of the summed bands in samples at the given frequency in Hz.
+    fn all_pass_group_delay(&self, frequency: T) -> Option<T> {
+        let omega = T::from(2.0 * PI)? * frequency / T::from(self.sample_rate)?;
+        let step = T::from(1e-4)?;
+        let (low_below, high_below) = self.band_responses(omega - step);
+        let (low_above, high_above) = self.band_responses(omega + step);
+        Some(-((low_above + high_above) / (low_below + high_below)).arg() / (step + step))
+    }
+
+    /// Returns the complex responses of the low and high bands at the normalized angular
+    /// frequency `omega`.
+    fn band_responses(&self, omega: T) -> (Complex<T>, Complex<T>) {
+        let product = |sections: &[DigitalBiquadFilter<T>]| {
+            sections
+                .iter()
+                .map(|section| section.get_coefficients().response(omega))
+                .fold(Complex::from(T::one()), |product, response| product * response)
+        };
+        match &self.topology {
+            Topology::LinkwitzRiley { low, high } => {
+                (product(low.get_sections()), product(high.get_sections()))
+            }
+            Topology::AllpassComplementary { first, second } => {
+                let first = product(first);
+                let second = product(second);
+                let half = (T::one() + T::one()).recip();
+                ((first + second).scale(half), (second - first).scale(half))
+            }
+        }
+    }
+
+    /// Resets the state of every filter.
+    pub fn reset(&mut self) {
+        match &mut self.topology {
+            Topology::LinkwitzRiley { low, high } => {
+                low.reset();
+                high.reset();
+            }
+            Topology::AllpassComplementary { first, second } => {
+                for section in first.iter_mut().chain(second.iter_mut()) {
+                    section.reset();
+                }
+            }
+        }
+    }
+}
+
+/// A multi-way crossover built from two-way crossovers in series: the first splits off the lowest
+/// band, the second splits the remainder, and so on, so band `i` passes through the high band of
+/// every earlier crossover and the low band of its own. Each band is also passed through the
+/// summed bands of every later crossover, an all-pass that gives all bands the same phase so they
+/// sum to a flat response.
+///
+/// Bands still differ in group delay, since each is shaped by different filters. In aligned mode
+/// each band is delayed by the difference between its group delay and the largest group delay of
+/// any band, measured at the centre of each band, so transients line up across the bands. The
+/// alignment trades the flat summed response for time coherence.
+#[derive(Debug, Clone)]
+pub struct CrossoverNetwork<T: Float> {
+    crossovers: Vec<Crossover<T>>,
+    /// For every band but the last two, copies of the later crossovers used as all-passes.
+    compensation: Vec<Vec<Crossover<T>>>,
+    delays: Vec<ThiranDelay<T>>,
+    aligned: bool,
+}
+
+impl<T: Float> CrossoverNetwork<T> {
+    /// Creates a network with a band below, between and above the given crossover frequencies.
+    /// Returns `None` if no frequencies are given, they are not strictly ascending, or any
+    /// crossover is invalid.
+    pub fn new(mode: CrossoverMode, frequencies: &[T], sample_rate: u32) -> Option<Self> {
+        let ascending = frequencies.windows(2).all(|pair| pair[0] < pair[1]);
+        if frequencies.is_empty() || !ascending {
+            return None;
+        }
+        let crossovers = frequencies
+            .iter()
+            .map(|&frequency| Crossover::new(mode, frequency, sample_rate))
+            .collect::<Option<Vec<_>>>()?;
+        let compensation = (0..crossovers.len().saturating_sub(1))
+            .map(|band| crossovers[band + 1..].to_vec())
+            .collect();
+        let mut network = Self {
+            delays: Vec::new(),
+            compensation,
+            crossovers,
+            aligned: false,
+        };
+        network.delays = network
+            .alignment_delays()?
+            .into_iter()
+            .map(ThiranDelay::new)
+            .collect::<Option<Vec<_>>>()?;
+        Some(network)
+    }
+
+    /// Returns the number of bands.
+    pub fn bands(&self) -> usize {
+        self.crossovers.len() + 1
+    }
+
+    /// Processes one sample, writing each band to `bands` from lowest to highest. Returns `false`
+    /// if `bands` does not have one element per band.
+    pub fn process(&mut self, sample: T, bands: &mut [T]) -> bool {
+        if bands.len() != self.bands() {
+            return false;
+        }
+        let mut remainder = sample;
+        for (crossover, band) in self.crossovers.iter_mut().zip(bands.iter_mut()) {
+            (*band, remainder) = crossover.process(remainder);
+        }
+        bands[self.crossovers.len()] = remainder;
+        for (all_passes, band) in self.compensation.iter_mut().zip(bands.iter_mut()) {
+            for all_pass in all_passes.iter_mut() {
+                let (low, high) = all_pass.process(*band);
+                *band = low + high;
+            }
+        }
+        if self.aligned {
+            for (delay, band) in self.delays.iter_mut().zip(bands.iter_mut()) {
+                delay.process(band);
+            }
+        }
+        true
+    }
+
+    /// Returns whether the bands are time-aligned.
+    pub fn is_aligned(&self) -> bool {
+        self.aligned
+    }
+
+    /// Sets whether the bands are time-aligned, clearing the alignment delays.
+    pub fn set_aligned(&mut self, aligned: bool) {
+        self.aligned = aligned;
+        self.delays.iter_mut().for_each(ThiranDelay::reset);
+    }
+
+    /// Returns the group delay of each band in samples, measured at the centre of the band: the
+    /// geometric mean of its crossover frequencies, or an octave beyond the outermost crossovers
+    /// for the lowest and highest bands.
+    pub fn group_delays(&self) -> Option<Vec<T>> {
+        let two = T::one() + T::one();
+        let sample_rate = self.crossovers[0].get_sample_rate();
+        let nyquist = T::from(sample_rate)? / two;
+        let frequencies: Vec<T> = self.crossovers.iter().map(Crossover::get_frequency).collect();
+        let last = frequencies.len() - 1;
+        (0..self.bands())
+            .map(|band| {
+                let centre = match band {
+                    0 => frequencies[0] / two,
+                    band if band > last => (frequencies[last] * two).min(nyquist * T::from(0.9)?),
+                    band => (frequencies[band - 1] * frequencies[band]).sqrt(),
+                };
+                let mut delay = T::zero();
+                for (index, crossover) in self.crossovers.iter().enumerate() {
+                    let (low, high) = crossover.group_delays(centre)?;
+                    delay = delay
+                        + match index {
+                            index if index < band => high,
+                            index if index == band => low,
+                            _ => crossover.all_pass_group_delay(centre)?,
+                        };
+                }
+                Some(delay)
+            })
+            .collect()
+    }
+
+    /// Returns the delay added to each band in aligned mode, in samples.
+    pub fn alignment_delays(&self) -> Option<Vec<T>> {
+        let delays = self.group_delays()?;
+        let longest = delays.iter().fold(T::zero(), |longest, &delay| longest.max(delay));
+        Some(delays.iter().map(|&delay| (longest - delay).max(T::zero())).collect())
+    }
+
+    /// Resets every crossover and alignment delay.
+    pub fn reset(&mut self) {
+        self.crossovers.iter_mut().for_each(Crossover::reset);
+        self.compensation.iter_mut().flatten().for_each(Crossover::reset);
+        self.delays.iter_mut().for_each(ThiranDelay::reset);
+    }
+}
+
+/// Returns a bilinear-transformed second-order Butterworth-style section with the given
+/// prewarped frequency `k = tan(pi * f / fs)` and Q.
+pub(crate) fn butterworth_section<T: Float>(k: T, q: T, high_pass: bool) -> Option<Coefficients<T>> {
+    let two = T::from(2.0)?;
+    let k2 = k * k;
+    let (b0, b1) = if high_pass { (T::one(), -two) } else { (k2, two * k2) };
+    Some(Coefficients {
+        b0,
+        b1,
+        b2: b0,
+        a0: T::one() + k / q + k2,
+        a1: two * (k2 - T::one()),
+        a2: T::one() - k / q + k2,
+    })
+}
+
+/// Returns a second-order all-pass section whose poles match a bilinear-transformed analog pole
+/// pair with the given prewarped frequency and Q.
+fn allpass_section<T: Float>(k: T, q: T) -> Option<Coefficients<T>> {
+    let k2 = k * k;
+    let a0 = T::one() + k / q + k2;
+    let a1 = T::from(2.0)? * (k2 - T::one());
+    let a2 = T::one() - k / q + k2;
+    Some(Coefficients {
+        b0: a2,
+        b1: a1,
+        b2: a0,
+        a0,
+        a1,
+        a2,
+    })
+}
@@ -0,0 +1,240 @@
+/// deesser.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::band_pass::BandPassFilter;
+use crate::filters::envelope::EnvelopeFollower;
+use crate::filters::filter::{BiquadFilterWrapper, Filter};
+use crate::filters::high_shelf::HighShelfFilter;
+use crate::filters::peaking_eq::PeakingEQFilter;
+use num_traits::Float;
+
+/// The filter that cuts the sibilance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DeEsserBand {
+    /// A peaking cut centred on the detector frequency, which leaves the air above it alone.
+    #[default]
+    Peaking,
+    /// A high-shelf cut starting at the detector frequency, which also tames the top octave.
+    HighShelf,
+}
+
+/// A de-esser: a band-pass sidechain detects sibilance, and while its level is above the
+/// threshold a peaking or high-shelf cut at the same frequency reduces it.
+///
+/// The cut follows the detected level one for one above the threshold, up to `range` decibels,
+/// so louder sibilance is brought down to the threshold. Its gain is updated every
+/// `CONTROL_INTERVAL` samples with a fast attack and a slower release.
+#[derive(Debug, Clone)]
+pub struct DeEsser<T: Float> {
+    detector: BandPassFilter<T>,
+    peaking: PeakingEQFilter<T>,
+    shelf: HighShelfFilter<T>,
+    band: DeEsserBand,
+    threshold: T,
+    range: T,
+    follower: EnvelopeFollower<T>,
+    reduction: T,
+    countdown: usize,
+}
+
+impl<T: Float> DeEsser<T> {
+    /// The lowest accepted detector frequency in Hz.
+    pub const MIN_FREQUENCY: f64 = 4000.0;
+    /// The highest accepted detector frequency in Hz.
+    pub const MAX_FREQUENCY: f64 = 9000.0;
+    /// The default threshold in dBFS.
+    pub const DEFAULT_THRESHOLD: f64 = -30.0;
+    /// The default largest cut in decibels.
+    pub const DEFAULT_RANGE: f64 = 6.0;
+    /// The Q factor of the detector and the peaking cut.
+    pub const Q_FACTOR: f64 = 1.5;
+    /// The detector attack time in seconds.
+    pub const ATTACK: f64 = 0.001;
+    /// The detector release time in seconds.
+    pub const RELEASE: f64 = 0.06;
+    /// The number of samples between updates of the cut.
+    pub const CONTROL_INTERVAL: usize = 16;
+
+    /// Creates a de-esser detecting and cutting around `frequency`. Returns `None` if the
+    /// frequency is outside `MIN_FREQUENCY` to `MAX_FREQUENCY` or not below the Nyquist frequency.
+    pub fn new(frequency: T, sample_rate: u32) -> Option<Self> {
+        let q_factor = T::from(Self::Q_FACTOR)?;
+        // Start at a frequency every sample rate accepts and move to the requested one below.
+        let start = T::from(Self::MIN_FREQUENCY)?;
+        let mut de_esser = Self {
+            detector: BandPassFilter::new(start, sample_rate, q_factor, false)?,
+            peaking: PeakingEQFilter::new(start, sample_rate, q_factor, T::zero())?,
+            shelf: HighShelfFilter::new(
+                start,
+                sample_rate,
+                T::from(core::f64::consts::FRAC_1_SQRT_2)?,
+                T::zero(),
+            )?,
+            band: DeEsserBand::Peaking,
+            threshold: T::from(Self::DEFAULT_THRESHOLD)?,
+            range: T::from(Self::DEFAULT_RANGE)?,
+            follower: EnvelopeFollower::new(
+                T::from(Self::ATTACK)?,
+                T::from(Self::RELEASE)?,
+                sample_rate,
+            )?,
+            reduction: T::zero(),
+            countdown: 0,
+        };
+        if !de_esser.set_frequency(frequency) {
+            return None;
+        }
+        Some(de_esser)
+    }
+
+    /// Processes a single sample in-place.
+    pub fn process(&mut self, sample: &mut T) -> bool {
+        let mut side = *sample;
+        self.detector.process(&mut side);
+        self.follower.process(side);
+
+        if self.countdown == 0 {
+            self.update_reduction();
+            self.countdown = Self::CONTROL_INTERVAL;
+        }
+        self.countdown -= 1;
+
+        match self.band {
+            DeEsserBand::Peaking => self.peaking.process(sample),
+            DeEsserBand::HighShelf => self.shelf.process(sample),
+        }
+    }
+
+    /// Processes a block of samples in-place.
+    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
+        if samples.is_empty() {
+            return false;
+        }
+        for sample in samples.iter_mut() {
+            self.process(sample);
+        }
+        true
+    }
+
+    /// Returns the current cut in decibels, as a positive number, for gain-reduction meters.
+    pub fn get_reduction(&self) -> T {
+        self.reduction
+    }
+
+    /// Returns the detector and cut frequency in Hz.
+    pub fn get_frequency(&self) -> T {
+        self.detector.get_cutoff()
+    }
+
+    /// Sets the detector and cut frequency. Returns `false` and keeps the current frequency if it
+    /// is outside `MIN_FREQUENCY` to `MAX_FREQUENCY` or not below the Nyquist frequency.
+    pub fn set_frequency(&mut self, frequency: T) -> bool {
+        let (Some(min), Some(max), Some(nyquist)) = (
+            T::from(Self::MIN_FREQUENCY),
+            T::from(Self::MAX_FREQUENCY),
+            T::from(self.detector.get_sample_rate() / 2),
+        ) else {
+            return false;
+        };
+        let in_range = frequency >= min && frequency <= max && frequency < nyquist;
+        if !in_range {
+            return false;
+        }
+        let previous = self.detector.get_cutoff();
+        if self.detector.set_cutoff(frequency)
+            && self.peaking.set_cutoff(frequency)
+            && self.shelf.set_cutoff(frequency)
+        {
+            return true;
+        }
+        self.detector.set_cutoff(previous);
+        self.peaking.set_cutoff(previous);
+        self.shelf.set_cutoff(previous);
+        false
+    }
+
+    /// Returns the detector level in dBFS above which sibilance is cut.
+    pub fn get_threshold(&self) -> T {
+        self.threshold
+    }
+
+    /// Sets the threshold in dBFS. Returns `false` if it is not finite.
+    pub fn set_threshold(&mut self, threshold: T) -> bool {
+        if !threshold.is_finite() {
+            return false;
+        }
+        self.threshold = threshold;
+        true
+    }
+
+    /// Returns the largest cut in decibels.
+    pub fn get_range(&self) -> T {
+        self.range
+    }
+
+    /// Sets the largest cut in decibels. Returns `false` if it is negative or not finite.
+    pub fn set_range(&mut self, range: T) -> bool {
+        let in_range = range >= T::zero() && range.is_finite();
+        if !in_range {
+            return false;
+        }
+        self.range = range;
+        true
+    }
+
+    /// Returns the filter that cuts the sibilance.
+    pub fn get_band(&self) -> DeEsserBand {
+        self.band
+    }
+
+    /// Sets the filter that cuts the sibilance. The new filter takes over the current cut.
+    pub fn set_band(&mut self, band: DeEsserBand) {
+        self.band = band;
+        self.countdown = 0;
+    }
+
+    /// Clears the detector and both cut filters, and removes the cut.
+    pub fn reset(&mut self) {
+        self.detector.get_filter().reset();
+        self.peaking.get_filter().reset();
+        self.shelf.get_filter().reset();
+        self.follower.reset();
+        self.reduction = T::zero();
+        self.countdown = 0;
+        self.peaking.set_gain(T::zero());
+        self.shelf.set_gain(T::zero());
+    }
+
+    /// Recomputes the cut from the detector envelope.
+    fn update_reduction(&mut self) {
+        let Some(twenty) = T::from(20.0) else {
+            return;
+        };
+        let level = twenty * self.follower.get_envelope().max(T::min_positive_value()).log10();
+        self.reduction = (level - self.threshold).max(T::zero()).min(self.range);
+        let gain = -self.reduction;
+        match self.band {
+            DeEsserBand::Peaking => self.peaking.set_gain(gain),
+            DeEsserBand::HighShelf => self.shelf.set_gain(gain),
+        };
+    }
+}
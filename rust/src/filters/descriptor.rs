@@ -0,0 +1,134 @@
+/// descriptor.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::filter::FilterType;
+use num_traits::Float;
+
+/// How a parameter's range maps onto a control.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParameterScale {
+    /// Equal control movements give equal changes in value.
+    Linear,
+    /// Equal control movements give equal ratios of value, as suits frequencies and Q factors.
+    Logarithmic,
+}
+
+/// Describes one parameter of a filter type, so generic hosts and UIs can build a control for it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ParameterDescriptor {
+    /// A stable identifier, such as `"cutoff"`.
+    pub id: &'static str,
+    /// A name for display.
+    pub name: &'static str,
+    /// The unit of the value, or an empty string if it has none.
+    pub unit: &'static str,
+    /// The value a new control starts at.
+    pub default: f64,
+    /// The smallest value offered.
+    pub min: f64,
+    /// The largest value offered.
+    pub max: f64,
+    /// How the range maps onto a control.
+    pub scale: ParameterScale,
+}
+
+impl ParameterDescriptor {
+    /// The cutoff or centre frequency.
+    pub const CUTOFF: ParameterDescriptor = ParameterDescriptor {
+        id: "cutoff",
+        name: "Frequency",
+        unit: "Hz",
+        default: 1000.0,
+        min: 20.0,
+        max: 20000.0,
+        scale: ParameterScale::Logarithmic,
+    };
+    /// The Q factor.
+    pub const Q_FACTOR: ParameterDescriptor = ParameterDescriptor {
+        id: "q_factor",
+        name: "Q",
+        unit: "",
+        default: core::f64::consts::FRAC_1_SQRT_2,
+        min: 0.1,
+        max: 24.0,
+        scale: ParameterScale::Logarithmic,
+    };
+    /// The gain of peaking and shelving filters.
+    pub const GAIN: ParameterDescriptor = ParameterDescriptor {
+        id: "gain",
+        name: "Gain",
+        unit: "dB",
+        default: 0.0,
+        min: -24.0,
+        max: 24.0,
+        scale: ParameterScale::Linear,
+    };
+
+    /// Maps a value to a control position between zero and one, clamping values outside the
+    /// range.
+    pub fn normalize(&self, value: f64) -> f64 {
+        let position = match self.scale {
+            ParameterScale::Linear => (value - self.min) / (self.max - self.min),
+            ParameterScale::Logarithmic => {
+                Float::ln(value / self.min) / Float::ln(self.max / self.min)
+            }
+        };
+        if position.is_nan() {
+            return 0.0;
+        }
+        position.clamp(0.0, 1.0)
+    }
+
+    /// Maps a control position between zero and one to a value, clamping positions outside the
+    /// range.
+    pub fn denormalize(&self, position: f64) -> f64 {
+        let position = if position.is_nan() { 0.0 } else { position.clamp(0.0, 1.0) };
+        match self.scale {
+            ParameterScale::Linear => self.min + position * (self.max - self.min),
+            ParameterScale::Logarithmic => self.min * Float::powf(self.max / self.min, position),
+        }
+    }
+}
+
+impl FilterType {
+    /// Returns the parameters the filter type responds to, in the order a UI would usually show
+    /// them.
+    pub fn parameters(&self) -> &'static [ParameterDescriptor] {
+        match self {
+            FilterType::LowPass
+            | FilterType::HighPass
+            | FilterType::BandPass
+            | FilterType::Notch
+            | FilterType::AllPass => &[ParameterDescriptor::CUTOFF, ParameterDescriptor::Q_FACTOR],
+            FilterType::PeakingEQ
+            | FilterType::LowShelf
+            | FilterType::HighShelf
+            | FilterType::Tilt => &[
+                ParameterDescriptor::CUTOFF,
+                ParameterDescriptor::Q_FACTOR,
+                ParameterDescriptor::GAIN,
+            ],
+        }
+    }
+}
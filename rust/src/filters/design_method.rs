@@ -0,0 +1,277 @@
+/// design_method.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::Coefficients;
+use crate::filters::filter_configuration::FilterConfiguration;
+use num_traits::Float;
+use core::f64::consts::PI;
+
+/// How a filter maps its analog prototype to digital coefficients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DesignMethod {
+    /// The bilinear transform of the Audio EQ Cookbook. The cutoff is exact, but the response is
+    /// squeezed towards the Nyquist frequency, where every low-pass reaches zero and every peak
+    /// narrows, so designs with a cutoff within an octave or so of Nyquist lose their shape.
+    #[default]
+    Bilinear,
+    /// Martin Vicanek's matched designs ("Matched Second Order Digital Filters", 2016). The
+    /// poles are those of the impulse-invariant filter and the zeros are chosen to match the
+    /// analog magnitude at DC, the cutoff and the Nyquist frequency, so the response stays close
+    /// to the analog prototype right up to Nyquist. Low-pass, high-pass, band-pass and peaking EQ
+    /// filters support it; other filter types use the bilinear transform.
+    Matched,
+    /// Sophocles Orfanidis' decramped peaking EQ ("Digital Parametric Equalizer Design with
+    /// Prescribed Nyquist-Frequency Gain", 1997). The analog prototype is evaluated without
+    /// frequency warping and its gain at the Nyquist frequency is carried over exactly, so bands
+    /// near the top of the spectrum keep their width and shape. Only peaking EQ filters support
+    /// it, and their bandwidth `cutoff / q_factor` must be below the Nyquist frequency; other
+    /// filter types use the bilinear transform.
+    Orfanidis,
+}
+
+/// The terms shared by every matched design for a pole pair at `w0` radians per sample.
+struct MatchedPoles<T: Float> {
+    a1: T,
+    a2: T,
+    /// The squared magnitudes of the denominator at DC and at Nyquist, and the cross term.
+    big_a: [T; 3],
+    /// `cos^2(w0 / 2)`, `sin^2(w0 / 2)` and four times their product.
+    phi: [T; 3],
+}
+
+impl<T: Float> MatchedPoles<T> {
+    /// Places the poles of the analog prototype with the given Q factor at its impulse-invariant
+    /// location.
+    fn new(w0: T, q_factor: T) -> Option<Self> {
+        let one = T::one();
+        let two = T::from(2.0)?;
+        let four = T::from(4.0)?;
+        let q = one / (two * q_factor);
+        let decay = (-q * w0).exp();
+        let a1 = if q <= one {
+            -two * decay * ((one - q * q).sqrt() * w0).cos()
+        } else {
+            -two * decay * ((q * q - one).sqrt() * w0).cosh()
+        };
+        let a2 = (-two * q * w0).exp();
+        let sine = (w0 / two).sin();
+        let phi1 = sine * sine;
+        let phi0 = one - phi1;
+        Some(Self {
+            a1,
+            a2,
+            big_a: [
+                (one + a1 + a2) * (one + a1 + a2),
+                (one - a1 + a2) * (one - a1 + a2),
+                -four * a2,
+            ],
+            phi: [phi0, phi1, four * phi0 * phi1],
+        })
+    }
+
+    /// Returns `A0 phi0 + A1 phi1 + A2 phi2`.
+    fn weighted_sum(&self) -> T {
+        self.big_a[0] * self.phi[0] + self.big_a[1] * self.phi[1] + self.big_a[2] * self.phi[2]
+    }
+
+    /// Returns `-A0 + A1 + 4 (phi0 - phi1) A2`.
+    fn slope_sum(&self) -> Option<T> {
+        let four = T::from(4.0)?;
+        Some(-self.big_a[0] + self.big_a[1] + four * (self.phi[0] - self.phi[1]) * self.big_a[2])
+    }
+
+    fn coefficients(&self, b0: T, b1: T, b2: T) -> Coefficients<T> {
+        Coefficients {
+            b0,
+            b1,
+            b2,
+            a0: T::one(),
+            a1: self.a1,
+            a2: self.a2,
+        }
+    }
+}
+
+/// Returns the normalized angular frequency of a configuration's cutoff.
+fn cutoff_angle<T: Float>(config: &FilterConfiguration<T>) -> Option<T> {
+    Some(T::from(2.0 * PI)? * config.get_cutoff() / T::from(config.get_sample_rate())?)
+}
+
+/// Designs a matched low-pass. Returns `None` if the configuration is invalid.
+pub(crate) fn matched_low_pass<T: Float>(
+    config: &FilterConfiguration<T>,
+) -> Option<Coefficients<T>> {
+    if !config.is_valid() {
+        return None;
+    }
+    let half = T::from(0.5)?;
+    let q = config.get_q_factor();
+    let poles = MatchedPoles::new(cutoff_angle(config)?, q)?;
+    let r1 = poles.weighted_sum() * q * q;
+    let big_b0 = poles.big_a[0];
+    let big_b1 = (r1 - big_b0 * poles.phi[0]) / poles.phi[1];
+    let b0 = half * (big_b0.sqrt() + big_b1.max(T::zero()).sqrt());
+    let b1 = big_b0.sqrt() - b0;
+    Some(poles.coefficients(b0, b1, T::zero()))
+}
+
+/// Designs a matched high-pass. Returns `None` if the configuration is invalid.
+pub(crate) fn matched_high_pass<T: Float>(
+    config: &FilterConfiguration<T>,
+) -> Option<Coefficients<T>> {
+    if !config.is_valid() {
+        return None;
+    }
+    let two = T::from(2.0)?;
+    let q = config.get_q_factor();
+    let poles = MatchedPoles::new(cutoff_angle(config)?, q)?;
+    let b0 = q * poles.weighted_sum().sqrt() / (T::from(4.0)? * poles.phi[1]);
+    Some(poles.coefficients(b0, -two * b0, b0))
+}
+
+/// Designs a matched band-pass with unity gain at the centre frequency, or a gain of Q with a
+/// constant skirt gain. Returns `None` if the configuration is invalid.
+pub(crate) fn matched_band_pass<T: Float>(
+    config: &FilterConfiguration<T>,
+) -> Option<Coefficients<T>> {
+    if !config.is_valid() {
+        return None;
+    }
+    let half = T::from(0.5)?;
+    let four = T::from(4.0)?;
+    let q = config.get_q_factor();
+    let poles = MatchedPoles::new(cutoff_angle(config)?, q)?;
+    let [phi0, phi1, _] = poles.phi;
+    let r1 = poles.weighted_sum();
+    let r2 = poles.slope_sum()?;
+    let big_b2 = (r1 - r2 * phi1) / (four * phi1 * phi1);
+    let big_b1 = r2 + four * (phi1 - phi0) * big_b2;
+    let b1 = -half * big_b1.max(T::zero()).sqrt();
+    let b0 = half * ((big_b2 + b1 * b1).max(T::zero()).sqrt() - b1);
+    let b2 = -b0 - b1;
+    let scale = if config.get_constant_skirt_gain() { q } else { T::one() };
+    Some(poles.coefficients(scale * b0, scale * b1, scale * b2))
+}
+
+/// Designs a matched peaking EQ with the same analog prototype as the bilinear design. A cut is
+/// the inverse of the boost by the same amount, as in the prototype, which matches the analog
+/// response more closely than designing the cut directly. Returns `None` if the configuration is
+/// invalid.
+pub(crate) fn matched_peaking<T: Float>(
+    config: &FilterConfiguration<T>,
+) -> Option<Coefficients<T>> {
+    if !config.is_valid() {
+        return None;
+    }
+    let half = T::from(0.5)?;
+    let four = T::from(4.0)?;
+    let cut = config.get_gain() < T::zero();
+    let gain = T::from(10.0)?.powf(config.get_gain().abs() / T::from(20.0)?);
+    // The cookbook prototype has poles with a Q factor of sqrt(gain) times the band's Q factor.
+    let q_factor = gain.sqrt() * config.get_effective_q_factor();
+    let poles = MatchedPoles::new(cutoff_angle(config)?, q_factor)?;
+    let [phi0, phi1, _] = poles.phi;
+    let gain_squared = gain * gain;
+    let r1 = poles.weighted_sum() * gain_squared;
+    let r2 = poles.slope_sum()? * gain_squared;
+    let big_b0 = poles.big_a[0];
+    let big_b2 = (r1 - r2 * phi1 - big_b0) / (four * phi1 * phi1);
+    let big_b1 = r2 + big_b0 + four * (phi1 - phi0) * big_b2;
+    let root_b0 = big_b0.sqrt();
+    let root_b1 = big_b1.max(T::zero()).sqrt();
+    let w = half * (root_b0 + root_b1);
+    let b0 = half * (w + (w * w + big_b2).max(T::zero()).sqrt());
+    let b1 = half * (root_b0 - root_b1);
+    let b2 = -big_b2 / (four * b0);
+    if !cut {
+        return Some(poles.coefficients(b0, b1, b2));
+    }
+    Some(Coefficients {
+        b0: T::one(),
+        b1: poles.a1,
+        b2: poles.a2,
+        a0: b0,
+        a1: b1,
+        a2: b2,
+    })
+}
+
+/// Designs an Orfanidis peaking EQ with the same analog prototype as the bilinear design: unity
+/// gain at DC and a bandwidth of `cutoff / q_factor` between the frequencies where the gain in
+/// decibels is half the peak gain. Returns `None` if the configuration is invalid or the
+/// bandwidth reaches the Nyquist frequency.
+pub(crate) fn orfanidis_peaking<T: Float>(
+    config: &FilterConfiguration<T>,
+) -> Option<Coefficients<T>> {
+    if !config.is_valid() {
+        return None;
+    }
+    let one = T::one();
+    let two = T::from(2.0)?;
+    let pi = T::from(PI)?;
+    let w0 = cutoff_angle(config)?;
+    let bandwidth = w0 / config.get_effective_q_factor();
+    if w0 >= pi || bandwidth >= pi {
+        return None;
+    }
+    let gain = T::from(10.0)?.powf(config.get_gain() / T::from(20.0)?);
+    if gain == one {
+        return Some(Coefficients {
+            b0: one,
+            b1: T::zero(),
+            b2: T::zero(),
+            a0: one,
+            a1: T::zero(),
+            a2: T::zero(),
+        });
+    }
+    // The gain at the band edges, with the reference gain at DC of one.
+    let edge = gain.sqrt();
+    let (g2, e2) = (gain * gain, edge * edge);
+    let f = (g2 - e2).abs();
+    let g00 = (g2 - one).abs();
+    let f00 = (e2 - one).abs();
+    let distance = (w0 * w0 - pi * pi) * (w0 * w0 - pi * pi);
+    let spread = f00 * pi * pi * bandwidth * bandwidth / f;
+    // The gain of the analog prototype at the Nyquist frequency.
+    let g1 = ((distance + g2 * spread) / (distance + spread)).sqrt();
+    let g01 = (g2 - g1).abs();
+    let g11 = (g2 - g1 * g1).abs();
+    let f01 = (e2 - g1).abs();
+    let f11 = (e2 - g1 * g1).abs();
+    let half_tan = (w0 / two).tan();
+    let w2 = (g11 / g00).sqrt() * half_tan * half_tan;
+    let dw = (one + (f00 / f11).sqrt() * w2) * (bandwidth / two).tan();
+    let c = f11 * dw * dw - two * w2 * (f01 - (f00 * f11).sqrt());
+    let d = two * w2 * (g01 - (g00 * g11).sqrt());
+    let a = ((c + d) / f).sqrt();
+    let b = ((g2 * c + e2 * d) / f).sqrt();
+    Some(Coefficients {
+        b0: g1 + w2 + b,
+        b1: -two * (g1 - w2),
+        b2: g1 - b + w2,
+        a0: one + w2 + a,
+        a1: -two * (one - w2),
+        a2: one + w2 - a,
+    })
+}
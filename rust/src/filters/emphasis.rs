@@ -0,0 +1,171 @@
+/// emphasis.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::{Coefficients, DigitalBiquadFilter};
+use crate::filters::response::FrequencyResponse;
+use num_complex::Complex;
+use num_traits::Float;
+use core::f64::consts::PI;
+
+/// The fraction of the Nyquist frequency at which a pre-emphasis filter stops rising.
+const PRE_EMPHASIS_CEILING: f64 = 0.925;
+
+/// The time constant of an FM broadcast emphasis curve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EmphasisTimeConstant {
+    /// 50 µs, used in Europe and most of the world. The corner is at 3183 Hz.
+    Microseconds50,
+    /// 75 µs, used in the Americas and South Korea. The corner is at 2122 Hz.
+    Microseconds75,
+}
+
+impl EmphasisTimeConstant {
+    /// Returns the time constant in seconds.
+    pub fn seconds(&self) -> f64 {
+        match self {
+            EmphasisTimeConstant::Microseconds50 => 50e-6,
+            EmphasisTimeConstant::Microseconds75 => 75e-6,
+        }
+    }
+
+    /// Returns the corner frequency `1 / (2 pi tau)` in Hz.
+    pub fn corner_frequency(&self) -> f64 {
+        1.0 / (2.0 * PI * self.seconds())
+    }
+}
+
+/// An FM broadcast pre-emphasis or de-emphasis filter. De-emphasis is the first-order low-pass
+/// `1 / (1 + s tau)`, which falls at 6 dB per octave above the corner. Pre-emphasis is its
+/// inverse, `1 + s tau`, which would rise without limit, so like most digital implementations it
+/// levels off at 92.5% of the Nyquist frequency. Both are mapped with the bilinear transform,
+/// prewarped so the corners are exact at any sample rate, and have unity gain at DC.
+#[derive(Debug, Clone)]
+pub struct EmphasisFilter<T: Float> {
+    time_constant: EmphasisTimeConstant,
+    sample_rate: u32,
+    pre_emphasis: bool,
+    filter: DigitalBiquadFilter<T>,
+}
+
+impl<T: Float> EmphasisFilter<T> {
+    /// Creates a pre-emphasis filter, applied before transmission. Returns `None` if the corner
+    /// frequency is not below the frequency where the filter levels off.
+    pub fn pre_emphasis(time_constant: EmphasisTimeConstant, sample_rate: u32) -> Option<Self> {
+        let corner = prewarp(time_constant.corner_frequency(), sample_rate)?;
+        let ceiling = prewarp(PRE_EMPHASIS_CEILING * f64::from(sample_rate) / 2.0, sample_rate)?;
+        if corner >= ceiling {
+            return None;
+        }
+        // (1 + s / corner) / (1 + s / ceiling) with s = (1 - z^-1) / (1 + z^-1).
+        let coefficients = Coefficients {
+            b0: ceiling * (corner + 1.0) / corner,
+            b1: ceiling * (corner - 1.0) / corner,
+            b2: 0.0,
+            a0: ceiling + 1.0,
+            a1: ceiling - 1.0,
+            a2: 0.0,
+        };
+        Self::new(time_constant, sample_rate, true, coefficients)
+    }
+
+    /// Creates a de-emphasis filter, applied after reception. Returns `None` if the corner
+    /// frequency is not below the Nyquist frequency.
+    pub fn de_emphasis(time_constant: EmphasisTimeConstant, sample_rate: u32) -> Option<Self> {
+        let corner = prewarp(time_constant.corner_frequency(), sample_rate)?;
+        // 1 / (1 + s / corner) with s = (1 - z^-1) / (1 + z^-1).
+        let coefficients = Coefficients {
+            b0: corner,
+            b1: corner,
+            b2: 0.0,
+            a0: corner + 1.0,
+            a1: corner - 1.0,
+            a2: 0.0,
+        };
+        Self::new(time_constant, sample_rate, false, coefficients)
+    }
+
+    fn new(
+        time_constant: EmphasisTimeConstant,
+        sample_rate: u32,
+        pre_emphasis: bool,
+        coefficients: Coefficients<f64>,
+    ) -> Option<Self> {
+        Some(Self {
+            time_constant,
+            sample_rate,
+            pre_emphasis,
+            filter: DigitalBiquadFilter::new(coefficients.cast())?,
+        })
+    }
+
+    /// Processes a single sample.
+    pub fn process(&mut self, sample: &mut T) -> bool {
+        self.filter.process(sample)
+    }
+
+    /// Processes a block of samples. Returns `false` if the block is empty.
+    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
+        self.filter.process_block(samples)
+    }
+
+    /// Returns the time constant of the emphasis curve.
+    pub fn get_time_constant(&self) -> EmphasisTimeConstant {
+        self.time_constant
+    }
+
+    /// Returns the sample rate the filter was designed for.
+    pub fn get_sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Returns whether the filter applies pre-emphasis rather than de-emphasis.
+    pub fn is_pre_emphasis(&self) -> bool {
+        self.pre_emphasis
+    }
+
+    /// Returns the underlying biquad, which has a single pole and zero.
+    pub fn get_filter(&self) -> &DigitalBiquadFilter<T> {
+        &self.filter
+    }
+
+    /// Resets the filter state.
+    pub fn reset(&mut self) {
+        self.filter.reset();
+    }
+}
+
+impl<T: Float> FrequencyResponse<T> for EmphasisFilter<T> {
+    fn response_at(&self, frequency: T, sample_rate: u32) -> Complex<T> {
+        self.filter.response_at(frequency, sample_rate)
+    }
+}
+
+/// Returns the prewarped frequency `tan(pi * frequency / sample_rate)`, or `None` if the frequency
+/// is not below the Nyquist frequency.
+fn prewarp(frequency: f64, sample_rate: u32) -> Option<f64> {
+    let rate = f64::from(sample_rate);
+    if !(frequency > 0.0 && 2.0 * frequency < rate) {
+        return None;
+    }
+    Some(Float::tan(PI * frequency / rate))
+}
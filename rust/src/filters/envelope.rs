@@ -0,0 +1,323 @@
+/// envelope.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::filter::Filter;
+use crate::filters::state_variable::{StateVariableFilter, SvfMode};
+use num_traits::Float;
+
+/// Follows the level of a signal: the rectified input is smoothed by a one-pole filter that rises
+/// with the attack time constant and falls with the release time constant.
+#[derive(Debug, Clone)]
+pub struct EnvelopeFollower<T: Float> {
+    sample_rate: u32,
+    /// The attack time in seconds.
+    attack: T,
+    /// The release time in seconds.
+    release: T,
+    attack_coefficient: T,
+    release_coefficient: T,
+    envelope: T,
+}
+
+impl<T: Float> EnvelopeFollower<T> {
+    /// Creates a follower with the given attack and release times in seconds. Returns `None` if
+    /// either time is negative or not finite, or the sample rate is zero.
+    pub fn new(attack: T, release: T, sample_rate: u32) -> Option<Self> {
+        Some(Self {
+            sample_rate,
+            attack,
+            release,
+            attack_coefficient: smoothing_coefficient(attack, sample_rate)?,
+            release_coefficient: smoothing_coefficient(release, sample_rate)?,
+            envelope: T::zero(),
+        })
+    }
+
+    /// Returns the attack time in seconds.
+    pub fn get_attack(&self) -> T {
+        self.attack
+    }
+
+    /// Sets the attack time in seconds. Returns `false` if it is negative or not finite.
+    pub fn set_attack(&mut self, attack: T) -> bool {
+        let Some(coefficient) = smoothing_coefficient(attack, self.sample_rate) else {
+            return false;
+        };
+        self.attack = attack;
+        self.attack_coefficient = coefficient;
+        true
+    }
+
+    /// Returns the release time in seconds.
+    pub fn get_release(&self) -> T {
+        self.release
+    }
+
+    /// Sets the release time in seconds. Returns `false` if it is negative or not finite.
+    pub fn set_release(&mut self, release: T) -> bool {
+        let Some(coefficient) = smoothing_coefficient(release, self.sample_rate) else {
+            return false;
+        };
+        self.release = release;
+        self.release_coefficient = coefficient;
+        true
+    }
+
+    /// Returns the sample rate.
+    pub fn get_sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Sets the sample rate, keeping the attack and release times. Returns `false` if it is zero.
+    pub fn set_sample_rate(&mut self, sample_rate: u32) -> bool {
+        let (Some(attack), Some(release)) = (
+            smoothing_coefficient(self.attack, sample_rate),
+            smoothing_coefficient(self.release, sample_rate),
+        ) else {
+            return false;
+        };
+        self.sample_rate = sample_rate;
+        self.attack_coefficient = attack;
+        self.release_coefficient = release;
+        true
+    }
+
+    /// Returns the current envelope.
+    pub fn get_envelope(&self) -> T {
+        self.envelope
+    }
+
+    /// Follows one input sample and returns the envelope.
+    pub fn process(&mut self, sample: T) -> T {
+        let level = sample.abs();
+        let coefficient = if level > self.envelope {
+            self.attack_coefficient
+        } else {
+            self.release_coefficient
+        };
+        self.envelope = level + coefficient * (self.envelope - level);
+        self.envelope
+    }
+
+    /// Follows a block of samples, writing the envelope. Returns `false` if the block is empty or
+    /// the slices differ in length.
+    pub fn process_block(&mut self, input: &[T], envelope: &mut [T]) -> bool {
+        if input.is_empty() || input.len() != envelope.len() {
+            return false;
+        }
+        for (sample, output) in input.iter().zip(envelope.iter_mut()) {
+            *output = self.process(*sample);
+        }
+        true
+    }
+
+    /// Returns the envelope to zero.
+    pub fn reset(&mut self) {
+        self.envelope = T::zero();
+    }
+}
+
+/// Returns the one-pole coefficient that reaches `1 - 1/e` of a step in `time` seconds, or `None`
+/// if the time is negative or not finite, or the sample rate is zero.
+fn smoothing_coefficient<T: Float>(time: T, sample_rate: u32) -> Option<T> {
+    if !time.is_finite() || time < T::zero() || sample_rate == 0 {
+        return None;
+    }
+    if time == T::zero() {
+        return Some(T::zero());
+    }
+    Some((-(time * T::from(sample_rate)?).recip()).exp())
+}
+
+/// An auto-wah: an `EnvelopeFollower` tracks the input level and sweeps the cutoff of a resonant
+/// state-variable filter between a low and a high frequency, so louder notes open the filter.
+///
+/// The envelope is multiplied by the sensitivity and limited to one, then mapped to the cutoff in
+/// octaves: zero gives the low frequency and one the high frequency. The cutoff is updated every
+/// `CONTROL_INTERVAL` samples.
+#[derive(Debug, Clone)]
+pub struct AutoWah<T: Float> {
+    follower: EnvelopeFollower<T>,
+    filter: StateVariableFilter<T>,
+    min_frequency: T,
+    max_frequency: T,
+    sensitivity: T,
+    countdown: usize,
+}
+
+impl<T: Float> AutoWah<T> {
+    /// The default lowest cutoff in Hz.
+    pub const DEFAULT_MIN_FREQUENCY: f64 = 400.0;
+    /// The default highest cutoff in Hz.
+    pub const DEFAULT_MAX_FREQUENCY: f64 = 2500.0;
+    /// The default Q factor of the filter.
+    pub const DEFAULT_Q_FACTOR: f64 = 5.0;
+    /// The default multiplier applied to the envelope.
+    pub const DEFAULT_SENSITIVITY: f64 = 4.0;
+    /// The default attack time in seconds.
+    pub const DEFAULT_ATTACK: f64 = 0.01;
+    /// The default release time in seconds.
+    pub const DEFAULT_RELEASE: f64 = 0.15;
+    /// The number of samples between updates of the cutoff.
+    pub const CONTROL_INTERVAL: usize = 16;
+
+    /// Creates an auto-wah with a band-pass filter and the default settings. Returns `None` if
+    /// `DEFAULT_MAX_FREQUENCY` is not below the Nyquist frequency.
+    pub fn new(sample_rate: u32) -> Option<Self> {
+        let min_frequency = T::from(Self::DEFAULT_MIN_FREQUENCY)?;
+        let max_frequency = T::from(Self::DEFAULT_MAX_FREQUENCY)?;
+        if max_frequency >= T::from(sample_rate)? / T::from(2.0)? {
+            return None;
+        }
+        Some(Self {
+            follower: EnvelopeFollower::new(
+                T::from(Self::DEFAULT_ATTACK)?,
+                T::from(Self::DEFAULT_RELEASE)?,
+                sample_rate,
+            )?,
+            filter: StateVariableFilter::new(
+                min_frequency,
+                sample_rate,
+                T::from(Self::DEFAULT_Q_FACTOR)?,
+                SvfMode::BandPass,
+            )?,
+            min_frequency,
+            max_frequency,
+            sensitivity: T::from(Self::DEFAULT_SENSITIVITY)?,
+            countdown: 0,
+        })
+    }
+
+    /// Returns the filter output, band-pass by default.
+    pub fn get_mode(&self) -> SvfMode {
+        self.filter.get_mode()
+    }
+
+    /// Sets the filter output. A low-pass gives a softer sweep than the band-pass.
+    pub fn set_mode(&mut self, mode: SvfMode) {
+        self.filter.set_mode(mode);
+    }
+
+    /// Returns the lowest and highest cutoff in Hz.
+    pub fn get_range(&self) -> (T, T) {
+        (self.min_frequency, self.max_frequency)
+    }
+
+    /// Sets the lowest and highest cutoff in Hz. Returns `false` and keeps the current range
+    /// unless `0 < min_frequency <= max_frequency` and the highest cutoff is below the Nyquist
+    /// frequency.
+    pub fn set_range(&mut self, min_frequency: T, max_frequency: T) -> bool {
+        let (Some(sample_rate), Some(two)) = (T::from(self.filter.get_sample_rate()), T::from(2.0))
+        else {
+            return false;
+        };
+        let nyquist = sample_rate / two;
+        let in_range = min_frequency > T::zero()
+            && min_frequency <= max_frequency
+            && max_frequency < nyquist;
+        if !in_range {
+            return false;
+        }
+        self.min_frequency = min_frequency;
+        self.max_frequency = max_frequency;
+        self.countdown = 0;
+        true
+    }
+
+    /// Returns the multiplier applied to the envelope.
+    pub fn get_sensitivity(&self) -> T {
+        self.sensitivity
+    }
+
+    /// Sets the multiplier applied to the envelope. Returns `false` if it is negative or not
+    /// finite.
+    pub fn set_sensitivity(&mut self, sensitivity: T) -> bool {
+        if !sensitivity.is_finite() || sensitivity < T::zero() {
+            return false;
+        }
+        self.sensitivity = sensitivity;
+        true
+    }
+
+    /// Returns the Q factor of the filter.
+    pub fn get_q_factor(&self) -> T {
+        self.filter.get_q_factor()
+    }
+
+    /// Sets the Q factor of the filter, which sets how pronounced the sweep is. Returns `false` if
+    /// it is not positive and finite.
+    pub fn set_q_factor(&mut self, q_factor: T) -> bool {
+        self.filter.set_q_factor(q_factor)
+    }
+
+    /// Returns the envelope follower, for reading the level.
+    pub fn get_follower(&self) -> &EnvelopeFollower<T> {
+        &self.follower
+    }
+
+    /// Returns the envelope follower mutably, to change the attack and release times.
+    pub fn get_follower_mut(&mut self) -> &mut EnvelopeFollower<T> {
+        &mut self.follower
+    }
+
+    /// Returns the current cutoff in Hz.
+    pub fn get_cutoff(&self) -> T {
+        self.filter.get_cutoff()
+    }
+
+    /// Processes a single sample in-place.
+    pub fn process(&mut self, sample: &mut T) -> bool {
+        self.follower.process(*sample);
+        if self.countdown == 0 {
+            self.update_cutoff();
+            self.countdown = Self::CONTROL_INTERVAL;
+        }
+        self.countdown -= 1;
+        self.filter.process(sample)
+    }
+
+    /// Processes a block of samples in-place. Returns `false` if the block is empty.
+    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
+        if samples.is_empty() {
+            return false;
+        }
+        for sample in samples.iter_mut() {
+            self.process(sample);
+        }
+        true
+    }
+
+    /// Clears the follower and the filter, and returns the cutoff to the lowest frequency.
+    pub fn reset(&mut self) {
+        self.follower.reset();
+        self.filter.reset();
+        self.countdown = 0;
+        self.filter.set_cutoff(self.min_frequency);
+    }
+
+    /// Moves the cutoff to the position given by the envelope.
+    fn update_cutoff(&mut self) {
+        let position = (self.follower.get_envelope() * self.sensitivity).min(T::one());
+        let ratio = self.max_frequency / self.min_frequency;
+        self.filter.set_cutoff(self.min_frequency * ratio.powf(position));
+    }
+}
@@ -0,0 +1,62 @@
+/// error.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use core::fmt;
+
+/// The reason a filter setting was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FilterError {
+    /// The cutoff frequency is not positive and finite.
+    InvalidCutoff,
+    /// The cutoff frequency is at or above the Nyquist frequency.
+    CutoffAboveNyquist,
+    /// The sample rate is zero.
+    InvalidSampleRate,
+    /// The Q factor is not positive and finite.
+    InvalidQFactor,
+    /// The gain is not finite or is outside `±FilterConfiguration::MAX_GAIN` decibels.
+    InvalidGain,
+    /// The notch gain is not between zero and one.
+    InvalidNotchGain,
+    /// The cut Q factor is not positive and finite.
+    InvalidCutQFactor,
+    /// The shelf slope is not positive and finite, or too steep for the gain.
+    InvalidShelfSlope,
+}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            FilterError::InvalidCutoff => "cutoff frequency must be positive and finite",
+            FilterError::CutoffAboveNyquist => "cutoff frequency must be below the Nyquist frequency",
+            FilterError::InvalidSampleRate => "sample rate must be positive",
+            FilterError::InvalidQFactor => "Q factor must be positive and finite",
+            FilterError::InvalidGain => "gain must be finite and within the supported range",
+            FilterError::InvalidNotchGain => "notch gain must be between zero and one",
+            FilterError::InvalidCutQFactor => "cut Q factor must be positive and finite",
+            FilterError::InvalidShelfSlope => "shelf slope must be positive and not too steep for the gain",
+        };
+        f.write_str(message)
+    }
+}
+
+impl core::error::Error for FilterError {}
@@ -0,0 +1,57 @@
+/// fault.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+/// A problem detected while a filter is configured or running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Fault {
+    /// The filter produced an infinite or NaN output sample.
+    NonFiniteOutput,
+    /// The filter was given coefficients with a pole on or outside the unit circle.
+    UnstableCoefficients,
+}
+
+/// A function called when a filter first detects a fault. It runs on the audio thread, so it
+/// should only record the fault, for example by setting an atomic flag.
+pub type FaultHandler = fn(Fault);
+
+/// The faults a filter has detected since they were last taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Faults {
+    pub non_finite_output: bool,
+    pub unstable_coefficients: bool,
+}
+
+impl Faults {
+    /// Returns whether no fault has been detected.
+    pub fn is_empty(&self) -> bool {
+        !self.non_finite_output && !self.unstable_coefficients
+    }
+
+    /// Marks a fault as detected. Returns `true` if it was not already marked.
+    pub(crate) fn mark(&mut self, fault: Fault) -> bool {
+        let flag = match fault {
+            Fault::NonFiniteOutput => &mut self.non_finite_output,
+            Fault::UnstableCoefficients => &mut self.unstable_coefficients,
+        };
+        !core::mem::replace(flag, true)
+    }
+}
@@ -1,7 +1,4 @@
-use crate::filters::biquad::{Coefficients, DigitalBiquadFilter};
-use crate::filters::filter_configuration::FilterConfiguration;
 /// filter.rs
-
 /**
 Copyright © 2025 Alex Parisi
 
@@ -23,21 +20,172 @@ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
 OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
+use crate::convert::Sample;
+use crate::filters::all_pass::AllPassFilter;
+use crate::filters::band_pass::BandPassFilter;
+use crate::filters::biquad::{Coefficients, DigitalBiquadFilter};
+use crate::filters::design_method::DesignMethod;
+use crate::filters::fault::{FaultHandler, Faults};
+use crate::filters::filter_configuration::FilterConfiguration;
+use crate::filters::high_pass::HighPassFilter;
+use crate::filters::high_shelf::HighShelfFilter;
+use crate::filters::limiter::OutputLimit;
+use crate::filters::low_pass::LowPassFilter;
+use crate::filters::low_shelf::LowShelfFilter;
+#[cfg(feature = "state-history")]
+use crate::filters::history::HistoryEntry;
+use crate::filters::notch::NotchFilter;
+use crate::filters::peaking_eq::PeakingEQFilter;
+use crate::filters::snapshot::{ConfigDiff, ConfigSnapshot};
+use crate::filters::stats::BlockStats;
+use crate::filters::tilt::TiltFilter;
+use crate::filters::topology::Topology;
+use crate::filters::update::UpdatePolicy;
 use num_traits::Float;
 
-pub trait BiquadFilterWrapper<T: Float + Default + Copy + std::ops::MulAssign> {
+/// Identifies the response type produced by a filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FilterType {
+    LowPass,
+    HighPass,
+    BandPass,
+    Notch,
+    AllPass,
+    PeakingEQ,
+    LowShelf,
+    HighShelf,
+    Tilt,
+}
+
+impl FilterType {
+    /// Designs the coefficients of this response type for a configuration, as the matching filter
+    /// would. Returns `None` if the configuration is invalid.
+    pub fn design_coefficients<T: Float>(
+        self,
+        config: &FilterConfiguration<T>,
+    ) -> Option<Coefficients<T>> {
+        match self {
+            FilterType::LowPass => LowPassFilter::calculate_coefficients(config),
+            FilterType::HighPass => HighPassFilter::calculate_coefficients(config),
+            FilterType::BandPass => BandPassFilter::calculate_coefficients(config),
+            FilterType::Notch => NotchFilter::calculate_coefficients(config),
+            FilterType::AllPass => AllPassFilter::calculate_coefficients(config),
+            FilterType::PeakingEQ => PeakingEQFilter::calculate_coefficients(config),
+            FilterType::LowShelf => LowShelfFilter::calculate_coefficients(config),
+            FilterType::HighShelf => HighShelfFilter::calculate_coefficients(config),
+            FilterType::Tilt => TiltFilter::calculate_coefficients(config),
+        }
+    }
+}
+
+/// A filter built from one `DigitalBiquadFilter` and the `FilterConfiguration` its coefficients
+/// are designed from, which is what `analysis::report` needs. With the `macros` feature it can be
+/// derived with `#[derive(BiquadFilterWrapper)]`.
+pub trait BiquadFilterWrapper<T: Float> {
+    /// Returns the wrapped biquad.
     fn get_filter(&mut self) -> &mut DigitalBiquadFilter<T>;
+    /// Returns the wrapped biquad without borrowing it mutably.
+    fn get_filter_ref(&self) -> &DigitalBiquadFilter<T>;
+    /// Returns the configuration the coefficients are designed from.
     fn get_config(&self) -> &FilterConfiguration<T>;
+    /// Returns the configuration mutably.
     fn get_config_mut(&mut self) -> &mut FilterConfiguration<T>;
-    fn calculate_coefficients(config: &FilterConfiguration<T>) -> Option<Coefficients<T>>;
+    /// Returns the built-in response type the design follows, or `None` for a custom design.
+    /// Only read by the analysis module and tracing events.
+    #[cfg_attr(not(any(feature = "std", feature = "tracing")), allow(dead_code))]
+    fn filter_type(&self) -> Option<FilterType>;
+    /// Designs coefficients for a configuration. Requiring `Self: Sized` keeps the trait usable
+    /// as a trait object.
+    fn calculate_coefficients(config: &FilterConfiguration<T>) -> Option<Coefficients<T>>
+    where
+        Self: Sized;
+}
+
+/// Applies a configuration to a wrapped filter without resetting its state. The previous
+/// configuration and coefficients are kept if the new configuration does not produce valid
+/// coefficients. With lazy updates the configuration is only checked and stored, and the
+/// coefficients are recomputed by `refresh_coefficients`.
+#[doc(hidden)]
+pub fn apply_configuration<T, F>(filter: &mut F, config: FilterConfiguration<T>) -> bool
+where
+    T: Float,
+    F: BiquadFilterWrapper<T>,
+{
+    if filter.get_filter_ref().is_lazy() {
+        if !config.is_valid() {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                instance = filter as *const F as usize,
+                filter_type = ?filter.filter_type(),
+                "rejected filter configuration"
+            );
+            return false;
+        }
+        *filter.get_config_mut() = config;
+        filter.get_filter().mark_stale();
+        return true;
+    }
+    match F::calculate_coefficients(&config) {
+        Some(coefficients) if filter.get_filter().update_coefficients(coefficients) => {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                instance = filter as *const F as usize,
+                filter_type = ?filter.filter_type(),
+                cutoff = config.get_cutoff().to_f64(),
+                sample_rate = config.get_sample_rate(),
+                q_factor = config.get_q_factor().to_f64(),
+                gain = config.get_gain().to_f64(),
+                bypass = config.get_bypass(),
+                "recomputed filter coefficients"
+            );
+            *filter.get_config_mut() = config;
+            true
+        }
+        _ => {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                instance = filter as *const F as usize,
+                filter_type = ?filter.filter_type(),
+                cutoff = config.get_cutoff().to_f64(),
+                sample_rate = config.get_sample_rate(),
+                q_factor = config.get_q_factor().to_f64(),
+                gain = config.get_gain().to_f64(),
+                "rejected filter configuration"
+            );
+            false
+        }
+    }
+}
+
+/// Recomputes coefficients deferred by lazy updates, if the configuration changed since they were
+/// last computed. The current coefficients are kept if the configuration does not produce valid
+/// coefficients.
+#[doc(hidden)]
+pub fn refresh_coefficients<T, F>(filter: &mut F)
+where
+    T: Float,
+    F: BiquadFilterWrapper<T>,
+{
+    if !filter.get_filter().take_stale() {
+        return;
+    }
+    if let Some(coefficients) = F::calculate_coefficients(filter.get_config()) {
+        filter.get_filter().update_coefficients(coefficients);
+    }
 }
 
+/// The number of samples converted at a time by `FilterExt::process_samples`.
+const SAMPLE_CHUNK: usize = 64;
+
 /// A Generic Filter trait for processing audio samples.
-pub trait Filter<T: Float + Default> {
+pub trait Filter<T: Float> {
     /// Processes a single sample in-place and returns a boolean indicating success.
     fn process(&mut self, sample: &mut T) -> bool;
     /// Processes a block of samples in-place and returns a boolean indicating success.
     fn process_block(&mut self, samples: &mut [T]) -> bool;
+    /// Clears the filter state and the count of samples processed, keeping the configuration.
+    fn reset(&mut self);
     /// Returns the current configuration of the filter.
     fn get_configuration(&self) -> FilterConfiguration<T>;
     /// Sets the configuration of the filter.
@@ -66,136 +214,825 @@ pub trait Filter<T: Float + Default> {
     fn get_bypass(&self) -> bool;
     /// Sets whether the filter should be bypassed.
     fn set_bypass(&mut self, bypass: bool) -> bool;
+    /// Returns the number of samples processed, including bypassed samples. The count keeps
+    /// running across parameter changes and wraps around to zero after `u64::MAX` samples.
+    fn samples_processed(&self) -> u64;
+    /// Returns the limit applied to the output.
+    fn get_output_limit(&self) -> OutputLimit<T>;
+    /// Sets the limit applied to the output. Returns `false` if the limit is not positive and
+    /// finite.
+    fn set_output_limit(&mut self, output_limit: OutputLimit<T>) -> bool;
+    /// Returns the faults detected since they were last taken.
+    fn get_faults(&self) -> Faults;
+    /// Returns the faults detected since they were last taken and clears them.
+    fn take_faults(&mut self) -> Faults;
+    /// Sets the function called the first time each fault is detected, or removes it.
+    fn set_fault_handler(&mut self, handler: Option<FaultHandler>);
+    /// Returns when parameter changes take effect.
+    fn get_update_policy(&self) -> UpdatePolicy;
+    /// Sets when parameter changes take effect. Changes held back by the previous policy are
+    /// applied immediately.
+    fn set_update_policy(&mut self, update_policy: UpdatePolicy);
+
+    /// Starts recording the last `capacity` processed samples and filter states. Does nothing by
+    /// default, for filters whose state does not fit a `HistoryEntry`.
+    #[cfg(feature = "state-history")]
+    fn enable_history(&mut self, _capacity: usize) {}
+
+    /// Stops recording and discards the history.
+    #[cfg(feature = "state-history")]
+    fn disable_history(&mut self) {}
+
+    /// Returns the recorded history, oldest first. Returns an empty history by default.
+    #[cfg(feature = "state-history")]
+    fn get_history(&self) -> Vec<HistoryEntry<T>> {
+        Vec::new()
+    }
+
+    /// Returns the time processed in seconds at the current sample rate, for synchronizing
+    /// modulation and automation with the audio stream.
+    fn seconds_processed(&self) -> f64 {
+        match self.get_sample_rate() {
+            0 => 0.0,
+            rate => self.samples_processed() as f64 / rate as f64,
+        }
+    }
+
+    /// Returns whether parameter changes only mark the coefficients for recomputation. Filters
+    /// without lazy updates always return `false`.
+    fn get_lazy_updates(&self) -> bool {
+        false
+    }
+
+    /// Sets whether parameter changes only validate and store the new settings, leaving the
+    /// coefficients to be recomputed once on the next `process` or `process_block` call. This
+    /// avoids redundant work when many setters are called per block. Getters report new settings
+    /// straight away, but the coefficients and response stay at the previous settings until the
+    /// next processing call. Turning lazy updates off recomputes any outstanding change. Returns
+    /// `false` if the filter does not support lazy updates.
+    fn set_lazy_updates(&mut self, lazy: bool) -> bool {
+        !lazy
+    }
+
+    /// Returns the structure used to evaluate the difference equation. Filters with a single
+    /// structure report `Topology::DirectForm1`.
+    fn get_topology(&self) -> Topology {
+        Topology::DirectForm1
+    }
+
+    /// Sets the structure used to evaluate the difference equation. Returns `false` if the filter
+    /// does not support the topology.
+    fn set_topology(&mut self, topology: Topology) -> bool {
+        topology == Topology::DirectForm1
+    }
+
+    /// Returns how the analog prototype is mapped to digital coefficients.
+    fn get_design_method(&self) -> DesignMethod {
+        self.get_configuration().get_design_method()
+    }
+
+    /// Sets how the analog prototype is mapped to digital coefficients and recomputes them.
+    /// Filter types without a design for the method use the bilinear transform. Returns `false`
+    /// and keeps the current coefficients if they cannot be designed.
+    fn set_design_method(&mut self, design_method: DesignMethod) -> bool {
+        let mut configuration = self.get_configuration();
+        configuration.set_design_method(design_method);
+        self.set_configuration(configuration)
+    }
+
+    /// Sets the cutoff, Q factor and gain together, recomputing the coefficients once instead of
+    /// once per setter. Returns `false` and keeps every current value if the combination is
+    /// invalid.
+    fn set_parameters(&mut self, cutoff: T, q_factor: T, gain: T) -> bool {
+        let mut configuration = self.get_configuration();
+        configuration.set_cutoff(cutoff);
+        configuration.set_q_factor(q_factor);
+        configuration.set_gain(gain);
+        self.set_configuration(configuration)
+    }
+
+    /// Processes `input` into `output`, leaving the input untouched. Returns `false` if the
+    /// slices differ in length or the block could not be processed.
+    fn process_block_to(&mut self, input: &[T], output: &mut [T]) -> bool {
+        if input.len() != output.len() {
+            return false;
+        }
+        output.copy_from_slice(input);
+        self.process_block(output)
+    }
+
+    /// Processes a block of samples and returns the peak levels, clipped and NaN sample counts,
+    /// and whether subnormal values were flushed to zero. Returns `None` if the block could not
+    /// be processed.
+    fn process_block_with_stats(&mut self, samples: &mut [T]) -> Option<BlockStats<T>> {
+        BlockStats::measure(samples, |samples| self.process_block(samples))
+    }
+
+    /// Captures the complete settings of the filter.
+    fn snapshot(&self) -> ConfigSnapshot<T> {
+        ConfigSnapshot::new(self.get_configuration())
+    }
+
+    /// Restores settings previously captured with `snapshot`.
+    fn restore(&mut self, snapshot: &ConfigSnapshot<T>) -> bool {
+        self.set_configuration(snapshot.get_configuration())
+    }
+
+    /// Reports which settings of the filter differ from the given snapshot.
+    fn diff(&self, snapshot: &ConfigSnapshot<T>) -> ConfigDiff {
+        ConfigDiff::between(&self.get_configuration(), &snapshot.get_configuration())
+    }
 }
 
-impl<T, F> Filter<T> for F
+/// Applies changes to a copy of a filter's configuration and sets it, for `FilterExt::update`.
+fn update_configuration<T, F>(
+    filter: &mut F,
+    change: impl FnOnce(&mut FilterConfiguration<T>),
+) -> bool
 where
-    T: Float + Default + Copy + std::ops::MulAssign,
-    F: BiquadFilterWrapper<T>,
+    T: Float,
+    F: Filter<T> + ?Sized,
 {
-    /// Processes a single sample in-place and returns a boolean indicating success.
-    fn process(&mut self, sample: &mut T) -> bool {
-        if self.get_config().get_bypass() {
-            return true;
+    let mut configuration = filter.get_configuration();
+    change(&mut configuration);
+    filter.set_configuration(configuration)
+}
+
+/// Processes samples of another format in chunks, for `FilterExt::process_samples`.
+fn process_converted<T, S, F>(filter: &mut F, samples: &mut [S]) -> bool
+where
+    T: Float,
+    S: Sample,
+    F: Filter<T> + ?Sized,
+{
+    if samples.is_empty() {
+        return false;
+    }
+    let mut buffer = [T::zero(); SAMPLE_CHUNK];
+    for chunk in samples.chunks_mut(SAMPLE_CHUNK) {
+        let buffer = &mut buffer[..chunk.len()];
+        for (value, sample) in buffer.iter_mut().zip(chunk.iter()) {
+            *value = sample.to_float();
+        }
+        if !filter.process_block(buffer) {
+            return false;
+        }
+        for (sample, value) in chunk.iter_mut().zip(buffer.iter()) {
+            *sample = S::from_float(*value);
         }
-        self.get_filter().process(sample)
+    }
+    true
+}
+
+/// The generic methods of filters. They live apart from `Filter` so that `Filter` stays object
+/// safe, and are implemented for every filter, including `dyn Filter<T>`, so they can be called
+/// on boxed filters and trait object references alike.
+pub trait FilterExt<T: Float>: Filter<T> {
+    /// Applies any number of changes to a copy of the configuration and then recomputes the
+    /// coefficients once. Returns `false` and keeps the current configuration if the result is
+    /// invalid.
+    fn update(&mut self, change: impl FnOnce(&mut FilterConfiguration<T>)) -> bool {
+        update_configuration(self, change)
+    }
+
+    /// Processes a block of samples of another format, such as 16-bit integers, converting them
+    /// to `T` and back as they are processed. Integer results are rounded and clipped to full
+    /// scale. The samples are converted in chunks of 64, each processed as a block.
+    fn process_samples<S: Sample>(&mut self, samples: &mut [S]) -> bool {
+        process_converted(self, samples)
+    }
+}
+
+impl<T: Float, F: Filter<T> + ?Sized> FilterExt<T> for F {}
+
+/// Forwards every method to the boxed filter, so `Box<dyn Filter<T>>` can be used anywhere a
+/// `Filter<T>` is expected, such as in chains assembled at runtime.
+#[cfg(feature = "std")]
+impl<T: Float, F: Filter<T> + ?Sized> Filter<T> for Box<F> {
+    fn process(&mut self, sample: &mut T) -> bool {
+        (**self).process(sample)
     }
 
-    /// Processes a block of samples in-place and returns a boolean indicating success.
     fn process_block(&mut self, samples: &mut [T]) -> bool {
-        if self.get_config().get_bypass() {
-            return true;
-        }
-        self.get_filter().process_block(samples)
+        (**self).process_block(samples)
+    }
+
+    fn reset(&mut self) {
+        (**self).reset();
     }
 
-    /// Returns the current configuration of the filter.
     fn get_configuration(&self) -> FilterConfiguration<T> {
-        self.get_config().clone()
+        (**self).get_configuration()
     }
 
-    /// Sets the configuration of the filter.
-    fn set_configuration(&mut self, config: FilterConfiguration<T>) -> bool {
-        *self.get_config_mut() = config;
-        if let Some(coefficients) = Self::calculate_coefficients(self.get_config()) {
-            self.get_filter().set_coefficients(coefficients)
-        } else {
-            false
-        }
+    fn set_configuration(&mut self, configuration: FilterConfiguration<T>) -> bool {
+        (**self).set_configuration(configuration)
     }
 
-    /// Returns the cutoff frequency of the filter.
     fn get_cutoff(&self) -> T {
-        self.get_config().get_cutoff()
+        (**self).get_cutoff()
     }
 
-    /// Sets the cutoff frequency of the filter.
     fn set_cutoff(&mut self, cutoff: T) -> bool {
-        self.get_config_mut().set_cutoff(cutoff);
-        if let Some(coefficients) = Self::calculate_coefficients(self.get_config()) {
-            self.get_filter().set_coefficients(coefficients)
-        } else {
-            false
-        }
+        (**self).set_cutoff(cutoff)
     }
 
-    /// Returns the sample rate of the filter.
     fn get_sample_rate(&self) -> u32 {
-        self.get_config().get_sample_rate()
+        (**self).get_sample_rate()
     }
 
-    /// Sets the sample rate of the filter.
-    fn set_sample_rate(&mut self, rate: u32) -> bool {
-        self.get_config_mut().set_sample_rate(rate);
-        if let Some(coefficients) = Self::calculate_coefficients(self.get_config()) {
-            self.get_filter().set_coefficients(coefficients)
-        } else {
-            false
-        }
+    fn set_sample_rate(&mut self, sample_rate: u32) -> bool {
+        (**self).set_sample_rate(sample_rate)
     }
 
-    /// Returns the Q factor of the filter.
     fn get_q_factor(&self) -> T {
-        self.get_config().get_q_factor()
+        (**self).get_q_factor()
     }
 
-    /// Sets the Q factor of the filter.
-    fn set_q_factor(&mut self, q: T) -> bool {
-        self.get_config_mut().set_q_factor(q);
-        if let Some(coefficients) = Self::calculate_coefficients(self.get_config()) {
-            self.get_filter().set_coefficients(coefficients)
-        } else {
-            false
-        }
+    fn set_q_factor(&mut self, q_factor: T) -> bool {
+        (**self).set_q_factor(q_factor)
     }
 
-    /// Returns the gain of the filter. This is only applicable for peaking and shelving filters.
     fn get_gain(&self) -> T {
-        self.get_config().get_gain()
+        (**self).get_gain()
     }
 
-    /// Sets the gain of the filter. This is only applicable for peaking and shelving filters.
-    /// If this parameter is not applicable for the current filter type, this will do nothing.
     fn set_gain(&mut self, gain: T) -> bool {
-        self.get_config_mut().set_gain(gain);
-        if let Some(coefficients) = Self::calculate_coefficients(self.get_config()) {
-            self.get_filter().set_coefficients(coefficients)
-        } else {
-            false
-        }
+        (**self).set_gain(gain)
     }
 
-    /// Returns whether the filter has a constant skirt gain. This is only applicable for band-pass
-    /// filters.
     fn get_constant_skirt_gain(&self) -> bool {
-        self.get_config().get_constant_skirt_gain()
+        (**self).get_constant_skirt_gain()
     }
 
-    /// Sets whether the filter should have a constant skirt gain. This is only applicable for
-    /// band-pass filters. If this parameter is not applicable for the current filter type, this
-    /// will do nothing.
     fn set_constant_skirt_gain(&mut self, constant_skirt_gain: bool) -> bool {
-        self.get_config_mut()
-            .set_constant_skirt_gain(constant_skirt_gain);
-        if let Some(coefficients) = Self::calculate_coefficients(self.get_config()) {
-            self.get_filter().set_coefficients(coefficients)
-        } else {
-            false
-        }
+        (**self).set_constant_skirt_gain(constant_skirt_gain)
     }
 
-    /// Returns whether the filter should be bypassed.
     fn get_bypass(&self) -> bool {
-        self.get_config().get_bypass()
+        (**self).get_bypass()
     }
 
-    /// Sets whether the filter should be bypassed.
     fn set_bypass(&mut self, bypass: bool) -> bool {
-        self.get_config_mut().set_bypass(bypass);
-        if let Some(coefficients) = Self::calculate_coefficients(self.get_config()) {
-            self.get_filter().set_coefficients(coefficients)
-        } else {
-            false
-        }
+        (**self).set_bypass(bypass)
+    }
+
+    fn samples_processed(&self) -> u64 {
+        (**self).samples_processed()
+    }
+
+    fn get_output_limit(&self) -> OutputLimit<T> {
+        (**self).get_output_limit()
+    }
+
+    fn set_output_limit(&mut self, output_limit: OutputLimit<T>) -> bool {
+        (**self).set_output_limit(output_limit)
+    }
+
+    fn get_faults(&self) -> Faults {
+        (**self).get_faults()
+    }
+
+    fn take_faults(&mut self) -> Faults {
+        (**self).take_faults()
+    }
+
+    fn set_fault_handler(&mut self, handler: Option<FaultHandler>) {
+        (**self).set_fault_handler(handler);
+    }
+
+    fn get_update_policy(&self) -> UpdatePolicy {
+        (**self).get_update_policy()
+    }
+
+    fn set_update_policy(&mut self, update_policy: UpdatePolicy) {
+        (**self).set_update_policy(update_policy);
+    }
+
+    #[cfg(feature = "state-history")]
+    fn enable_history(&mut self, capacity: usize) {
+        (**self).enable_history(capacity);
+    }
+
+    #[cfg(feature = "state-history")]
+    fn disable_history(&mut self) {
+        (**self).disable_history();
+    }
+
+    #[cfg(feature = "state-history")]
+    fn get_history(&self) -> Vec<HistoryEntry<T>> {
+        (**self).get_history()
+    }
+
+    fn seconds_processed(&self) -> f64 {
+        (**self).seconds_processed()
+    }
+
+    fn get_lazy_updates(&self) -> bool {
+        (**self).get_lazy_updates()
+    }
+
+    fn set_lazy_updates(&mut self, lazy: bool) -> bool {
+        (**self).set_lazy_updates(lazy)
+    }
+
+    fn get_topology(&self) -> Topology {
+        (**self).get_topology()
+    }
+
+    fn set_topology(&mut self, topology: Topology) -> bool {
+        (**self).set_topology(topology)
+    }
+
+    fn set_parameters(&mut self, cutoff: T, q_factor: T, gain: T) -> bool {
+        (**self).set_parameters(cutoff, q_factor, gain)
+    }
+
+    fn process_block_to(&mut self, input: &[T], output: &mut [T]) -> bool {
+        (**self).process_block_to(input, output)
+    }
+
+    fn process_block_with_stats(&mut self, samples: &mut [T]) -> Option<BlockStats<T>> {
+        (**self).process_block_with_stats(samples)
+    }
+
+    fn snapshot(&self) -> ConfigSnapshot<T> {
+        (**self).snapshot()
+    }
+
+    fn restore(&mut self, snapshot: &ConfigSnapshot<T>) -> bool {
+        (**self).restore(snapshot)
+    }
+
+    fn diff(&self, snapshot: &ConfigSnapshot<T>) -> ConfigDiff {
+        (**self).diff(snapshot)
+    }
+}
+
+/// Processes a sample through a wrapped filter, or only counts it while the filter is bypassed.
+#[doc(hidden)]
+pub fn process_wrapped<T, F>(filter: &mut F, sample: &mut T) -> bool
+where
+    T: Float,
+    F: BiquadFilterWrapper<T>,
+{
+    if filter.get_config().get_bypass() {
+        filter.get_filter().advance(1);
+        return true;
+    }
+    refresh_coefficients(filter);
+    filter.get_filter().process(sample)
+}
+
+/// Processes a block through a wrapped filter, or only counts it while the filter is bypassed.
+#[doc(hidden)]
+pub fn process_block_wrapped<T, F>(filter: &mut F, samples: &mut [T]) -> bool
+where
+    T: Float,
+    F: BiquadFilterWrapper<T>,
+{
+    if filter.get_config().get_bypass() {
+        filter.get_filter().advance(samples.len() as u64);
+        return true;
+    }
+    refresh_coefficients(filter);
+    filter.get_filter().process_block(samples)
+}
+
+/// Processes a block through a wrapped filter and returns statistics about it.
+#[doc(hidden)]
+pub fn process_block_with_stats_wrapped<T, F>(
+    filter: &mut F,
+    samples: &mut [T],
+) -> Option<BlockStats<T>>
+where
+    T: Float,
+    F: BiquadFilterWrapper<T>,
+{
+    if filter.get_config().get_bypass() {
+        let filter = filter.get_filter();
+        return BlockStats::measure(samples, |samples| {
+            filter.advance(samples.len() as u64);
+            true
+        });
+    }
+    refresh_coefficients(filter);
+    filter.get_filter().process_block_with_stats(samples)
+}
+
+/// Processes a block of another sample format through a wrapped filter.
+#[doc(hidden)]
+pub fn process_samples_wrapped<T, F, S>(filter: &mut F, samples: &mut [S]) -> bool
+where
+    T: Float,
+    F: BiquadFilterWrapper<T>,
+    S: Sample,
+{
+    if filter.get_config().get_bypass() {
+        filter.get_filter().advance(samples.len() as u64);
+        return true;
+    }
+    refresh_coefficients(filter);
+    filter.get_filter().process_samples(samples)
+}
+
+/// Returns whether a wrapped filter defers coefficient updates.
+#[doc(hidden)]
+pub fn lazy_updates_wrapped<T, F>(filter: &F) -> bool
+where
+    T: Float,
+    F: BiquadFilterWrapper<T>,
+{
+    filter.get_filter_ref().is_lazy()
+}
+
+/// Sets whether a wrapped filter defers coefficient updates, recomputing any deferred update when
+/// they are turned off.
+#[doc(hidden)]
+pub fn set_lazy_updates_wrapped<T, F>(filter: &mut F, lazy: bool) -> bool
+where
+    T: Float,
+    F: BiquadFilterWrapper<T>,
+{
+    filter.get_filter().set_lazy(lazy);
+    if !lazy {
+        refresh_coefficients(filter);
     }
+    true
+}
+
+/// Expands its items only when the `metering` feature is enabled. The check happens in this
+/// crate, so it also holds when `impl_biquad_filter!` expands in another crate.
+#[cfg(feature = "metering")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __with_metering {
+    ($($item:tt)*) => { $($item)* };
 }
+
+#[cfg(not(feature = "metering"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __with_metering {
+    ($($item:tt)*) => {};
+}
+
+/// Expands its items only when the `state-history` feature is enabled.
+#[cfg(feature = "state-history")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __with_state_history {
+    ($($item:tt)*) => { $($item)* };
+}
+
+#[cfg(not(feature = "state-history"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __with_state_history {
+    ($($item:tt)*) => {};
+}
+
+/// Implements `Filter`, `FrequencyResponse` and, with the `metering` feature, `Metered` for a
+/// filter type that wraps a `DigitalBiquadFilter` through `BiquadFilterWrapper`, plus an inherent
+/// `process_samples`. The wrappers in this crate invoke it with their name, and
+/// `#[derive(BiquadFilterWrapper)]` invokes it with the generics of the deriving type, so it only
+/// names items through public paths.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_biquad_filter {
+    ($filter:ident) => {
+        $crate::__impl_biquad_filter!([<T: $crate::__private::Float>] $filter<T>, T, []);
+    };
+    ([$($generics:tt)*] $filter:ty, $sample:ident, [$($where:tt)*]) => {
+        const _: () = {
+            use $crate::BiquadFilterWrapper as _;
+
+            impl $($generics)* $crate::Filter<$sample> for $filter $($where)* {
+                /// Processes a single sample in-place and returns a boolean indicating success.
+                fn process(&mut self, sample: &mut $sample) -> bool {
+                    $crate::__private::process_wrapped(self, sample)
+                }
+
+                /// Processes a block of samples in-place and returns a boolean indicating success.
+                fn process_block(&mut self, samples: &mut [$sample]) -> bool {
+                    $crate::__private::process_block_wrapped(self, samples)
+                }
+
+                /// Clears the filter state and the count of samples processed.
+                fn reset(&mut self) {
+                    self.get_filter().reset();
+                }
+
+                /// Processes a block of samples and returns statistics about it.
+                fn process_block_with_stats(
+                    &mut self,
+                    samples: &mut [$sample],
+                ) -> Option<$crate::BlockStats<$sample>> {
+                    $crate::__private::process_block_with_stats_wrapped(self, samples)
+                }
+
+                /// Returns the current configuration of the filter.
+                fn get_configuration(&self) -> $crate::FilterConfiguration<$sample> {
+                    *self.get_config()
+                }
+
+                /// Sets the configuration of the filter.
+                fn set_configuration(
+                    &mut self,
+                    config: $crate::FilterConfiguration<$sample>,
+                ) -> bool {
+                    $crate::__private::apply_configuration(self, config)
+                }
+
+                /// Returns the cutoff frequency of the filter.
+                fn get_cutoff(&self) -> $sample {
+                    self.get_config().get_cutoff()
+                }
+
+                /// Sets the cutoff frequency of the filter.
+                fn set_cutoff(&mut self, cutoff: $sample) -> bool {
+                    let mut config = *self.get_config();
+                    config.set_cutoff(cutoff);
+                    $crate::__private::apply_configuration(self, config)
+                }
+
+                /// Returns the sample rate of the filter.
+                fn get_sample_rate(&self) -> u32 {
+                    self.get_config().get_sample_rate()
+                }
+
+                /// Sets the sample rate of the filter.
+                fn set_sample_rate(&mut self, rate: u32) -> bool {
+                    let mut config = *self.get_config();
+                    config.set_sample_rate(rate);
+                    $crate::__private::apply_configuration(self, config)
+                }
+
+                /// Returns the Q factor of the filter.
+                fn get_q_factor(&self) -> $sample {
+                    self.get_config().get_q_factor()
+                }
+
+                /// Sets the Q factor of the filter.
+                fn set_q_factor(&mut self, q: $sample) -> bool {
+                    let mut config = *self.get_config();
+                    config.set_q_factor(q);
+                    $crate::__private::apply_configuration(self, config)
+                }
+
+                /// Returns the gain of the filter. This is only applicable for peaking and
+                /// shelving filters.
+                fn get_gain(&self) -> $sample {
+                    self.get_config().get_gain()
+                }
+
+                /// Sets the gain of the filter. This is only applicable for peaking and shelving
+                /// filters. If this parameter is not applicable for the current filter type, this
+                /// will do nothing.
+                fn set_gain(&mut self, gain: $sample) -> bool {
+                    let mut config = *self.get_config();
+                    config.set_gain(gain);
+                    $crate::__private::apply_configuration(self, config)
+                }
+
+                /// Returns whether the filter has a constant skirt gain. This is only applicable
+                /// for band-pass filters.
+                fn get_constant_skirt_gain(&self) -> bool {
+                    self.get_config().get_constant_skirt_gain()
+                }
+
+                /// Sets whether the filter should have a constant skirt gain. This is only
+                /// applicable for band-pass filters. If this parameter is not applicable for the
+                /// current filter type, this will do nothing.
+                fn set_constant_skirt_gain(&mut self, constant_skirt_gain: bool) -> bool {
+                    let mut config = *self.get_config();
+                    config.set_constant_skirt_gain(constant_skirt_gain);
+                    $crate::__private::apply_configuration(self, config)
+                }
+
+                /// Returns whether the filter should be bypassed.
+                fn get_bypass(&self) -> bool {
+                    self.get_config().get_bypass()
+                }
+
+                /// Sets whether the filter should be bypassed.
+                fn set_bypass(&mut self, bypass: bool) -> bool {
+                    let mut config = *self.get_config();
+                    config.set_bypass(bypass);
+                    $crate::__private::apply_configuration(self, config)
+                }
+
+                /// Returns the number of samples processed, including bypassed samples.
+                fn samples_processed(&self) -> u64 {
+                    self.get_filter_ref().samples_processed()
+                }
+
+                /// Returns the limit applied to the output.
+                fn get_output_limit(&self) -> $crate::OutputLimit<$sample> {
+                    self.get_filter_ref().get_output_limit()
+                }
+
+                /// Sets the limit applied to the output.
+                fn set_output_limit(&mut self, output_limit: $crate::OutputLimit<$sample>) -> bool {
+                    self.get_filter().set_output_limit(output_limit)
+                }
+
+                /// Returns the faults detected since they were last taken.
+                fn get_faults(&self) -> $crate::Faults {
+                    self.get_filter_ref().get_faults()
+                }
+
+                /// Returns the faults detected since they were last taken and clears them.
+                fn take_faults(&mut self) -> $crate::Faults {
+                    self.get_filter().take_faults()
+                }
+
+                /// Sets the function called the first time each fault is detected, or removes it.
+                fn set_fault_handler(&mut self, handler: Option<$crate::FaultHandler>) {
+                    self.get_filter().set_fault_handler(handler);
+                }
+
+                /// Returns when parameter changes take effect.
+                fn get_update_policy(&self) -> $crate::UpdatePolicy {
+                    self.get_filter_ref().get_update_policy()
+                }
+
+                /// Sets when parameter changes take effect.
+                fn set_update_policy(&mut self, update_policy: $crate::UpdatePolicy) {
+                    self.get_filter().set_update_policy(update_policy);
+                }
+
+                /// Returns whether parameter changes only mark the coefficients for
+                /// recomputation.
+                fn get_lazy_updates(&self) -> bool {
+                    $crate::__private::lazy_updates_wrapped(self)
+                }
+
+                /// Sets whether parameter changes are deferred until the next processing call.
+                fn set_lazy_updates(&mut self, lazy: bool) -> bool {
+                    $crate::__private::set_lazy_updates_wrapped(self, lazy)
+                }
+
+                /// Returns the structure used to evaluate the difference equation.
+                fn get_topology(&self) -> $crate::Topology {
+                    self.get_filter_ref().get_topology()
+                }
+
+                /// Sets the structure used to evaluate the difference equation.
+                fn set_topology(&mut self, topology: $crate::Topology) -> bool {
+                    self.get_filter().set_topology(topology);
+                    true
+                }
+
+                $crate::__with_state_history! {
+                    /// Starts recording the last `capacity` processed samples and filter states.
+                    fn enable_history(&mut self, capacity: usize) {
+                        self.get_filter().enable_history(capacity);
+                    }
+
+                    /// Stops recording and discards the history.
+                    fn disable_history(&mut self) {
+                        self.get_filter().disable_history();
+                    }
+
+                    /// Returns the recorded history, oldest first.
+                    fn get_history(&self) -> ::std::vec::Vec<$crate::HistoryEntry<$sample>> {
+                        self.get_filter_ref().get_history()
+                    }
+                }
+            }
+
+            $crate::__with_metering! {
+                impl $($generics)* $crate::Metered<$sample> for $filter $($where)* {
+                    /// Starts metering the input and output levels.
+                    fn enable_metering(&mut self, window: u32) -> $crate::Meters {
+                        self.get_filter().enable_metering(window)
+                    }
+
+                    /// Stops metering.
+                    fn disable_metering(&mut self) {
+                        self.get_filter().disable_metering();
+                    }
+
+                    /// Returns handles to the meters, if metering is enabled.
+                    fn get_meters(&self) -> Option<$crate::Meters> {
+                        self.get_filter_ref().get_meters()
+                    }
+                }
+            }
+
+            impl $($generics)* $filter $($where)* {
+                /// Processes a block of samples of another format without a separate conversion
+                /// pass. This takes precedence over `FilterExt::process_samples`, which converts
+                /// in chunks.
+                pub fn process_samples<S: $crate::convert::Sample>(
+                    &mut self,
+                    samples: &mut [S],
+                ) -> bool {
+                    $crate::__private::process_samples_wrapped(self, samples)
+                }
+            }
+
+            impl $($generics)* $crate::FrequencyResponse<$sample> for $filter $($where)* {
+                /// Returns the response of the filter, or a flat response while it is bypassed.
+                fn response_at(
+                    &self,
+                    frequency: $sample,
+                    sample_rate: u32,
+                ) -> $crate::__private::Complex<$sample> {
+                    if self.get_config().get_bypass() {
+                        return $crate::__private::Complex::from(<$sample>::one());
+                    }
+                    $crate::FrequencyResponse::response_at(
+                        self.get_filter_ref(),
+                        frequency,
+                        sample_rate,
+                    )
+                }
+
+                /// Returns the exact peak of the section, or one while the filter is bypassed.
+                fn max_gain(&self) -> $sample {
+                    if self.get_config().get_bypass() {
+                        return <$sample>::one();
+                    }
+                    self.get_filter_ref().get_coefficients().max_gain()
+                }
+            }
+        };
+    };
+}
+
+pub(crate) use crate::__impl_biquad_filter as impl_biquad_filter;
+
+/// Implements `to_f32` and `to_f64` for a filter type that holds a `filter` and a `config`. The
+/// coefficients and state are converted directly rather than recomputed, so a design computed in
+/// double precision keeps its exact coefficients apart from rounding.
+macro_rules! impl_precision_conversions {
+    ($filter:ident) => {
+        impl $filter<f64> {
+            /// Converts the filter, including its state, to single precision.
+            pub fn to_f32(&self) -> $filter<f32> {
+                $filter {
+                    filter: self.filter.to_f32(),
+                    config: self.config.into(),
+                }
+            }
+        }
+
+        impl $filter<f32> {
+            /// Converts the filter, including its state, to double precision.
+            pub fn to_f64(&self) -> $filter<f64> {
+                $filter {
+                    filter: self.filter.to_f64(),
+                    config: self.config.into(),
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use impl_precision_conversions;
+
+/// Implements `BiquadFilterWrapper` for a filter type that holds a `filter` and a `config`, so a
+/// new filter type only supplies its `FilterType` variant and an inherent `design_coefficients`
+/// function. The design always runs in double precision and only the finished coefficients are
+/// cast to `T`, since `cos(w0)` rounded to `f32` visibly moves low cutoffs at high sample rates.
+/// Filter types outside the crate use `#[derive(BiquadFilterWrapper)]` from the `macros` feature
+/// instead.
+macro_rules! impl_biquad_filter_wrapper {
+    ($filter:ident, $filter_type:ident) => {
+        impl<T> $crate::filters::filter::BiquadFilterWrapper<T> for $filter<T>
+        where
+            T: Float,
+        {
+            fn get_filter(&mut self) -> &mut $crate::filters::biquad::DigitalBiquadFilter<T> {
+                &mut self.filter
+            }
+
+            fn get_filter_ref(&self) -> &$crate::filters::biquad::DigitalBiquadFilter<T> {
+                &self.filter
+            }
+
+            fn get_config(&self) -> &$crate::filters::filter_configuration::FilterConfiguration<T> {
+                &self.config
+            }
+
+            fn get_config_mut(&mut self) -> &mut $crate::filters::filter_configuration::FilterConfiguration<T> {
+                &mut self.config
+            }
+
+            fn filter_type(&self) -> Option<$crate::filters::filter::FilterType> {
+                Some($crate::filters::filter::FilterType::$filter_type)
+            }
+
+            fn calculate_coefficients(
+                config: &$crate::filters::filter_configuration::FilterConfiguration<T>,
+            ) -> Option<$crate::filters::biquad::Coefficients<T>> {
+                $filter::<f64>::design_coefficients(&config.cast())
+                    .map(|coefficients| coefficients.cast())
+            }
+        }
+    };
+}
+
+pub(crate) use impl_biquad_filter_wrapper;
@@ -0,0 +1,184 @@
+/// filter_bank.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::band_pass::BandPassFilter;
+use crate::filters::filter::{BiquadFilterWrapper, Filter};
+use num_traits::Float;
+
+/// A perceptual frequency scale used to space the bands of a filter bank.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FrequencyScale {
+    /// The mel scale, `2595 * log10(1 + f / 700)`.
+    Mel,
+    /// The Bark critical-band scale, using Traunmüller's approximation.
+    Bark,
+}
+
+impl FrequencyScale {
+    /// Converts a frequency in Hz to this scale.
+    pub fn from_hz<T: Float>(self, frequency: T) -> Option<T> {
+        match self {
+            FrequencyScale::Mel => {
+                Some(T::from(2595.0)? * (T::one() + frequency / T::from(700.0)?).log10())
+            }
+            FrequencyScale::Bark => Some(
+                T::from(26.81)? * frequency / (T::from(1960.0)? + frequency) - T::from(0.53)?,
+            ),
+        }
+    }
+
+    /// Converts a value on this scale to a frequency in Hz.
+    pub fn to_hz<T: Float>(self, value: T) -> Option<T> {
+        match self {
+            FrequencyScale::Mel => {
+                Some(T::from(700.0)? * (T::from(10.0)?.powf(value / T::from(2595.0)?) - T::one()))
+            }
+            FrequencyScale::Bark => {
+                Some(T::from(1960.0)? * (value + T::from(0.53)?) / (T::from(26.28)? - value))
+            }
+        }
+    }
+}
+
+/// A bank of band-pass filters fed with the same input, each producing its own output. Useful as
+/// an IIR front-end for speech and machine-learning feature extraction.
+#[derive(Debug, Clone)]
+pub struct FilterBank<T: Float> {
+    /// One band-pass filter per band, in ascending order of center frequency.
+    bands: Vec<BandPassFilter<T>>,
+}
+
+impl<T: Float> FilterBank<T> {
+    /// Creates a bank from the given band-pass filters.
+    pub fn new(bands: Vec<BandPassFilter<T>>) -> Self {
+        Self { bands }
+    }
+
+    /// Creates a bank of `count` band-pass filters with center frequencies evenly spaced on the
+    /// mel scale between `min_frequency` and `max_frequency`.
+    pub fn mel(count: usize, min_frequency: T, max_frequency: T, sample_rate: u32) -> Option<Self> {
+        Self::with_scale(FrequencyScale::Mel, count, min_frequency, max_frequency, sample_rate)
+    }
+
+    /// Creates a bank of `count` band-pass filters with center frequencies evenly spaced on the
+    /// Bark scale between `min_frequency` and `max_frequency`.
+    pub fn bark(count: usize, min_frequency: T, max_frequency: T, sample_rate: u32) -> Option<Self> {
+        Self::with_scale(FrequencyScale::Bark, count, min_frequency, max_frequency, sample_rate)
+    }
+
+    /// Creates a bank of `count` band-pass filters evenly spaced on the given scale. The range is
+    /// divided into `count + 1` equal steps on the scale and each band is centered on one of the
+    /// inner points, with a bandwidth equal to the spacing of its neighbours, so adjacent bands
+    /// cross near their -3 dB points. Returns `None` if the range is empty or exceeds the Nyquist
+    /// frequency.
+    pub fn with_scale(
+        scale: FrequencyScale,
+        count: usize,
+        min_frequency: T,
+        max_frequency: T,
+        sample_rate: u32,
+    ) -> Option<Self> {
+        let nyquist = T::from(sample_rate)? / T::from(2.0)?;
+        let in_range = min_frequency >= T::zero()
+            && max_frequency > min_frequency
+            && max_frequency <= nyquist;
+        if count == 0 || !in_range {
+            return None;
+        }
+        let low = scale.from_hz(min_frequency)?;
+        let high = scale.from_hz(max_frequency)?;
+        let step = (high - low) / T::from(count + 1)?;
+        let edges = (0..count + 2)
+            .map(|index| scale.to_hz(low + step * T::from(index)?))
+            .collect::<Option<Vec<T>>>()?;
+
+        let mut bands = Vec::with_capacity(count);
+        for window in edges.windows(3) {
+            let center = window[1];
+            let bandwidth = (window[2] - window[0]) / T::from(2.0)?;
+            bands.push(BandPassFilter::new(center, sample_rate, center / bandwidth, false)?);
+        }
+        Some(Self { bands })
+    }
+
+    /// Processes one input sample, writing each band's output to `outputs`. Returns `false` if
+    /// `outputs` does not have one entry per band.
+    pub fn process(&mut self, sample: T, outputs: &mut [T]) -> bool {
+        if outputs.len() != self.bands.len() {
+            return false;
+        }
+        for (band, output) in self.bands.iter_mut().zip(outputs.iter_mut()) {
+            *output = sample;
+            band.process(output);
+        }
+        true
+    }
+
+    /// Processes a block of input samples, writing each band's output to the matching slice of
+    /// `outputs`. Returns `false` if `outputs` does not have one slice per band, each as long as
+    /// the input.
+    pub fn process_block(&mut self, input: &[T], outputs: &mut [&mut [T]]) -> bool {
+        if input.is_empty()
+            || outputs.len() != self.bands.len()
+            || outputs.iter().any(|output| output.len() != input.len())
+        {
+            return false;
+        }
+        for (band, output) in self.bands.iter_mut().zip(outputs.iter_mut()) {
+            output.copy_from_slice(input);
+            band.process_block(output);
+        }
+        true
+    }
+
+    /// Returns the number of bands.
+    pub fn len(&self) -> usize {
+        self.bands.len()
+    }
+
+    /// Returns whether the bank has no bands.
+    pub fn is_empty(&self) -> bool {
+        self.bands.is_empty()
+    }
+
+    /// Returns the band at `index`, if it exists.
+    pub fn get_band(&self, index: usize) -> Option<&BandPassFilter<T>> {
+        self.bands.get(index)
+    }
+
+    /// Returns the band at `index` mutably, if it exists.
+    pub fn get_band_mut(&mut self, index: usize) -> Option<&mut BandPassFilter<T>> {
+        self.bands.get_mut(index)
+    }
+
+    /// Returns the center frequency of every band.
+    pub fn get_center_frequencies(&self) -> Vec<T> {
+        self.bands.iter().map(|band| band.get_cutoff()).collect()
+    }
+
+    /// Resets the state of every band.
+    pub fn reset(&mut self) {
+        for band in self.bands.iter_mut() {
+            band.get_filter().reset();
+        }
+    }
+}
@@ -1,5 +1,4 @@
 /// filter_configuration.rs
-
 /**
 Copyright © 2025 Alex Parisi
 
@@ -21,27 +20,55 @@ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
 OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
+use crate::filters::biquad::cast;
+use crate::filters::design_method::DesignMethod;
+use crate::filters::error::FilterError;
+use crate::filters::resonance::shelf_slope_to_q;
 use num_traits::{Float, Zero};
-use std::ops::MulAssign;
 
 
 /// Configuration for a filter.
 #[derive(Debug, Clone, Copy)]
-pub struct FilterConfiguration<T: Float + Default> {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FilterConfiguration<T: Float> {
     cutoff: T,
     sample_rate: u32,
     q_factor: T,
     gain: T,
     constant_skirt_gain: bool,
     bypass: bool,
+    /// The linear gain at the center of a notch, from zero for a complete notch up to one.
+    #[cfg_attr(feature = "serde", serde(default = "num_traits::Zero::zero"))]
+    notch_gain: T,
+    /// The Q factor used by peaking bands when the gain is negative, if it differs from
+    /// `q_factor`.
+    #[cfg_attr(feature = "serde", serde(default = "Option::default"))]
+    cut_q_factor: Option<T>,
+    /// The cookbook shelf slope used by shelving filters instead of `q_factor`, if one is set.
+    #[cfg_attr(feature = "serde", serde(default = "Option::default"))]
+    shelf_slope: Option<T>,
+    /// How the analog prototype is mapped to digital coefficients.
+    #[cfg_attr(feature = "serde", serde(default))]
+    design_method: DesignMethod,
 }
 
 /// Implementation of FilterConfiguration.
 /// TODO - Add bandwidth setting
 impl<T> FilterConfiguration<T>
 where
-    T: Float + Default + MulAssign + Copy,
+    T: Float,
 {
+    /// The largest gain magnitude in decibels accepted by the builder.
+    pub const MAX_GAIN: f64 = 60.0;
+
+    /// Returns a builder that checks every setting as the configuration is built.
+    pub fn builder() -> FilterConfigurationBuilder<T> {
+        FilterConfigurationBuilder::default()
+    }
+
+    /// Creates a configuration from the given settings without checking them. Invalid settings
+    /// are only rejected when coefficients are designed, so prefer `builder`, which reports
+    /// which setting is wrong.
     pub fn new(
         cutoff: T,
         sample_rate: u32,
@@ -57,6 +84,10 @@ where
             gain,
             constant_skirt_gain,
             bypass,
+            notch_gain: T::zero(),
+            cut_q_factor: None,
+            shelf_slope: None,
+            design_method: DesignMethod::Bilinear,
         }
     }
 
@@ -119,10 +150,95 @@ where
     pub fn get_bypass(&self) -> bool {
         self.bypass
     }
+
+    /// Returns the linear gain at the center of a notch filter. Zero, the default, removes the
+    /// center frequency completely.
+    pub fn get_notch_gain(&self) -> T {
+        self.notch_gain
+    }
+
+    /// Sets the linear gain at the center of a notch filter, between zero and one.
+    pub fn set_notch_gain(&mut self, value: T) {
+        self.notch_gain = value;
+    }
+
+    /// Returns the Q factor peaking bands use for cuts, or `None` if cuts use `q_factor` like
+    /// boosts do.
+    pub fn get_cut_q_factor(&self) -> Option<T> {
+        self.cut_q_factor
+    }
+
+    /// Sets a separate Q factor for peaking bands with a negative gain, such as a narrow cut
+    /// paired with a wide boost, or `None` to use `q_factor` for both.
+    pub fn set_cut_q_factor(&mut self, value: Option<T>) {
+        self.cut_q_factor = value;
+    }
+
+    /// Returns the shelf slope shelving filters use instead of `q_factor`, or `None` if they use
+    /// `q_factor`.
+    pub fn get_shelf_slope(&self) -> Option<T> {
+        self.shelf_slope
+    }
+
+    /// Sets the cookbook shelf slope `S`, which shelving filters convert to a Q factor at the
+    /// current gain, or `None` to use `q_factor`. A slope of one is the steepest shelf without
+    /// overshoot.
+    pub fn set_shelf_slope(&mut self, value: Option<T>) {
+        self.shelf_slope = value;
+    }
+
+    /// Returns the Q factor a shelving filter uses at the current gain: the one given by the shelf
+    /// slope if one is set, and `q_factor` otherwise. Returns NaN if the slope is too steep for
+    /// the gain.
+    pub fn get_shelf_q_factor(&self) -> T {
+        match self.shelf_slope {
+            Some(slope) => shelf_slope_to_q(slope, self.gain).unwrap_or_else(T::nan),
+            None => self.q_factor,
+        }
+    }
+
+    /// Returns how the analog prototype is mapped to digital coefficients.
+    pub fn get_design_method(&self) -> DesignMethod {
+        self.design_method
+    }
+
+    /// Sets how the analog prototype is mapped to digital coefficients. Filter types without a
+    /// design for the method use the bilinear transform.
+    pub fn set_design_method(&mut self, value: DesignMethod) {
+        self.design_method = value;
+    }
+
+    /// Returns the Q factor a peaking band uses at the current gain: the cut Q factor if one is
+    /// set and the gain is negative, and `q_factor` otherwise.
+    pub fn get_effective_q_factor(&self) -> T {
+        match self.cut_q_factor {
+            Some(cut_q_factor) if self.gain < T::zero() => cut_q_factor,
+            _ => self.q_factor,
+        }
+    }
+
+    /// Returns whether coefficients can be designed from this configuration: the cutoff
+    /// frequency, sample rate and Q factor must be positive, and every parameter must be finite.
+    pub fn is_valid(&self) -> bool {
+        self.cutoff.is_finite()
+            && self.cutoff > T::zero()
+            && self.sample_rate > 0
+            && self.q_factor.is_finite()
+            && self.q_factor > T::zero()
+            && self.gain.is_finite()
+            && self.notch_gain >= T::zero()
+            && self.notch_gain <= T::one()
+            && self
+                .cut_q_factor
+                .is_none_or(|q_factor| q_factor.is_finite() && q_factor > T::zero())
+            && self
+                .shelf_slope
+                .is_none_or(|slope| shelf_slope_to_q(slope, self.gain).is_some())
+    }
 }
 
 /// Implementing Default for FilterConfiguration.
-impl<T: Float + Default> Default for FilterConfiguration<T> {
+impl<T: Float> Default for FilterConfiguration<T> {
     fn default() -> Self {
         Self {
             cutoff: T::zero(),
@@ -131,6 +247,209 @@ impl<T: Float + Default> Default for FilterConfiguration<T> {
             gain: T::zero(),
             constant_skirt_gain: false,
             bypass: true,
+            notch_gain: T::zero(),
+            cut_q_factor: None,
+            shelf_slope: None,
+            design_method: DesignMethod::Bilinear,
+        }
+    }
+}
+
+impl<T: Float> FilterConfiguration<T> {
+    /// Converts the configuration to another float type.
+    pub(crate) fn cast<U: Float>(&self) -> FilterConfiguration<U> {
+        FilterConfiguration {
+            cutoff: cast(self.cutoff),
+            sample_rate: self.sample_rate,
+            q_factor: cast(self.q_factor),
+            gain: cast(self.gain),
+            constant_skirt_gain: self.constant_skirt_gain,
+            bypass: self.bypass,
+            notch_gain: cast(self.notch_gain),
+            cut_q_factor: self.cut_q_factor.map(cast),
+            shelf_slope: self.shelf_slope.map(cast),
+            design_method: self.design_method,
+        }
+    }
+}
+
+impl<T: Float> FilterConfiguration<T> {
+    /// Blends two configurations, from `a` at `t = 0` to `b` at `t = 1`, for morphing between
+    /// settings. `t` is clamped to that range. The cutoff and Q factors move linearly in octaves,
+    /// so a sweep covers equal frequency ratios in equal steps, and the gain moves linearly in
+    /// decibels. The notch gain and shelf slope move linearly. Settings that cannot be blended,
+    /// such as the sample rate, bypass state and design method, or an optional setting given by
+    /// only one side, switch from `a` to `b` halfway.
+    pub fn lerp(a: &Self, b: &Self, t: T) -> Self {
+        let t = t.max(T::zero()).min(T::one());
+        let half = T::from(0.5).unwrap_or_else(T::one);
+        let nearest = if t < half { a } else { b };
+        let blend_optional = |a: Option<T>, b: Option<T>, blend: fn(T, T, T) -> T| match (a, b) {
+            (Some(a), Some(b)) => Some(blend(a, b, t)),
+            _ if t < half => a,
+            _ => b,
+        };
+        Self {
+            cutoff: geometric_lerp(a.cutoff, b.cutoff, t),
+            sample_rate: nearest.sample_rate,
+            q_factor: geometric_lerp(a.q_factor, b.q_factor, t),
+            gain: linear_lerp(a.gain, b.gain, t),
+            constant_skirt_gain: nearest.constant_skirt_gain,
+            bypass: nearest.bypass,
+            notch_gain: linear_lerp(a.notch_gain, b.notch_gain, t),
+            cut_q_factor: blend_optional(a.cut_q_factor, b.cut_q_factor, geometric_lerp),
+            shelf_slope: blend_optional(a.shelf_slope, b.shelf_slope, linear_lerp),
+            design_method: nearest.design_method,
+        }
+    }
+}
+
+/// Interpolates linearly from `a` at `t = 0` to `b` at `t = 1`.
+pub(crate) fn linear_lerp<T: Float>(a: T, b: T, t: T) -> T {
+    a + (b - a) * t
+}
+
+/// Interpolates linearly in the logarithm of positive values, so each step is the same ratio.
+/// Falls back to linear interpolation unless both values are positive.
+pub(crate) fn geometric_lerp<T: Float>(a: T, b: T, t: T) -> T {
+    if a > T::zero() && b > T::zero() {
+        a * (b / a).powf(t)
+    } else {
+        linear_lerp(a, b, t)
+    }
+}
+
+impl From<FilterConfiguration<f64>> for FilterConfiguration<f32> {
+    fn from(configuration: FilterConfiguration<f64>) -> Self {
+        configuration.cast()
+    }
+}
+
+impl From<FilterConfiguration<f32>> for FilterConfiguration<f64> {
+    fn from(configuration: FilterConfiguration<f32>) -> Self {
+        configuration.cast()
+    }
+}
+
+/// Builds a `FilterConfiguration`, checking each setting in `build`. The cutoff and sample rate
+/// must be given; the Q factor defaults to `1/sqrt(2)` and the gain to zero.
+#[derive(Debug, Clone, Copy)]
+pub struct FilterConfigurationBuilder<T: Float> {
+    configuration: FilterConfiguration<T>,
+}
+
+impl<T: Float> Default for FilterConfigurationBuilder<T> {
+    fn default() -> Self {
+        let q_factor = T::from(core::f64::consts::FRAC_1_SQRT_2).unwrap_or_else(T::one);
+        Self {
+            configuration: FilterConfiguration::new(T::zero(), 0, q_factor, T::zero(), false, false),
+        }
+    }
+}
+
+impl<T: Float> FilterConfigurationBuilder<T> {
+    /// Sets the cutoff frequency in Hz.
+    pub fn cutoff(mut self, cutoff: T) -> Self {
+        self.configuration.set_cutoff(cutoff);
+        self
+    }
+
+    /// Sets the sample rate in Hz.
+    pub fn sample_rate(mut self, sample_rate: u32) -> Self {
+        self.configuration.set_sample_rate(sample_rate);
+        self
+    }
+
+    /// Sets the Q factor.
+    pub fn q_factor(mut self, q_factor: T) -> Self {
+        self.configuration.set_q_factor(q_factor);
+        self
+    }
+
+    /// Sets the gain in decibels.
+    pub fn gain(mut self, gain: T) -> Self {
+        self.configuration.set_gain(gain);
+        self
+    }
+
+    /// Sets whether band-pass filters have a constant skirt gain.
+    pub fn constant_skirt_gain(mut self, constant_skirt_gain: bool) -> Self {
+        self.configuration.set_constant_skirt_gain(constant_skirt_gain);
+        self
+    }
+
+    /// Sets whether the filter is bypassed.
+    pub fn bypass(mut self, bypass: bool) -> Self {
+        self.configuration.set_bypass(bypass);
+        self
+    }
+
+    /// Sets the linear gain at the center of a notch filter.
+    pub fn notch_gain(mut self, notch_gain: T) -> Self {
+        self.configuration.set_notch_gain(notch_gain);
+        self
+    }
+
+    /// Sets a separate Q factor for peaking cuts.
+    pub fn cut_q_factor(mut self, cut_q_factor: T) -> Self {
+        self.configuration.set_cut_q_factor(Some(cut_q_factor));
+        self
+    }
+
+    /// Sets the cookbook shelf slope used by shelving filters instead of the Q factor.
+    pub fn shelf_slope(mut self, shelf_slope: T) -> Self {
+        self.configuration.set_shelf_slope(Some(shelf_slope));
+        self
+    }
+
+    /// Sets how the analog prototype is mapped to digital coefficients.
+    pub fn design_method(mut self, design_method: DesignMethod) -> Self {
+        self.configuration.set_design_method(design_method);
+        self
+    }
+
+    /// Checks every setting and returns the configuration, or the first problem found.
+    pub fn build(self) -> Result<FilterConfiguration<T>, FilterError> {
+        let configuration = self.configuration;
+        let cutoff = configuration.get_cutoff();
+        let sample_rate = configuration.get_sample_rate();
+        let q_factor = configuration.get_q_factor();
+        let gain = configuration.get_gain();
+        let notch_gain = configuration.get_notch_gain();
+
+        if !(cutoff.is_finite() && cutoff > T::zero()) {
+            return Err(FilterError::InvalidCutoff);
+        }
+        if sample_rate == 0 {
+            return Err(FilterError::InvalidSampleRate);
+        }
+        let nyquist = T::from(sample_rate).ok_or(FilterError::InvalidSampleRate)?
+            / (T::one() + T::one());
+        if cutoff >= nyquist {
+            return Err(FilterError::CutoffAboveNyquist);
+        }
+        if !(q_factor.is_finite() && q_factor > T::zero()) {
+            return Err(FilterError::InvalidQFactor);
+        }
+        let max_gain = T::from(FilterConfiguration::<T>::MAX_GAIN).ok_or(FilterError::InvalidGain)?;
+        if !(gain.is_finite() && gain.abs() <= max_gain) {
+            return Err(FilterError::InvalidGain);
+        }
+        if !(notch_gain >= T::zero() && notch_gain <= T::one()) {
+            return Err(FilterError::InvalidNotchGain);
+        }
+        if configuration
+            .get_cut_q_factor()
+            .is_some_and(|q_factor| !(q_factor.is_finite() && q_factor > T::zero()))
+        {
+            return Err(FilterError::InvalidCutQFactor);
+        }
+        if configuration
+            .get_shelf_slope()
+            .is_some_and(|slope| shelf_slope_to_q(slope, gain).is_none())
+        {
+            return Err(FilterError::InvalidShelfSlope);
         }
+        Ok(configuration)
     }
 }
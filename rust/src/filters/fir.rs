@@ -0,0 +1,110 @@
+/// fir.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::{Coefficients, DigitalBiquadFilter};
+use num_traits::Float;
+use core::f64::consts::PI;
+
+/// The impulse response is measured over this many times the FIR length, and never fewer than
+/// `MIN_REFERENCE_LENGTH` samples, to estimate the error of the approximation.
+const REFERENCE_LENGTH_FACTOR: usize = 16;
+const MIN_REFERENCE_LENGTH: usize = 4096;
+
+/// The taper applied to a truncated impulse response. Only the decaying half of each window is
+/// used, so the first tap is left unchanged and the last tap approaches zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Window {
+    /// Plain truncation.
+    #[default]
+    Rectangular,
+    Hann,
+    Hamming,
+    Blackman,
+}
+
+impl Window {
+    /// Returns the weight of tap `n` out of `len`.
+    fn weight(&self, n: usize, len: usize) -> f64 {
+        let x = PI * n as f64 / len as f64;
+        match self {
+            Window::Rectangular => 1.0,
+            Window::Hann => 0.5 + 0.5 * x.cos(),
+            Window::Hamming => 0.54 + 0.46 * x.cos(),
+            Window::Blackman => 0.42 + 0.5 * x.cos() + 0.08 * (2.0 * x).cos(),
+        }
+    }
+}
+
+/// FIR taps approximating an IIR filter, along with the error of the approximation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FirApproximation<T: Float> {
+    /// The FIR taps, starting with the response to the impulse itself.
+    pub taps: Vec<T>,
+    /// The RMS difference between the taps and the full impulse response, relative to the RMS of
+    /// the impulse response. Zero for an exact match.
+    pub error: T,
+}
+
+/// Windows the impulse response of a series of sections into `len` FIR taps. Returns `None` if
+/// `len` is zero, any section is invalid or the impulse response does not stay finite.
+pub(crate) fn approximate<T>(
+    sections: &[Coefficients<T>],
+    len: usize,
+    window: Window,
+) -> Option<FirApproximation<T>>
+where
+    T: Float,
+{
+    if len == 0 {
+        return None;
+    }
+    let mut filters = sections
+        .iter()
+        .map(|coefficients| DigitalBiquadFilter::new(*coefficients))
+        .collect::<Option<Vec<_>>>()?;
+    let reference_length = (len * REFERENCE_LENGTH_FACTOR).max(MIN_REFERENCE_LENGTH);
+
+    let mut taps = Vec::with_capacity(len);
+    let mut total = T::zero();
+    let mut residual = T::zero();
+    for n in 0..reference_length {
+        let mut sample = if n == 0 { T::one() } else { T::zero() };
+        for filter in filters.iter_mut() {
+            filter.process(&mut sample);
+        }
+        if !sample.is_finite() {
+            return None;
+        }
+        total = total + sample * sample;
+        if n < len {
+            let tap = sample * T::from(window.weight(n, len))?;
+            taps.push(tap);
+            residual = residual + (sample - tap) * (sample - tap);
+        } else {
+            residual = residual + sample * sample;
+        }
+    }
+
+    let error = if total > T::zero() { (residual / total).sqrt() } else { T::zero() };
+    error.is_finite().then_some(FirApproximation { taps, error })
+}
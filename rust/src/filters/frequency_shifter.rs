@@ -0,0 +1,131 @@
+/// frequency_shifter.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::hilbert::HilbertTransformer;
+use num_traits::Float;
+use core::f64::consts::PI;
+
+/// Shifts every frequency in a signal by a fixed number of Hz, unlike a pitch shifter which
+/// scales them. The input is split into an analytic signal by a `HilbertTransformer` and multiplied
+/// by a quadrature oscillator, which moves the whole spectrum up for a positive shift and down for
+/// a negative one.
+#[derive(Debug, Clone)]
+pub struct FrequencyShifter<T: Float> {
+    hilbert: HilbertTransformer<T>,
+    shift: T,
+    mix: T,
+    sample_rate: u32,
+    /// The oscillator phase in cycles, kept in `[0, 1)`.
+    phase: T,
+    /// The phase advance per sample in cycles.
+    increment: T,
+}
+
+impl<T: Float> FrequencyShifter<T> {
+    /// Creates a frequency shifter with a fully wet mix. Returns `None` if the shift is not
+    /// smaller in magnitude than the Nyquist frequency.
+    pub fn new(shift: T, sample_rate: u32) -> Option<Self> {
+        let mut shifter = Self {
+            hilbert: HilbertTransformer::new()?,
+            shift: T::zero(),
+            mix: T::one(),
+            sample_rate,
+            phase: T::zero(),
+            increment: T::zero(),
+        };
+        if !shifter.set_shift(shift) {
+            return None;
+        }
+        Some(shifter)
+    }
+
+    /// Processes a single sample in-place.
+    pub fn process(&mut self, sample: &mut T) -> bool {
+        let (in_phase, quadrature) = self.hilbert.process(*sample);
+        let angle = T::from(2.0 * PI).unwrap_or_else(T::zero) * self.phase;
+        let (sine, cosine) = angle.sin_cos();
+        let shifted = in_phase * cosine - quadrature * sine;
+        *sample = *sample + (shifted - *sample) * self.mix;
+
+        self.phase = self.phase + self.increment;
+        self.phase = self.phase - self.phase.floor();
+        true
+    }
+
+    /// Processes a block of samples in-place.
+    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
+        if samples.is_empty() {
+            return false;
+        }
+        for sample in samples.iter_mut() {
+            self.process(sample);
+        }
+        true
+    }
+
+    /// Returns the frequency shift in Hz.
+    pub fn get_shift(&self) -> T {
+        self.shift
+    }
+
+    /// Sets the frequency shift in Hz. Positive values shift up and negative values shift down.
+    /// Returns `false` and keeps the current shift if it is not smaller in magnitude than the
+    /// Nyquist frequency.
+    pub fn set_shift(&mut self, shift: T) -> bool {
+        let Some(sample_rate) = T::from(self.sample_rate) else {
+            return false;
+        };
+        let in_range = shift.abs() < sample_rate / (T::one() + T::one());
+        if !in_range {
+            return false;
+        }
+        self.shift = shift;
+        self.increment = shift / sample_rate;
+        true
+    }
+
+    /// Returns the proportion of shifted signal in the output.
+    pub fn get_mix(&self) -> T {
+        self.mix
+    }
+
+    /// Sets the proportion of shifted signal in the output, from `0` (dry) to `1` (fully
+    /// shifted). Returns `false` and keeps the current mix if it is outside that range.
+    pub fn set_mix(&mut self, mix: T) -> bool {
+        if !(mix >= T::zero() && mix <= T::one()) {
+            return false;
+        }
+        self.mix = mix;
+        true
+    }
+
+    /// Returns the sample rate of the shifter.
+    pub fn get_sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Resets the Hilbert transformer and the oscillator phase.
+    pub fn reset(&mut self) {
+        self.hilbert.reset();
+        self.phase = T::zero();
+    }
+}
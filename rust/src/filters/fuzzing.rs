@@ -0,0 +1,99 @@
+/// fuzzing.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::Coefficients;
+use crate::filters::filter::FilterType;
+use crate::filters::filter_configuration::FilterConfiguration;
+use arbitrary::{Arbitrary, Result, Unstructured};
+use num_traits::Float;
+
+/// The sample rates arbitrary configurations are drawn from.
+const SAMPLE_RATES: [u32; 7] = [8000, 16000, 22050, 44100, 48000, 96000, 192000];
+/// The largest pole radius of arbitrary coefficients, which keeps them clear of instability.
+const MAX_POLE_RADIUS: f64 = 0.999;
+
+/// Draws a value uniformly from `[min, max]`.
+fn in_range(u: &mut Unstructured<'_>, min: f64, max: f64) -> Result<f64> {
+    let fraction = u.int_in_range(0..=u32::MAX)? as f64 / u32::MAX as f64;
+    Ok(min + fraction * (max - min))
+}
+
+/// Draws a value spread evenly over the ratios in `[min, max]`, for frequencies and Q factors.
+fn in_log_range(u: &mut Unstructured<'_>, min: f64, max: f64) -> Result<f64> {
+    Ok(min * (max / min).powf(in_range(u, 0.0, 1.0)?))
+}
+
+/// Converts a drawn value to the float type, which always succeeds for `f32` and `f64`.
+fn to_float<T: Float>(value: f64) -> Result<T> {
+    T::from(value).ok_or(arbitrary::Error::IncorrectFormat)
+}
+
+/// Produces stable, normalized coefficients: the poles are drawn inside a circle of radius
+/// 0.999 and the numerator coefficients from `[-4, 4]`.
+impl<'a, T: Float> Arbitrary<'a> for Coefficients<T> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let radius = in_range(u, 0.0, MAX_POLE_RADIUS)?;
+        let angle = in_range(u, 0.0, core::f64::consts::PI)?;
+        Ok(Coefficients {
+            b0: to_float(in_range(u, -4.0, 4.0)?)?,
+            b1: to_float(in_range(u, -4.0, 4.0)?)?,
+            b2: to_float(in_range(u, -4.0, 4.0)?)?,
+            a0: T::one(),
+            a1: to_float(-2.0 * radius * angle.cos())?,
+            a2: to_float(radius * radius)?,
+        })
+    }
+}
+
+/// Produces configurations every filter type accepts: a common sample rate, a cutoff between
+/// 10 Hz and 45% of the sample rate, a Q factor between 0.1 and 20, and a gain within ±24 dB.
+impl<'a, T: Float> Arbitrary<'a> for FilterConfiguration<T> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let sample_rate = *u.choose(&SAMPLE_RATES)?;
+        let cutoff = in_log_range(u, 10.0, 0.45 * sample_rate as f64)?;
+        Ok(FilterConfiguration::new(
+            to_float(cutoff)?,
+            sample_rate,
+            to_float(in_log_range(u, 0.1, 20.0)?)?,
+            to_float(in_range(u, -24.0, 24.0)?)?,
+            u.arbitrary()?,
+            u.arbitrary()?,
+        ))
+    }
+}
+
+impl<'a> Arbitrary<'a> for FilterType {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u.choose(&[
+            FilterType::LowPass,
+            FilterType::HighPass,
+            FilterType::BandPass,
+            FilterType::Notch,
+            FilterType::AllPass,
+            FilterType::PeakingEQ,
+            FilterType::LowShelf,
+            FilterType::HighShelf,
+            FilterType::Tilt,
+        ])
+        .copied()
+    }
+}
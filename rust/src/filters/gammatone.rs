@@ -0,0 +1,172 @@
+/// gammatone.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::Coefficients;
+use crate::filters::cascade::Cascade;
+use crate::filters::response::FrequencyResponse;
+use num_traits::Float;
+use core::f64::consts::{PI, SQRT_2};
+
+/// Glasberg and Moore's asymptotic filter quality.
+const EAR_Q: f64 = 9.26449;
+/// Glasberg and Moore's minimum bandwidth, in Hz.
+const MIN_BANDWIDTH: f64 = 24.7;
+/// Bandwidth scaling that matches a fourth-order gammatone to the ERB.
+const BANDWIDTH_SCALE: f64 = 1.019;
+
+/// Returns the equivalent rectangular bandwidth of the auditory filter at the given frequency.
+pub fn equivalent_rectangular_bandwidth<T: Float>(frequency: T) -> Option<T> {
+    Some(frequency / T::from(EAR_Q)? + T::from(MIN_BANDWIDTH)?)
+}
+
+/// A fourth-order gammatone filter bank for auditory modeling. Each channel is a cascade of four
+/// biquads (Slaney's all-pole-sharing realization) normalized to unity gain at its center
+/// frequency.
+#[derive(Debug, Clone)]
+pub struct GammatoneBank<T: Float> {
+    /// One cascade per channel, in the same order as the center frequencies.
+    channels: Vec<Cascade<T, 4>>,
+    /// The center frequency of every channel, in Hz.
+    center_frequencies: Vec<T>,
+}
+
+impl<T: Float> GammatoneBank<T> {
+    /// Creates a bank of `count` channels with center frequencies evenly spaced on the ERB-rate
+    /// scale from `min_frequency` to `max_frequency` inclusive.
+    pub fn new(count: usize, min_frequency: T, max_frequency: T, sample_rate: u32) -> Option<Self> {
+        if count == 0 || min_frequency <= T::zero() || max_frequency < min_frequency {
+            return None;
+        }
+        let offset = T::from(EAR_Q * MIN_BANDWIDTH)?;
+        let low = (min_frequency + offset).ln();
+        let high = (max_frequency + offset).ln();
+        let steps = T::from(count.saturating_sub(1).max(1))?;
+        let center_frequencies = (0..count)
+            .map(|index| Some((low + (high - low) * T::from(index)? / steps).exp() - offset))
+            .collect::<Option<Vec<T>>>()?;
+        Self::with_center_frequencies(&center_frequencies, sample_rate)
+    }
+
+    /// Creates a bank with one channel per given center frequency. Returns `None` if any center
+    /// frequency is not between zero and the Nyquist frequency.
+    pub fn with_center_frequencies(center_frequencies: &[T], sample_rate: u32) -> Option<Self> {
+        let channels = center_frequencies
+            .iter()
+            .map(|&frequency| Self::design_channel(frequency, sample_rate))
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self {
+            channels,
+            center_frequencies: center_frequencies.to_vec(),
+        })
+    }
+
+    /// Designs the four sections of one channel.
+    fn design_channel(frequency: T, sample_rate: u32) -> Option<Cascade<T, 4>> {
+        let rate = T::from(sample_rate)?;
+        let nyquist = rate / T::from(2.0)?;
+        if !(frequency > T::zero() && frequency < nyquist) {
+            return None;
+        }
+        let bandwidth = T::from(BANDWIDTH_SCALE * 2.0 * PI)?
+            * equivalent_rectangular_bandwidth(frequency)?;
+        let theta = T::from(2.0 * PI)? * frequency / rate;
+        let radius = (-bandwidth / rate).exp();
+        let (sin, cos) = theta.sin_cos();
+
+        let two = T::from(2.0)?;
+        let outer = T::from((3.0 + 2.0 * SQRT_2).sqrt())?;
+        let inner = T::from((3.0 - 2.0 * SQRT_2).sqrt())?;
+        let mut sections = [outer, -outer, inner, -inner].map(|spread| Coefficients {
+            b0: T::one(),
+            b1: -radius * (cos + spread * sin),
+            b2: T::zero(),
+            a0: T::one(),
+            a1: -two * radius * cos,
+            a2: radius * radius,
+        });
+
+        let mut gain = T::one();
+        for section in sections.iter() {
+            gain = gain * section.magnitude_at(frequency, sample_rate);
+        }
+        sections[0].b0 = sections[0].b0 / gain;
+        sections[0].b1 = sections[0].b1 / gain;
+        Cascade::new(sections)
+    }
+
+    /// Processes one input sample, writing each channel's output to `outputs`. Returns `false` if
+    /// `outputs` does not have one entry per channel.
+    pub fn process(&mut self, sample: T, outputs: &mut [T]) -> bool {
+        if outputs.len() != self.channels.len() {
+            return false;
+        }
+        for (channel, output) in self.channels.iter_mut().zip(outputs.iter_mut()) {
+            *output = sample;
+            channel.process(output);
+        }
+        true
+    }
+
+    /// Processes a block of input samples, writing each channel's output to the matching slice of
+    /// `outputs`. Returns `false` if `outputs` does not have one slice per channel, each as long as
+    /// the input.
+    pub fn process_block(&mut self, input: &[T], outputs: &mut [&mut [T]]) -> bool {
+        if input.is_empty()
+            || outputs.len() != self.channels.len()
+            || outputs.iter().any(|output| output.len() != input.len())
+        {
+            return false;
+        }
+        for (channel, output) in self.channels.iter_mut().zip(outputs.iter_mut()) {
+            output.copy_from_slice(input);
+            channel.process_block(output);
+        }
+        true
+    }
+
+    /// Returns the number of channels.
+    pub fn len(&self) -> usize {
+        self.channels.len()
+    }
+
+    /// Returns whether the bank has no channels.
+    pub fn is_empty(&self) -> bool {
+        self.channels.is_empty()
+    }
+
+    /// Returns the center frequency of every channel.
+    pub fn get_center_frequencies(&self) -> &[T] {
+        &self.center_frequencies
+    }
+
+    /// Returns the cascade of the channel at `index`, if it exists.
+    pub fn get_channel(&self, index: usize) -> Option<&Cascade<T, 4>> {
+        self.channels.get(index)
+    }
+
+    /// Resets the state of every channel.
+    pub fn reset(&mut self) {
+        for channel in self.channels.iter_mut() {
+            channel.reset();
+        }
+    }
+}
@@ -0,0 +1,143 @@
+/// goertzel.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::{Coefficients, State, tick};
+use crate::filters::response::angular_frequency;
+use num_complex::Complex;
+use num_traits::Float;
+
+/// Measures the amplitude and phase of one frequency in a signal with the Goertzel algorithm,
+/// which computes a single DFT term for far less work than an FFT when only a few frequencies are
+/// needed, as in DTMF decoding or tracking a calibration tone.
+///
+/// Each sample runs through the second-order resonator `1 / (1 - 2 cos(w) z^-1 + z^-2)` using the
+/// same difference equation as every biquad in the crate, and the DFT term is formed from its last
+/// two outputs when a result is read. Results cover every sample since the detector was created
+/// or last reset, so call `reset` between blocks. The frequency need not fall on a DFT bin, but
+/// tones at other frequencies leak into the result unless the block holds a whole number of
+/// periods of each.
+#[derive(Debug, Clone)]
+pub struct GoertzelDetector<T: Float> {
+    frequency: T,
+    sample_rate: u32,
+    omega: T,
+    coefficients: Coefficients<T>,
+    state: State<T>,
+    count: usize,
+}
+
+impl<T: Float> GoertzelDetector<T> {
+    /// Creates a detector for a frequency. Returns `None` if the frequency is negative, not below
+    /// the Nyquist frequency, or the sample rate is zero.
+    pub fn new(frequency: T, sample_rate: u32) -> Option<Self> {
+        let nyquist = T::from(sample_rate)? / T::from(2.0)?;
+        let in_range = sample_rate > 0 && frequency >= T::zero() && frequency < nyquist;
+        if !in_range {
+            return None;
+        }
+        let omega = angular_frequency(frequency, sample_rate);
+        Some(Self {
+            frequency,
+            sample_rate,
+            omega,
+            coefficients: Coefficients {
+                b0: T::one(),
+                b1: T::zero(),
+                b2: T::zero(),
+                a0: T::one(),
+                a1: -T::from(2.0)? * omega.cos(),
+                a2: T::one(),
+            },
+            state: State::default(),
+            count: 0,
+        })
+    }
+
+    /// Adds one sample to the measurement.
+    pub fn process(&mut self, sample: T) {
+        tick(&self.coefficients, &mut self.state, sample);
+        self.count += 1;
+    }
+
+    /// Adds a block of samples to the measurement. Returns `false` if the block is empty.
+    pub fn process_block(&mut self, samples: &[T]) -> bool {
+        if samples.is_empty() {
+            return false;
+        }
+        for sample in samples {
+            self.process(*sample);
+        }
+        true
+    }
+
+    /// Returns the DFT term `sum x[n] e^(-j w n)` of the samples processed since the last reset,
+    /// with `n` counted from the first of them.
+    pub fn get_response(&self) -> Complex<T> {
+        let last = Complex::new(self.state.y1, T::zero())
+            - Complex::from_polar(self.state.y2, -self.omega);
+        let elapsed = T::from(self.count.saturating_sub(1)).unwrap_or_else(T::nan);
+        last * Complex::from_polar(T::one(), -self.omega * elapsed)
+    }
+
+    /// Returns the estimated amplitude of a sine at the frequency: `2 |X| / N`, or `|X| / N` at
+    /// DC. Returns zero if no samples have been processed.
+    pub fn magnitude(&self) -> T {
+        if self.count == 0 {
+            return T::zero();
+        }
+        let count = T::from(self.count).unwrap_or_else(T::nan);
+        let amplitude = self.get_response().norm() / count;
+        if self.frequency.is_zero() { amplitude } else { amplitude + amplitude }
+    }
+
+    /// Returns the estimated amplitude in decibels, where a sine with an amplitude of one reads
+    /// 0 dB.
+    pub fn magnitude_db(&self) -> T {
+        T::from(20.0).unwrap_or_else(T::nan) * self.magnitude().log10()
+    }
+
+    /// Returns the phase in radians of a cosine at the frequency, at the first processed sample.
+    pub fn phase(&self) -> T {
+        self.get_response().arg()
+    }
+
+    /// Returns the frequency being measured.
+    pub fn get_frequency(&self) -> T {
+        self.frequency
+    }
+
+    /// Returns the sample rate.
+    pub fn get_sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Returns the number of samples processed since the last reset.
+    pub fn samples_processed(&self) -> usize {
+        self.count
+    }
+
+    /// Clears the measurement to start a new block.
+    pub fn reset(&mut self) {
+        self.state = State::default();
+        self.count = 0;
+    }
+}
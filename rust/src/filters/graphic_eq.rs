@@ -0,0 +1,205 @@
+/// graphic_eq.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::filter::FilterType;
+use crate::filters::filter_configuration::FilterConfiguration;
+use crate::filters::parametric_eq::ParametricEq;
+use crate::filters::response::FrequencyResponse;
+use num_complex::Complex;
+use num_traits::Float;
+
+/// ISO 266 centre frequencies of the octave bands.
+const OCTAVE: [f64; 10] =
+    [31.5, 63.0, 125.0, 250.0, 500.0, 1000.0, 2000.0, 4000.0, 8000.0, 16000.0];
+/// ISO 266 centre frequencies of the two-thirds-octave bands.
+const TWO_THIRDS_OCTAVE: [f64; 15] = [
+    25.0, 40.0, 63.0, 100.0, 160.0, 250.0, 400.0, 630.0, 1000.0, 1600.0, 2500.0, 4000.0, 6300.0,
+    10000.0, 16000.0,
+];
+/// ISO 266 centre frequencies of the third-octave bands.
+const THIRD_OCTAVE: [f64; 31] = [
+    20.0, 25.0, 31.5, 40.0, 50.0, 63.0, 80.0, 100.0, 125.0, 160.0, 200.0, 250.0, 315.0, 400.0,
+    500.0, 630.0, 800.0, 1000.0, 1250.0, 1600.0, 2000.0, 2500.0, 3150.0, 4000.0, 5000.0, 6300.0,
+    8000.0, 10000.0, 12500.0, 16000.0, 20000.0,
+];
+
+/// The band layout of a `GraphicEq`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GraphicEqLayout {
+    /// Ten octave bands from 31.5 Hz to 16 kHz.
+    Octave,
+    /// Fifteen two-thirds-octave bands from 25 Hz to 16 kHz.
+    TwoThirdsOctave,
+    /// Thirty-one third-octave bands from 20 Hz to 20 kHz.
+    ThirdOctave,
+}
+
+impl GraphicEqLayout {
+    /// Returns the ISO 266 centre frequencies of the bands in Hz, in ascending order.
+    pub fn center_frequencies(&self) -> &'static [f64] {
+        match self {
+            GraphicEqLayout::Octave => &OCTAVE,
+            GraphicEqLayout::TwoThirdsOctave => &TWO_THIRDS_OCTAVE,
+            GraphicEqLayout::ThirdOctave => &THIRD_OCTAVE,
+        }
+    }
+
+    /// Returns the width of each band in octaves.
+    pub fn bandwidth(&self) -> f64 {
+        match self {
+            GraphicEqLayout::Octave => 1.0,
+            GraphicEqLayout::TwoThirdsOctave => 2.0 / 3.0,
+            GraphicEqLayout::ThirdOctave => 1.0 / 3.0,
+        }
+    }
+
+    /// Returns the Q factor of a band one `bandwidth` wide, `sqrt(2^N) / (2^N - 1)`.
+    pub fn q_factor(&self) -> f64 {
+        let ratio = Float::powf(2.0, self.bandwidth());
+        Float::sqrt(ratio) / (ratio - 1.0)
+    }
+}
+
+/// A graphic equalizer: a peaking band at each ISO centre frequency of a layout, with a gain in
+/// decibels per band. All bands start flat. Setting a gain redesigns only that band, keeping its
+/// state, so the gains can be moved while audio is running.
+///
+/// Neighbouring bands overlap, as on analog graphic equalizers, so raising several adjacent
+/// bands by the same amount gives a little more than that gain between their centres.
+#[derive(Debug, Clone)]
+pub struct GraphicEq<T: Float> {
+    layout: GraphicEqLayout,
+    eq: ParametricEq<T>,
+}
+
+impl<T: Float> GraphicEq<T> {
+    /// Creates a flat graphic equalizer. Returns `None` if the highest centre frequency of the
+    /// layout is not below the Nyquist frequency, such as a third-octave layout below 40.1 kHz.
+    pub fn new(layout: GraphicEqLayout, sample_rate: u32) -> Option<Self> {
+        let highest = layout.center_frequencies().last()?;
+        if *highest >= f64::from(sample_rate) / 2.0 {
+            return None;
+        }
+        let mut eq = ParametricEq::new(sample_rate);
+        for center in layout.center_frequencies() {
+            let config = FilterConfiguration::builder()
+                .cutoff(T::from(*center)?)
+                .sample_rate(sample_rate)
+                .q_factor(T::from(layout.q_factor())?)
+                .build()
+                .ok()?;
+            eq.add_band(FilterType::PeakingEQ, config)?;
+        }
+        Some(Self { layout, eq })
+    }
+
+    /// Returns the band layout.
+    pub fn get_layout(&self) -> GraphicEqLayout {
+        self.layout
+    }
+
+    /// Returns the sample rate.
+    pub fn get_sample_rate(&self) -> u32 {
+        self.eq.get_sample_rate()
+    }
+
+    /// Returns the number of bands.
+    pub fn len(&self) -> usize {
+        self.eq.len()
+    }
+
+    /// Returns whether the equalizer has no bands, which is never the case.
+    pub fn is_empty(&self) -> bool {
+        self.eq.is_empty()
+    }
+
+    /// Returns the centre frequency of the band at `index`, if it exists.
+    pub fn get_center_frequency(&self, index: usize) -> Option<T> {
+        self.eq.get_band(index).map(|(_, config)| config.get_cutoff())
+    }
+
+    /// Returns the gain in decibels of the band at `index`, if it exists.
+    pub fn get_gain(&self, index: usize) -> Option<T> {
+        self.eq.get_band(index).map(|(_, config)| config.get_gain())
+    }
+
+    /// Returns the gains of every band in decibels, from the lowest band to the highest.
+    pub fn get_gains(&self) -> Vec<T> {
+        (0..self.len()).filter_map(|index| self.get_gain(index)).collect()
+    }
+
+    /// Sets the gain in decibels of the band at `index`. Returns `false` and keeps the current
+    /// gain if the band does not exist or the gain is not finite or beyond
+    /// `FilterConfiguration::MAX_GAIN`.
+    pub fn set_gain(&mut self, index: usize, gain: T) -> bool {
+        let in_range = T::from(FilterConfiguration::<T>::MAX_GAIN)
+            .is_some_and(|max_gain| gain.is_finite() && gain.abs() <= max_gain);
+        in_range && self.eq.update_band(index, |config| config.set_gain(gain))
+    }
+
+    /// Sets the gain of every band, from the lowest band to the highest. Returns `false` and keeps
+    /// every current gain if there is not one gain per band or any gain is invalid.
+    pub fn set_gains(&mut self, gains: &[T]) -> bool {
+        if gains.len() != self.len() {
+            return false;
+        }
+        let previous = self.get_gains();
+        for (index, gain) in gains.iter().enumerate() {
+            if !self.set_gain(index, *gain) {
+                for (index, gain) in previous.iter().enumerate() {
+                    self.set_gain(index, *gain);
+                }
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Sets every band back to 0 dB.
+    pub fn flatten(&mut self) {
+        for index in 0..self.len() {
+            self.set_gain(index, T::zero());
+        }
+    }
+
+    /// Processes a single sample.
+    pub fn process(&mut self, sample: &mut T) -> bool {
+        self.eq.process(sample)
+    }
+
+    /// Processes a block of samples. Returns `false` if the block is empty.
+    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
+        self.eq.process_block(samples)
+    }
+
+    /// Resets the state of every band.
+    pub fn reset(&mut self) {
+        self.eq.reset();
+    }
+}
+
+impl<T: Float> FrequencyResponse<T> for GraphicEq<T> {
+    fn response_at(&self, frequency: T, sample_rate: u32) -> Complex<T> {
+        self.eq.response_at(frequency, sample_rate)
+    }
+}
@@ -0,0 +1,128 @@
+/// hilbert.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::Coefficients;
+use crate::filters::cascade::Cascade;
+use num_traits::Float;
+
+/// All-pass coefficients of the in-phase branch.
+const IN_PHASE: [f64; 4] = [0.4021921162426, 0.8561710882420, 0.9722909545651, 0.9952884791278];
+/// All-pass coefficients of the quadrature branch, whose output is also delayed by one sample.
+const QUADRATURE: [f64; 4] = [0.6923878, 0.9360654322959, 0.9882295226860, 0.9987488452737];
+
+/// A pair of all-pass branches whose outputs are 90 degrees apart, turning a real signal into an
+/// analytic one. Each branch is four second-order all-pass sections `(a² - z⁻²) / (1 - a² z⁻²)`
+/// using Olli Niemitalo's coefficients. The phase difference stays within a degree of 90 degrees
+/// from about 40 Hz to 22 kHz at 48 kHz, scaling with the sample rate.
+#[derive(Debug, Clone)]
+pub struct HilbertTransformer<T: Float> {
+    in_phase: Cascade<T, 4>,
+    quadrature: Cascade<T, 4>,
+    /// The previous quadrature branch output.
+    delayed: T,
+}
+
+impl<T: Float> HilbertTransformer<T> {
+    /// Creates a Hilbert transformer. Returns `None` if the coefficients cannot be represented in
+    /// `T`.
+    pub fn new() -> Option<Self> {
+        Some(Self {
+            in_phase: Cascade::new(Self::branch(&IN_PHASE)?)?,
+            quadrature: Cascade::new(Self::branch(&QUADRATURE)?)?,
+            delayed: T::zero(),
+        })
+    }
+
+    /// Processes one sample and returns the in-phase and quadrature outputs. For a sine input the
+    /// quadrature output lags the in-phase output by 90 degrees.
+    pub fn process(&mut self, sample: T) -> (T, T) {
+        let mut in_phase = sample;
+        let mut quadrature = sample;
+        self.in_phase.process(&mut in_phase);
+        self.quadrature.process(&mut quadrature);
+        let delayed = core::mem::replace(&mut self.delayed, quadrature);
+        (in_phase, delayed)
+    }
+
+    /// Processes a block of samples, writing the in-phase and quadrature outputs. Returns `false`
+    /// if the block is empty or the slices differ in length.
+    pub fn process_block(&mut self, input: &[T], in_phase: &mut [T], quadrature: &mut [T]) -> bool {
+        if input.is_empty() || input.len() != in_phase.len() || input.len() != quadrature.len() {
+            return false;
+        }
+        for ((sample, i), q) in input.iter().zip(in_phase.iter_mut()).zip(quadrature.iter_mut()) {
+            (*i, *q) = self.process(*sample);
+        }
+        true
+    }
+
+    /// Processes one sample and returns the envelope, the magnitude of the analytic signal. Unlike
+    /// a rectifier followed by a smoothing filter, it follows the amplitude of a sine without
+    /// ripple or lag.
+    pub fn envelope(&mut self, sample: T) -> T {
+        let (in_phase, quadrature) = self.process(sample);
+        in_phase.hypot(quadrature)
+    }
+
+    /// Processes a block of samples, writing the envelope. Returns `false` if the block is empty or
+    /// the slices differ in length.
+    pub fn envelope_block(&mut self, input: &[T], envelope: &mut [T]) -> bool {
+        if input.is_empty() || input.len() != envelope.len() {
+            return false;
+        }
+        for (sample, output) in input.iter().zip(envelope.iter_mut()) {
+            *output = self.envelope(*sample);
+        }
+        true
+    }
+
+    /// Resets the state of both branches.
+    pub fn reset(&mut self) {
+        self.in_phase.reset();
+        self.quadrature.reset();
+        self.delayed = T::zero();
+    }
+
+    /// Builds the sections of one branch.
+    fn branch(coefficients: &[f64; 4]) -> Option<[Coefficients<T>; 4]> {
+        let mut sections = [Coefficients {
+            b0: T::one(),
+            b1: T::zero(),
+            b2: T::zero(),
+            a0: T::one(),
+            a1: T::zero(),
+            a2: T::zero(),
+        }; 4];
+        for (section, a) in sections.iter_mut().zip(coefficients.iter()) {
+            let a2 = T::from(a * a)?;
+            *section = Coefficients {
+                b0: a2,
+                b1: T::zero(),
+                b2: -T::one(),
+                a0: T::one(),
+                a1: T::zero(),
+                a2: -a2,
+            };
+        }
+        Some(sections)
+    }
+}
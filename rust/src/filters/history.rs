@@ -0,0 +1,87 @@
+/// history.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::State;
+use num_traits::Float;
+
+/// One processed sample recorded by the state history.
+#[derive(Debug, Clone, Copy)]
+pub struct HistoryEntry<T: Float> {
+    /// The position of the sample, as counted by `samples_processed`.
+    pub sample_index: u64,
+    /// The input sample.
+    pub input: T,
+    /// The filter state the output was computed from.
+    pub state: State<T>,
+    /// The output sample.
+    pub output: T,
+}
+
+/// A ring buffer holding the most recent processed samples of a filter, so the lead-up to an
+/// instability, denormal or NaN can be inspected after the fact.
+#[derive(Debug, Clone)]
+pub(crate) struct StateHistory<T: Float> {
+    entries: Vec<HistoryEntry<T>>,
+    capacity: usize,
+    next: usize,
+}
+
+impl<T: Float> StateHistory<T> {
+    /// Creates a history holding up to `capacity` entries.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+            capacity,
+            next: 0,
+        }
+    }
+
+    /// Records an entry, overwriting the oldest once the buffer is full. The buffer is allocated
+    /// up front, so recording never allocates.
+    #[inline]
+    pub(crate) fn record(&mut self, entry: HistoryEntry<T>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() < self.capacity {
+            self.entries.push(entry);
+        } else {
+            self.entries[self.next] = entry;
+        }
+        self.next = (self.next + 1) % self.capacity;
+    }
+
+    /// Returns the recorded entries, oldest first.
+    pub(crate) fn entries(&self) -> Vec<HistoryEntry<T>> {
+        if self.entries.len() < self.capacity {
+            return self.entries.clone();
+        }
+        let (newest, oldest) = self.entries.split_at(self.next);
+        oldest.iter().chain(newest.iter()).copied().collect()
+    }
+
+    /// Removes every entry.
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+        self.next = 0;
+    }
+}
@@ -0,0 +1,453 @@
+/// iir.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::fault::{Fault, FaultHandler, Faults};
+use crate::filters::filter::Filter;
+use crate::filters::filter_configuration::FilterConfiguration;
+use crate::filters::limiter::OutputLimit;
+#[cfg(feature = "metering")]
+use crate::filters::metering::{Metered, Metering, Meters};
+use crate::filters::response::{FrequencyResponse, angular_frequency};
+use crate::filters::update::UpdatePolicy;
+use num_complex::Complex;
+use num_traits::Float;
+
+/// An IIR filter of arbitrary order, defined by numerator and denominator coefficient vectors in
+/// ascending powers of `z^-1` and run in Direct Form II Transposed. This is for designs that are
+/// only available as a single transfer function; prefer a `Cascade` of biquads when a
+/// second-order-section factorization exists, since high-order direct forms are sensitive to
+/// coefficient rounding.
+///
+/// The coefficients are fixed by the design, so the cutoff, Q factor, gain and constant skirt gain
+/// of the `Filter` trait cannot be changed and always read as zero. The sample rate is only used to
+/// report the time processed.
+#[derive(Debug, Clone)]
+pub struct IirFilter<T: Float> {
+    /// The numerator, normalized by the leading denominator coefficient.
+    numerator: Vec<T>,
+    /// The denominator, normalized so its leading coefficient is one.
+    denominator: Vec<T>,
+    state: Vec<T>,
+    sample_rate: u32,
+    bypass: bool,
+    output_limit: OutputLimit<T>,
+    iter: u64,
+    faults: Faults,
+    fault_handler: Option<FaultHandler>,
+    #[cfg(feature = "metering")]
+    metering: Option<Metering>,
+    update_policy: UpdatePolicy,
+    /// A normalized numerator and denominator waiting for the next block.
+    pending: Option<(Vec<T>, Vec<T>)>,
+}
+
+impl<T: Float> IirFilter<T> {
+    /// Creates a new filter from the numerator and denominator of its transfer function. Returns
+    /// `None` if either is empty, the leading denominator coefficient is zero, any coefficient is
+    /// not finite, or the sample rate is zero. Unstable denominators are accepted but recorded as a
+    /// fault.
+    pub fn new(numerator: &[T], denominator: &[T], sample_rate: u32) -> Option<Self> {
+        if sample_rate == 0 {
+            return None;
+        }
+        let mut filter = Self {
+            numerator: Vec::new(),
+            denominator: Vec::new(),
+            state: Vec::new(),
+            sample_rate,
+            bypass: false,
+            output_limit: OutputLimit::default(),
+            iter: 0,
+            faults: Faults::default(),
+            fault_handler: None,
+            #[cfg(feature = "metering")]
+            metering: None,
+            update_policy: UpdatePolicy::Immediate,
+            pending: None,
+        };
+        filter.set_coefficients(numerator, denominator).then_some(filter)
+    }
+
+    /// Returns the normalized numerator, padded with zeros to the order of the filter plus one.
+    pub fn get_numerator(&self) -> &[T] {
+        &self.numerator
+    }
+
+    /// Returns the normalized denominator, padded with zeros to the order of the filter plus one.
+    pub fn get_denominator(&self) -> &[T] {
+        &self.denominator
+    }
+
+    /// Returns the order of the filter: the larger of the numerator and denominator orders.
+    pub fn get_order(&self) -> usize {
+        self.state.len()
+    }
+
+    /// Sets a new transfer function, which may have a different order, and resets the filter.
+    /// Returns `false` and keeps the current coefficients under the same conditions `new` rejects.
+    /// Under `UpdatePolicy::NextBlock` the change is held until the next block.
+    pub fn set_coefficients(&mut self, numerator: &[T], denominator: &[T]) -> bool {
+        let valid = !numerator.is_empty()
+            && !denominator.is_empty()
+            && denominator[0] != T::zero()
+            && numerator.iter().chain(denominator).all(|c| c.is_finite());
+        if !valid {
+            return false;
+        }
+        let length = numerator.len().max(denominator.len());
+        let scale = T::one() / denominator[0];
+        let normalize = |coefficients: &[T]| -> Vec<T> {
+            let mut normalized: Vec<T> = coefficients.iter().map(|&c| c * scale).collect();
+            normalized.resize(length, T::zero());
+            normalized
+        };
+        let numerator = normalize(numerator);
+        let denominator = normalize(denominator);
+        if !numerator.iter().chain(&denominator).all(|c| c.is_finite()) {
+            return false;
+        }
+        match self.update_policy {
+            UpdatePolicy::Immediate => self.adopt_coefficients(numerator, denominator),
+            // Changing the transfer function always resets the state, so it is never smoothed.
+            UpdatePolicy::NextBlock | UpdatePolicy::Smoothed => {
+                self.pending = Some((numerator, denominator));
+            }
+        }
+        true
+    }
+
+    /// Starts using a normalized transfer function and resets the state.
+    fn adopt_coefficients(&mut self, numerator: Vec<T>, denominator: Vec<T>) {
+        self.state = vec![T::zero(); numerator.len() - 1];
+        self.numerator = numerator;
+        self.denominator = denominator;
+        if !self.is_stable() {
+            self.report(Fault::UnstableCoefficients);
+        }
+    }
+
+    /// Adopts a transfer function held back by `UpdatePolicy::NextBlock`.
+    fn begin_block(&mut self) {
+        if let Some((numerator, denominator)) = self.pending.take() {
+            self.adopt_coefficients(numerator, denominator);
+        }
+    }
+
+    /// Returns whether a new transfer function is waiting for the next block.
+    pub fn has_pending_coefficients(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Returns whether every pole lies strictly inside the unit circle, using the Schur-Cohn
+    /// step-down recursion on the denominator.
+    pub fn is_stable(&self) -> bool {
+        let mut polynomial = self.denominator.clone();
+        while polynomial.len() > 1 {
+            let order = polynomial.len() - 1;
+            let reflection = polynomial[order];
+            let in_range = reflection.abs() < T::one();
+            if !in_range {
+                return false;
+            }
+            let scale = T::one() - reflection * reflection;
+            polynomial = (0..order)
+                .map(|i| (polynomial[i] - reflection * polynomial[order - i]) / scale)
+                .collect();
+        }
+        true
+    }
+
+    /// Processes a single sample.
+    pub fn process(&mut self, sample: &mut T) -> bool {
+        if self.bypass {
+            self.iter = self.iter.wrapping_add(1);
+            return true;
+        }
+        self.begin_block();
+        self.tick(sample);
+        self.finish_block();
+        true
+    }
+
+    /// Processes a block of samples. Returns `false` if the block is empty.
+    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
+        if samples.is_empty() {
+            return false;
+        }
+        if self.bypass {
+            self.iter = self.iter.wrapping_add(samples.len() as u64);
+            return true;
+        }
+        self.begin_block();
+        for sample in samples.iter_mut() {
+            self.tick(sample);
+        }
+        self.finish_block();
+        true
+    }
+
+    /// Publishes the meter levels accumulated since the last call.
+    fn finish_block(&mut self) {
+        #[cfg(feature = "metering")]
+        if let Some(metering) = self.metering.as_mut() {
+            metering.publish();
+        }
+    }
+
+    /// Runs the transposed direct form for one sample.
+    #[inline(always)]
+    fn tick(&mut self, sample: &mut T) {
+        let input = *sample;
+        let order = self.state.len();
+        let output = match order {
+            0 => self.numerator[0] * input,
+            _ => self.numerator[0] * input + self.state[0],
+        };
+        for i in 0..order {
+            let next = if i + 1 < order { self.state[i + 1] } else { T::zero() };
+            self.state[i] =
+                next + self.numerator[i + 1] * input - self.denominator[i + 1] * output;
+        }
+
+        #[cfg(feature = "metering")]
+        if let Some(metering) = self.metering.as_mut() {
+            metering.record(input, output);
+        }
+        *sample = self.output_limit.apply(output);
+
+        self.iter = self.iter.wrapping_add(1);
+        if !output.is_finite() {
+            self.report(Fault::NonFiniteOutput);
+        }
+    }
+
+    /// Records a fault, calling the fault handler the first time it is seen.
+    #[cold]
+    fn report(&mut self, fault: Fault) {
+        if self.faults.mark(fault) {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(instance = self as *const Self as usize, fault = ?fault, "filter fault");
+            if let Some(handler) = self.fault_handler {
+                handler(fault);
+            }
+        }
+    }
+
+    /// Resets the filter state and sample count.
+    pub fn reset(&mut self) {
+        self.state.iter_mut().for_each(|s| *s = T::zero());
+        self.iter = 0;
+    }
+}
+
+impl<T: Float> Filter<T> for IirFilter<T> {
+    /// Processes a single sample in-place and returns a boolean indicating success.
+    fn process(&mut self, sample: &mut T) -> bool {
+        IirFilter::process(self, sample)
+    }
+
+    /// Processes a block of samples in-place and returns a boolean indicating success.
+    fn process_block(&mut self, samples: &mut [T]) -> bool {
+        IirFilter::process_block(self, samples)
+    }
+
+    /// Resets the filter state and sample count.
+    fn reset(&mut self) {
+        IirFilter::reset(self);
+    }
+
+    /// Returns a configuration holding the sample rate and bypass state.
+    fn get_configuration(&self) -> FilterConfiguration<T> {
+        FilterConfiguration::new(
+            T::zero(),
+            self.sample_rate,
+            T::zero(),
+            T::zero(),
+            false,
+            self.bypass,
+        )
+    }
+
+    /// Sets the sample rate and bypass state. Returns `false` if the configuration also changes a
+    /// design parameter or has a zero sample rate.
+    fn set_configuration(&mut self, configuration: FilterConfiguration<T>) -> bool {
+        let design_unchanged = configuration.get_cutoff() == T::zero()
+            && configuration.get_q_factor() == T::zero()
+            && configuration.get_gain() == T::zero()
+            && !configuration.get_constant_skirt_gain();
+        if !design_unchanged || configuration.get_sample_rate() == 0 {
+            return false;
+        }
+        self.sample_rate = configuration.get_sample_rate();
+        self.bypass = configuration.get_bypass();
+        true
+    }
+
+    /// Returns zero, since the filter has no cutoff parameter.
+    fn get_cutoff(&self) -> T {
+        T::zero()
+    }
+
+    /// Returns `false`, since the filter has no cutoff parameter.
+    fn set_cutoff(&mut self, _cutoff: T) -> bool {
+        false
+    }
+
+    /// Returns the sample rate of the filter.
+    fn get_sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Sets the sample rate used to report the time processed. The coefficients are not changed.
+    fn set_sample_rate(&mut self, sample_rate: u32) -> bool {
+        if sample_rate == 0 {
+            return false;
+        }
+        self.sample_rate = sample_rate;
+        true
+    }
+
+    /// Returns zero, since the filter has no Q factor parameter.
+    fn get_q_factor(&self) -> T {
+        T::zero()
+    }
+
+    /// Returns `false`, since the filter has no Q factor parameter.
+    fn set_q_factor(&mut self, _q_factor: T) -> bool {
+        false
+    }
+
+    /// Returns zero, since the filter has no gain parameter.
+    fn get_gain(&self) -> T {
+        T::zero()
+    }
+
+    /// Returns `false`, since the filter has no gain parameter.
+    fn set_gain(&mut self, _gain: T) -> bool {
+        false
+    }
+
+    /// Returns `false`, since the filter has no constant skirt gain parameter.
+    fn get_constant_skirt_gain(&self) -> bool {
+        false
+    }
+
+    /// Returns `false`, since the filter has no constant skirt gain parameter.
+    fn set_constant_skirt_gain(&mut self, _constant_skirt_gain: bool) -> bool {
+        false
+    }
+
+    /// Returns whether the filter should be bypassed.
+    fn get_bypass(&self) -> bool {
+        self.bypass
+    }
+
+    /// Sets whether the filter should be bypassed.
+    fn set_bypass(&mut self, bypass: bool) -> bool {
+        self.bypass = bypass;
+        true
+    }
+
+    /// Returns the number of samples processed, including bypassed samples.
+    fn samples_processed(&self) -> u64 {
+        self.iter
+    }
+
+    /// Returns the limit applied to the output.
+    fn get_output_limit(&self) -> OutputLimit<T> {
+        self.output_limit
+    }
+
+    /// Sets the limit applied to the output.
+    fn set_output_limit(&mut self, output_limit: OutputLimit<T>) -> bool {
+        if !output_limit.is_valid() {
+            return false;
+        }
+        self.output_limit = output_limit;
+        true
+    }
+
+    /// Returns the faults detected since they were last taken.
+    fn get_faults(&self) -> Faults {
+        self.faults
+    }
+
+    /// Returns the faults detected since they were last taken and clears them.
+    fn take_faults(&mut self) -> Faults {
+        core::mem::take(&mut self.faults)
+    }
+
+    /// Sets the function called the first time each fault is detected, or removes it.
+    fn set_fault_handler(&mut self, handler: Option<FaultHandler>) {
+        self.fault_handler = handler;
+    }
+
+    /// Returns when changes to the transfer function take effect.
+    fn get_update_policy(&self) -> UpdatePolicy {
+        self.update_policy
+    }
+
+    /// Sets when changes to the transfer function take effect.
+    fn set_update_policy(&mut self, update_policy: UpdatePolicy) {
+        self.update_policy = update_policy;
+        self.begin_block();
+    }
+}
+
+#[cfg(feature = "metering")]
+impl<T: Float> Metered<T> for IirFilter<T> {
+    /// Starts metering the input and output levels.
+    fn enable_metering(&mut self, window: u32) -> Meters {
+        let metering = Metering::new(window);
+        let meters = metering.get_meters();
+        self.metering = Some(metering);
+        meters
+    }
+
+    /// Stops metering.
+    fn disable_metering(&mut self) {
+        self.metering = None;
+    }
+
+    /// Returns handles to the meters, if metering is enabled.
+    fn get_meters(&self) -> Option<Meters> {
+        self.metering.as_ref().map(Metering::get_meters)
+    }
+}
+
+impl<T: Float> FrequencyResponse<T> for IirFilter<T> {
+    /// Returns the response of the filter, or a flat response while it is bypassed.
+    fn response_at(&self, frequency: T, sample_rate: u32) -> Complex<T> {
+        if self.bypass {
+            return Complex::from(T::one());
+        }
+        let z1 = Complex::from_polar(T::one(), -angular_frequency(frequency, sample_rate));
+        // Horner's method in z^-1, starting from the highest power.
+        let evaluate = |coefficients: &[T]| {
+            coefficients
+                .iter()
+                .rev()
+                .fold(Complex::from(T::zero()), |sum, &c| sum * z1 + Complex::from(c))
+        };
+        evaluate(&self.numerator) / evaluate(&self.denominator)
+    }
+}
@@ -0,0 +1,197 @@
+/// ladder.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::saturation::Saturation;
+use num_traits::Float;
+use core::f64::consts::PI;
+
+/// The number of one-pole sections in the ladder.
+const STAGES: usize = 4;
+
+/// A four-pole resonant low-pass modeled on the Moog transistor ladder: four cascaded one-pole
+/// sections with the output fed back, inverted, to the input. The loop is solved without a unit
+/// delay, so the cutoff and resonance stay accurate up to the Nyquist frequency.
+///
+/// Resonance runs from `0` to `MAX_RESONANCE`. At `SELF_OSCILLATION` the feedback gain reaches
+/// four and the filter sustains a sine at the cutoff frequency with no input. From there on the
+/// loop is always amplitude limited: if no saturation is set, a `tanh` with unity drive is used,
+/// which holds the oscillation below full scale. The input is scaled by the feedback gain plus one
+/// so the passband gain stays at unity as the resonance rises.
+#[derive(Debug, Clone)]
+pub struct LadderFilter<T: Float> {
+    cutoff: T,
+    resonance: T,
+    sample_rate: u32,
+    saturation: Saturation<T>,
+    /// The one-pole gain `g / (1 + g)`, where `g` is the prewarped cutoff.
+    gain: T,
+    stages: [T; STAGES],
+}
+
+impl<T: Float> LadderFilter<T> {
+    /// The resonance at which the filter starts to self-oscillate.
+    pub const SELF_OSCILLATION: f64 = 1.0;
+    /// The largest accepted resonance.
+    pub const MAX_RESONANCE: f64 = 1.5;
+
+    /// Creates a ladder filter. Returns `None` if the cutoff is not between zero and the Nyquist
+    /// frequency or the resonance is not between zero and `MAX_RESONANCE`.
+    pub fn new(cutoff: T, resonance: T, sample_rate: u32) -> Option<Self> {
+        let mut filter = Self {
+            cutoff,
+            resonance: T::zero(),
+            sample_rate,
+            saturation: Saturation::Linear,
+            gain: T::zero(),
+            stages: [T::zero(); STAGES],
+        };
+        if !filter.set_cutoff(cutoff) || !filter.set_resonance(resonance) {
+            return None;
+        }
+        Some(filter)
+    }
+
+    /// Processes a single sample in-place.
+    pub fn process(&mut self, sample: &mut T) -> bool {
+        let one = T::one();
+        let feedback = self.feedback();
+        let state_gain = one - self.gain;
+
+        // The ladder output is `gain^4 * input + sum`, where `sum` collects the contribution of
+        // each stage's state passed through the stages after it.
+        let mut sum = T::zero();
+        for stage in self.stages.iter() {
+            sum = sum * self.gain + *stage * state_gain;
+        }
+        let gain4 = self.gain * self.gain * self.gain * self.gain;
+        let compensated = *sample * (one + feedback);
+        let input = (compensated - feedback * sum) / (one + feedback * gain4);
+
+        let mut signal = self.limiter().apply(input);
+        for stage in self.stages.iter_mut() {
+            let v = (signal - *stage) * self.gain;
+            let output = v + *stage;
+            *stage = output + v;
+            signal = output;
+        }
+        *sample = signal;
+        true
+    }
+
+    /// Processes a block of samples in-place.
+    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
+        if samples.is_empty() {
+            return false;
+        }
+        for sample in samples.iter_mut() {
+            self.process(sample);
+        }
+        true
+    }
+
+    /// Returns the cutoff frequency in Hz.
+    pub fn get_cutoff(&self) -> T {
+        self.cutoff
+    }
+
+    /// Sets the cutoff frequency in Hz without resetting the filter state. Returns `false` and
+    /// keeps the current cutoff if it is not between zero and the Nyquist frequency.
+    pub fn set_cutoff(&mut self, cutoff: T) -> bool {
+        let Some(sample_rate) = T::from(self.sample_rate) else {
+            return false;
+        };
+        let Some(pi) = T::from(PI) else {
+            return false;
+        };
+        if !(cutoff > T::zero() && cutoff < sample_rate / (T::one() + T::one())) {
+            return false;
+        }
+        let g = (pi * cutoff / sample_rate).tan();
+        self.cutoff = cutoff;
+        self.gain = g / (T::one() + g);
+        true
+    }
+
+    /// Returns the resonance.
+    pub fn get_resonance(&self) -> T {
+        self.resonance
+    }
+
+    /// Sets the resonance without resetting the filter state. Returns `false` and keeps the
+    /// current resonance if it is not between zero and `MAX_RESONANCE`.
+    pub fn set_resonance(&mut self, resonance: T) -> bool {
+        let Some(max_resonance) = T::from(Self::MAX_RESONANCE) else {
+            return false;
+        };
+        if !(resonance >= T::zero() && resonance <= max_resonance) {
+            return false;
+        }
+        self.resonance = resonance;
+        true
+    }
+
+    /// Returns the saturation applied to the input of the ladder, after the feedback is mixed in.
+    pub fn get_saturation(&self) -> Saturation<T> {
+        self.saturation
+    }
+
+    /// Sets the saturation applied to the input of the ladder, after the feedback is mixed in.
+    /// Returns `false` and keeps the current saturation if its drive is not positive and finite.
+    pub fn set_saturation(&mut self, saturation: Saturation<T>) -> bool {
+        if !saturation.is_valid() {
+            return false;
+        }
+        self.saturation = saturation;
+        true
+    }
+
+    /// Returns the sample rate of the filter.
+    pub fn get_sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Resets the filter state.
+    pub fn reset(&mut self) {
+        self.stages = [T::zero(); STAGES];
+    }
+
+    /// Returns whether the resonance is high enough for the filter to self-oscillate.
+    pub fn is_self_oscillating(&self) -> bool {
+        T::from(Self::SELF_OSCILLATION).is_some_and(|threshold| self.resonance >= threshold)
+    }
+
+    /// Returns the saturation used in the loop, falling back to a unity-drive `tanh` when the
+    /// filter self-oscillates without one.
+    fn limiter(&self) -> Saturation<T> {
+        if self.saturation == Saturation::Linear && self.is_self_oscillating() {
+            Saturation::Tanh { drive: T::one() }
+        } else {
+            self.saturation
+        }
+    }
+
+    /// Returns the feedback gain, four times the resonance.
+    fn feedback(&self) -> T {
+        let two = T::one() + T::one();
+        two * two * self.resonance
+    }
+}
@@ -0,0 +1,82 @@
+/// limiter.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::cast;
+use num_traits::Float;
+
+/// A clamp applied to a filter's output, protecting hardware outputs and fixed-point stages from
+/// resonant overshoot. The limit is applied after the output is fed back, so it does not change
+/// the filter's response below the limit. NaN samples are replaced with zero.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OutputLimit<T: Float> {
+    /// The output is not limited.
+    #[default]
+    Unlimited,
+    /// The output is hard clipped to `±limit`.
+    Clip { limit: T },
+    /// The output passes unchanged up to half of `limit`, then bends smoothly towards `±limit`
+    /// without ever reaching it.
+    Soft { limit: T },
+}
+
+impl<T: Float> OutputLimit<T> {
+    /// Converts the limit to another float type.
+    pub(crate) fn cast<U: Float>(&self) -> OutputLimit<U> {
+        match *self {
+            OutputLimit::Unlimited => OutputLimit::Unlimited,
+            OutputLimit::Clip { limit } => OutputLimit::Clip { limit: cast(limit) },
+            OutputLimit::Soft { limit } => OutputLimit::Soft { limit: cast(limit) },
+        }
+    }
+
+    /// Returns whether the limit can be used: it must be positive and finite.
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            OutputLimit::Unlimited => true,
+            OutputLimit::Clip { limit } | OutputLimit::Soft { limit } => {
+                limit > T::zero() && limit.is_finite()
+            }
+        }
+    }
+
+    /// Applies the limit to a single value.
+    #[inline]
+    pub fn apply(&self, value: T) -> T {
+        match *self {
+            OutputLimit::Unlimited => value,
+            _ if value.is_nan() => T::zero(),
+            OutputLimit::Clip { limit } => value.max(-limit).min(limit),
+            OutputLimit::Soft { limit } => {
+                let knee = limit / (T::one() + T::one());
+                let magnitude = value.abs();
+                if magnitude <= knee {
+                    value
+                } else {
+                    let range = limit - knee;
+                    let limited = knee + range * ((magnitude - knee) / range).tanh();
+                    limited.copysign(value)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,165 @@
+/// linkwitz_riley.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::design::butterworth_q_values;
+use crate::filters::biquad::{Coefficients, DigitalBiquadFilter};
+use crate::filters::crossover::butterworth_section;
+use crate::filters::response::{FrequencyResponse, series_response};
+use num_complex::Complex;
+use num_traits::Float;
+use core::f64::consts::PI;
+
+/// A Linkwitz-Riley low-pass or high-pass filter: a Butterworth filter of half the order applied
+/// twice, built from cascaded biquads. A low-pass and a high-pass of the same order and frequency
+/// are both -6 dB at the crossover frequency and sum to an all-pass, so the summed magnitude is
+/// flat. Orders 2, 4 and 8 (LR2, LR4 and LR8) are the usual choices; any even order is accepted.
+///
+/// For orders of 2, 6, 10 and so on, the bands are a half cycle apart at the crossover frequency
+/// and would cancel there, so the high-pass is inverted to keep the sum flat.
+#[derive(Debug, Clone)]
+pub struct LinkwitzRileyFilter<T: Float> {
+    frequency: T,
+    sample_rate: u32,
+    order: usize,
+    high_pass: bool,
+    sections: Vec<DigitalBiquadFilter<T>>,
+}
+
+impl<T: Float> LinkwitzRileyFilter<T> {
+    /// Creates a low-pass filter. Returns `None` if the frequency is not between zero and the
+    /// Nyquist frequency or the order is not a positive even number.
+    pub fn low_pass(frequency: T, sample_rate: u32, order: usize) -> Option<Self> {
+        Self::new(frequency, sample_rate, order, false)
+    }
+
+    /// Creates a high-pass filter. Returns `None` if the frequency is not between zero and the
+    /// Nyquist frequency or the order is not a positive even number.
+    pub fn high_pass(frequency: T, sample_rate: u32, order: usize) -> Option<Self> {
+        Self::new(frequency, sample_rate, order, true)
+    }
+
+    fn new(frequency: T, sample_rate: u32, order: usize, high_pass: bool) -> Option<Self> {
+        let sections = Self::design(frequency, sample_rate, order, high_pass)?
+            .into_iter()
+            .map(DigitalBiquadFilter::new)
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self {
+            frequency,
+            sample_rate,
+            order,
+            high_pass,
+            sections,
+        })
+    }
+
+    /// Designs the sections: each second-order Butterworth section twice, and for odd Butterworth
+    /// orders the first-order section squared, which is a biquad with a Q of one half.
+    fn design(
+        frequency: T,
+        sample_rate: u32,
+        order: usize,
+        high_pass: bool,
+    ) -> Option<Vec<Coefficients<T>>> {
+        let nyquist = T::from(sample_rate)? / T::from(2.0)?;
+        if order == 0 || !order.is_multiple_of(2) || !(frequency > T::zero() && frequency < nyquist) {
+            return None;
+        }
+        let k = (T::from(PI)? * frequency / T::from(sample_rate)?).tan();
+        let butterworth_order = order / 2;
+        let mut sections = Vec::with_capacity(order.div_ceil(4));
+        if !butterworth_order.is_multiple_of(2) {
+            let mut section = butterworth_section(k, T::from(0.5)?, high_pass)?;
+            if high_pass {
+                section.b0 = -section.b0;
+                section.b1 = -section.b1;
+                section.b2 = -section.b2;
+            }
+            sections.push(section);
+        }
+        for q in butterworth_q_values::<T>(butterworth_order) {
+            let section = butterworth_section(k, q, high_pass)?;
+            sections.push(section);
+            sections.push(section);
+        }
+        Some(sections)
+    }
+
+    /// Processes a single sample.
+    pub fn process(&mut self, sample: &mut T) -> bool {
+        self.sections.iter_mut().all(|section| section.process(sample))
+    }
+
+    /// Processes a block of samples. Returns `false` if the block is empty.
+    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
+        self.sections.iter_mut().all(|section| section.process_block(samples))
+    }
+
+    /// Returns the crossover frequency.
+    pub fn get_frequency(&self) -> T {
+        self.frequency
+    }
+
+    /// Moves the crossover frequency without resetting the filter. Returns `false` and keeps the
+    /// current frequency if it is not between zero and the Nyquist frequency.
+    pub fn set_frequency(&mut self, frequency: T) -> bool {
+        let Some(design) = Self::design(frequency, self.sample_rate, self.order, self.high_pass)
+        else {
+            return false;
+        };
+        for (section, coefficients) in self.sections.iter_mut().zip(design) {
+            section.update_coefficients(coefficients);
+        }
+        self.frequency = frequency;
+        true
+    }
+
+    /// Returns the sample rate.
+    pub fn get_sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Returns the order: twice the order of the underlying Butterworth filter.
+    pub fn get_order(&self) -> usize {
+        self.order
+    }
+
+    /// Returns whether the filter is a high-pass.
+    pub fn is_high_pass(&self) -> bool {
+        self.high_pass
+    }
+
+    /// Returns the biquad sections, in processing order.
+    pub fn get_sections(&self) -> &[DigitalBiquadFilter<T>] {
+        &self.sections
+    }
+
+    /// Resets the state of every section.
+    pub fn reset(&mut self) {
+        self.sections.iter_mut().for_each(DigitalBiquadFilter::reset);
+    }
+}
+
+impl<T: Float> FrequencyResponse<T> for LinkwitzRileyFilter<T> {
+    fn response_at(&self, frequency: T, sample_rate: u32) -> Complex<T> {
+        series_response(&self.sections, frequency, sample_rate)
+    }
+}
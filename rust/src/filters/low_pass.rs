@@ -1,5 +1,4 @@
 /// low_pass.rs
-
 /**
 Copyright © 2025 Alex Parisi
 
@@ -22,22 +21,30 @@ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
 use crate::filters::biquad::{Coefficients, DigitalBiquadFilter};
-use crate::filters::filter::BiquadFilterWrapper;
+use crate::filters::filter::{
+    BiquadFilterWrapper, apply_configuration, impl_biquad_filter, impl_biquad_filter_wrapper,
+    impl_precision_conversions,
+};
+use crate::filters::design_method::{DesignMethod, matched_low_pass};
 use crate::filters::filter_configuration::FilterConfiguration;
+use crate::filters::resonance::{q_to_resonance, resonance_to_q};
 use num_traits::Float;
-use std::f64::consts::PI;
+use core::f64::consts::PI;
 
 
 /// Low-pass filter implementation using a digital biquad filter.
+///
+/// The poles stay inside the unit circle for every finite Q, so the filter rings for longer as Q
+/// rises but never self-oscillates. Use a `LadderFilter` for a self-oscillating low-pass.
 #[derive(Debug, Clone)]
-pub struct LowPassFilter<T: Float + Default + Copy> {
+pub struct LowPassFilter<T: Float> {
     /// The digital biquad filter used for processing.
     filter: DigitalBiquadFilter<T>,
     /// The configuration for the filter, including cutoff frequency, sample rate, and Q factor.
     config: FilterConfiguration<T>,
 }
 
-impl<T: Float + Default + Copy + std::ops::MulAssign> LowPassFilter<T> {
+impl<T: Float> LowPassFilter<T> {
     /// Creates a new low-pass filter with the given cutoff frequency, sample rate, and Q factor.
     pub fn new(cutoff: T, sample_rate: u32, q_factor: T) -> Option<Self> {
         let config = FilterConfiguration::new(
@@ -52,33 +59,47 @@ impl<T: Float + Default + Copy + std::ops::MulAssign> LowPassFilter<T> {
         let filter = DigitalBiquadFilter::new(coefficients)?;
         Some(Self { filter, config })
     }
-}
 
-/// Provide internal access and coefficient logic via BiquadFilterWrapper.
-impl<T: Float + Default + Copy + std::ops::MulAssign> BiquadFilterWrapper<T> for LowPassFilter<T> {
-    fn get_filter(&mut self) -> &mut DigitalBiquadFilter<T> {
-        &mut self.filter
+    /// Returns the Q factor expressed as a resonance between zero and one, using the mapping
+    /// described by `resonance_to_q`.
+    pub fn get_resonance(&self) -> T {
+        q_to_resonance(self.config.get_q_factor()).unwrap_or_else(T::zero)
     }
 
-    fn get_config(&self) -> &FilterConfiguration<T> {
-        &self.config
+    /// Sets the Q factor from a resonance between zero and one, where zero gives `Q = 0.5` and
+    /// the Q rises without bound towards one, as described by `resonance_to_q`. Returns `false`
+    /// if the resonance is not in `[0, 1)`.
+    pub fn set_resonance(&mut self, resonance: T) -> bool {
+        let Some(q_factor) = resonance_to_q(resonance) else {
+            return false;
+        };
+        let mut config = self.config;
+        config.set_q_factor(q_factor);
+        apply_configuration(self, config)
     }
+}
 
-    fn get_config_mut(&mut self) -> &mut FilterConfiguration<T> {
-        &mut self.config
-    }
+impl_biquad_filter!(LowPassFilter);
+impl_precision_conversions!(LowPassFilter);
+impl_biquad_filter_wrapper!(LowPassFilter, LowPass);
 
-    fn calculate_coefficients(config: &FilterConfiguration<T>) -> Option<Coefficients<T>> {
+impl<T: Float> LowPassFilter<T> {
+    /// Designs the low-pass coefficients for a configuration. Returns `None` if the configuration
+    /// is invalid.
+    fn design_coefficients(config: &FilterConfiguration<T>) -> Option<Coefficients<T>> {
+        if config.get_design_method() == DesignMethod::Matched {
+            return matched_low_pass(config);
+        }
         let cutoff = config.get_cutoff();
         let sample_rate = config.get_sample_rate();
         let q = config.get_q_factor();
 
-        if cutoff <= T::zero() || sample_rate == 0 || q <= T::zero() {
+        if !config.is_valid() {
             return None;
         }
 
-        let two = T::from(2.0).unwrap();
-        let pi = T::from(PI).unwrap();
+        let two = T::from(2.0)?;
+        let pi = T::from(PI)?;
         let one = T::one();
 
         let w0 = two * pi * cutoff / T::from(sample_rate)?;
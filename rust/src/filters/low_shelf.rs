@@ -1,5 +1,4 @@
 /// low_shelf.rs
-
 /**
 Copyright © 2025 Alex Parisi
 
@@ -22,22 +21,25 @@ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
 use crate::filters::biquad::{Coefficients, DigitalBiquadFilter};
-use crate::filters::filter::BiquadFilterWrapper;
+use crate::filters::filter::{
+    BiquadFilterWrapper, apply_configuration, impl_biquad_filter, impl_biquad_filter_wrapper,
+    impl_precision_conversions,
+};
 use crate::filters::filter_configuration::FilterConfiguration;
 use num_traits::Float;
-use std::f64::consts::PI;
+use core::f64::consts::PI;
 
 
 /// Low Shelf filter implementation using a digital biquad filter.
 #[derive(Debug, Clone)]
-pub struct LowShelfFilter<T: Float + Default + Copy> {
+pub struct LowShelfFilter<T: Float> {
     /// The digital biquad filter used for processing.
     filter: DigitalBiquadFilter<T>,
     /// The configuration for the filter, including cutoff frequency, sample rate, and Q factor.
     config: FilterConfiguration<T>,
 }
 
-impl<T: Float + Default + Copy + std::ops::MulAssign> LowShelfFilter<T> {
+impl<T: Float> LowShelfFilter<T> {
     /// Creates a new low shelf filter with the given cutoff frequency, sample rate, and Q factor.
     pub fn new(cutoff: T, sample_rate: u32, q_factor: T, gain: T) -> Option<Self> {
         let config =
@@ -53,29 +55,36 @@ impl<T: Float + Default + Copy + std::ops::MulAssign> LowShelfFilter<T> {
         let filter = DigitalBiquadFilter::new(coefficients)?;
         Some(Self { filter, config })
     }
-}
 
-/// Provide internal access and coefficient logic via BiquadFilterWrapper.
-impl<T: Float + Default + Copy + std::ops::MulAssign> BiquadFilterWrapper<T> for LowShelfFilter<T> {
-    fn get_filter(&mut self) -> &mut DigitalBiquadFilter<T> {
-        &mut self.filter
+    /// Returns the cookbook shelf slope, or `None` if the shelf uses its Q factor.
+    pub fn get_shelf_slope(&self) -> Option<T> {
+        self.config.get_shelf_slope()
     }
 
-    fn get_config(&self) -> &FilterConfiguration<T> {
-        &self.config
+    /// Sets the cookbook shelf slope `S`, which is converted to a Q factor at the current gain,
+    /// or `None` to use the Q factor. A slope of one is the steepest shelf without overshoot.
+    /// Returns `false` if the slope is not positive or is too steep for the gain.
+    pub fn set_shelf_slope(&mut self, shelf_slope: Option<T>) -> bool {
+        let mut config = self.config;
+        config.set_shelf_slope(shelf_slope);
+        apply_configuration(self, config)
     }
+}
 
-    fn get_config_mut(&mut self) -> &mut FilterConfiguration<T> {
-        &mut self.config
-    }
+impl_biquad_filter!(LowShelfFilter);
+impl_precision_conversions!(LowShelfFilter);
+impl_biquad_filter_wrapper!(LowShelfFilter, LowShelf);
 
-    fn calculate_coefficients(config: &FilterConfiguration<T>) -> Option<Coefficients<T>> {
+impl<T: Float> LowShelfFilter<T> {
+    /// Designs the low-shelf coefficients for a configuration. Returns `None` if the configuration
+    /// is invalid.
+    fn design_coefficients(config: &FilterConfiguration<T>) -> Option<Coefficients<T>> {
         let cutoff = config.get_cutoff();
         let sample_rate = config.get_sample_rate();
-        let q = config.get_q_factor();
+        let q = config.get_shelf_q_factor();
         let gain = config.get_gain();
 
-        if cutoff <= T::zero() || sample_rate == 0 || q <= T::zero() {
+        if !config.is_valid() {
             return None;
         }
 
@@ -0,0 +1,140 @@
+/// metering.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::filter::Filter;
+use num_traits::Float;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Peak and RMS levels of a signal, written by the audio thread and readable from any thread.
+/// Levels are stored as linear `f32` amplitudes.
+#[derive(Debug, Default)]
+pub struct LevelMeter {
+    peak: AtomicU32,
+    rms: AtomicU32,
+}
+
+impl LevelMeter {
+    /// Returns the largest absolute sample seen since the peak was last taken.
+    pub fn get_peak(&self) -> f32 {
+        f32::from_bits(self.peak.load(Ordering::Relaxed))
+    }
+
+    /// Returns the peak and clears it, so the next reading covers only newer samples.
+    pub fn take_peak(&self) -> f32 {
+        f32::from_bits(self.peak.swap(0, Ordering::Relaxed))
+    }
+
+    /// Returns the RMS level, averaged over the window the metering was enabled with.
+    pub fn get_rms(&self) -> f32 {
+        f32::from_bits(self.rms.load(Ordering::Relaxed))
+    }
+
+    /// Stores the RMS level and raises the peak if `peak` is larger. Non-negative floats order the
+    /// same way as their bit patterns, so the comparison can be done on the integers.
+    fn publish(&self, peak: f32, rms: f32) {
+        self.peak.fetch_max(peak.to_bits(), Ordering::Relaxed);
+        self.rms.store(rms.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// Handles to the input and output meters of a filter. Clones share the same meters, so one can be
+/// handed to a UI thread while the filter runs on the audio thread.
+#[derive(Debug, Clone, Default)]
+pub struct Meters {
+    pub input: Arc<LevelMeter>,
+    pub output: Arc<LevelMeter>,
+}
+
+/// A filter that can meter the levels of its input and output.
+pub trait Metered<T: Float>: Filter<T> {
+    /// Starts metering the input and output levels, with RMS levels averaged over roughly
+    /// `window` samples, and returns handles to the meters. Bypassed samples are not metered.
+    fn enable_metering(&mut self, window: u32) -> Meters;
+    /// Stops metering.
+    fn disable_metering(&mut self);
+    /// Returns handles to the meters, if metering is enabled.
+    fn get_meters(&self) -> Option<Meters>;
+}
+
+impl<T: Float, F: Metered<T> + ?Sized> Metered<T> for Box<F> {
+    fn enable_metering(&mut self, window: u32) -> Meters {
+        (**self).enable_metering(window)
+    }
+
+    fn disable_metering(&mut self) {
+        (**self).disable_metering();
+    }
+
+    fn get_meters(&self) -> Option<Meters> {
+        (**self).get_meters()
+    }
+}
+
+/// The metering state kept by a filter on the audio thread.
+#[derive(Debug, Clone)]
+pub(crate) struct Metering {
+    meters: Meters,
+    coefficient: f32,
+    input_mean_square: f32,
+    output_mean_square: f32,
+    input_peak: f32,
+    output_peak: f32,
+}
+
+impl Metering {
+    /// Creates metering with an RMS averaging window of `window` samples.
+    pub(crate) fn new(window: u32) -> Self {
+        Self {
+            meters: Meters::default(),
+            coefficient: 1.0 / window.max(1) as f32,
+            input_mean_square: 0.0,
+            output_mean_square: 0.0,
+            input_peak: 0.0,
+            output_peak: 0.0,
+        }
+    }
+
+    /// Returns handles to the meters.
+    pub(crate) fn get_meters(&self) -> Meters {
+        self.meters.clone()
+    }
+
+    /// Accumulates one input and output sample.
+    #[inline]
+    pub(crate) fn record<T: Float>(&mut self, input: T, output: T) {
+        let input = input.to_f32().unwrap_or(f32::NAN);
+        let output = output.to_f32().unwrap_or(f32::NAN);
+        self.input_mean_square += (input * input - self.input_mean_square) * self.coefficient;
+        self.output_mean_square += (output * output - self.output_mean_square) * self.coefficient;
+        self.input_peak = self.input_peak.max(input.abs());
+        self.output_peak = self.output_peak.max(output.abs());
+    }
+
+    /// Publishes the levels accumulated since the last call to the shared meters.
+    pub(crate) fn publish(&mut self) {
+        self.meters.input.publish(self.input_peak, self.input_mean_square.sqrt());
+        self.meters.output.publish(self.output_peak, self.output_mean_square.sqrt());
+        self.input_peak = 0.0;
+        self.output_peak = 0.0;
+    }
+}
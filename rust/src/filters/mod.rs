@@ -1,5 +1,4 @@
 /// mod.rs
-
 /**
 Copyright © 2025 Alex Parisi
 
@@ -21,14 +20,88 @@ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
 OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
+pub mod adaptive_notch;
+#[cfg(feature = "std")]
+pub mod automation;
 pub mod biquad;
+#[cfg(feature = "std")]
+pub mod butterworth;
+pub mod cascade;
+#[cfg(feature = "std")]
+pub mod chain;
+pub mod complementary;
+pub mod complex_biquad;
+#[cfg(feature = "std")]
+pub mod convolution;
+#[cfg(feature = "std")]
+pub mod crossover;
+pub mod deesser;
+pub mod descriptor;
+pub mod design_method;
+pub mod emphasis;
+pub mod envelope;
+pub mod error;
+pub mod fault;
 pub mod filter;
+#[cfg(feature = "std")]
+pub mod filter_bank;
 pub mod filter_configuration;
+#[cfg(feature = "std")]
+pub mod fir;
+pub mod frequency_shifter;
+#[cfg(feature = "arbitrary")]
+mod fuzzing;
+#[cfg(feature = "std")]
+pub mod gammatone;
+pub mod goertzel;
+#[cfg(feature = "std")]
+pub mod graphic_eq;
 pub mod high_pass;
+pub mod hilbert;
+#[cfg(feature = "state-history")]
+pub mod history;
+#[cfg(feature = "std")]
+pub mod iir;
+pub mod ladder;
+pub mod limiter;
+#[cfg(feature = "std")]
+pub mod linkwitz_riley;
 pub mod low_pass;
+#[cfg(feature = "metering")]
+pub mod metering;
+#[cfg(feature = "std")]
+pub mod modulation;
+#[cfg(feature = "std")]
+pub mod multichannel;
 pub mod band_pass;
 pub mod notch;
+#[cfg(feature = "std")]
+pub mod parallel;
+#[cfg(feature = "std")]
+pub mod parametric_eq;
+#[cfg(feature = "std")]
+mod notation;
 pub mod all_pass;
 pub mod peaking_eq;
+pub mod pinking;
+#[cfg(feature = "std")]
+pub mod preset;
+pub mod resonance;
+pub mod response;
+pub mod saturation;
 pub mod low_shelf;
+pub mod snapshot;
+#[cfg(feature = "std")]
+pub mod spec;
+pub mod state_variable;
+pub mod stats;
+pub mod stereo;
+pub mod tilt;
+pub mod topology;
+pub mod update;
+pub mod weighting;
+#[cfg(feature = "std")]
+pub mod thiran;
+#[cfg(feature = "std")]
+pub mod zero_phase;
 pub mod high_shelf;
\ No newline at end of file
@@ -0,0 +1,363 @@
+/// modulation.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::automation::Parameter;
+use crate::filters::filter::Filter;
+use core::f64::consts::PI;
+use num_traits::Float;
+
+/// The default number of samples between coefficient updates.
+const DEFAULT_CONTROL_INTERVAL: usize = 16;
+/// The highest modulated cutoff, as a fraction of the sample rate.
+const MAX_CUTOFF_RATIO: f64 = 0.49;
+
+/// The waveform of a low-frequency oscillator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LfoShape {
+    #[default]
+    Sine,
+    Triangle,
+    /// A new random value at the start of every cycle, held until the next one.
+    Random,
+}
+
+/// A low-frequency oscillator whose output swings between `-depth` and `depth`.
+#[derive(Debug, Clone)]
+pub struct Lfo<T: Float> {
+    shape: LfoShape,
+    /// The rate in hertz.
+    rate: T,
+    depth: T,
+    /// The position within the current cycle, in `[0, 1)`.
+    phase: T,
+    seed: u32,
+    random: u32,
+    /// The value held by the random shape for the current cycle.
+    held: T,
+}
+
+impl<T: Float> Lfo<T> {
+    /// The seed used by `new`.
+    pub const DEFAULT_SEED: u32 = 0x9E37_79B9;
+
+    /// Creates an oscillator with the given shape, rate in hertz and depth. Returns `None` if the
+    /// rate or depth is negative or not finite.
+    pub fn new(shape: LfoShape, rate: T, depth: T) -> Option<Self> {
+        Self::with_seed(shape, rate, depth, Self::DEFAULT_SEED)
+    }
+
+    /// Creates an oscillator whose random values start from `seed`, so several random oscillators
+    /// can move independently. Returns `None` if the rate or depth is negative or not finite.
+    pub fn with_seed(shape: LfoShape, rate: T, depth: T, seed: u32) -> Option<Self> {
+        if !is_non_negative(rate) || !is_non_negative(depth) {
+            return None;
+        }
+        let mut lfo = Self {
+            shape,
+            rate,
+            depth,
+            phase: T::zero(),
+            seed: seed.max(1),
+            random: seed.max(1),
+            held: T::zero(),
+        };
+        lfo.held = lfo.next_bipolar();
+        Some(lfo)
+    }
+
+    /// Returns the waveform.
+    pub fn get_shape(&self) -> LfoShape {
+        self.shape
+    }
+
+    /// Sets the waveform, keeping the phase.
+    pub fn set_shape(&mut self, shape: LfoShape) {
+        self.shape = shape;
+    }
+
+    /// Returns the rate in hertz.
+    pub fn get_rate(&self) -> T {
+        self.rate
+    }
+
+    /// Sets the rate in hertz. Returns `false` if the rate is negative or not finite.
+    pub fn set_rate(&mut self, rate: T) -> bool {
+        if !is_non_negative(rate) {
+            return false;
+        }
+        self.rate = rate;
+        true
+    }
+
+    /// Returns the depth.
+    pub fn get_depth(&self) -> T {
+        self.depth
+    }
+
+    /// Sets the depth. Returns `false` if the depth is negative or not finite.
+    pub fn set_depth(&mut self, depth: T) -> bool {
+        if !is_non_negative(depth) {
+            return false;
+        }
+        self.depth = depth;
+        true
+    }
+
+    /// Returns the position within the current cycle, in `[0, 1)`.
+    pub fn get_phase(&self) -> T {
+        self.phase
+    }
+
+    /// Moves to a position within the cycle, wrapped into `[0, 1)`. Returns `false` if the phase
+    /// is not finite.
+    pub fn set_phase(&mut self, phase: T) -> bool {
+        if !phase.is_finite() {
+            return false;
+        }
+        self.phase = phase - phase.floor();
+        true
+    }
+
+    /// Returns the current output, between `-depth` and `depth`.
+    pub fn value(&self) -> T {
+        let phase = self.phase;
+        let one = T::one();
+        let unit = match self.shape {
+            LfoShape::Sine => T::from(2.0 * PI).map_or(T::zero(), |tau| (tau * phase).sin()),
+            LfoShape::Triangle => match (T::from(0.25), T::from(0.75), T::from(4.0)) {
+                (Some(quarter), Some(three_quarters), Some(four)) => {
+                    if phase < quarter {
+                        four * phase
+                    } else if phase < three_quarters {
+                        one + one - four * phase
+                    } else {
+                        four * phase - four
+                    }
+                }
+                _ => T::zero(),
+            },
+            LfoShape::Random => self.held,
+        };
+        unit * self.depth
+    }
+
+    /// Moves the oscillator forward by `samples` samples at the given sample rate.
+    pub fn advance(&mut self, samples: usize, sample_rate: u32) {
+        let (Some(samples), Some(sample_rate)) = (T::from(samples), T::from(sample_rate)) else {
+            return;
+        };
+        if sample_rate <= T::zero() {
+            return;
+        }
+        let phase = self.phase + self.rate * samples / sample_rate;
+        if phase >= T::one() {
+            self.held = self.next_bipolar();
+        }
+        self.phase = phase - phase.floor();
+    }
+
+    /// Returns to the start of the cycle and restarts the random sequence from its seed.
+    pub fn reset(&mut self) {
+        self.phase = T::zero();
+        self.random = self.seed;
+        self.held = self.next_bipolar();
+    }
+
+    /// Returns a uniform value in `[-1, 1)` from a xorshift generator.
+    fn next_bipolar(&mut self) -> T {
+        self.random ^= self.random << 13;
+        self.random ^= self.random >> 17;
+        self.random ^= self.random << 5;
+        let fraction = (self.random >> 8) as f64 / (1_u32 << 23) as f64 - 1.0;
+        T::from(fraction).unwrap_or_else(T::zero)
+    }
+}
+
+/// Returns whether a value is finite and not negative.
+fn is_non_negative<T: Float>(value: T) -> bool {
+    value.is_finite() && value >= T::zero()
+}
+
+/// Wraps a filter and sweeps one of its parameters with an `Lfo` while processing, for
+/// auto-filter and auto-wah effects. The oscillator swings the parameter around a centre value:
+/// its depth is in octaves for the cutoff and Q factor, and in decibels for the gain. The
+/// coefficients are updated every `get_control_interval()` samples, across block boundaries.
+#[derive(Debug, Clone)]
+pub struct ModulatedFilter<T: Float, F> {
+    /// The filter being modulated.
+    filter: F,
+    lfo: Lfo<T>,
+    /// The modulated parameter.
+    parameter: Parameter,
+    /// The value the parameter is modulated around.
+    center: T,
+    /// The number of samples between coefficient updates.
+    control_interval: usize,
+    /// The number of samples until the next coefficient update.
+    countdown: usize,
+}
+
+impl<T, F> ModulatedFilter<T, F>
+where
+    T: Float,
+    F: Filter<T>,
+{
+    /// Wraps the given filter, modulating `parameter` around its current value.
+    pub fn new(filter: F, parameter: Parameter, lfo: Lfo<T>) -> Self {
+        Self {
+            center: parameter.get(&filter),
+            filter,
+            lfo,
+            parameter,
+            control_interval: DEFAULT_CONTROL_INTERVAL,
+            countdown: 0,
+        }
+    }
+
+    /// Returns the oscillator.
+    pub fn get_lfo(&self) -> &Lfo<T> {
+        &self.lfo
+    }
+
+    /// Returns the oscillator mutably, to change its shape, rate or depth.
+    pub fn get_lfo_mut(&mut self) -> &mut Lfo<T> {
+        &mut self.lfo
+    }
+
+    /// Returns the modulated parameter.
+    pub fn get_parameter(&self) -> Parameter {
+        self.parameter
+    }
+
+    /// Modulates a different parameter around its current value. The previous parameter is
+    /// returned to its centre value. Returns `false` if that fails.
+    pub fn set_parameter(&mut self, parameter: Parameter) -> bool {
+        let success = self.parameter.change(self.center).apply(&mut self.filter);
+        self.parameter = parameter;
+        self.center = parameter.get(&self.filter);
+        self.countdown = 0;
+        success
+    }
+
+    /// Returns the value the parameter is modulated around.
+    pub fn get_center(&self) -> T {
+        self.center
+    }
+
+    /// Sets the value the parameter is modulated around, taking effect at the next coefficient
+    /// update. Returns `false` if the value is not finite, or not positive for the cutoff and Q
+    /// factor.
+    pub fn set_center(&mut self, center: T) -> bool {
+        let positive = matches!(self.parameter, Parameter::Cutoff | Parameter::QFactor);
+        if !center.is_finite() || (positive && center <= T::zero()) {
+            return false;
+        }
+        self.center = center;
+        self.countdown = 0;
+        true
+    }
+
+    /// Returns the number of samples between coefficient updates.
+    pub fn get_control_interval(&self) -> usize {
+        self.control_interval
+    }
+
+    /// Sets the number of samples between coefficient updates. Smaller intervals give smoother
+    /// sweeps at a higher cost. Returns `false` if the interval is zero.
+    pub fn set_control_interval(&mut self, control_interval: usize) -> bool {
+        if control_interval == 0 {
+            return false;
+        }
+        self.control_interval = control_interval;
+        self.countdown = self.countdown.min(control_interval);
+        true
+    }
+
+    /// Returns the wrapped filter.
+    pub fn get_filter(&self) -> &F {
+        &self.filter
+    }
+
+    /// Returns the wrapped filter mutably. Changes to the modulated parameter are overwritten at
+    /// the next coefficient update; use `set_center` instead.
+    pub fn get_filter_mut(&mut self) -> &mut F {
+        &mut self.filter
+    }
+
+    /// Consumes the wrapper and returns the wrapped filter with the parameter at its centre value.
+    pub fn into_inner(mut self) -> F {
+        self.parameter.change(self.center).apply(&mut self.filter);
+        self.filter
+    }
+
+    /// Returns the modulated value of the parameter at the oscillator's current position.
+    pub fn modulated_value(&self) -> T {
+        let offset = self.lfo.value();
+        match self.parameter {
+            Parameter::Gain => self.center + offset,
+            Parameter::QFactor => self.center * offset.exp2(),
+            Parameter::Cutoff => {
+                let value = self.center * offset.exp2();
+                match (T::from(MAX_CUTOFF_RATIO), T::from(self.filter.get_sample_rate())) {
+                    (Some(ratio), Some(sample_rate)) => value.min(ratio * sample_rate),
+                    _ => value,
+                }
+            }
+        }
+    }
+
+    /// Processes a single sample in-place.
+    pub fn process(&mut self, sample: &mut T) -> bool {
+        self.process_block(core::slice::from_mut(sample))
+    }
+
+    /// Processes a block of samples in-place, moving the parameter to the oscillator's value
+    /// every control interval. Returns `false` if processing or any update failed.
+    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
+        if samples.is_empty() {
+            return false;
+        }
+        let length = samples.len();
+        let sample_rate = self.filter.get_sample_rate();
+        let mut success = true;
+        let mut position = 0;
+        while position < length {
+            if self.countdown == 0 {
+                success &= self.parameter.change(self.modulated_value()).apply(&mut self.filter);
+                self.countdown = self.control_interval;
+            }
+            let end = length.min(position + self.countdown);
+            success &= self.filter.process_block(&mut samples[position..end]);
+            self.lfo.advance(end - position, sample_rate);
+            self.countdown -= end - position;
+            position = end;
+        }
+        success
+    }
+
+    /// Clears the filter's state and returns the oscillator to the start of its cycle.
+    pub fn reset(&mut self) {
+        self.filter.reset();
+        self.lfo.reset();
+        self.countdown = 0;
+    }
+}
@@ -0,0 +1,184 @@
+/// multichannel.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::{Coefficients, State, tick, tick_block};
+use crate::filters::filter::FilterType;
+use crate::filters::filter_configuration::FilterConfiguration;
+use crate::filters::response::FrequencyResponse;
+use num_complex::Complex;
+use num_traits::Float;
+
+/// A biquad filter shared by several channels: one configuration and one set of coefficients,
+/// with independent state per channel. Every channel of a stereo or surround bus stays in sync
+/// with a single setter call, and the coefficients are only designed once.
+///
+/// Blocks can be processed as planar buffers, one slice per channel, or as interleaved frames.
+/// A bypassed configuration leaves the samples unchanged.
+#[derive(Debug, Clone)]
+pub struct MultiChannelFilter<T: Float> {
+    filter_type: FilterType,
+    config: FilterConfiguration<T>,
+    /// The designed coefficients, normalized so `a0` is one.
+    coefficients: Coefficients<T>,
+    states: Vec<State<T>>,
+}
+
+impl<T: Float> MultiChannelFilter<T> {
+    /// Creates a filter for the given number of channels. Returns `None` if there are no channels
+    /// or the configuration is invalid for the filter type.
+    pub fn new(
+        filter_type: FilterType,
+        config: FilterConfiguration<T>,
+        channels: usize,
+    ) -> Option<Self> {
+        if channels == 0 {
+            return None;
+        }
+        let coefficients = filter_type.design_coefficients(&config)?.normalized()?;
+        Some(Self {
+            filter_type,
+            config,
+            coefficients,
+            states: vec![State::default(); channels],
+        })
+    }
+
+    /// Returns the number of channels.
+    pub fn get_channels(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Changes the number of channels. Existing channels keep their state and new channels start
+    /// from silence. Returns `false` if there would be no channels.
+    pub fn set_channels(&mut self, channels: usize) -> bool {
+        if channels == 0 {
+            return false;
+        }
+        self.states.resize(channels, State::default());
+        true
+    }
+
+    /// Returns the filter type.
+    pub fn get_filter_type(&self) -> FilterType {
+        self.filter_type
+    }
+
+    /// Returns the configuration shared by every channel.
+    pub fn get_configuration(&self) -> FilterConfiguration<T> {
+        self.config
+    }
+
+    /// Returns the normalized coefficients shared by every channel.
+    pub fn get_coefficients(&self) -> Coefficients<T> {
+        self.coefficients
+    }
+
+    /// Changes the filter type and configuration of every channel without resetting their state.
+    /// Returns `false` and keeps the current settings if the configuration is invalid for the
+    /// filter type.
+    pub fn set_filter(&mut self, filter_type: FilterType, config: FilterConfiguration<T>) -> bool {
+        match filter_type.design_coefficients(&config).and_then(|c| c.normalized()) {
+            Some(coefficients) => {
+                self.filter_type = filter_type;
+                self.config = config;
+                self.coefficients = coefficients;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Sets the configuration of every channel without resetting their state. Returns `false` and
+    /// keeps the current configuration if it is invalid.
+    pub fn set_configuration(&mut self, config: FilterConfiguration<T>) -> bool {
+        self.set_filter(self.filter_type, config)
+    }
+
+    /// Applies any number of changes to a copy of the configuration and then recomputes the
+    /// coefficients once. Returns `false` and keeps the current configuration if the result is
+    /// invalid.
+    pub fn update(&mut self, change: impl FnOnce(&mut FilterConfiguration<T>)) -> bool {
+        let mut config = self.config;
+        change(&mut config);
+        self.set_configuration(config)
+    }
+
+    /// Processes one slice per channel in place. The slices may differ in length. Returns `false`
+    /// and leaves every slice unchanged if the number of slices does not match the number of
+    /// channels.
+    pub fn process_planar(&mut self, channels: &mut [&mut [T]]) -> bool {
+        if channels.len() != self.states.len() {
+            return false;
+        }
+        if self.config.get_bypass() {
+            return true;
+        }
+        for (samples, state) in channels.iter_mut().zip(self.states.iter_mut()) {
+            tick_block(&self.coefficients, state, samples);
+        }
+        true
+    }
+
+    /// Processes interleaved frames in place, with `channels` samples per frame. Returns `false`
+    /// and leaves the samples unchanged if `channels` does not match the number of channels or the
+    /// samples do not hold a whole number of frames.
+    pub fn process_interleaved(&mut self, samples: &mut [T], channels: usize) -> bool {
+        if channels != self.states.len() || !samples.len().is_multiple_of(channels) {
+            return false;
+        }
+        if self.config.get_bypass() {
+            return true;
+        }
+        for frame in samples.chunks_exact_mut(channels) {
+            for (sample, state) in frame.iter_mut().zip(self.states.iter_mut()) {
+                *sample = tick(&self.coefficients, state, *sample);
+            }
+        }
+        true
+    }
+
+    /// Resets the state of every channel.
+    pub fn reset(&mut self) {
+        self.states.fill(State::default());
+    }
+
+    /// Resets the state of one channel. Returns `false` if the channel does not exist.
+    pub fn reset_channel(&mut self, channel: usize) -> bool {
+        match self.states.get_mut(channel) {
+            Some(state) => {
+                *state = State::default();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<T: Float> FrequencyResponse<T> for MultiChannelFilter<T> {
+    /// Returns the response shared by every channel, or a flat response while bypassed.
+    fn response_at(&self, frequency: T, sample_rate: u32) -> Complex<T> {
+        if self.config.get_bypass() {
+            return Complex::from(T::one());
+        }
+        self.coefficients.response_at(frequency, sample_rate)
+    }
+}
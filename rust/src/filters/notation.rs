@@ -0,0 +1,136 @@
+/// notation.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::Coefficients;
+use num_traits::Float;
+
+/// The notation used to print a transfer function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Notation {
+    /// Plain text, such as `(0.2 + 0.4 z^-1) / (1 - 0.5 z^-1)`.
+    Plain,
+    /// LaTeX math mode, such as `\frac{0.2 + 0.4 z^{-1}}{1 - 0.5 z^{-1}}`.
+    Latex,
+}
+
+/// The number of significant digits printed for each coefficient.
+const SIGNIFICANT_DIGITS: i32 = 6;
+
+/// Prints the product of the normalized transfer functions of `sections` as `H(z) = ...`. An
+/// empty product prints as `H(z) = 1`.
+pub(crate) fn transfer_function<T: Float>(
+    sections: &[Coefficients<T>],
+    notation: Notation,
+) -> String {
+    let factors: Vec<String> = sections
+        .iter()
+        .map(|coefficients| {
+            let coefficients = coefficients.normalized().unwrap_or(*coefficients);
+            let numerator =
+                polynomial([coefficients.b0, coefficients.b1, coefficients.b2], notation);
+            let denominator =
+                polynomial([coefficients.a0, coefficients.a1, coefficients.a2], notation);
+            match notation {
+                Notation::Plain if sections.len() > 1 => {
+                    format!("[({numerator}) / ({denominator})]")
+                }
+                Notation::Plain => format!("({numerator}) / ({denominator})"),
+                Notation::Latex => format!("\\frac{{{numerator}}}{{{denominator}}}"),
+            }
+        })
+        .collect();
+    if factors.is_empty() {
+        return String::from("H(z) = 1");
+    }
+    let separator = match notation {
+        Notation::Plain => " * ",
+        Notation::Latex => " \\cdot ",
+    };
+    format!("H(z) = {}", factors.join(separator))
+}
+
+/// Prints `c0 + c1 z^-1 + c2 z^-2`, leaving out zero terms and unit coefficients.
+fn polynomial<T: Float>(coefficients: [T; 3], notation: Notation) -> String {
+    let mut text = String::new();
+    for (power, coefficient) in coefficients.iter().enumerate() {
+        let value = coefficient.to_f64().unwrap_or(f64::NAN);
+        if value == 0.0 {
+            continue;
+        }
+        let sign = if value.is_sign_negative() { "-" } else { "+" };
+        if text.is_empty() {
+            if sign == "-" {
+                text.push('-');
+            }
+        } else {
+            text.push_str(&format!(" {sign} "));
+        }
+        let magnitude = number(value.abs(), notation);
+        let variable = match (power, notation) {
+            (0, _) => "",
+            (1, Notation::Plain) => "z^-1",
+            (_, Notation::Plain) => "z^-2",
+            (1, Notation::Latex) => "z^{-1}",
+            (_, Notation::Latex) => "z^{-2}",
+        };
+        match (variable.is_empty(), magnitude == "1") {
+            (true, _) => text.push_str(&magnitude),
+            (false, true) => text.push_str(variable),
+            (false, false) => text.push_str(&format!("{magnitude} {variable}")),
+        }
+    }
+    if text.is_empty() {
+        text.push('0');
+    }
+    text
+}
+
+/// Prints a non-negative number to `SIGNIFICANT_DIGITS` significant digits without trailing
+/// zeros, switching to scientific notation for very small or very large values.
+fn number(value: f64, notation: Notation) -> String {
+    if !value.is_finite() {
+        return match notation {
+            Notation::Latex if value.is_infinite() => String::from("\\infty"),
+            _ => format!("{value}"),
+        };
+    }
+    let exponent = value.log10().floor() as i32;
+    if (-3..4).contains(&exponent) {
+        let decimals = (SIGNIFICANT_DIGITS - 1 - exponent).max(0) as usize;
+        return trim_zeros(format!("{value:.decimals$}"));
+    }
+    let scientific = format!("{:.*e}", (SIGNIFICANT_DIGITS - 1) as usize, value);
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let mantissa = trim_zeros(String::from(mantissa));
+    match notation {
+        Notation::Plain => format!("{mantissa}e{exponent}"),
+        Notation::Latex => format!("{mantissa} \\times 10^{{{exponent}}}"),
+    }
+}
+
+/// Removes trailing zeros after the decimal point, and the point itself if nothing follows it.
+fn trim_zeros(text: String) -> String {
+    if !text.contains('.') {
+        return text;
+    }
+    String::from(text.trim_end_matches('0').trim_end_matches('.'))
+}
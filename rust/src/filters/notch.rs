@@ -1,5 +1,4 @@
 /// notch.rs
-
 /**
 Copyright © 2025 Alex Parisi
 
@@ -22,22 +21,29 @@ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
 use crate::filters::biquad::{Coefficients, DigitalBiquadFilter};
-use crate::filters::filter::BiquadFilterWrapper;
+use crate::filters::filter::{
+    BiquadFilterWrapper, apply_configuration, impl_biquad_filter, impl_biquad_filter_wrapper,
+    impl_precision_conversions,
+};
 use crate::filters::filter_configuration::FilterConfiguration;
 use num_traits::Float;
-use std::f64::consts::PI;
+use core::f64::consts::PI;
 
 
 /// Notch filter implementation using a digital biquad filter.
+///
+/// By default the center frequency is removed completely. A finite depth blends the notch with
+/// a unity response in the coefficient domain, so the center is attenuated by exactly the depth
+/// and the bandwidth still follows the Q factor.
 #[derive(Debug, Clone)]
-pub struct NotchFilter<T: Float + Default + Copy> {
+pub struct NotchFilter<T: Float> {
     /// The digital biquad filter used for processing.
     filter: DigitalBiquadFilter<T>,
     /// The configuration for the filter, including cutoff frequency, sample rate, and Q factor.
     config: FilterConfiguration<T>,
 }
 
-impl<T: Float + Default + Copy + std::ops::MulAssign> NotchFilter<T> {
+impl<T: Float> NotchFilter<T> {
     /// Creates a new notch filter with the given cutoff frequency, sample rate, and Q factor.
     pub fn new(cutoff: T, sample_rate: u32, q_factor: T) -> Option<Self> {
         let config =
@@ -53,28 +59,45 @@ impl<T: Float + Default + Copy + std::ops::MulAssign> NotchFilter<T> {
         let filter = DigitalBiquadFilter::new(coefficients)?;
         Some(Self { filter, config })
     }
-}
 
-/// Provide internal access and coefficient logic via BiquadFilterWrapper.
-impl<T: Float + Default + Copy + std::ops::MulAssign> BiquadFilterWrapper<T> for NotchFilter<T> {
-    fn get_filter(&mut self) -> &mut DigitalBiquadFilter<T> {
-        &mut self.filter
+    /// Returns the attenuation at the center frequency in decibels, which is negative infinity
+    /// for a complete notch.
+    pub fn get_depth(&self) -> T {
+        let Some(twenty) = T::from(20.0) else {
+            return T::neg_infinity();
+        };
+        twenty * self.config.get_notch_gain().log10()
     }
 
-    fn get_config(&self) -> &FilterConfiguration<T> {
-        &self.config
+    /// Sets the attenuation at the center frequency in decibels, such as -6 for a gentle dip or
+    /// negative infinity for a complete notch. Returns `false` if the depth is positive or NaN.
+    pub fn set_depth(&mut self, depth: T) -> bool {
+        let in_range = depth <= T::zero();
+        let (Some(ten), Some(twenty)) = (T::from(10.0), T::from(20.0)) else {
+            return false;
+        };
+        if !in_range {
+            return false;
+        }
+        let mut config = self.config;
+        config.set_notch_gain(ten.powf(depth / twenty));
+        apply_configuration(self, config)
     }
+}
 
-    fn get_config_mut(&mut self) -> &mut FilterConfiguration<T> {
-        &mut self.config
-    }
+impl_biquad_filter!(NotchFilter);
+impl_precision_conversions!(NotchFilter);
+impl_biquad_filter_wrapper!(NotchFilter, Notch);
 
-    fn calculate_coefficients(config: &FilterConfiguration<T>) -> Option<Coefficients<T>> {
+impl<T: Float> NotchFilter<T> {
+    /// Designs the notch coefficients for a configuration. Returns `None` if the configuration
+    /// is invalid.
+    fn design_coefficients(config: &FilterConfiguration<T>) -> Option<Coefficients<T>> {
         let cutoff = config.get_cutoff();
         let sample_rate = config.get_sample_rate();
         let q = config.get_q_factor();
 
-        if cutoff <= T::zero() || sample_rate == 0 || q <= T::zero() {
+        if !config.is_valid() {
             return None;
         }
 
@@ -86,10 +109,13 @@ impl<T: Float + Default + Copy + std::ops::MulAssign> BiquadFilterWrapper<T> for
         let cos_w0 = w0.cos();
         let sin_w0 = w0.sin();
         let alpha = sin_w0 / (two * q);
+        // Blending the notch with a unity response, g + (1 - g) H, leaves the poles alone and
+        // moves the zeros off the unit circle, so the center gain becomes exactly g.
+        let notch_gain = config.get_notch_gain();
 
-        let b0 = one;
+        let b0 = one + notch_gain * alpha;
         let b1 = -two * cos_w0;
-        let b2 = one;
+        let b2 = one - notch_gain * alpha;
         let a0 = one + alpha;
         let a1 = -two * cos_w0;
         let a2 = one - alpha;
@@ -0,0 +1,223 @@
+/// parallel.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::chain::{ChainStage, FilterStage};
+use crate::filters::filter::Filter;
+use crate::filters::response::FrequencyResponse;
+use num_complex::Complex;
+use num_traits::Float;
+
+/// Number of samples each branch processes at a time, so blocks of any length can be split
+/// across branches with fixed buffers on the stack.
+const BRANCH_CHUNK: usize = 64;
+
+/// One branch of a `ParallelBank`.
+struct Branch<T: Float> {
+    filter: ChainStage<T>,
+    gain: T,
+}
+
+/// Filters in parallel: every branch filters the same input, and the outputs are scaled by a
+/// linear gain per branch and summed, together with the input scaled by a direct gain. This suits
+/// parallel EQs, formant banks, and parallel realizations of a high-order filter, where the direct
+/// gain holds the constant term of a partial fraction expansion. Gains may be negative.
+///
+/// Every branch runs at the bank's sample rate: branches are retuned to it when they are added,
+/// and changing the bank's sample rate changes every branch.
+pub struct ParallelBank<T: Float> {
+    branches: Vec<Branch<T>>,
+    direct_gain: T,
+    sample_rate: u32,
+}
+
+impl<T: Float> ParallelBank<T> {
+    /// Creates an empty bank with a direct gain of zero, which outputs silence until branches are
+    /// added. Returns `None` if the sample rate is zero.
+    pub fn new(sample_rate: u32) -> Option<Self> {
+        (sample_rate != 0).then(|| Self {
+            branches: Vec::new(),
+            direct_gain: T::zero(),
+            sample_rate,
+        })
+    }
+
+    /// Returns the sample rate.
+    pub fn get_sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Sets the sample rate of the bank and every branch. Returns `false` and restores the
+    /// previous sample rate of every branch if it is zero or any branch rejects it.
+    pub fn set_sample_rate(&mut self, sample_rate: u32) -> bool {
+        if sample_rate == 0 {
+            return false;
+        }
+        for index in 0..self.branches.len() {
+            if !self.branches[index].filter.set_sample_rate(sample_rate) {
+                for branch in &mut self.branches[..index] {
+                    branch.filter.set_sample_rate(self.sample_rate);
+                }
+                return false;
+            }
+        }
+        self.sample_rate = sample_rate;
+        true
+    }
+
+    /// Returns the number of branches.
+    pub fn len(&self) -> usize {
+        self.branches.len()
+    }
+
+    /// Returns whether the bank has no branches.
+    pub fn is_empty(&self) -> bool {
+        self.branches.is_empty()
+    }
+
+    /// Adds a branch with a linear output gain and returns its index. Returns `None` and drops the
+    /// filter if the gain is not finite or the filter cannot run at the bank's sample rate.
+    pub fn add_branch(&mut self, mut filter: ChainStage<T>, gain: T) -> Option<usize> {
+        if !gain.is_finite() || !filter.set_sample_rate(self.sample_rate) {
+            return None;
+        }
+        self.branches.push(Branch { filter, gain });
+        Some(self.branches.len() - 1)
+    }
+
+    /// Removes the branch at `index` and returns its filter and gain, if it exists. Later
+    /// branches move down one index.
+    pub fn remove_branch(&mut self, index: usize) -> Option<(ChainStage<T>, T)> {
+        (index < self.branches.len()).then(|| {
+            let branch = self.branches.remove(index);
+            (branch.filter, branch.gain)
+        })
+    }
+
+    /// Removes every branch.
+    pub fn clear(&mut self) {
+        self.branches.clear();
+    }
+
+    /// Returns the filter of the branch at `index`, if it exists.
+    pub fn get_branch(&self, index: usize) -> Option<&(dyn FilterStage<T> + Send)> {
+        self.branches.get(index).map(|branch| branch.filter.as_ref())
+    }
+
+    /// Returns the filter of the branch at `index` for retuning, if it exists. Changing its sample
+    /// rate leaves it out of step with the rest of the bank.
+    pub fn get_branch_mut(&mut self, index: usize) -> Option<&mut (dyn FilterStage<T> + Send)> {
+        match self.branches.get_mut(index) {
+            Some(branch) => Some(branch.filter.as_mut()),
+            None => None,
+        }
+    }
+
+    /// Returns the linear output gain of the branch at `index`, if it exists.
+    pub fn get_gain(&self, index: usize) -> Option<T> {
+        self.branches.get(index).map(|branch| branch.gain)
+    }
+
+    /// Sets the linear output gain of the branch at `index`. Returns `false` if the branch does
+    /// not exist or the gain is not finite.
+    pub fn set_gain(&mut self, index: usize, gain: T) -> bool {
+        match self.branches.get_mut(index) {
+            Some(branch) if gain.is_finite() => {
+                branch.gain = gain;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the linear gain of the unfiltered input added to the output.
+    pub fn get_direct_gain(&self) -> T {
+        self.direct_gain
+    }
+
+    /// Sets the linear gain of the unfiltered input added to the output. Returns `false` if the
+    /// gain is not finite.
+    pub fn set_direct_gain(&mut self, direct_gain: T) -> bool {
+        if !direct_gain.is_finite() {
+            return false;
+        }
+        self.direct_gain = direct_gain;
+        true
+    }
+
+    /// Processes a single sample. Returns `false` if any branch failed.
+    pub fn process(&mut self, sample: &mut T) -> bool {
+        let input = *sample;
+        let mut output = self.direct_gain * input;
+        let mut processed = true;
+        for branch in &mut self.branches {
+            let mut branch_sample = input;
+            processed &= branch.filter.process(&mut branch_sample);
+            output = output + branch.gain * branch_sample;
+        }
+        *sample = output;
+        processed
+    }
+
+    /// Processes a block of samples. Each branch filters the block in chunks, so no memory is
+    /// allocated. Returns `false` if the block is empty or any branch failed.
+    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
+        if samples.is_empty() {
+            return false;
+        }
+        let mut processed = true;
+        for chunk in samples.chunks_mut(BRANCH_CHUNK) {
+            let mut input = [T::zero(); BRANCH_CHUNK];
+            let input = &mut input[..chunk.len()];
+            input.copy_from_slice(chunk);
+            for (output, sample) in chunk.iter_mut().zip(input.iter()) {
+                *output = self.direct_gain * *sample;
+            }
+            for branch in &mut self.branches {
+                let mut branch_samples = [T::zero(); BRANCH_CHUNK];
+                let branch_samples = &mut branch_samples[..chunk.len()];
+                branch_samples.copy_from_slice(input);
+                processed &= branch.filter.process_block(branch_samples);
+                for (output, sample) in chunk.iter_mut().zip(branch_samples.iter()) {
+                    *output = *output + branch.gain * *sample;
+                }
+            }
+        }
+        processed
+    }
+
+    /// Resets the state of every branch.
+    pub fn reset(&mut self) {
+        for branch in &mut self.branches {
+            branch.filter.reset();
+        }
+    }
+}
+
+impl<T: Float> FrequencyResponse<T> for ParallelBank<T> {
+    /// Returns the sum of the branch responses scaled by their gains, plus the direct gain.
+    fn response_at(&self, frequency: T, sample_rate: u32) -> Complex<T> {
+        self.branches
+            .iter()
+            .map(|branch| branch.filter.response_at(frequency, sample_rate) * branch.gain)
+            .fold(Complex::from(self.direct_gain), |sum, response| sum + response)
+    }
+}
@@ -0,0 +1,242 @@
+/// parametric_eq.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::DigitalBiquadFilter;
+use crate::filters::filter::FilterType;
+use crate::filters::filter_configuration::FilterConfiguration;
+use crate::filters::response::FrequencyResponse;
+use num_complex::Complex;
+use num_traits::Float;
+
+/// One band of a `ParametricEq`.
+#[derive(Debug, Clone)]
+struct Band<T: Float> {
+    filter_type: FilterType,
+    config: FilterConfiguration<T>,
+    filter: DigitalBiquadFilter<T>,
+}
+
+impl<T: Float> Band<T> {
+    /// Designs a band. Returns `None` if the configuration is invalid for the filter type.
+    fn new(filter_type: FilterType, config: FilterConfiguration<T>) -> Option<Self> {
+        let filter = DigitalBiquadFilter::new(filter_type.design_coefficients(&config)?)?;
+        Some(Self {
+            filter_type,
+            config,
+            filter,
+        })
+    }
+
+    /// Redesigns the band, keeping its state so it can change while audio is running. Returns
+    /// `false` and keeps the current settings if the configuration is invalid.
+    fn update(&mut self, filter_type: FilterType, config: FilterConfiguration<T>) -> bool {
+        match filter_type.design_coefficients(&config) {
+            Some(coefficients) if self.filter.update_coefficients(coefficients) => {
+                self.filter_type = filter_type;
+                self.config = config;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A parametric equalizer: an ordered list of bands, each a filter type with its own
+/// configuration, run in series by a single call. Bands can be added, removed and changed while
+/// audio is running; changing a band keeps its state, so there is no click from the filter
+/// restarting.
+///
+/// Every band runs at the equalizer's sample rate, which replaces the sample rate in the
+/// configurations given to it. Bypassed bands pass the signal through unchanged.
+#[derive(Debug, Clone)]
+pub struct ParametricEq<T: Float> {
+    sample_rate: u32,
+    bands: Vec<Band<T>>,
+}
+
+impl<T: Float> ParametricEq<T> {
+    /// Creates an equalizer with no bands.
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            bands: Vec::new(),
+        }
+    }
+
+    /// Returns the sample rate.
+    pub fn get_sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Sets the sample rate and redesigns every band. Returns `false` and keeps every band
+    /// unchanged if any of them cannot be designed at the new rate.
+    pub fn set_sample_rate(&mut self, sample_rate: u32) -> bool {
+        let bands = self.bands.iter().map(|band| {
+            let mut config = band.config;
+            config.set_sample_rate(sample_rate);
+            Band::new(band.filter_type, config)
+        });
+        match bands.collect::<Option<Vec<_>>>() {
+            Some(bands) => {
+                self.sample_rate = sample_rate;
+                self.bands = bands;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Adds a band after the existing ones and returns its index. Returns `None` if the
+    /// configuration is invalid for the filter type.
+    pub fn add_band(
+        &mut self,
+        filter_type: FilterType,
+        config: FilterConfiguration<T>,
+    ) -> Option<usize> {
+        let band = Band::new(filter_type, self.at_sample_rate(config))?;
+        self.bands.push(band);
+        Some(self.bands.len() - 1)
+    }
+
+    /// Inserts a band at `index`, moving later bands along. Returns `false` if the index is past
+    /// the end or the configuration is invalid for the filter type.
+    pub fn insert_band(
+        &mut self,
+        index: usize,
+        filter_type: FilterType,
+        config: FilterConfiguration<T>,
+    ) -> bool {
+        if index > self.bands.len() {
+            return false;
+        }
+        match Band::new(filter_type, self.at_sample_rate(config)) {
+            Some(band) => {
+                self.bands.insert(index, band);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the band at `index` and returns its filter type and configuration, if it exists.
+    pub fn remove_band(&mut self, index: usize) -> Option<(FilterType, FilterConfiguration<T>)> {
+        if index >= self.bands.len() {
+            return None;
+        }
+        let band = self.bands.remove(index);
+        Some((band.filter_type, band.config))
+    }
+
+    /// Removes every band.
+    pub fn clear(&mut self) {
+        self.bands.clear();
+    }
+
+    /// Returns the filter type and configuration of the band at `index`, if it exists.
+    pub fn get_band(&self, index: usize) -> Option<(FilterType, FilterConfiguration<T>)> {
+        self.bands.get(index).map(|band| (band.filter_type, band.config))
+    }
+
+    /// Changes the filter type and configuration of the band at `index`, keeping its state.
+    /// Returns `false` and keeps the current settings if the band does not exist or the
+    /// configuration is invalid for the filter type.
+    pub fn set_band(
+        &mut self,
+        index: usize,
+        filter_type: FilterType,
+        config: FilterConfiguration<T>,
+    ) -> bool {
+        let config = self.at_sample_rate(config);
+        self.bands.get_mut(index).is_some_and(|band| band.update(filter_type, config))
+    }
+
+    /// Applies any number of changes to a copy of the band's configuration and then redesigns it
+    /// once. Returns `false` and keeps the current settings if the band does not exist or the
+    /// result is invalid.
+    pub fn update_band(
+        &mut self,
+        index: usize,
+        change: impl FnOnce(&mut FilterConfiguration<T>),
+    ) -> bool {
+        let Some((filter_type, mut config)) = self.get_band(index) else {
+            return false;
+        };
+        change(&mut config);
+        self.set_band(index, filter_type, config)
+    }
+
+    /// Returns the biquad of the band at `index`, if it exists.
+    pub fn get_band_filter(&self, index: usize) -> Option<&DigitalBiquadFilter<T>> {
+        self.bands.get(index).map(|band| &band.filter)
+    }
+
+    /// Returns the number of bands.
+    pub fn len(&self) -> usize {
+        self.bands.len()
+    }
+
+    /// Returns whether the equalizer has no bands.
+    pub fn is_empty(&self) -> bool {
+        self.bands.is_empty()
+    }
+
+    /// Processes a single sample through every band in order.
+    pub fn process(&mut self, sample: &mut T) -> bool {
+        for band in self.bands.iter_mut().filter(|band| !band.config.get_bypass()) {
+            band.filter.process(sample);
+        }
+        true
+    }
+
+    /// Processes a block of samples through every band in order. Returns `false` if the block is
+    /// empty.
+    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
+        if samples.is_empty() {
+            return false;
+        }
+        for band in self.bands.iter_mut().filter(|band| !band.config.get_bypass()) {
+            band.filter.process_block(samples);
+        }
+        true
+    }
+
+    /// Resets the state of every band.
+    pub fn reset(&mut self) {
+        self.bands.iter_mut().for_each(|band| band.filter.reset());
+    }
+
+    /// Returns the configuration with the equalizer's sample rate.
+    fn at_sample_rate(&self, mut config: FilterConfiguration<T>) -> FilterConfiguration<T> {
+        config.set_sample_rate(self.sample_rate);
+        config
+    }
+}
+
+impl<T: Float> FrequencyResponse<T> for ParametricEq<T> {
+    fn response_at(&self, frequency: T, sample_rate: u32) -> Complex<T> {
+        self.bands
+            .iter()
+            .filter(|band| !band.config.get_bypass())
+            .map(|band| band.filter.response_at(frequency, sample_rate))
+            .fold(Complex::from(T::one()), |product, response| product * response)
+    }
+}
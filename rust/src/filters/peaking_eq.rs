@@ -1,5 +1,4 @@
 /// peaking_eq.rs
-
 /**
 Copyright © 2025 Alex Parisi
 
@@ -22,22 +21,32 @@ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
 use crate::filters::biquad::{Coefficients, DigitalBiquadFilter};
-use crate::filters::filter::BiquadFilterWrapper;
+use crate::filters::filter::{
+    BiquadFilterWrapper, apply_configuration, impl_biquad_filter, impl_biquad_filter_wrapper,
+    impl_precision_conversions,
+};
+use crate::filters::design_method::{DesignMethod, matched_peaking, orfanidis_peaking};
 use crate::filters::filter_configuration::FilterConfiguration;
 use num_traits::Float;
-use std::f64::consts::PI;
+use core::f64::consts::PI;
 
 
 /// Peaking EQ filter implementation using a digital biquad filter.
+///
+/// The response is symmetric: with every design method, a cut is the exact inverse of the boost
+/// with the same cutoff, Q factor and gain magnitude, so a correction band undoes a boost without
+/// leaving any residue. Boosts and cuts can instead use different Q factors, set with
+/// `set_cut_q_factor`, so one band can give a narrow cut and a wide boost as its gain is swept
+/// through zero.
 #[derive(Debug, Clone)]
-pub struct PeakingEQFilter<T: Float + Default + Copy> {
+pub struct PeakingEQFilter<T: Float> {
     /// The digital biquad filter used for processing.
     filter: DigitalBiquadFilter<T>,
     /// The configuration for the filter, including cutoff frequency, sample rate, and Q factor.
     config: FilterConfiguration<T>,
 }
 
-impl<T: Float + Default + Copy + std::ops::MulAssign> PeakingEQFilter<T> {
+impl<T: Float> PeakingEQFilter<T> {
     /// Creates a new peaking EQ filter with the given cutoff frequency, sample rate, and Q factor.
     pub fn new(cutoff: T, sample_rate: u32, q_factor: T, gain: T) -> Option<Self> {
         let config =
@@ -53,29 +62,41 @@ impl<T: Float + Default + Copy + std::ops::MulAssign> PeakingEQFilter<T> {
         let filter = DigitalBiquadFilter::new(coefficients)?;
         Some(Self { filter, config })
     }
-}
-
-/// Provide internal access and coefficient logic via BiquadFilterWrapper.
-impl<T: Float + Default + Copy + std::ops::MulAssign> BiquadFilterWrapper<T> for PeakingEQFilter<T> {
-    fn get_filter(&mut self) -> &mut DigitalBiquadFilter<T> {
-        &mut self.filter
-    }
 
-    fn get_config(&self) -> &FilterConfiguration<T> {
-        &self.config
+    /// Returns the Q factor used when the gain is negative, or `None` if cuts use the same Q
+    /// factor as boosts.
+    pub fn get_cut_q_factor(&self) -> Option<T> {
+        self.config.get_cut_q_factor()
     }
 
-    fn get_config_mut(&mut self) -> &mut FilterConfiguration<T> {
-        &mut self.config
+    /// Sets the Q factor used when the gain is negative, or `None` to use the Q factor for both
+    /// boosts and cuts. Returns `false` if the Q factor is not positive and finite.
+    pub fn set_cut_q_factor(&mut self, cut_q_factor: Option<T>) -> bool {
+        let mut config = self.config;
+        config.set_cut_q_factor(cut_q_factor);
+        apply_configuration(self, config)
     }
+}
 
-    fn calculate_coefficients(config: &FilterConfiguration<T>) -> Option<Coefficients<T>> {
+impl_biquad_filter!(PeakingEQFilter);
+impl_precision_conversions!(PeakingEQFilter);
+impl_biquad_filter_wrapper!(PeakingEQFilter, PeakingEQ);
+
+impl<T: Float> PeakingEQFilter<T> {
+    /// Designs the peaking EQ coefficients for a configuration. Returns `None` if the configuration
+    /// is invalid.
+    fn design_coefficients(config: &FilterConfiguration<T>) -> Option<Coefficients<T>> {
+        match config.get_design_method() {
+            DesignMethod::Bilinear => {}
+            DesignMethod::Matched => return matched_peaking(config),
+            DesignMethod::Orfanidis => return orfanidis_peaking(config),
+        }
         let cutoff = config.get_cutoff();
         let sample_rate = config.get_sample_rate();
-        let q = config.get_q_factor();
+        let q = config.get_effective_q_factor();
         let gain = config.get_gain();
 
-        if cutoff <= T::zero() || sample_rate == 0 || q <= T::zero() {
+        if !config.is_valid() {
             return None;
         }
 
@@ -0,0 +1,137 @@
+/// pinking.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::{Coefficients, DigitalBiquadFilter};
+use crate::filters::cascade::Cascade;
+use crate::filters::response::FrequencyResponse;
+use num_complex::Complex;
+use num_traits::Float;
+use core::f64::consts::PI;
+
+/// The frequency in Hz of the lowest pole, below which the response levels off.
+const LOWEST_POLE: f64 = 5.0;
+/// The highest zero as a fraction of the sample rate, above which the response levels off.
+const HIGHEST_ZERO_RATIO: f64 = 0.3;
+/// The upper limit in Hz for the highest zero at high sample rates.
+const HIGHEST_ZERO_LIMIT: f64 = 40000.0;
+/// The frequency at which the filter has unity gain.
+const REFERENCE_FREQUENCY: f64 = 1000.0;
+
+/// A filter with a slope of -3 dB per octave, which turns white noise into pink noise and can
+/// compensate for a spectral tilt of the same slope.
+///
+/// No rational filter has a slope of exactly -3 dB per octave, so the slope is approximated by
+/// six real poles, each followed half a step later by a real zero, spaced evenly on a log scale
+/// from 5 Hz up to 30% of the sample rate. The pairs are mapped to three biquads with the
+/// bilinear transform. From 20 Hz to 20 kHz (or 45% of the sample rate, if lower) the response is
+/// within 0.4 dB of the ideal slope, with unity gain at 1 kHz.
+#[derive(Debug, Clone)]
+pub struct PinkingFilter<T: Float> {
+    sample_rate: u32,
+    cascade: Cascade<T, 3>,
+}
+
+impl<T: Float> PinkingFilter<T> {
+    /// Creates the pinking filter for a sample rate. Returns `None` if the reference frequency of
+    /// 1 kHz is not below the Nyquist frequency.
+    pub fn new(sample_rate: u32) -> Option<Self> {
+        let rate = f64::from(sample_rate);
+        if rate <= 2.0 * REFERENCE_FREQUENCY {
+            return None;
+        }
+        let highest_zero = (HIGHEST_ZERO_RATIO * rate).min(HIGHEST_ZERO_LIMIT);
+        let step = Float::powf(highest_zero / LOWEST_POLE, 1.0 / 5.5);
+        let pair = |index: i32| {
+            let pole = LOWEST_POLE * Float::powi(step, index);
+            first_order_pair(pole, pole * Float::sqrt(step), sample_rate)
+        };
+        let mut sections = [0, 1, 2].map(|section| {
+            let (first, second) = (pair(2 * section), pair(2 * section + 1));
+            Coefficients {
+                b0: first.b0 * second.b0,
+                b1: first.b0 * second.b1 + first.b1 * second.b0,
+                b2: first.b1 * second.b1,
+                a0: first.a0 * second.a0,
+                a1: first.a0 * second.a1 + first.a1 * second.a0,
+                a2: first.a1 * second.a1,
+            }
+        });
+        let gain = 1.0
+            / sections
+                .iter()
+                .map(|section| section.magnitude_at(REFERENCE_FREQUENCY, sample_rate))
+                .product::<f64>();
+        sections[0].b0 *= gain;
+        sections[0].b1 *= gain;
+        sections[0].b2 *= gain;
+        Some(Self {
+            sample_rate,
+            cascade: Cascade::new(sections.map(|section| section.cast()))?,
+        })
+    }
+
+    /// Processes a single sample.
+    pub fn process(&mut self, sample: &mut T) -> bool {
+        self.cascade.process(sample)
+    }
+
+    /// Processes a block of samples. Returns `false` if the block is empty.
+    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
+        self.cascade.process_block(samples)
+    }
+
+    /// Returns the sample rate the filter was designed for.
+    pub fn get_sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Returns the biquad sections, from the lowest poles to the highest.
+    pub fn get_sections(&self) -> &[DigitalBiquadFilter<T>; 3] {
+        self.cascade.get_sections()
+    }
+
+    /// Resets the state of every section.
+    pub fn reset(&mut self) {
+        self.cascade.reset();
+    }
+}
+
+impl<T: Float> FrequencyResponse<T> for PinkingFilter<T> {
+    fn response_at(&self, frequency: T, sample_rate: u32) -> Complex<T> {
+        self.cascade.response_at(frequency, sample_rate)
+    }
+}
+
+/// Returns the bilinear transform of `(1 + s / wz) / (1 + s / wp)` for a real pole and zero at
+/// the given frequencies in Hz, as a first-order section with `b2` and `a2` set to zero.
+fn first_order_pair(pole: f64, zero: f64, sample_rate: u32) -> Coefficients<f64> {
+    let c = 2.0 * f64::from(sample_rate);
+    let (wp, wz) = (2.0 * PI * pole, 2.0 * PI * zero);
+    Coefficients {
+        b0: wp * (wz + c),
+        b1: wp * (wz - c),
+        b2: 0.0,
+        a0: wz * (wp + c),
+        a1: wz * (wp - c),
+        a2: 0.0,
+    }
+}
@@ -0,0 +1,271 @@
+/// preset.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::chain::FilterChain;
+use crate::filters::parametric_eq::ParametricEq;
+use crate::filters::spec::{FilterSpec, SpecError};
+use core::fmt;
+use core::str::FromStr;
+use num_traits::Float;
+use std::io::{self, Read, Write};
+
+/// The reason a preset could not be loaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PresetError {
+    /// The first setting is not the format version.
+    MissingVersion,
+    /// The preset was saved by a newer format version than this crate reads.
+    UnsupportedVersion(u32),
+    /// A line, numbered from one, is not a known `key = value` setting.
+    InvalidLine(usize),
+    /// The band on a line, numbered from one, is not a valid filter spec.
+    InvalidBand { line: usize, error: SpecError },
+    /// The preset could not be read or written.
+    Io(io::ErrorKind),
+}
+
+impl fmt::Display for PresetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PresetError::MissingVersion => f.write_str("preset does not start with a version"),
+            PresetError::UnsupportedVersion(version) => {
+                write!(f, "preset version {version} is newer than this crate supports")
+            }
+            PresetError::InvalidLine(line) => write!(f, "invalid preset setting on line {line}"),
+            PresetError::InvalidBand { line, error } => {
+                write!(f, "invalid band on line {line}: {error}")
+            }
+            PresetError::Io(kind) => write!(f, "could not access preset: {kind}"),
+        }
+    }
+}
+
+impl core::error::Error for PresetError {}
+
+impl From<io::Error> for PresetError {
+    fn from(error: io::Error) -> Self {
+        PresetError::Io(error.kind())
+    }
+}
+
+/// The settings of a whole EQ or filter chain, with a stable text format for saving them.
+///
+/// Each band is a `FilterSpec`, holding its filter type, cutoff, Q factor, gain, constant skirt
+/// gain and bypass state, so a preset applies at any sample rate. The saved form starts with the
+/// format version and lists one setting per line; blank lines and lines starting with `#` are
+/// ignored:
+///
+/// ```text
+/// version = 1
+/// name = Vocal
+/// band = hp:80:q=0.7071067811865476
+/// band = peak:2500:q=2:+3dB
+/// ```
+///
+/// Presets saved by older versions of the format keep loading, and presets from newer versions
+/// are rejected with `PresetError::UnsupportedVersion` rather than being misread.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Preset<T: Float> {
+    name: String,
+    bands: Vec<FilterSpec<T>>,
+}
+
+impl<T: Float> Preset<T> {
+    /// The format version written by `save`.
+    pub const VERSION: u32 = 1;
+
+    /// Creates an empty preset. Returns `None` if the name spans several lines.
+    pub fn new(name: &str) -> Option<Self> {
+        let mut preset = Self {
+            name: String::new(),
+            bands: Vec::new(),
+        };
+        preset.set_name(name).then_some(preset)
+    }
+
+    /// Captures every band of a parametric EQ, in processing order.
+    pub fn from_parametric_eq(name: &str, eq: &ParametricEq<T>) -> Option<Self> {
+        let mut preset = Self::new(name)?;
+        preset.bands = (0..eq.len())
+            .filter_map(|index| eq.get_band(index))
+            .map(|(filter_type, config)| FilterSpec::from_configuration(filter_type, &config))
+            .collect();
+        Some(preset)
+    }
+
+    /// Returns the name of the preset.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets the name of the preset. Returns `false` and keeps the current name if the new one
+    /// spans several lines.
+    pub fn set_name(&mut self, name: &str) -> bool {
+        if name.contains(['\n', '\r']) {
+            return false;
+        }
+        self.name = name.trim().to_string();
+        true
+    }
+
+    /// Returns the bands, in processing order.
+    pub fn get_bands(&self) -> &[FilterSpec<T>] {
+        &self.bands
+    }
+
+    /// Adds a band after the existing ones.
+    pub fn add_band(&mut self, band: FilterSpec<T>) {
+        self.bands.push(band);
+    }
+
+    /// Removes and returns the band at `index`, if it exists.
+    pub fn remove_band(&mut self, index: usize) -> Option<FilterSpec<T>> {
+        (index < self.bands.len()).then(|| self.bands.remove(index))
+    }
+
+    /// Blends two presets band by band, from `a` at `t = 0` to `b` at `t = 1`, for morphing
+    /// between scenes. Each band follows `FilterSpec::lerp`, moving its cutoff and Q factor in
+    /// octaves and its gain in decibels, and the name switches from `a` to `b` halfway. Returns
+    /// `None` if the presets have different numbers of bands.
+    pub fn lerp(a: &Self, b: &Self, t: T) -> Option<Self> {
+        if a.bands.len() != b.bands.len() {
+            return None;
+        }
+        let nearest = if t < T::from(0.5)? { a } else { b };
+        Some(Self {
+            name: nearest.name.clone(),
+            bands: a.bands.iter().zip(&b.bands).map(|(a, b)| FilterSpec::lerp(a, b, t)).collect(),
+        })
+    }
+
+    /// Applies the preset to a parametric EQ with the same number of bands, keeping the state of
+    /// every band so the EQ can move between presets while audio is running. Returns `false` and
+    /// leaves every band unchanged if the band counts differ or any band is invalid.
+    pub fn apply_to(&self, eq: &mut ParametricEq<T>) -> bool {
+        if self.bands.len() != eq.len() {
+            return false;
+        }
+        let sample_rate = eq.get_sample_rate();
+        let mut bands = Vec::with_capacity(self.bands.len());
+        for band in &self.bands {
+            let filter_type = band.get_filter_type();
+            match band.to_configuration(sample_rate) {
+                Ok(config) if filter_type.design_coefficients(&config).is_some() => {
+                    bands.push((filter_type, config));
+                }
+                _ => return false,
+            }
+        }
+        for (index, (filter_type, config)) in bands.into_iter().enumerate() {
+            eq.set_band(index, filter_type, config);
+        }
+        true
+    }
+
+    /// Builds a parametric EQ with the preset's bands at the given sample rate. Returns the first
+    /// problem found with a band.
+    pub fn to_parametric_eq(&self, sample_rate: u32) -> Result<ParametricEq<T>, SpecError> {
+        let mut eq = ParametricEq::new(sample_rate);
+        for band in &self.bands {
+            let config = band.to_configuration(sample_rate)?;
+            eq.add_band(band.get_filter_type(), config).ok_or(SpecError::InvalidDesign)?;
+        }
+        Ok(eq)
+    }
+
+    /// Writes the preset in the current format version.
+    pub fn save(&self, mut writer: impl Write) -> io::Result<()> {
+        write!(writer, "{self}")
+    }
+
+    /// Reads a preset written by `save`.
+    pub fn load(mut reader: impl Read) -> Result<Self, PresetError> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        text.parse()
+    }
+}
+
+impl<T: Float + Send + 'static> Preset<T> {
+    /// Builds a filter chain with one stage per band at the given sample rate. Returns the first
+    /// problem found with a band.
+    pub fn to_chain(&self, sample_rate: u32) -> Result<FilterChain<T>, SpecError> {
+        FilterChain::from_specs(&self.bands, sample_rate)
+    }
+}
+
+/// Writes the preset in the current format version.
+impl<T: Float> fmt::Display for Preset<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "version = {}", Self::VERSION)?;
+        if !self.name.is_empty() {
+            writeln!(f, "name = {}", self.name)?;
+        }
+        for band in &self.bands {
+            writeln!(f, "band = {band}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Float> FromStr for Preset<T> {
+    type Err = PresetError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut settings = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+        let version = settings
+            .next()
+            .and_then(|(_, line)| setting(line))
+            .filter(|(key, _)| *key == "version")
+            .and_then(|(_, value)| value.parse::<u32>().ok())
+            .ok_or(PresetError::MissingVersion)?;
+        if version == 0 || version > Self::VERSION {
+            return Err(PresetError::UnsupportedVersion(version));
+        }
+        let mut preset = Self {
+            name: String::new(),
+            bands: Vec::new(),
+        };
+        for (line, text) in settings {
+            match setting(text) {
+                Some(("name", name)) => preset.name = name.to_string(),
+                Some(("band", band)) => {
+                    let band = band
+                        .parse()
+                        .map_err(|error| PresetError::InvalidBand { line, error })?;
+                    preset.bands.push(band);
+                }
+                _ => return Err(PresetError::InvalidLine(line)),
+            }
+        }
+        Ok(preset)
+    }
+}
+
+/// Splits a `key = value` line, trimming both parts.
+fn setting(line: &str) -> Option<(&str, &str)> {
+    line.split_once('=').map(|(key, value)| (key.trim(), value.trim()))
+}
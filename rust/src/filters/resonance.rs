@@ -0,0 +1,82 @@
+/// resonance.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use num_traits::Float;
+
+/// Converts a resonance between zero and one, as shown on synthesizer-style controls, to a Q
+/// factor using `Q = 0.5 / (1 - r)`. Zero gives `Q = 0.5`, the widest response without a peak,
+/// a resonance of one half gives `Q = 1`, and the Q rises without bound as the resonance
+/// approaches one, so the filter rings for longer and longer. Returns `None` if the resonance is
+/// not in `[0, 1)`.
+pub fn resonance_to_q<T: Float>(resonance: T) -> Option<T> {
+    let in_range = resonance >= T::zero() && resonance < T::one();
+    if !in_range {
+        return None;
+    }
+    Some(T::from(0.5)? / (T::one() - resonance))
+}
+
+/// Converts a Q factor back to a resonance, the inverse of `resonance_to_q`. Q factors below
+/// one half map to zero. Returns `None` if the Q factor is not positive and finite.
+pub fn q_to_resonance<T: Float>(q_factor: T) -> Option<T> {
+    let in_range = q_factor > T::zero() && q_factor.is_finite();
+    if !in_range {
+        return None;
+    }
+    Some((T::one() - T::from(0.5)? / q_factor).max(T::zero()))
+}
+
+/// Converts the cookbook shelf slope `S` to the Q factor of a shelf with the given gain in
+/// decibels, using `1/Q = sqrt((A + 1/A)(1/S - 1) + 2)` with `A = 10^(gain/40)`. A slope of one is
+/// the steepest shelf whose response still rises or falls monotonically, and smaller slopes give
+/// gentler transitions. Returns `None` if the slope is not positive and finite, or so steep for
+/// the gain that no Q factor gives it.
+pub fn shelf_slope_to_q<T: Float>(slope: T, gain: T) -> Option<T> {
+    let in_range = slope > T::zero() && slope.is_finite() && gain.is_finite();
+    if !in_range {
+        return None;
+    }
+    let one = T::one();
+    let a = T::from(10.0)?.powf(gain / T::from(40.0)?);
+    let inverse_squared = (a + one / a) * (one / slope - one) + one + one;
+    if inverse_squared <= T::zero() {
+        return None;
+    }
+    Some(one / inverse_squared.sqrt())
+}
+
+/// Converts the Q factor of a shelf with the given gain in decibels back to its shelf slope, the
+/// inverse of `shelf_slope_to_q`. Returns `None` if the Q factor is not positive and finite, or
+/// so high that no slope gives it.
+pub fn q_to_shelf_slope<T: Float>(q_factor: T, gain: T) -> Option<T> {
+    let in_range = q_factor > T::zero() && q_factor.is_finite() && gain.is_finite();
+    if !in_range {
+        return None;
+    }
+    let one = T::one();
+    let a = T::from(10.0)?.powf(gain / T::from(40.0)?);
+    let inverse_slope = one + (one / (q_factor * q_factor) - one - one) / (a + one / a);
+    if inverse_slope <= T::zero() {
+        return None;
+    }
+    Some(one / inverse_slope)
+}
@@ -0,0 +1,194 @@
+/// response.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::{Coefficients, DigitalBiquadFilter};
+use crate::filters::cascade::Cascade;
+#[cfg(feature = "heapless")]
+use crate::filters::cascade::BoundedCascade;
+use num_complex::Complex;
+use num_traits::Float;
+use core::f64::consts::PI;
+
+/// Number of points in the logarithmic grid searched by `max_gain`.
+const MAX_GAIN_GRID_POINTS: usize = 4096;
+/// Lowest grid frequency searched by `max_gain`, as a fraction of the Nyquist frequency.
+const MAX_GAIN_GRID_FLOOR: f64 = 1e-5;
+/// Number of golden-section steps used to refine the peak found on the grid.
+const MAX_GAIN_REFINE_ITERATIONS: usize = 48;
+const GOLDEN_RATIO: f64 = 1.618_033_988_749_895;
+
+/// The frequency response of a linear filter, for plotting and analysis code that should work
+/// with any filter or container. The trait is object safe, so responses can be evaluated through
+/// `&dyn FrequencyResponse<T>`.
+///
+/// Frequencies are in Hz at the given sample rate. Saturation and output limits are ignored, so
+/// this is the response to signals small enough to stay linear.
+pub trait FrequencyResponse<T: Float> {
+    /// Returns the complex response at the given frequency.
+    fn response_at(&self, frequency: T, sample_rate: u32) -> Complex<T>;
+
+    /// Returns the magnitude of the response at the given frequency as a linear gain.
+    fn magnitude_at(&self, frequency: T, sample_rate: u32) -> T {
+        self.response_at(frequency, sample_rate).norm()
+    }
+
+    /// Returns the phase of the response at the given frequency in radians, between -pi and pi.
+    fn phase_at(&self, frequency: T, sample_rate: u32) -> T {
+        self.response_at(frequency, sample_rate).arg()
+    }
+
+    /// Returns the largest magnitude of the response between DC and Nyquist, the worst-case gain
+    /// for any input. The response is searched on a dense logarithmic grid and the largest point
+    /// is refined, which resolves peaks with a Q up to several hundred.
+    fn max_gain(&self) -> T {
+        let magnitude = |position: f64| match T::from(position) {
+            // At a sample rate of 2 Hz the Nyquist frequency is 1 Hz, so positions are fractions of
+            // the Nyquist frequency.
+            Some(frequency) => self.magnitude_at(frequency, 2),
+            None => T::nan(),
+        };
+        let ratio = (1.0 / MAX_GAIN_GRID_FLOOR).powf(1.0 / (MAX_GAIN_GRID_POINTS - 1) as f64);
+        let mut peak = magnitude(0.0);
+        let mut peak_position = 0.0;
+        let mut position = MAX_GAIN_GRID_FLOOR;
+        for _ in 0..MAX_GAIN_GRID_POINTS {
+            let gain = magnitude(position.min(1.0));
+            if gain.is_nan() || gain > peak {
+                peak = gain;
+                peak_position = position.min(1.0);
+            }
+            position *= ratio;
+        }
+        if peak.is_nan() || peak_position == 0.0 {
+            return peak;
+        }
+        // Narrow the bracket around the largest grid point with a golden-section search.
+        let mut lower = peak_position / ratio;
+        let mut upper = (peak_position * ratio).min(1.0);
+        for _ in 0..MAX_GAIN_REFINE_ITERATIONS {
+            let first = upper - (upper - lower) / GOLDEN_RATIO;
+            let second = lower + (upper - lower) / GOLDEN_RATIO;
+            if magnitude(first) > magnitude(second) {
+                upper = second;
+            } else {
+                lower = first;
+            }
+        }
+        peak.max(magnitude(0.5 * (lower + upper)))
+    }
+
+    /// Returns the linear gain to apply before the filter so that no input within full scale can
+    /// clip its output, which fixed-point and integer output paths need. The gain is the inverse
+    /// of `max_gain`, or one if the filter never amplifies.
+    fn pre_attenuation(&self) -> T {
+        let max_gain = self.max_gain();
+        if max_gain > T::one() { max_gain.recip() } else { T::one() }
+    }
+
+    /// Returns the complex response at each of the given frequencies.
+    #[cfg(feature = "std")]
+    fn response_grid(&self, frequencies: &[T], sample_rate: u32) -> Vec<Complex<T>> {
+        frequencies
+            .iter()
+            .map(|&frequency| self.response_at(frequency, sample_rate))
+            .collect()
+    }
+
+    /// Returns the magnitude of the response at each of the given frequencies as a linear gain,
+    /// for drawing the response or checking it against a measurement.
+    #[cfg(feature = "std")]
+    fn magnitude_response(&self, frequencies: &[T], sample_rate: u32) -> Vec<T> {
+        frequencies
+            .iter()
+            .map(|&frequency| self.magnitude_at(frequency, sample_rate))
+            .collect()
+    }
+}
+
+/// Converts a frequency in Hz to radians per sample.
+pub(crate) fn angular_frequency<T: Float>(frequency: T, sample_rate: u32) -> T {
+    match (T::from(2.0 * PI), T::from(sample_rate)) {
+        (Some(two_pi), Some(rate)) => two_pi * frequency / rate,
+        _ => T::nan(),
+    }
+}
+
+/// Returns the product of the responses of a series of sections.
+pub(crate) fn series_response<T: Float>(
+    sections: &[DigitalBiquadFilter<T>],
+    frequency: T,
+    sample_rate: u32,
+) -> Complex<T> {
+    sections
+        .iter()
+        .map(|section| section.response_at(frequency, sample_rate))
+        .fold(Complex::from(T::one()), |product, response| product * response)
+}
+
+impl<T: Float> FrequencyResponse<T> for Coefficients<T> {
+    fn response_at(&self, frequency: T, sample_rate: u32) -> Complex<T> {
+        self.response(angular_frequency(frequency, sample_rate))
+    }
+
+    /// Returns the exact peak of a single section.
+    fn max_gain(&self) -> T {
+        Coefficients::max_gain(self)
+    }
+}
+
+impl<T: Float> FrequencyResponse<T> for DigitalBiquadFilter<T> {
+    fn response_at(&self, frequency: T, sample_rate: u32) -> Complex<T> {
+        self.get_coefficients().response_at(frequency, sample_rate)
+    }
+
+    /// Returns the exact peak of a single section.
+    fn max_gain(&self) -> T {
+        self.get_coefficients().max_gain()
+    }
+}
+
+impl<T: Float, const N: usize> FrequencyResponse<T> for Cascade<T, N> {
+    fn response_at(&self, frequency: T, sample_rate: u32) -> Complex<T> {
+        series_response(self.get_sections(), frequency, sample_rate).scale(self.get_output_gain())
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<T, const N: usize> FrequencyResponse<T> for BoundedCascade<T, N>
+where
+    T: Float,
+{
+    fn response_at(&self, frequency: T, sample_rate: u32) -> Complex<T> {
+        series_response(self.get_sections(), frequency, sample_rate)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Float, F: FrequencyResponse<T> + ?Sized> FrequencyResponse<T> for Box<F> {
+    fn response_at(&self, frequency: T, sample_rate: u32) -> Complex<T> {
+        (**self).response_at(frequency, sample_rate)
+    }
+
+    fn max_gain(&self) -> T {
+        (**self).max_gain()
+    }
+}
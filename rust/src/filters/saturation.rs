@@ -0,0 +1,74 @@
+/// saturation.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::cast;
+use num_traits::Float;
+
+/// A memoryless nonlinearity applied to a signal. Every curve has unity slope around zero, so
+/// small signals pass unchanged and `drive` sets the level at which the curve starts to bend.
+/// Because the curves hold no state they can run inside an oversampled loop unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Saturation<T: Float> {
+    /// No saturation; the signal passes through unchanged.
+    #[default]
+    Linear,
+    /// Hyperbolic tangent saturation, limiting the signal to `±1 / drive`.
+    Tanh { drive: T },
+    /// Cubic soft clipping, limiting the signal to `±2 / (3 * drive)`.
+    SoftClip { drive: T },
+}
+
+impl<T: Float> Saturation<T> {
+    /// Converts the drive to another float type.
+    pub(crate) fn cast<U: Float>(&self) -> Saturation<U> {
+        match *self {
+            Saturation::Linear => Saturation::Linear,
+            Saturation::Tanh { drive } => Saturation::Tanh { drive: cast(drive) },
+            Saturation::SoftClip { drive } => Saturation::SoftClip { drive: cast(drive) },
+        }
+    }
+
+    /// Returns whether the saturation can be used: the drive must be positive and finite.
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            Saturation::Linear => true,
+            Saturation::Tanh { drive } | Saturation::SoftClip { drive } => {
+                drive > T::zero() && drive.is_finite()
+            }
+        }
+    }
+
+    /// Applies the saturation to a single value.
+    #[inline]
+    pub fn apply(&self, value: T) -> T {
+        match *self {
+            Saturation::Linear => value,
+            Saturation::Tanh { drive } => (drive * value).tanh() / drive,
+            Saturation::SoftClip { drive } => {
+                let driven = (drive * value).max(-T::one()).min(T::one());
+                let three = T::one() + T::one() + T::one();
+                (driven - driven * driven * driven / three) / drive
+            }
+        }
+    }
+}
@@ -0,0 +1,112 @@
+/// snapshot.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::filter_configuration::FilterConfiguration;
+use num_traits::Float;
+
+/// A complete copy of a filter's settings that can be restored later, e.g. for A/B comparison.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigSnapshot<T: Float> {
+    configuration: FilterConfiguration<T>,
+}
+
+impl<T> ConfigSnapshot<T>
+where
+    T: Float,
+{
+    /// Creates a snapshot of the given configuration.
+    pub fn new(configuration: FilterConfiguration<T>) -> Self {
+        Self { configuration }
+    }
+
+    /// Returns the captured configuration.
+    pub fn get_configuration(&self) -> FilterConfiguration<T> {
+        self.configuration
+    }
+
+    /// Reports which settings differ between this snapshot and another.
+    pub fn diff(&self, other: &ConfigSnapshot<T>) -> ConfigDiff {
+        ConfigDiff::between(&self.configuration, &other.configuration)
+    }
+}
+
+/// The settings that differ between two configurations.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigDiff {
+    pub cutoff: bool,
+    pub sample_rate: bool,
+    pub q_factor: bool,
+    pub gain: bool,
+    pub constant_skirt_gain: bool,
+    pub bypass: bool,
+    pub notch_gain: bool,
+    pub cut_q_factor: bool,
+    pub shelf_slope: bool,
+    pub design_method: bool,
+}
+
+impl ConfigDiff {
+    /// Compares two configurations setting by setting.
+    pub fn between<T>(a: &FilterConfiguration<T>, b: &FilterConfiguration<T>) -> Self
+    where
+        T: Float,
+    {
+        Self {
+            cutoff: a.get_cutoff() != b.get_cutoff(),
+            sample_rate: a.get_sample_rate() != b.get_sample_rate(),
+            q_factor: a.get_q_factor() != b.get_q_factor(),
+            gain: a.get_gain() != b.get_gain(),
+            constant_skirt_gain: a.get_constant_skirt_gain() != b.get_constant_skirt_gain(),
+            bypass: a.get_bypass() != b.get_bypass(),
+            notch_gain: a.get_notch_gain() != b.get_notch_gain(),
+            cut_q_factor: a.get_cut_q_factor() != b.get_cut_q_factor(),
+            shelf_slope: a.get_shelf_slope() != b.get_shelf_slope(),
+            design_method: a.get_design_method() != b.get_design_method(),
+        }
+    }
+
+    /// Returns whether no setting differs.
+    pub fn is_empty(&self) -> bool {
+        self.count() == 0
+    }
+
+    /// Returns the number of settings that differ.
+    pub fn count(&self) -> usize {
+        [
+            self.cutoff,
+            self.sample_rate,
+            self.q_factor,
+            self.gain,
+            self.constant_skirt_gain,
+            self.bypass,
+            self.notch_gain,
+            self.cut_q_factor,
+            self.shelf_slope,
+            self.design_method,
+        ]
+        .iter()
+        .filter(|differs| **differs)
+        .count()
+    }
+}
@@ -0,0 +1,358 @@
+/// spec.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::all_pass::AllPassFilter;
+use crate::filters::band_pass::BandPassFilter;
+use crate::filters::chain::{ChainStage, FilterStage};
+use crate::filters::error::FilterError;
+use crate::filters::filter::{Filter, FilterType};
+use crate::filters::filter_configuration::{FilterConfiguration, geometric_lerp, linear_lerp};
+use crate::filters::high_pass::HighPassFilter;
+use crate::filters::high_shelf::HighShelfFilter;
+use crate::filters::low_pass::LowPassFilter;
+use crate::filters::low_shelf::LowShelfFilter;
+use crate::filters::notch::NotchFilter;
+use crate::filters::peaking_eq::PeakingEQFilter;
+use crate::filters::tilt::TiltFilter;
+use core::fmt;
+use core::str::FromStr;
+use num_traits::Float;
+
+/// The reason a filter spec could not be parsed or built.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpecError {
+    /// The spec, or one of the stages of a chain, is empty.
+    Empty,
+    /// The filter type is not one of the recognized names.
+    UnknownFilterType,
+    /// The cutoff frequency, a gain or a Q factor is not a number.
+    InvalidNumber,
+    /// A field is not recognized or is given more than once.
+    InvalidField,
+    /// The settings do not describe a valid filter.
+    Filter(FilterError),
+    /// The settings are valid but the designed coefficients were rejected.
+    InvalidDesign,
+}
+
+impl fmt::Display for SpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpecError::Empty => f.write_str("filter spec is empty"),
+            SpecError::UnknownFilterType => f.write_str("unknown filter type"),
+            SpecError::InvalidNumber => f.write_str("filter spec contains an invalid number"),
+            SpecError::InvalidField => f.write_str("filter spec contains an unknown or repeated field"),
+            SpecError::Filter(error) => error.fmt(f),
+            SpecError::InvalidDesign => f.write_str("filter spec produced invalid coefficients"),
+        }
+    }
+}
+
+impl core::error::Error for SpecError {}
+
+impl From<FilterError> for SpecError {
+    fn from(error: FilterError) -> Self {
+        SpecError::Filter(error)
+    }
+}
+
+/// A filter described by a compact spec string such as `"lp:1000:q=0.707"`,
+/// `"peak:2500:+3dB:q=2"` or `"hs:8k:-4dB"`.
+///
+/// A spec is the filter type, the cutoff frequency in Hz and any number of optional fields, all
+/// separated by colons. The cutoff may end in `Hz`, `k` or `kHz`. The fields are a Q factor
+/// (`q=0.707`), a gain in decibels (`+3dB`), `skirt` for a constant skirt gain band-pass, and
+/// `bypass`. The Q factor defaults to `1/sqrt(2)` and the gain to zero. The sample rate is given
+/// when the filter is built, so the same spec works at any rate.
+///
+/// | Type | Names |
+/// |------|-------|
+/// | Low-pass | `lp`, `lowpass` |
+/// | High-pass | `hp`, `highpass` |
+/// | Band-pass | `bp`, `bandpass` |
+/// | Notch | `notch` |
+/// | All-pass | `ap`, `allpass` |
+/// | Peaking EQ | `peak`, `peq`, `peaking` |
+/// | Low shelf | `ls`, `lowshelf` |
+/// | High shelf | `hs`, `highshelf` |
+/// | Tilt | `tilt` |
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FilterSpec<T: Float> {
+    filter_type: FilterType,
+    cutoff: T,
+    q_factor: T,
+    gain: T,
+    constant_skirt_gain: bool,
+    bypass: bool,
+}
+
+impl<T: Float> FilterSpec<T> {
+    /// Creates the spec of a filter type and configuration. The spec has no sample rate, and
+    /// settings it cannot describe, such as the shelf slope or design method, are left out.
+    pub fn from_configuration(filter_type: FilterType, config: &FilterConfiguration<T>) -> Self {
+        Self {
+            filter_type,
+            cutoff: config.get_cutoff(),
+            q_factor: config.get_q_factor(),
+            gain: config.get_gain(),
+            constant_skirt_gain: config.get_constant_skirt_gain(),
+            bypass: config.get_bypass(),
+        }
+    }
+
+    /// Blends two specs, from `a` at `t = 0` to `b` at `t = 1`, with the cutoff and Q factor
+    /// moving linearly in octaves and the gain linearly in decibels, like
+    /// `FilterConfiguration::lerp`. `t` is clamped to that range. The filter type, constant
+    /// skirt gain and bypass state switch from `a` to `b` halfway.
+    pub fn lerp(a: &Self, b: &Self, t: T) -> Self {
+        let t = t.max(T::zero()).min(T::one());
+        let nearest = if t < T::from(0.5).unwrap_or_else(T::one) { a } else { b };
+        Self {
+            filter_type: nearest.filter_type,
+            cutoff: geometric_lerp(a.cutoff, b.cutoff, t),
+            q_factor: geometric_lerp(a.q_factor, b.q_factor, t),
+            gain: linear_lerp(a.gain, b.gain, t),
+            constant_skirt_gain: nearest.constant_skirt_gain,
+            bypass: nearest.bypass,
+        }
+    }
+
+    /// Returns the type of filter the spec describes.
+    pub fn get_filter_type(&self) -> FilterType {
+        self.filter_type
+    }
+
+    /// Returns the cutoff frequency in Hz.
+    pub fn get_cutoff(&self) -> T {
+        self.cutoff
+    }
+
+    /// Returns the Q factor.
+    pub fn get_q_factor(&self) -> T {
+        self.q_factor
+    }
+
+    /// Returns the gain in decibels.
+    pub fn get_gain(&self) -> T {
+        self.gain
+    }
+
+    /// Returns whether a band-pass filter has a constant skirt gain.
+    pub fn get_constant_skirt_gain(&self) -> bool {
+        self.constant_skirt_gain
+    }
+
+    /// Returns whether the filter starts bypassed.
+    pub fn get_bypass(&self) -> bool {
+        self.bypass
+    }
+
+    /// Returns the configuration the spec describes at the given sample rate, or the first
+    /// problem found with it.
+    pub fn to_configuration(&self, sample_rate: u32) -> Result<FilterConfiguration<T>, FilterError> {
+        FilterConfiguration::builder()
+            .cutoff(self.cutoff)
+            .sample_rate(sample_rate)
+            .q_factor(self.q_factor)
+            .gain(self.gain)
+            .constant_skirt_gain(self.constant_skirt_gain)
+            .bypass(self.bypass)
+            .build()
+    }
+}
+
+impl<T: Float + Send + 'static> FilterSpec<T> {
+    /// Constructs the filter the spec describes at the given sample rate.
+    pub fn build(&self, sample_rate: u32) -> Result<ChainStage<T>, SpecError> {
+        let config = self.to_configuration(sample_rate)?;
+        let (cutoff, q_factor, gain) = (self.cutoff, self.q_factor, self.gain);
+        let mut filter: ChainStage<T> = match self.filter_type {
+            FilterType::LowPass => boxed(LowPassFilter::new(cutoff, sample_rate, q_factor))?,
+            FilterType::HighPass => boxed(HighPassFilter::new(cutoff, sample_rate, q_factor))?,
+            FilterType::BandPass => boxed(BandPassFilter::new(
+                cutoff,
+                sample_rate,
+                q_factor,
+                self.constant_skirt_gain,
+            ))?,
+            FilterType::Notch => boxed(NotchFilter::new(cutoff, sample_rate, q_factor))?,
+            FilterType::AllPass => boxed(AllPassFilter::new(cutoff, sample_rate, q_factor))?,
+            FilterType::PeakingEQ => {
+                boxed(PeakingEQFilter::new(cutoff, sample_rate, q_factor, gain))?
+            }
+            FilterType::LowShelf => {
+                boxed(LowShelfFilter::new(cutoff, sample_rate, q_factor, gain))?
+            }
+            FilterType::HighShelf => {
+                boxed(HighShelfFilter::new(cutoff, sample_rate, q_factor, gain))?
+            }
+            FilterType::Tilt => boxed(TiltFilter::new(cutoff, sample_rate, gain))?,
+        };
+        // Applies the settings the constructors do not take, such as bypass.
+        if !filter.set_configuration(config) {
+            return Err(SpecError::InvalidDesign);
+        }
+        Ok(filter)
+    }
+}
+
+/// Boxes a newly constructed filter, or reports that its coefficients were rejected.
+fn boxed<T: Float, F: FilterStage<T> + Send + 'static>(
+    filter: Option<F>,
+) -> Result<ChainStage<T>, SpecError> {
+    match filter {
+        Some(filter) => Ok(Box::new(filter)),
+        None => Err(SpecError::InvalidDesign),
+    }
+}
+
+impl<T: Float> FromStr for FilterSpec<T> {
+    type Err = SpecError;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let mut fields = spec.trim().split(':').map(str::trim);
+        let filter_type = match fields.next() {
+            None | Some("") => return Err(SpecError::Empty),
+            Some(name) => parse_filter_type(name).ok_or(SpecError::UnknownFilterType)?,
+        };
+        let cutoff = parse_frequency(fields.next().ok_or(SpecError::Empty)?)?;
+        let mut parsed = FilterSpec {
+            filter_type,
+            cutoff,
+            q_factor: number(core::f64::consts::FRAC_1_SQRT_2)?,
+            gain: T::zero(),
+            constant_skirt_gain: false,
+            bypass: false,
+        };
+        let (mut has_q_factor, mut has_gain) = (false, false);
+        for field in fields {
+            let lower = field.to_ascii_lowercase();
+            if let Some(value) = lower.strip_prefix("q=") {
+                check_once(&mut has_q_factor)?;
+                parsed.q_factor = parse_number(value)?;
+            } else if let Some(value) = lower.strip_suffix("db") {
+                check_once(&mut has_gain)?;
+                parsed.gain = parse_number(value)?;
+            } else if lower == "skirt" && !parsed.constant_skirt_gain {
+                parsed.constant_skirt_gain = true;
+            } else if lower == "bypass" && !parsed.bypass {
+                parsed.bypass = true;
+            } else {
+                return Err(SpecError::InvalidField);
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+/// Writes the spec in its canonical form, which parses back to the same spec.
+impl<T: Float> fmt::Display for FilterSpec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cutoff = self.cutoff.to_f64().unwrap_or(f64::NAN);
+        let q_factor = self.q_factor.to_f64().unwrap_or(f64::NAN);
+        write!(f, "{}:{cutoff}:q={q_factor}", filter_type_name(self.filter_type))?;
+        let gain = self.gain.to_f64().unwrap_or(f64::NAN);
+        if gain != 0.0 {
+            write!(f, ":{gain:+}dB")?;
+        }
+        if self.constant_skirt_gain {
+            f.write_str(":skirt")?;
+        }
+        if self.bypass {
+            f.write_str(":bypass")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a semicolon-separated list of filter specs, such as
+/// `"hp:80; peak:2500:+3dB:q=2; hs:8k:-4dB"`, in processing order. A trailing semicolon is
+/// allowed.
+pub fn parse_chain<T: Float>(specs: &str) -> Result<Vec<FilterSpec<T>>, SpecError> {
+    let specs = specs.trim();
+    let specs = specs.strip_suffix(';').unwrap_or(specs);
+    if specs.trim().is_empty() {
+        return Err(SpecError::Empty);
+    }
+    specs.split(';').map(str::parse).collect()
+}
+
+/// Returns the filter type for a spec name.
+fn parse_filter_type(name: &str) -> Option<FilterType> {
+    let filter_type = match name.to_ascii_lowercase().as_str() {
+        "lp" | "lowpass" => FilterType::LowPass,
+        "hp" | "highpass" => FilterType::HighPass,
+        "bp" | "bandpass" => FilterType::BandPass,
+        "notch" => FilterType::Notch,
+        "ap" | "allpass" => FilterType::AllPass,
+        "peak" | "peq" | "peaking" => FilterType::PeakingEQ,
+        "ls" | "lowshelf" => FilterType::LowShelf,
+        "hs" | "highshelf" => FilterType::HighShelf,
+        "tilt" => FilterType::Tilt,
+        _ => return None,
+    };
+    Some(filter_type)
+}
+
+/// Returns the short spec name for a filter type.
+fn filter_type_name(filter_type: FilterType) -> &'static str {
+    match filter_type {
+        FilterType::LowPass => "lp",
+        FilterType::HighPass => "hp",
+        FilterType::BandPass => "bp",
+        FilterType::Notch => "notch",
+        FilterType::AllPass => "ap",
+        FilterType::PeakingEQ => "peak",
+        FilterType::LowShelf => "ls",
+        FilterType::HighShelf => "hs",
+        FilterType::Tilt => "tilt",
+    }
+}
+
+/// Parses a frequency in Hz, with an optional `Hz`, `k` or `kHz` suffix.
+fn parse_frequency<T: Float>(field: &str) -> Result<T, SpecError> {
+    let lower = field.to_ascii_lowercase();
+    let value = lower.strip_suffix("hz").unwrap_or(&lower);
+    match value.strip_suffix('k') {
+        Some(kilohertz) => Ok(parse_number::<T>(kilohertz)? * number(1000.0)?),
+        None => parse_number(value),
+    }
+}
+
+/// Parses a number, allowing an explicit `+` sign.
+fn parse_number<T: Float>(field: &str) -> Result<T, SpecError> {
+    let value: f64 = field.trim().parse().map_err(|_| SpecError::InvalidNumber)?;
+    number(value)
+}
+
+/// Converts a parsed number to the filter's float type.
+fn number<T: Float>(value: f64) -> Result<T, SpecError> {
+    T::from(value).ok_or(SpecError::InvalidNumber)
+}
+
+/// Marks a field as seen, failing if it was already given.
+fn check_once(seen: &mut bool) -> Result<(), SpecError> {
+    if core::mem::replace(seen, true) {
+        return Err(SpecError::InvalidField);
+    }
+    Ok(())
+}
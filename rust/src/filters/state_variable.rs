@@ -0,0 +1,492 @@
+/// state_variable.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::fault::{Fault, FaultHandler, Faults};
+use crate::filters::filter::{Filter, FilterExt};
+use crate::filters::filter_configuration::FilterConfiguration;
+use crate::filters::limiter::OutputLimit;
+#[cfg(feature = "metering")]
+use crate::filters::metering::{Metered, Metering, Meters};
+use crate::filters::response::{FrequencyResponse, angular_frequency};
+use crate::filters::update::UpdatePolicy;
+use num_complex::Complex;
+use num_traits::Float;
+use core::f64::consts::PI;
+
+/// The output a `StateVariableFilter` produces through the `Filter` trait.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SvfMode {
+    #[default]
+    LowPass,
+    HighPass,
+    /// A band-pass with a peak gain of one, or of the Q factor with a constant skirt gain.
+    BandPass,
+    Notch,
+    AllPass,
+}
+
+/// Every output of a `StateVariableFilter` for one input sample.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SvfOutputs<T: Float> {
+    pub low_pass: T,
+    pub high_pass: T,
+    pub band_pass: T,
+    pub notch: T,
+    pub all_pass: T,
+}
+
+/// The prewarped cutoff and damping of the filter, with the gains derived from them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SvfCoefficients<T: Float> {
+    /// The prewarped cutoff `tan(pi * cutoff / sample_rate)`.
+    g: T,
+    /// The damping `1 / Q`.
+    k: T,
+    a1: T,
+    a2: T,
+    a3: T,
+}
+
+impl<T: Float> SvfCoefficients<T> {
+    fn new(g: T, k: T) -> Self {
+        let a1 = T::one() / (T::one() + g * (g + k));
+        let a2 = g * a1;
+        Self {
+            g,
+            k,
+            a1,
+            a2,
+            a3: g * a2,
+        }
+    }
+
+    /// Returns the coefficients for a configuration, or `None` if it is invalid or the cutoff is
+    /// not below the Nyquist frequency.
+    fn design(config: &FilterConfiguration<T>) -> Option<Self> {
+        let nyquist = T::from(config.get_sample_rate())? / T::from(2.0)?;
+        if !config.is_valid() || config.get_cutoff() >= nyquist {
+            return None;
+        }
+        let g = (T::from(PI)? * config.get_cutoff() / T::from(config.get_sample_rate())?).tan();
+        Some(Self::new(g, T::one() / config.get_q_factor()))
+    }
+
+    /// Returns the coefficients a fraction of the way to `target`. The prewarped cutoff and
+    /// damping stay positive at every step, so the filter stays stable.
+    fn towards(&self, target: &Self, fraction: T) -> Self {
+        Self::new(
+            self.g + (target.g - self.g) * fraction,
+            self.k + (target.k - self.k) * fraction,
+        )
+    }
+}
+
+/// A state-variable filter in the topology-preserving transform (zero-delay feedback) form, which
+/// produces the low-pass, high-pass, band-pass, notch and all-pass outputs of one resonator
+/// together. Its state is the charge of two integrators rather than past samples, so the cutoff
+/// and Q factor can be modulated every sample without the zipper noise and transients a direct
+/// form biquad suffers. At fixed settings each output matches the corresponding cookbook biquad.
+///
+/// The `Filter` trait processes the output selected by the mode; `process_all` returns every
+/// output. The gain of the configuration is kept but not used.
+#[derive(Debug, Clone)]
+pub struct StateVariableFilter<T: Float> {
+    config: FilterConfiguration<T>,
+    mode: SvfMode,
+    coefficients: SvfCoefficients<T>,
+    /// The state of the first (band-pass) integrator.
+    ic1eq: T,
+    /// The state of the second (low-pass) integrator.
+    ic2eq: T,
+    output_limit: OutputLimit<T>,
+    iter: u64,
+    faults: Faults,
+    fault_handler: Option<FaultHandler>,
+    #[cfg(feature = "metering")]
+    metering: Option<Metering>,
+    update_policy: UpdatePolicy,
+    /// Coefficients waiting for the next block.
+    pending: Option<SvfCoefficients<T>>,
+}
+
+impl<T: Float> StateVariableFilter<T> {
+    /// Creates a new state-variable filter with the given cutoff frequency, sample rate, Q factor
+    /// and output. Returns `None` if the settings are invalid or the cutoff is not below the
+    /// Nyquist frequency.
+    pub fn new(cutoff: T, sample_rate: u32, q_factor: T, mode: SvfMode) -> Option<Self> {
+        let config = FilterConfiguration::new(cutoff, sample_rate, q_factor, T::zero(), false, false);
+        Some(Self {
+            coefficients: SvfCoefficients::design(&config)?,
+            config,
+            mode,
+            ic1eq: T::zero(),
+            ic2eq: T::zero(),
+            output_limit: OutputLimit::default(),
+            iter: 0,
+            faults: Faults::default(),
+            fault_handler: None,
+            #[cfg(feature = "metering")]
+            metering: None,
+            update_policy: UpdatePolicy::Immediate,
+            pending: None,
+        })
+    }
+
+    /// Returns the output processed through the `Filter` trait.
+    pub fn get_mode(&self) -> SvfMode {
+        self.mode
+    }
+
+    /// Sets the output processed through the `Filter` trait. The state is shared by every output,
+    /// so the mode can change while processing.
+    pub fn set_mode(&mut self, mode: SvfMode) {
+        self.mode = mode;
+    }
+
+    /// Filters one sample and returns every output. The bypass setting and the output limit only
+    /// apply to `process` and `process_block`.
+    pub fn process_all(&mut self, input: T) -> SvfOutputs<T> {
+        self.begin_block();
+        let coefficients = self.coefficients;
+        self.tick(input, &coefficients)
+    }
+
+    /// Processes a single sample.
+    pub fn process(&mut self, sample: &mut T) -> bool {
+        if self.config.get_bypass() {
+            self.iter = self.iter.wrapping_add(1);
+            return true;
+        }
+        self.begin_block();
+        let coefficients = self.coefficients;
+        self.process_sample(sample, &coefficients);
+        self.finish_block();
+        true
+    }
+
+    /// Processes a block of samples. Returns `false` if the block is empty.
+    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
+        if samples.is_empty() {
+            return false;
+        }
+        if self.config.get_bypass() {
+            self.iter = self.iter.wrapping_add(samples.len() as u64);
+            return true;
+        }
+        match self.pending.take() {
+            Some(target) if self.update_policy == UpdatePolicy::Smoothed && samples.len() > 1 => {
+                let start = self.coefficients;
+                let length = T::from(samples.len()).unwrap_or_else(T::one);
+                for (index, sample) in samples.iter_mut().enumerate() {
+                    let fraction = T::from(index + 1).unwrap_or_else(T::one) / length;
+                    self.process_sample(sample, &start.towards(&target, fraction));
+                }
+                self.coefficients = target;
+            }
+            pending => {
+                if let Some(target) = pending {
+                    self.coefficients = target;
+                }
+                let coefficients = self.coefficients;
+                for sample in samples.iter_mut() {
+                    self.process_sample(sample, &coefficients);
+                }
+            }
+        }
+        self.finish_block();
+        true
+    }
+
+    /// Resets the integrators and sample count.
+    pub fn reset(&mut self) {
+        self.ic1eq = T::zero();
+        self.ic2eq = T::zero();
+        self.iter = 0;
+    }
+
+    /// Returns whether new settings are waiting for the next block.
+    pub fn has_pending_coefficients(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Adopts coefficients held back by the update policy.
+    fn begin_block(&mut self) {
+        if let Some(coefficients) = self.pending.take() {
+            self.coefficients = coefficients;
+        }
+    }
+
+    /// Publishes the meter levels accumulated since the last call.
+    fn finish_block(&mut self) {
+        #[cfg(feature = "metering")]
+        if let Some(metering) = self.metering.as_mut() {
+            metering.publish();
+        }
+    }
+
+    /// Filters one sample in place, keeping the selected output.
+    #[inline(always)]
+    fn process_sample(&mut self, sample: &mut T, coefficients: &SvfCoefficients<T>) {
+        let input = *sample;
+        let outputs = self.tick(input, coefficients);
+        let output = match self.mode {
+            SvfMode::LowPass => outputs.low_pass,
+            SvfMode::HighPass => outputs.high_pass,
+            SvfMode::BandPass => outputs.band_pass,
+            SvfMode::Notch => outputs.notch,
+            SvfMode::AllPass => outputs.all_pass,
+        };
+        #[cfg(feature = "metering")]
+        if let Some(metering) = self.metering.as_mut() {
+            metering.record(input, output);
+        }
+        *sample = self.output_limit.apply(output);
+    }
+
+    /// Advances the integrators by one sample and returns every output.
+    #[inline(always)]
+    fn tick(&mut self, input: T, coefficients: &SvfCoefficients<T>) -> SvfOutputs<T> {
+        let SvfCoefficients { k, a1, a2, a3, .. } = *coefficients;
+        let v3 = input - self.ic2eq;
+        let v1 = a1 * self.ic1eq + a2 * v3;
+        let v2 = self.ic2eq + a2 * self.ic1eq + a3 * v3;
+        let two = T::one() + T::one();
+        self.ic1eq = two * v1 - self.ic1eq;
+        self.ic2eq = two * v2 - self.ic2eq;
+
+        self.iter = self.iter.wrapping_add(1);
+        if !(self.ic1eq.is_finite() && self.ic2eq.is_finite()) {
+            self.report(Fault::NonFiniteOutput);
+        }
+        let band_pass = if self.config.get_constant_skirt_gain() { v1 } else { k * v1 };
+        SvfOutputs {
+            low_pass: v2,
+            high_pass: input - k * v1 - v2,
+            band_pass,
+            notch: input - k * v1,
+            all_pass: input - two * k * v1,
+        }
+    }
+
+    /// Records a fault, calling the fault handler the first time it is seen.
+    #[cold]
+    fn report(&mut self, fault: Fault) {
+        if self.faults.mark(fault) {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(instance = self as *const Self as usize, fault = ?fault, "filter fault");
+            if let Some(handler) = self.fault_handler {
+                handler(fault);
+            }
+        }
+    }
+}
+
+impl<T: Float> Filter<T> for StateVariableFilter<T> {
+    /// Processes a single sample in-place and returns a boolean indicating success.
+    fn process(&mut self, sample: &mut T) -> bool {
+        StateVariableFilter::process(self, sample)
+    }
+
+    /// Processes a block of samples in-place and returns a boolean indicating success.
+    fn process_block(&mut self, samples: &mut [T]) -> bool {
+        StateVariableFilter::process_block(self, samples)
+    }
+
+    /// Resets the integrators and sample count.
+    fn reset(&mut self) {
+        StateVariableFilter::reset(self);
+    }
+
+    /// Returns the configuration of the filter.
+    fn get_configuration(&self) -> FilterConfiguration<T> {
+        self.config
+    }
+
+    /// Sets the configuration without resetting the integrators. Returns `false` and keeps the
+    /// current configuration if the new one is invalid.
+    fn set_configuration(&mut self, configuration: FilterConfiguration<T>) -> bool {
+        let Some(coefficients) = SvfCoefficients::design(&configuration) else {
+            return false;
+        };
+        self.config = configuration;
+        match self.update_policy {
+            UpdatePolicy::Immediate => self.coefficients = coefficients,
+            UpdatePolicy::NextBlock | UpdatePolicy::Smoothed => self.pending = Some(coefficients),
+        }
+        true
+    }
+
+    /// Returns the cutoff frequency of the filter.
+    fn get_cutoff(&self) -> T {
+        self.config.get_cutoff()
+    }
+
+    /// Sets the cutoff frequency of the filter.
+    fn set_cutoff(&mut self, cutoff: T) -> bool {
+        self.update(|config| config.set_cutoff(cutoff))
+    }
+
+    /// Returns the sample rate of the filter.
+    fn get_sample_rate(&self) -> u32 {
+        self.config.get_sample_rate()
+    }
+
+    /// Sets the sample rate of the filter.
+    fn set_sample_rate(&mut self, sample_rate: u32) -> bool {
+        self.update(|config| config.set_sample_rate(sample_rate))
+    }
+
+    /// Returns the Q factor of the filter.
+    fn get_q_factor(&self) -> T {
+        self.config.get_q_factor()
+    }
+
+    /// Sets the Q factor of the filter.
+    fn set_q_factor(&mut self, q_factor: T) -> bool {
+        self.update(|config| config.set_q_factor(q_factor))
+    }
+
+    /// Returns the gain of the filter, which no output uses.
+    fn get_gain(&self) -> T {
+        self.config.get_gain()
+    }
+
+    /// Stores the gain of the filter, which no output uses.
+    fn set_gain(&mut self, gain: T) -> bool {
+        self.update(|config| config.set_gain(gain))
+    }
+
+    /// Returns whether the band-pass output has a constant skirt gain.
+    fn get_constant_skirt_gain(&self) -> bool {
+        self.config.get_constant_skirt_gain()
+    }
+
+    /// Sets whether the band-pass output has a constant skirt gain.
+    fn set_constant_skirt_gain(&mut self, constant_skirt_gain: bool) -> bool {
+        self.update(|config| config.set_constant_skirt_gain(constant_skirt_gain))
+    }
+
+    /// Returns whether the filter should be bypassed.
+    fn get_bypass(&self) -> bool {
+        self.config.get_bypass()
+    }
+
+    /// Sets whether the filter should be bypassed.
+    fn set_bypass(&mut self, bypass: bool) -> bool {
+        self.config.set_bypass(bypass);
+        true
+    }
+
+    /// Returns the number of samples processed, including bypassed samples.
+    fn samples_processed(&self) -> u64 {
+        self.iter
+    }
+
+    /// Returns the limit applied to the output.
+    fn get_output_limit(&self) -> OutputLimit<T> {
+        self.output_limit
+    }
+
+    /// Sets the limit applied to the output.
+    fn set_output_limit(&mut self, output_limit: OutputLimit<T>) -> bool {
+        if !output_limit.is_valid() {
+            return false;
+        }
+        self.output_limit = output_limit;
+        true
+    }
+
+    /// Returns the faults detected since they were last taken.
+    fn get_faults(&self) -> Faults {
+        self.faults
+    }
+
+    /// Returns the faults detected since they were last taken and clears them.
+    fn take_faults(&mut self) -> Faults {
+        core::mem::take(&mut self.faults)
+    }
+
+    /// Sets the function called the first time each fault is detected, or removes it.
+    fn set_fault_handler(&mut self, handler: Option<FaultHandler>) {
+        self.fault_handler = handler;
+    }
+
+    /// Returns when parameter changes take effect.
+    fn get_update_policy(&self) -> UpdatePolicy {
+        self.update_policy
+    }
+
+    /// Sets when parameter changes take effect. Under `UpdatePolicy::Smoothed` the prewarped
+    /// cutoff and damping are interpolated across the next block.
+    fn set_update_policy(&mut self, update_policy: UpdatePolicy) {
+        self.update_policy = update_policy;
+        self.begin_block();
+    }
+}
+
+#[cfg(feature = "metering")]
+impl<T: Float> Metered<T> for StateVariableFilter<T> {
+    /// Starts metering the input and output levels.
+    fn enable_metering(&mut self, window: u32) -> Meters {
+        let metering = Metering::new(window);
+        let meters = metering.get_meters();
+        self.metering = Some(metering);
+        meters
+    }
+
+    /// Stops metering.
+    fn disable_metering(&mut self) {
+        self.metering = None;
+    }
+
+    /// Returns handles to the meters, if metering is enabled.
+    fn get_meters(&self) -> Option<Meters> {
+        self.metering.as_ref().map(Metering::get_meters)
+    }
+}
+
+impl<T: Float> FrequencyResponse<T> for StateVariableFilter<T> {
+    /// Returns the response of the selected output, or a flat response while it is bypassed. The
+    /// filter is the bilinear transform of the analog state-variable filter, so this evaluates the
+    /// analog prototype at the prewarped frequency.
+    fn response_at(&self, frequency: T, sample_rate: u32) -> Complex<T> {
+        let one = Complex::from(T::one());
+        if self.config.get_bypass() {
+            return one;
+        }
+        let SvfCoefficients { g, k, .. } = self.coefficients;
+        let half = angular_frequency(frequency, sample_rate) / (T::one() + T::one());
+        let s = Complex::new(T::zero(), half.tan() / g);
+        let k = Complex::from(k);
+        let denominator = s * s + k * s + one;
+        let numerator = match self.mode {
+            SvfMode::LowPass => one,
+            SvfMode::HighPass => s * s,
+            SvfMode::BandPass if self.config.get_constant_skirt_gain() => s,
+            SvfMode::BandPass => k * s,
+            SvfMode::Notch => s * s + one,
+            SvfMode::AllPass => s * s - k * s + one,
+        };
+        numerator / denominator
+    }
+}
@@ -0,0 +1,86 @@
+/// stats.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use num_traits::Float;
+
+/// Statistics gathered while a block is processed, so hosts can drive meters and diagnostics
+/// without a second pass over the samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlockStats<T: Float> {
+    /// The largest input magnitude, ignoring NaN samples.
+    pub peak_input: T,
+    /// The largest output magnitude, ignoring NaN samples.
+    pub peak_output: T,
+    /// The number of output samples at or beyond full scale, which clip when converted to
+    /// integers.
+    pub clipped: usize,
+    /// The number of output samples that are NaN.
+    pub nan: usize,
+    /// Whether any subnormal output samples or filter state were flushed to zero.
+    pub denormals_flushed: bool,
+}
+
+impl<T: Float> BlockStats<T> {
+    /// Runs `process` over `samples`, measuring the block before and after and flushing subnormal
+    /// outputs to zero. Returns `None` if `process` fails.
+    pub(crate) fn measure(samples: &mut [T], process: impl FnOnce(&mut [T]) -> bool) -> Option<Self> {
+        let peak_input = peak(samples);
+        if !process(samples) {
+            return None;
+        }
+        let mut stats = Self {
+            peak_input,
+            peak_output: peak(samples),
+            clipped: 0,
+            nan: 0,
+            denormals_flushed: false,
+        };
+        for sample in samples.iter_mut() {
+            if sample.is_nan() {
+                stats.nan += 1;
+            } else if sample.abs() >= T::one() {
+                stats.clipped += 1;
+            } else if sample.is_subnormal() {
+                *sample = T::zero();
+                stats.denormals_flushed = true;
+            }
+        }
+        Some(stats)
+    }
+}
+
+/// Flushes `value` to zero if it is subnormal, returning whether it was.
+pub(crate) fn flush_denormal<T: Float>(value: &mut T) -> bool {
+    if value.is_subnormal() {
+        *value = T::zero();
+        return true;
+    }
+    false
+}
+
+/// Returns the largest magnitude in `samples`, ignoring NaN samples.
+fn peak<T: Float>(samples: &[T]) -> T {
+    samples
+        .iter()
+        .filter(|sample| !sample.is_nan())
+        .fold(T::zero(), |peak, sample| peak.max(sample.abs()))
+}
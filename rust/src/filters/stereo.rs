@@ -0,0 +1,224 @@
+/// stereo.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::{Coefficients, State, tick};
+use crate::filters::filter::FilterType;
+use crate::filters::filter_configuration::FilterConfiguration;
+use num_traits::Float;
+
+/// How far one channel of a `StereoFilter` departs from the shared configuration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChannelOffset<T: Float> {
+    /// The cutoff offset in octaves: the channel's cutoff is the shared cutoff times `2^cutoff`.
+    pub cutoff: T,
+    /// The gain offset in decibels, added to the shared gain.
+    pub gain: T,
+}
+
+impl<T: Float> Default for ChannelOffset<T> {
+    fn default() -> Self {
+        Self {
+            cutoff: T::zero(),
+            gain: T::zero(),
+        }
+    }
+}
+
+impl<T: Float> ChannelOffset<T> {
+    /// Applies the offset to a configuration. Returns `None` if either offset is not finite.
+    fn apply(&self, config: &FilterConfiguration<T>) -> Option<FilterConfiguration<T>> {
+        if !self.cutoff.is_finite() || !self.gain.is_finite() {
+            return None;
+        }
+        let mut config = *config;
+        config.set_cutoff(config.get_cutoff() * T::from(2.0)?.powf(self.cutoff));
+        config.set_gain(config.get_gain() + self.gain);
+        Some(config)
+    }
+}
+
+/// A biquad filter for a stereo pair with linked parameters: one configuration drives both
+/// channels, each with its own state. An optional `ChannelOffset` per channel shifts its cutoff or
+/// gain, for example to widen the image by tuning the channels slightly apart, and follows every
+/// change to the shared configuration.
+///
+/// The filter holds no allocations, so it is available without `std`. A bypassed configuration
+/// leaves the samples unchanged.
+#[derive(Debug, Clone)]
+pub struct StereoFilter<T: Float> {
+    filter_type: FilterType,
+    config: FilterConfiguration<T>,
+    offsets: [ChannelOffset<T>; 2],
+    /// The normalized coefficients of the left and right channels.
+    coefficients: [Coefficients<T>; 2],
+    states: [State<T>; 2],
+}
+
+impl<T: Float> StereoFilter<T> {
+    /// Creates a stereo filter with no offsets. Returns `None` if the configuration is invalid for
+    /// the filter type.
+    pub fn new(filter_type: FilterType, config: FilterConfiguration<T>) -> Option<Self> {
+        let offsets = [ChannelOffset::default(); 2];
+        let coefficients = Self::design(filter_type, &config, &offsets)?;
+        Some(Self {
+            filter_type,
+            config,
+            offsets,
+            coefficients,
+            states: [State::default(); 2],
+        })
+    }
+
+    /// Designs the normalized coefficients of both channels.
+    fn design(
+        filter_type: FilterType,
+        config: &FilterConfiguration<T>,
+        offsets: &[ChannelOffset<T>; 2],
+    ) -> Option<[Coefficients<T>; 2]> {
+        let design = |offset: &ChannelOffset<T>| {
+            filter_type.design_coefficients(&offset.apply(config)?)?.normalized()
+        };
+        Some([design(&offsets[0])?, design(&offsets[1])?])
+    }
+
+    /// Redesigns both channels without resetting their state. Returns `false` and keeps the
+    /// current settings if either channel cannot be designed.
+    fn redesign(
+        &mut self,
+        filter_type: FilterType,
+        config: FilterConfiguration<T>,
+        offsets: [ChannelOffset<T>; 2],
+    ) -> bool {
+        match Self::design(filter_type, &config, &offsets) {
+            Some(coefficients) => {
+                self.filter_type = filter_type;
+                self.config = config;
+                self.offsets = offsets;
+                self.coefficients = coefficients;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the filter type.
+    pub fn get_filter_type(&self) -> FilterType {
+        self.filter_type
+    }
+
+    /// Returns the shared configuration, before the offsets are applied.
+    pub fn get_configuration(&self) -> FilterConfiguration<T> {
+        self.config
+    }
+
+    /// Returns the configurations of the left and right channels, with their offsets applied.
+    pub fn get_channel_configurations(&self) -> (FilterConfiguration<T>, FilterConfiguration<T>) {
+        let apply = |offset: &ChannelOffset<T>| offset.apply(&self.config).unwrap_or(self.config);
+        (apply(&self.offsets[0]), apply(&self.offsets[1]))
+    }
+
+    /// Returns the normalized coefficients of the left and right channels.
+    pub fn get_coefficients(&self) -> (Coefficients<T>, Coefficients<T>) {
+        (self.coefficients[0], self.coefficients[1])
+    }
+
+    /// Changes the filter type and shared configuration without resetting the state. Returns
+    /// `false` and keeps the current settings if either channel cannot be designed.
+    pub fn set_filter(&mut self, filter_type: FilterType, config: FilterConfiguration<T>) -> bool {
+        self.redesign(filter_type, config, self.offsets)
+    }
+
+    /// Sets the shared configuration without resetting the state. Returns `false` and keeps the
+    /// current configuration if either channel cannot be designed.
+    pub fn set_configuration(&mut self, config: FilterConfiguration<T>) -> bool {
+        self.redesign(self.filter_type, config, self.offsets)
+    }
+
+    /// Applies any number of changes to a copy of the shared configuration and then recomputes
+    /// the coefficients once. Returns `false` and keeps the current configuration if either
+    /// channel cannot be designed.
+    pub fn update(&mut self, change: impl FnOnce(&mut FilterConfiguration<T>)) -> bool {
+        let mut config = self.config;
+        change(&mut config);
+        self.set_configuration(config)
+    }
+
+    /// Returns the offsets of the left and right channels.
+    pub fn get_offsets(&self) -> (ChannelOffset<T>, ChannelOffset<T>) {
+        (self.offsets[0], self.offsets[1])
+    }
+
+    /// Sets the offsets of the left and right channels. Returns `false` and keeps the current
+    /// offsets if an offset is not finite or moves a channel to an invalid configuration.
+    pub fn set_offsets(&mut self, left: ChannelOffset<T>, right: ChannelOffset<T>) -> bool {
+        self.redesign(self.filter_type, self.config, [left, right])
+    }
+
+    /// Processes one stereo frame in place.
+    pub fn process(&mut self, frame: &mut (T, T)) -> bool {
+        if self.config.get_bypass() {
+            return true;
+        }
+        frame.0 = tick(&self.coefficients[0], &mut self.states[0], frame.0);
+        frame.1 = tick(&self.coefficients[1], &mut self.states[1], frame.1);
+        true
+    }
+
+    /// Processes interleaved stereo frames in place, left sample first. Returns `false` and
+    /// leaves the samples unchanged if the block is empty or holds an odd number of samples.
+    pub fn process_interleaved(&mut self, samples: &mut [T]) -> bool {
+        if samples.is_empty() || !samples.len().is_multiple_of(2) {
+            return false;
+        }
+        if self.config.get_bypass() {
+            return true;
+        }
+        for frame in samples.chunks_exact_mut(2) {
+            frame[0] = tick(&self.coefficients[0], &mut self.states[0], frame[0]);
+            frame[1] = tick(&self.coefficients[1], &mut self.states[1], frame[1]);
+        }
+        true
+    }
+
+    /// Processes separate left and right blocks in place. Returns `false` and leaves the samples
+    /// unchanged if the blocks are empty or differ in length.
+    pub fn process_planar(&mut self, left: &mut [T], right: &mut [T]) -> bool {
+        if left.is_empty() || left.len() != right.len() {
+            return false;
+        }
+        if self.config.get_bypass() {
+            return true;
+        }
+        for (channel, samples) in [left, right].into_iter().enumerate() {
+            for sample in samples.iter_mut() {
+                *sample = tick(&self.coefficients[channel], &mut self.states[channel], *sample);
+            }
+        }
+        true
+    }
+
+    /// Resets the state of both channels.
+    pub fn reset(&mut self) {
+        self.states = [State::default(); 2];
+    }
+}
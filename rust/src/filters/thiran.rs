@@ -0,0 +1,171 @@
+/// thiran.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::{Coefficients, DigitalBiquadFilter};
+use crate::filters::response::{FrequencyResponse, angular_frequency};
+use num_complex::Complex;
+use num_traits::Float;
+
+/// The all-pass is designed for delays between this and one sample more, where a second-order
+/// Thiran filter is most accurate. The remainder of the delay is a plain delay line.
+const MIN_FRACTIONAL_DELAY: f64 = 1.5;
+
+/// A delay of any non-negative number of samples, made of a delay line for the whole samples and
+/// a Thiran all-pass for the fraction. The all-pass has a flat magnitude response and a maximally
+/// flat group delay at DC, so low and mid frequencies are delayed by the exact fractional amount.
+#[derive(Debug, Clone)]
+pub struct ThiranDelay<T: Float> {
+    delay: T,
+    line: Vec<T>,
+    position: usize,
+    all_pass: DigitalBiquadFilter<T>,
+}
+
+impl<T: Float> ThiranDelay<T> {
+    /// Creates a delay of `delay` samples. Returns `None` if the delay is negative or not finite.
+    pub fn new(delay: T) -> Option<Self> {
+        let (whole, all_pass) = Self::design(delay)?;
+        Some(Self {
+            delay,
+            line: vec![T::zero(); whole],
+            position: 0,
+            all_pass: DigitalBiquadFilter::new(all_pass)?,
+        })
+    }
+
+    /// Splits a delay into whole samples and the coefficients of the all-pass for the rest.
+    fn design(delay: T) -> Option<(usize, Coefficients<T>)> {
+        let in_range = delay >= T::zero() && delay.is_finite();
+        if !in_range {
+            return None;
+        }
+        let one = T::one();
+        let two = one + one;
+        let min_fraction = T::from(MIN_FRACTIONAL_DELAY)?;
+        let (whole, fraction) = if delay >= min_fraction {
+            let whole = (delay - min_fraction).floor();
+            (whole.to_usize()?, delay - whole)
+        } else {
+            (0, delay)
+        };
+        let coefficients = if fraction.is_zero() {
+            Coefficients {
+                b0: one,
+                b1: T::zero(),
+                b2: T::zero(),
+                a0: one,
+                a1: T::zero(),
+                a2: T::zero(),
+            }
+        } else if fraction > one {
+            // Second order: a1 = -2 (D - 2) / (D + 1), a2 = (D - 1)(D - 2) / ((D + 1)(D + 2)).
+            let a1 = -two * (fraction - two) / (fraction + one);
+            let a2 = (fraction - one) * (fraction - two) / ((fraction + one) * (fraction + two));
+            Coefficients {
+                b0: a2,
+                b1: a1,
+                b2: one,
+                a0: one,
+                a1,
+                a2,
+            }
+        } else {
+            // First order: a1 = (1 - D) / (1 + D).
+            let a1 = (one - fraction) / (one + fraction);
+            Coefficients {
+                b0: a1,
+                b1: one,
+                b2: T::zero(),
+                a0: one,
+                a1,
+                a2: T::zero(),
+            }
+        };
+        Some((whole, coefficients))
+    }
+
+    /// Delays a single sample.
+    pub fn process(&mut self, sample: &mut T) -> bool {
+        if let Some(slot) = self.line.get_mut(self.position) {
+            core::mem::swap(slot, sample);
+            self.position = (self.position + 1) % self.line.len();
+        }
+        self.all_pass.process(sample)
+    }
+
+    /// Delays a block of samples. Returns `false` if the block is empty.
+    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
+        if samples.is_empty() {
+            return false;
+        }
+        for sample in samples.iter_mut() {
+            self.process(sample);
+        }
+        true
+    }
+
+    /// Returns the delay in samples.
+    pub fn get_delay(&self) -> T {
+        self.delay
+    }
+
+    /// Returns the group delay in samples at the given frequency. It equals the delay at DC and
+    /// drifts from it towards the Nyquist frequency, where the all-pass is least accurate.
+    pub fn group_delay(&self, frequency: T, sample_rate: u32) -> T {
+        let whole = T::from(self.line.len()).unwrap_or_else(T::nan);
+        whole + self.all_pass.get_coefficients().group_delay(frequency, sample_rate)
+    }
+
+    /// Returns the all-pass that provides the fractional part of the delay.
+    pub fn get_all_pass(&self) -> &DigitalBiquadFilter<T> {
+        &self.all_pass
+    }
+
+    /// Sets the delay in samples and resets the delay. Returns `false` and keeps the current delay
+    /// if it is negative or not finite.
+    pub fn set_delay(&mut self, delay: T) -> bool {
+        match Self::design(delay) {
+            Some((whole, all_pass)) if self.all_pass.set_coefficients(all_pass) => {
+                self.delay = delay;
+                self.line = vec![T::zero(); whole];
+                self.position = 0;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Clears the delayed samples.
+    pub fn reset(&mut self) {
+        self.line.iter_mut().for_each(|sample| *sample = T::zero());
+        self.position = 0;
+        self.all_pass.reset();
+    }
+}
+
+impl<T: Float> FrequencyResponse<T> for ThiranDelay<T> {
+    fn response_at(&self, frequency: T, sample_rate: u32) -> Complex<T> {
+        let whole = T::from(self.line.len()).unwrap_or_else(T::nan);
+        let line = Complex::from_polar(T::one(), -angular_frequency(frequency, sample_rate) * whole);
+        line * self.all_pass.response_at(frequency, sample_rate)
+    }
+}
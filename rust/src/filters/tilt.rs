@@ -0,0 +1,111 @@
+/// tilt.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::{Coefficients, DigitalBiquadFilter};
+use crate::filters::filter::{
+    BiquadFilterWrapper, impl_biquad_filter, impl_biquad_filter_wrapper,
+    impl_precision_conversions,
+};
+use crate::filters::filter_configuration::FilterConfiguration;
+use num_traits::Float;
+use core::f64::consts::{FRAC_1_SQRT_2, PI};
+
+
+/// Tilt EQ implementation using a single digital biquad filter. The cutoff frequency is the pivot,
+/// where the response is flat, and the gain is the tilt: frequencies well above the pivot are
+/// boosted by the gain in decibels and frequencies well below it are cut by the same amount, so a
+/// negative gain tilts the spectrum towards the bass. The Q factor sets how quickly the response
+/// turns around the pivot, as it does for the shelving filters.
+#[derive(Debug, Clone)]
+pub struct TiltFilter<T: Float> {
+    /// The digital biquad filter used for processing.
+    filter: DigitalBiquadFilter<T>,
+    /// The configuration for the filter, including pivot frequency, sample rate, and tilt.
+    config: FilterConfiguration<T>,
+}
+
+impl<T: Float> TiltFilter<T> {
+    /// Creates a new tilt filter with the given pivot frequency, sample rate, and tilt in decibels.
+    /// The Q factor starts at `1/sqrt(2)`.
+    pub fn new(pivot: T, sample_rate: u32, gain: T) -> Option<Self> {
+        let config =
+            FilterConfiguration::new(
+                pivot,
+                sample_rate,
+                T::from(FRAC_1_SQRT_2)?,
+                gain,
+                false,
+                false
+            );
+        let coefficients = Self::calculate_coefficients(&config)?;
+        let filter = DigitalBiquadFilter::new(coefficients)?;
+        Some(Self { filter, config })
+    }
+}
+
+impl_biquad_filter!(TiltFilter);
+impl_precision_conversions!(TiltFilter);
+impl_biquad_filter_wrapper!(TiltFilter, Tilt);
+
+impl<T: Float> TiltFilter<T> {
+    /// Designs the tilt coefficients for a configuration. Returns `None` if the configuration is
+    /// invalid.
+    fn design_coefficients(config: &FilterConfiguration<T>) -> Option<Coefficients<T>> {
+        let cutoff = config.get_cutoff();
+        let sample_rate = config.get_sample_rate();
+        let q = config.get_q_factor();
+        let gain = config.get_gain();
+
+        if !config.is_valid() {
+            return None;
+        }
+
+        let two = T::from(2.0)?;
+        let pi = T::from(PI)?;
+        let one = T::one();
+
+        let w0 = two * pi * cutoff / T::from(sample_rate)?;
+        let cos_w0 = w0.cos();
+        let sin_w0 = w0.sin();
+        let alpha = sin_w0 / (two * q);
+        // A low shelf cutting twice the tilt, raised by the tilt. The shelf is at half its gain
+        // at the pivot, so the pivot stays at unity.
+        let a = T::from(10.0)?.powf(-gain / T::from(20.0)?);
+        let root = two * a.sqrt() * alpha;
+
+        let b0 = (a + one) - (a - one) * cos_w0 + root;
+        let b1 = two * ((a - one) - (a + one) * cos_w0);
+        let b2 = (a + one) - (a - one) * cos_w0 - root;
+        let a0 = (a + one) + (a - one) * cos_w0 + root;
+        let a1 = -two * ((a - one) + (a + one) * cos_w0);
+        let a2 = (a + one) + (a - one) * cos_w0 - root;
+
+        Some(Coefficients {
+            b0,
+            b1,
+            b2,
+            a0,
+            a1,
+            a2,
+        })
+    }
+}
@@ -0,0 +1,66 @@
+/// topology.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use num_traits::Float;
+
+/// The structure used to evaluate a biquad's difference equation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Topology {
+    /// Direct Form I, rounding every product and sum to the filter's float type.
+    #[default]
+    DirectForm1,
+    /// Direct Form I with first-order error feedback: the rounding error of each output is
+    /// measured exactly and added to the next output before rounding. This puts a zero at DC in
+    /// the response to the rounding noise, which cancels the noise gain of poles near DC, such
+    /// as a 30 Hz high-pass at 192 kHz in `f32`. Each sample costs several times as much.
+    DirectForm1ErrorFeedback,
+}
+
+/// Returns `a + b` and the rounding error of the sum, exactly.
+#[inline(always)]
+fn two_sum<T: Float>(a: T, b: T) -> (T, T) {
+    let sum = a + b;
+    let b_part = sum - a;
+    (sum, (a - (sum - b_part)) + (b - b_part))
+}
+
+/// Adds `coefficient * value` to a sum and its running rounding error.
+#[inline(always)]
+fn accumulate<T: Float>((sum, error): (T, T), coefficient: T, value: T) -> (T, T) {
+    let product = coefficient * value;
+    let product_error = coefficient.mul_add(value, -product);
+    let (sum, sum_error) = two_sum(sum, product);
+    (sum, error + product_error + sum_error)
+}
+
+/// Evaluates `b0 x + b1 x1 + b2 x2 - a1 y1 - a2 y2 + feedback` and returns the rounded result with
+/// its rounding error.
+#[inline(always)]
+pub(crate) fn error_feedback_sum<T: Float>(terms: [(T, T); 5], feedback: T) -> (T, T) {
+    let (sum, error) = terms
+        .iter()
+        .fold((feedback, T::zero()), |total, &(coefficient, value)| {
+            accumulate(total, coefficient, value)
+        });
+    two_sum(sum, error)
+}
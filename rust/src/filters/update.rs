@@ -0,0 +1,40 @@
+/// update.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+/// When new coefficients computed by a parameter change take effect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UpdatePolicy {
+    /// New coefficients are used from the next sample processed.
+    #[default]
+    Immediate,
+    /// New coefficients are held until the start of the next `process` or `process_block` call,
+    /// so coefficients never change partway through a block. Only the last change before a
+    /// block is applied.
+    NextBlock,
+    /// New coefficients are held until the next block, then approached by interpolating every
+    /// coefficient linearly across that block, reaching the new values on its last sample. The
+    /// stability region of a biquad is convex, so every step between two stable designs is
+    /// stable. Changes that reset the state, and blocks of a single sample, switch at the start
+    /// of the block as with `NextBlock`.
+    Smoothed,
+}
@@ -0,0 +1,220 @@
+/// weighting.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::{Coefficients, DigitalBiquadFilter};
+use crate::filters::cascade::Cascade;
+use crate::filters::response::FrequencyResponse;
+use num_complex::Complex;
+use num_traits::Float;
+use core::f64::consts::PI;
+
+/// The frequency in Hz of the double low-frequency pole of the A and C weighting curves.
+const LOW_POLE: f64 = 20.598997;
+/// The frequency in Hz of the double high-frequency pole of the A and C weighting curves.
+const HIGH_POLE: f64 = 12194.217;
+/// The frequency at which weighting curves are normalized to unity gain.
+const REFERENCE_FREQUENCY: f64 = 1000.0;
+/// The centre frequency in Hz of the K-weighting high shelf.
+const K_SHELF_FREQUENCY: f64 = 1681.974450955533;
+/// The gain in decibels of the K-weighting high shelf.
+const K_SHELF_GAIN: f64 = 3.999843853973347;
+/// The Q factor of the K-weighting high shelf.
+const K_SHELF_Q: f64 = 0.7071752369554196;
+/// The exponent relating the band gain of the K-weighting shelf to its high-frequency gain.
+const K_SHELF_BAND_EXPONENT: f64 = 0.4996667741545416;
+/// The cutoff in Hz of the K-weighting high-pass (the RLB weighting).
+const K_HIGH_PASS_FREQUENCY: f64 = 38.13547087602444;
+/// The Q factor of the K-weighting high-pass.
+const K_HIGH_PASS_Q: f64 = 0.5003270373238773;
+
+/// The C frequency weighting of IEC 61672-1, used for sound level measurements of loud and
+/// low-frequency sounds. It is flat between roughly 60 Hz and 4 kHz and falls off at 12 dB per
+/// octave outside that range, with unity gain at 1 kHz.
+///
+/// The analog curve `s^2 / ((s + w1)^2 (s + w4)^2)` is mapped to two biquads with the bilinear
+/// transform. The response is within 0.1 dB of the standard from 10 Hz to 4 kHz. Above that the
+/// transform compresses the curve towards the Nyquist frequency: at 44.1 kHz and 48 kHz the
+/// response stays within the class 1 tolerances of the standard, and it approaches the nominal
+/// values as the sample rate rises, to within 0.3 dB at 16 kHz when running at 192 kHz.
+#[derive(Debug, Clone)]
+pub struct CWeightingFilter<T: Float> {
+    sample_rate: u32,
+    cascade: Cascade<T, 2>,
+}
+
+impl<T: Float> CWeightingFilter<T> {
+    /// Creates the weighting filter for a sample rate. Returns `None` if the reference frequency
+    /// of 1 kHz is not below the Nyquist frequency.
+    pub fn new(sample_rate: u32) -> Option<Self> {
+        if f64::from(sample_rate) <= 2.0 * REFERENCE_FREQUENCY {
+            return None;
+        }
+        let mut low = double_pole(LOW_POLE, sample_rate, true);
+        let high = double_pole(HIGH_POLE, sample_rate, false);
+        let gain = 1.0
+            / (low.magnitude_at(REFERENCE_FREQUENCY, sample_rate)
+                * high.magnitude_at(REFERENCE_FREQUENCY, sample_rate));
+        low.b0 *= gain;
+        low.b1 *= gain;
+        low.b2 *= gain;
+        Some(Self {
+            sample_rate,
+            cascade: Cascade::new([low.cast(), high.cast()])?,
+        })
+    }
+
+    /// Processes a single sample.
+    pub fn process(&mut self, sample: &mut T) -> bool {
+        self.cascade.process(sample)
+    }
+
+    /// Processes a block of samples. Returns `false` if the block is empty.
+    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
+        self.cascade.process_block(samples)
+    }
+
+    /// Returns the sample rate the filter was designed for.
+    pub fn get_sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Returns the biquad sections: the low-frequency roll-off, then the high-frequency one.
+    pub fn get_sections(&self) -> &[DigitalBiquadFilter<T>; 2] {
+        self.cascade.get_sections()
+    }
+
+    /// Resets the state of both sections.
+    pub fn reset(&mut self) {
+        self.cascade.reset();
+    }
+}
+
+impl<T: Float> FrequencyResponse<T> for CWeightingFilter<T> {
+    fn response_at(&self, frequency: T, sample_rate: u32) -> Complex<T> {
+        self.cascade.response_at(frequency, sample_rate)
+    }
+}
+
+/// Returns the bilinear transform of a double real pole at `frequency` Hz, with a double zero at
+/// DC for a high-pass `s^2 / (s + w)^2` or at the Nyquist frequency for a low-pass
+/// `w^2 / (s + w)^2`.
+fn double_pole(frequency: f64, sample_rate: u32, high_pass: bool) -> Coefficients<f64> {
+    let c = 2.0 * f64::from(sample_rate);
+    let w = 2.0 * PI * frequency;
+    let (plus, minus) = (c + w, w - c);
+    let (scale, middle) = if high_pass { (c * c, -2.0) } else { (w * w, 2.0) };
+    Coefficients {
+        b0: scale,
+        b1: middle * scale,
+        b2: scale,
+        a0: plus * plus,
+        a1: 2.0 * plus * minus,
+        a2: minus * minus,
+    }
+}
+
+/// The K frequency weighting of ITU-R BS.1770, applied before measuring loudness in LUFS: a high
+/// shelf of about +4 dB above 1.5 kHz modelling the head, followed by a high-pass near 38 Hz (the
+/// revised low-frequency B curve).
+///
+/// The recommendation only lists coefficients for 48 kHz. Here both stages are derived from the
+/// analog parameters behind those coefficients, so the filter has the same response at any sample
+/// rate and reproduces the published coefficients at 48 kHz.
+#[derive(Debug, Clone)]
+pub struct KWeightingFilter<T: Float> {
+    sample_rate: u32,
+    cascade: Cascade<T, 2>,
+}
+
+impl<T: Float> KWeightingFilter<T> {
+    /// Creates the weighting filter for a sample rate. Returns `None` if the shelf frequency is
+    /// not below the Nyquist frequency.
+    pub fn new(sample_rate: u32) -> Option<Self> {
+        let rate = f64::from(sample_rate);
+        if rate <= 2.0 * K_SHELF_FREQUENCY {
+            return None;
+        }
+        let k = Float::tan(PI * K_SHELF_FREQUENCY / rate);
+        let high_gain = Float::powf(10.0, K_SHELF_GAIN / 20.0);
+        let band_gain = Float::powf(high_gain, K_SHELF_BAND_EXPONENT);
+        let shelf = Coefficients {
+            b0: high_gain + band_gain * k / K_SHELF_Q + k * k,
+            b1: 2.0 * (k * k - high_gain),
+            b2: high_gain - band_gain * k / K_SHELF_Q + k * k,
+            a0: 1.0 + k / K_SHELF_Q + k * k,
+            a1: 2.0 * (k * k - 1.0),
+            a2: 1.0 - k / K_SHELF_Q + k * k,
+        };
+        let k = Float::tan(PI * K_HIGH_PASS_FREQUENCY / rate);
+        let high_pass = Coefficients {
+            b0: 1.0,
+            b1: -2.0,
+            b2: 1.0,
+            a0: 1.0 + k / K_HIGH_PASS_Q + k * k,
+            a1: 2.0 * (k * k - 1.0),
+            a2: 1.0 - k / K_HIGH_PASS_Q + k * k,
+        };
+        // The published high-pass has a numerator of exactly one, two, one over a denominator
+        // normalized to one, so the denominator is normalized and the numerator is not.
+        let high_pass = Coefficients {
+            a1: high_pass.a1 / high_pass.a0,
+            a2: high_pass.a2 / high_pass.a0,
+            a0: 1.0,
+            ..high_pass
+        };
+        Some(Self {
+            sample_rate,
+            cascade: Cascade::new([shelf.cast(), high_pass.cast()])?,
+        })
+    }
+
+    /// Processes a single sample.
+    pub fn process(&mut self, sample: &mut T) -> bool {
+        self.cascade.process(sample)
+    }
+
+    /// Processes a block of samples. Returns `false` if the block is empty.
+    pub fn process_block(&mut self, samples: &mut [T]) -> bool {
+        self.cascade.process_block(samples)
+    }
+
+    /// Returns the sample rate the filter was designed for.
+    pub fn get_sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Returns the biquad sections: the high shelf, then the high-pass.
+    pub fn get_sections(&self) -> &[DigitalBiquadFilter<T>; 2] {
+        self.cascade.get_sections()
+    }
+
+    /// Resets the state of both sections.
+    pub fn reset(&mut self) {
+        self.cascade.reset();
+    }
+}
+
+impl<T: Float> FrequencyResponse<T> for KWeightingFilter<T> {
+    fn response_at(&self, frequency: T, sample_rate: u32) -> Complex<T> {
+        self.cascade.response_at(frequency, sample_rate)
+    }
+}
@@ -0,0 +1,245 @@
+/// zero_phase.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::{Coefficients, DigitalBiquadFilter};
+use num_traits::Float;
+
+/// Filters `samples` forwards and then backwards through a series of biquad sections, so the
+/// result has no phase shift and the magnitude response of the sections squared. Pass a single
+/// filter as `&[filter.get_coefficients()]`.
+///
+/// The signal is padded at both ends with its odd reflection, three samples per filter order,
+/// and each pass starts from the steady state for its first padded sample. This keeps the start
+/// and end of the output free of transients. Returns `false` if `samples` is empty or any
+/// section is invalid.
+pub fn filtfilt<T>(sections: &[Coefficients<T>], samples: &mut [T]) -> bool
+where
+    T: Float,
+{
+    let Some(mut filters) = build_sections(sections) else {
+        return false;
+    };
+    if samples.is_empty() {
+        return false;
+    }
+    let padding = (3 * (2 * sections.len() + 1)).min(samples.len() - 1);
+    let mut extended = Vec::with_capacity(samples.len() + 2 * padding);
+    extended.extend(reflect_start(samples, padding));
+    extended.extend_from_slice(samples);
+    extended.extend(reflect_end(samples, padding));
+
+    run(&mut filters, extended.iter_mut());
+    run(&mut filters, extended.iter_mut().rev());
+    samples.copy_from_slice(&extended[padding..padding + samples.len()]);
+    true
+}
+
+/// Creates a fresh filter for every section, or `None` if any section is invalid.
+fn build_sections<T>(sections: &[Coefficients<T>]) -> Option<Vec<DigitalBiquadFilter<T>>>
+where
+    T: Float,
+{
+    sections.iter().map(|coefficients| DigitalBiquadFilter::new(*coefficients)).collect()
+}
+
+/// Returns the odd reflection of the first `padding` samples about the first sample, in order.
+fn reflect_start<T: Float>(samples: &[T], padding: usize) -> impl Iterator<Item = T> + '_ {
+    let two = T::one() + T::one();
+    (1..=padding).rev().map(move |i| two * samples[0] - samples[i])
+}
+
+/// Returns the odd reflection of the last `padding` samples about the last sample, in order.
+fn reflect_end<T: Float>(samples: &[T], padding: usize) -> impl Iterator<Item = T> + '_ {
+    let two = T::one() + T::one();
+    let last = samples.len() - 1;
+    (1..=padding).map(move |i| two * samples[last] - samples[last - i])
+}
+
+/// Primes every section with the steady state for its first input, then filters the samples in
+/// the order given.
+fn run<'a, T, I>(filters: &mut [DigitalBiquadFilter<T>], samples: I)
+where
+    T: Float + 'a,
+    I: Iterator<Item = &'a mut T>,
+{
+    let mut samples = samples.peekable();
+    if let Some(first) = samples.peek() {
+        prime(filters, **first);
+    }
+    for sample in samples {
+        for filter in filters.iter_mut() {
+            filter.process(sample);
+        }
+    }
+}
+
+/// Sets every section to the state it would settle in after a constant input of `level`.
+/// Sections without a finite DC gain start from rest.
+fn prime<T>(filters: &mut [DigitalBiquadFilter<T>], level: T)
+where
+    T: Float,
+{
+    let mut input = level;
+    for filter in filters.iter_mut() {
+        filter.reset();
+        input = filter.settle(input).unwrap_or_else(T::zero);
+    }
+}
+
+/// Zero-phase filtering of a signal delivered in pieces, for recordings too large to hold in
+/// memory. The forward pass runs continuously, so it matches `filtfilt` exactly. The backward
+/// pass runs over `chunk_len` samples at a time plus `overlap` later samples, starting from the
+/// steady state of the last one, so its start-up transient has decayed by the time it reaches
+/// the samples that are output. The overlap should be several times the length of the impulse
+/// response of the sections; output is then indistinguishable from `filtfilt` of the whole
+/// signal.
+///
+/// Output lags input by up to `chunk_len + overlap` samples, and the remainder is produced by
+/// `finish`. Memory use is bounded by a few times `chunk_len + overlap` samples.
+#[derive(Debug, Clone)]
+pub struct ZeroPhaseStream<T: Float> {
+    forward: Vec<DigitalBiquadFilter<T>>,
+    backward: Vec<DigitalBiquadFilter<T>>,
+    padding: usize,
+    chunk_len: usize,
+    overlap: usize,
+    started: bool,
+    /// All input before the forward pass has started, then the last `padding + 1` input samples
+    /// for reflecting the end of the signal.
+    tail: Vec<T>,
+    /// Forward-filtered samples that have not been output yet.
+    filtered: Vec<T>,
+    scratch: Vec<T>,
+}
+
+impl<T> ZeroPhaseStream<T>
+where
+    T: Float,
+{
+    /// Creates a new stream through a series of biquad sections. Returns `None` if `chunk_len`
+    /// is zero or any section is invalid.
+    pub fn new(sections: &[Coefficients<T>], chunk_len: usize, overlap: usize) -> Option<Self> {
+        if chunk_len == 0 {
+            return None;
+        }
+        let forward = build_sections(sections)?;
+        Some(Self {
+            backward: forward.clone(),
+            forward,
+            padding: 3 * (2 * sections.len() + 1),
+            chunk_len,
+            overlap,
+            started: false,
+            tail: Vec::new(),
+            filtered: Vec::new(),
+            scratch: Vec::new(),
+        })
+    }
+
+    /// Filters the next piece of the signal, appending every sample that is ready to `output`.
+    /// Returns `false` if `input` is empty.
+    pub fn process(&mut self, input: &[T], output: &mut Vec<T>) -> bool {
+        if input.is_empty() {
+            return false;
+        }
+        self.tail.extend_from_slice(input);
+        if self.started {
+            for &sample in input {
+                self.push_forward(sample);
+            }
+        } else if self.tail.len() > self.padding {
+            self.start();
+        }
+        if self.started && self.tail.len() > self.padding + 1 {
+            let excess = self.tail.len() - self.padding - 1;
+            self.tail.drain(..excess);
+        }
+        while self.filtered.len() >= self.chunk_len + self.overlap {
+            self.run_backward();
+            output.extend_from_slice(&self.scratch[..self.chunk_len]);
+            self.filtered.drain(..self.chunk_len);
+        }
+        true
+    }
+
+    /// Ends the signal, appending the remaining samples to `output`, and resets the stream for
+    /// the next signal. Returns `false` if no samples were given since the last reset.
+    pub fn finish(&mut self, output: &mut Vec<T>) -> bool {
+        if !self.started {
+            let mut samples = core::mem::take(&mut self.tail);
+            let sections: Vec<Coefficients<T>> =
+                self.forward.iter().map(DigitalBiquadFilter::get_coefficients).collect();
+            let filtered = filtfilt(&sections, &mut samples);
+            output.extend_from_slice(&samples);
+            self.reset();
+            return filtered;
+        }
+        let end: Vec<T> = reflect_end(&self.tail, self.padding).collect();
+        for sample in end {
+            self.push_forward(sample);
+        }
+        self.run_backward();
+        output.extend_from_slice(&self.scratch[..self.filtered.len() - self.padding]);
+        self.reset();
+        true
+    }
+
+    /// Discards all buffered samples and state.
+    pub fn reset(&mut self) {
+        self.started = false;
+        self.tail.clear();
+        self.filtered.clear();
+        self.scratch.clear();
+    }
+
+    /// Starts the forward pass from the reflected start of the buffered input.
+    fn start(&mut self) {
+        let start: Vec<T> = reflect_start(&self.tail, self.padding).collect();
+        prime(&mut self.forward, start[0]);
+        for mut sample in start {
+            for filter in self.forward.iter_mut() {
+                filter.process(&mut sample);
+            }
+        }
+        let buffered = core::mem::take(&mut self.tail);
+        for &sample in buffered.iter() {
+            self.push_forward(sample);
+        }
+        self.tail = buffered;
+        self.started = true;
+    }
+
+    /// Runs one sample through the forward pass and buffers the result.
+    fn push_forward(&mut self, mut sample: T) {
+        for filter in self.forward.iter_mut() {
+            filter.process(&mut sample);
+        }
+        self.filtered.push(sample);
+    }
+
+    /// Runs the backward pass over a copy of every buffered forward-filtered sample.
+    fn run_backward(&mut self) {
+        self.scratch.clear();
+        self.scratch.extend_from_slice(&self.filtered);
+        run(&mut self.backward, self.scratch.iter_mut().rev());
+    }
+}
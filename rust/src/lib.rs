@@ -1,5 +1,5 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 /// lib.rs
-
 /**
 Copyright © 2025 Alex Parisi
 
@@ -21,10 +21,102 @@ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
 OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("either the `std` or the `libm` feature must be enabled");
+
 mod filters;
+#[cfg(feature = "macros")]
+pub use biquad_filters_macros::{BiquadFilterWrapper, biquad};
+#[cfg(feature = "std")]
+pub mod analysis;
+pub mod convert;
+#[cfg(feature = "std")]
+pub mod design;
+#[cfg(feature = "proptest")]
+pub mod strategies;
+#[cfg(feature = "symphonia")]
+pub mod symphonia;
+#[cfg(feature = "std")]
+pub mod stream;
 
-pub use crate::filters::filter::Filter;
-pub use crate::filters::biquad::{Coefficients, DigitalBiquadFilter};
+pub use crate::filters::deesser::{DeEsser, DeEsserBand};
+pub use crate::filters::descriptor::{ParameterDescriptor, ParameterScale};
+pub use crate::filters::design_method::DesignMethod;
+pub use crate::filters::emphasis::{EmphasisFilter, EmphasisTimeConstant};
+pub use crate::filters::envelope::{AutoWah, EnvelopeFollower};
+pub use crate::filters::error::FilterError;
+pub use crate::filters::fault::{Fault, FaultHandler, Faults};
+pub use crate::filters::filter::{BiquadFilterWrapper, Filter, FilterExt, FilterType};
+pub use crate::filters::filter_configuration::{FilterConfiguration, FilterConfigurationBuilder};
+pub use crate::filters::adaptive_notch::AdaptiveNotch;
+#[cfg(feature = "std")]
+pub use crate::filters::automation::{
+    AutomatedFilter, AutomationEvent, Parameter, ParameterBinding, ParameterChange, RampShape,
+};
+pub use crate::filters::biquad::{Coefficients, DigitalBiquadFilter, State, tick, tick_block};
+#[cfg(feature = "std")]
+pub use crate::filters::butterworth::ButterworthFilter;
+pub use crate::filters::cascade::{Cascade, ScalingNorm};
+#[cfg(feature = "std")]
+pub use crate::filters::chain::{ChainStage, FilterChain, FilterStage};
+pub use crate::filters::complementary::ComplementaryFilter;
+pub use crate::filters::complex_biquad::{ComplexBiquad, ComplexCoefficients};
+#[cfg(feature = "std")]
+pub use crate::filters::convolution::PartitionedConvolver;
+#[cfg(feature = "std")]
+pub use crate::filters::crossover::{Crossover, CrossoverMode, CrossoverNetwork};
+#[cfg(feature = "std")]
+pub use crate::filters::filter_bank::{FilterBank, FrequencyScale};
+#[cfg(feature = "std")]
+pub use crate::filters::gammatone::{GammatoneBank, equivalent_rectangular_bandwidth};
+#[cfg(feature = "state-history")]
+pub use crate::filters::history::HistoryEntry;
+#[cfg(feature = "std")]
+pub use crate::filters::fir::{FirApproximation, Window};
+pub use crate::filters::frequency_shifter::FrequencyShifter;
+pub use crate::filters::goertzel::GoertzelDetector;
+#[cfg(feature = "std")]
+pub use crate::filters::graphic_eq::{GraphicEq, GraphicEqLayout};
+pub use crate::filters::hilbert::HilbertTransformer;
+#[cfg(feature = "std")]
+pub use crate::filters::iir::IirFilter;
+pub use crate::filters::ladder::LadderFilter;
+pub use crate::filters::limiter::OutputLimit;
+pub use crate::filters::pinking::PinkingFilter;
+#[cfg(feature = "std")]
+pub use crate::filters::preset::{Preset, PresetError};
+#[cfg(feature = "std")]
+pub use crate::filters::linkwitz_riley::LinkwitzRileyFilter;
+#[cfg(feature = "metering")]
+pub use crate::filters::metering::{LevelMeter, Metered, Meters};
+#[cfg(feature = "std")]
+pub use crate::filters::modulation::{Lfo, LfoShape, ModulatedFilter};
+#[cfg(feature = "std")]
+pub use crate::filters::multichannel::MultiChannelFilter;
+#[cfg(feature = "std")]
+pub use crate::filters::parallel::ParallelBank;
+#[cfg(feature = "std")]
+pub use crate::filters::parametric_eq::ParametricEq;
+pub use crate::filters::resonance::{
+    q_to_resonance, q_to_shelf_slope, resonance_to_q, shelf_slope_to_q,
+};
+pub use crate::filters::response::FrequencyResponse;
+pub use crate::filters::saturation::Saturation;
+pub use crate::filters::topology::Topology;
+pub use crate::filters::update::UpdatePolicy;
+pub use crate::filters::weighting::{CWeightingFilter, KWeightingFilter};
+pub use crate::filters::snapshot::{ConfigDiff, ConfigSnapshot};
+#[cfg(feature = "std")]
+pub use crate::filters::spec::{FilterSpec, SpecError, parse_chain};
+pub use crate::filters::state_variable::{StateVariableFilter, SvfMode, SvfOutputs};
+pub use crate::filters::stats::BlockStats;
+pub use crate::filters::stereo::{ChannelOffset, StereoFilter};
+#[cfg(feature = "std")]
+pub use crate::filters::thiran::ThiranDelay;
+#[cfg(feature = "std")]
+pub use crate::filters::zero_phase::{ZeroPhaseStream, filtfilt};
+#[cfg(feature = "heapless")]
+pub use crate::filters::cascade::BoundedCascade;
 pub use crate::filters::low_pass::LowPassFilter;
 pub use crate::filters::high_pass::HighPassFilter;
 pub use crate::filters::band_pass::BandPassFilter;
@@ -32,4 +124,17 @@ pub use crate::filters::all_pass::AllPassFilter;
 pub use crate::filters::notch::NotchFilter;
 pub use crate::filters::peaking_eq::PeakingEQFilter;
 pub use crate::filters::low_shelf::LowShelfFilter;
-pub use crate::filters::high_shelf::HighShelfFilter;
\ No newline at end of file
+pub use crate::filters::high_shelf::HighShelfFilter;
+pub use crate::filters::tilt::TiltFilter;
+
+/// Items used by code that `impl_biquad_filter!` and `#[derive(BiquadFilterWrapper)]` expand in
+/// other crates. Not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::filters::filter::{
+        apply_configuration, lazy_updates_wrapped, process_block_with_stats_wrapped,
+        process_block_wrapped, process_samples_wrapped, process_wrapped, set_lazy_updates_wrapped,
+    };
+    pub use num_complex::Complex;
+    pub use num_traits::Float;
+}
\ No newline at end of file
@@ -0,0 +1,116 @@
+/// strategies.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::biquad::Coefficients;
+use crate::filters::filter::FilterType;
+use crate::filters::filter_configuration::FilterConfiguration;
+use core::fmt::Debug;
+use core::ops::Range;
+use num_traits::Float;
+use proptest::prelude::*;
+
+/// The sample rates generated configurations are drawn from.
+const SAMPLE_RATES: [u32; 7] = [8000, 16000, 22050, 44100, 48000, 96000, 192000];
+/// The largest pole radius of generated coefficients, which keeps them clear of instability.
+const MAX_POLE_RADIUS: f64 = 0.999;
+
+/// Converts a generated value to the float type.
+fn float<T: Float>(value: f64) -> T {
+    T::from(value).expect("generated value must be representable")
+}
+
+/// Generates common audio sample rates from 8 kHz to 192 kHz.
+pub fn sample_rate() -> impl Strategy<Value = u32> {
+    proptest::sample::select(SAMPLE_RATES.to_vec())
+}
+
+/// Generates every filter type.
+pub fn filter_type() -> impl Strategy<Value = FilterType> {
+    proptest::sample::select(vec![
+        FilterType::LowPass,
+        FilterType::HighPass,
+        FilterType::BandPass,
+        FilterType::Notch,
+        FilterType::AllPass,
+        FilterType::PeakingEQ,
+        FilterType::LowShelf,
+        FilterType::HighShelf,
+        FilterType::Tilt,
+    ])
+}
+
+/// Generates configurations every filter type accepts: a common sample rate, a cutoff between
+/// 10 Hz and 45% of the sample rate, a Q factor between 0.1 and 20, and a gain within ±24 dB.
+/// Frequencies and Q factors are spread evenly over ratios rather than values, and generated
+/// configurations are never bypassed.
+pub fn configuration<T>() -> impl Strategy<Value = FilterConfiguration<T>>
+where
+    T: Float + Debug + 'static,
+{
+    (
+        sample_rate(),
+        0.0..=1.0_f64,
+        0.1_f64.ln()..=20.0_f64.ln(),
+        -24.0..=24.0_f64,
+        any::<bool>(),
+    )
+        .prop_map(|(sample_rate, position, log_q, gain, constant_skirt_gain)| {
+            let cutoff = 10.0 * (0.045 * sample_rate as f64).powf(position);
+            FilterConfiguration::new(
+                float(cutoff),
+                sample_rate,
+                float(log_q.exp()),
+                float(gain),
+                constant_skirt_gain,
+                false,
+            )
+        })
+}
+
+/// Generates stable, normalized coefficients: the poles are drawn inside a circle of radius
+/// 0.999 and the numerator coefficients from `[-4, 4]`.
+pub fn stable_coefficients<T>() -> impl Strategy<Value = Coefficients<T>>
+where
+    T: Float + Debug + 'static,
+{
+    (
+        0.0..=MAX_POLE_RADIUS,
+        0.0..=core::f64::consts::PI,
+        [-4.0..=4.0_f64, -4.0..=4.0_f64, -4.0..=4.0_f64],
+    )
+        .prop_map(|(radius, angle, [b0, b1, b2])| Coefficients {
+            b0: float(b0),
+            b1: float(b1),
+            b2: float(b2),
+            a0: T::one(),
+            a1: float(-2.0 * radius * angle.cos()),
+            a2: float(radius * radius),
+        })
+}
+
+/// Generates blocks of samples in `[-1, 1]` whose length is drawn from `len`.
+pub fn samples<T>(len: Range<usize>) -> impl Strategy<Value = Vec<T>>
+where
+    T: Float + Debug + 'static,
+{
+    proptest::collection::vec((-1.0..=1.0_f64).prop_map(float), len)
+}
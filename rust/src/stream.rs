@@ -0,0 +1,318 @@
+/// stream.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::convert::Sample;
+use crate::filters::filter::Filter;
+use num_traits::Float;
+use std::io::{self, Read, Write};
+
+/// The number of bytes requested from the inner reader at a time.
+const READ_CHUNK: usize = 4096;
+
+/// The encoding of raw samples in a stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SampleFormat {
+    /// 32-bit little-endian floats in `[-1, 1)`.
+    #[default]
+    F32,
+    /// 16-bit little-endian signed integers.
+    I16,
+}
+
+impl SampleFormat {
+    /// Returns the number of bytes in one sample.
+    pub fn bytes_per_sample(&self) -> usize {
+        match self {
+            SampleFormat::F32 => 4,
+            SampleFormat::I16 => 2,
+        }
+    }
+
+    /// Decodes one sample from exactly `bytes_per_sample` bytes.
+    fn decode<T: Float>(&self, bytes: &[u8]) -> T {
+        match self {
+            SampleFormat::F32 => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]).to_float(),
+            SampleFormat::I16 => i16::from_le_bytes([bytes[0], bytes[1]]).to_float(),
+        }
+    }
+
+    /// Encodes one sample into exactly `bytes_per_sample` bytes.
+    fn encode<T: Float>(&self, value: T, bytes: &mut [u8]) {
+        match self {
+            SampleFormat::F32 => bytes.copy_from_slice(&f32::from_float(value).to_le_bytes()),
+            SampleFormat::I16 => bytes.copy_from_slice(&i16::from_float(value).to_le_bytes()),
+        }
+    }
+}
+
+/// How the samples of multiple channels are arranged in a stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Interleaving {
+    /// Each frame holds one sample of every channel in turn.
+    #[default]
+    Interleaved,
+    /// The stream is split into blocks of `block_frames` frames, each holding all the samples of
+    /// the first channel, then all of the second, and so on. The last block may be shorter.
+    Planar {
+        /// The number of frames in each block.
+        block_frames: usize,
+    },
+}
+
+/// Splits raw bytes into channels, filters them, and encodes the result, holding back bytes that
+/// do not yet form a complete frame or block.
+#[derive(Debug)]
+struct FrameFilter<T, F> {
+    filters: Vec<F>,
+    format: SampleFormat,
+    interleaving: Interleaving,
+    pending: Vec<u8>,
+    scratch: Vec<T>,
+}
+
+impl<T: Float, F: Filter<T>> FrameFilter<T, F> {
+    /// Returns `None` if there are no filters or planar blocks are empty.
+    fn new(filters: Vec<F>, format: SampleFormat, interleaving: Interleaving) -> Option<Self> {
+        if filters.is_empty() || interleaving == (Interleaving::Planar { block_frames: 0 }) {
+            return None;
+        }
+        Some(Self {
+            filters,
+            format,
+            interleaving,
+            pending: Vec::new(),
+            scratch: Vec::new(),
+        })
+    }
+
+    /// Returns the number of bytes in one frame.
+    fn frame_bytes(&self) -> usize {
+        self.filters.len() * self.format.bytes_per_sample()
+    }
+
+    /// Appends `bytes` and filters every complete frame or block into `output`.
+    fn push(&mut self, bytes: &[u8], output: &mut Vec<u8>) -> io::Result<()> {
+        self.pending.extend_from_slice(bytes);
+        let unit = match self.interleaving {
+            Interleaving::Interleaved => self.frame_bytes(),
+            Interleaving::Planar { block_frames } => block_frames * self.frame_bytes(),
+        };
+        let complete = self.pending.len() - self.pending.len() % unit;
+        if complete == 0 {
+            return Ok(());
+        }
+        let start = output.len();
+        output.extend(self.pending.drain(..complete));
+        let frames = unit / self.frame_bytes();
+        for block in output[start..].chunks_mut(unit) {
+            self.filter(block, frames)?;
+        }
+        Ok(())
+    }
+
+    /// Filters the bytes left at the end of the stream into `output`. Returns an error if the
+    /// stream ended partway through a frame.
+    fn finish(&mut self, output: &mut Vec<u8>) -> io::Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        if !self.pending.len().is_multiple_of(self.frame_bytes()) {
+            self.pending.clear();
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "stream ended partway through a frame",
+            ));
+        }
+        let frames = self.pending.len() / self.frame_bytes();
+        let start = output.len();
+        output.append(&mut self.pending);
+        self.filter(&mut output[start..], frames)
+    }
+
+    /// Filters `frames` complete frames laid out according to the interleaving.
+    fn filter(&mut self, bytes: &mut [u8], frames: usize) -> io::Result<()> {
+        let width = self.format.bytes_per_sample();
+        let channels = self.filters.len();
+        let interleaving = self.interleaving;
+        let index = |channel: usize, frame: usize| match interleaving {
+            Interleaving::Interleaved => (frame * channels + channel) * width,
+            Interleaving::Planar { .. } => (channel * frames + frame) * width,
+        };
+        self.scratch.resize(frames, T::zero());
+        for (channel, filter) in self.filters.iter_mut().enumerate() {
+            for (frame, value) in self.scratch.iter_mut().enumerate() {
+                let start = index(channel, frame);
+                *value = self.format.decode(&bytes[start..start + width]);
+            }
+            if !filter.process_block(&mut self.scratch) {
+                return Err(io::Error::other("filter rejected the block"));
+            }
+            for (frame, value) in self.scratch.iter().enumerate() {
+                let start = index(channel, frame);
+                self.format.encode(*value, &mut bytes[start..start + width]);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Wraps a reader of raw samples and filters them as they are read, with one filter per channel.
+/// Bytes are returned once a whole frame, or a whole block for planar streams, is available.
+#[derive(Debug)]
+pub struct FilteredReader<R, T, F> {
+    inner: R,
+    frames: FrameFilter<T, F>,
+    ready: Vec<u8>,
+    position: usize,
+    finished: bool,
+}
+
+impl<R: Read, T: Float, F: Filter<T>> FilteredReader<R, T, F> {
+    /// Creates a reader filtering one channel per filter. Returns `None` if there are no filters
+    /// or planar blocks are empty.
+    pub fn new(
+        inner: R,
+        format: SampleFormat,
+        interleaving: Interleaving,
+        filters: Vec<F>,
+    ) -> Option<Self> {
+        Some(Self {
+            inner,
+            frames: FrameFilter::new(filters, format, interleaving)?,
+            ready: Vec::new(),
+            position: 0,
+            finished: false,
+        })
+    }
+
+    /// Returns the filters, one per channel.
+    pub fn get_filters(&self) -> &[F] {
+        &self.frames.filters
+    }
+
+    /// Returns the filters for changing their parameters while streaming.
+    pub fn get_filters_mut(&mut self) -> &mut [F] {
+        &mut self.frames.filters
+    }
+
+    /// Returns the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the inner reader, discarding any bytes that have not been read yet.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read, T: Float, F: Filter<T>> Read for FilteredReader<R, T, F> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        while self.position == self.ready.len() {
+            self.ready.clear();
+            self.position = 0;
+            if self.finished {
+                return Ok(0);
+            }
+            let mut chunk = [0_u8; READ_CHUNK];
+            let read = self.inner.read(&mut chunk)?;
+            if read == 0 {
+                self.finished = true;
+                self.frames.finish(&mut self.ready)?;
+            } else {
+                self.frames.push(&chunk[..read], &mut self.ready)?;
+            }
+        }
+        let count = buf.len().min(self.ready.len() - self.position);
+        buf[..count].copy_from_slice(&self.ready[self.position..self.position + count]);
+        self.position += count;
+        Ok(count)
+    }
+}
+
+/// Wraps a writer of raw samples and filters them as they are written, with one filter per
+/// channel. Incomplete frames, or incomplete blocks for planar streams, are held back until the
+/// rest arrives, so call `finish` at the end of the stream to write a shorter final block.
+#[derive(Debug)]
+pub struct FilteredWriter<W, T, F> {
+    inner: W,
+    frames: FrameFilter<T, F>,
+    buffer: Vec<u8>,
+}
+
+impl<W: Write, T: Float, F: Filter<T>> FilteredWriter<W, T, F> {
+    /// Creates a writer filtering one channel per filter. Returns `None` if there are no filters
+    /// or planar blocks are empty.
+    pub fn new(
+        inner: W,
+        format: SampleFormat,
+        interleaving: Interleaving,
+        filters: Vec<F>,
+    ) -> Option<Self> {
+        Some(Self {
+            inner,
+            frames: FrameFilter::new(filters, format, interleaving)?,
+            buffer: Vec::new(),
+        })
+    }
+
+    /// Returns the filters, one per channel.
+    pub fn get_filters(&self) -> &[F] {
+        &self.frames.filters
+    }
+
+    /// Returns the filters for changing their parameters while streaming.
+    pub fn get_filters_mut(&mut self) -> &mut [F] {
+        &mut self.frames.filters
+    }
+
+    /// Returns the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Filters and writes any final partial block, flushes, and returns the inner writer.
+    /// Returns an error if the stream ended partway through a frame.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.buffer.clear();
+        self.frames.finish(&mut self.buffer)?;
+        self.inner.write_all(&self.buffer)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write, T: Float, F: Filter<T>> Write for FilteredWriter<W, T, F> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.clear();
+        self.frames.push(buf, &mut self.buffer)?;
+        self.inner.write_all(&self.buffer)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
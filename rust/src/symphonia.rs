@@ -0,0 +1,78 @@
+/// symphonia.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use crate::filters::filter::Filter;
+use num_traits::Float;
+use symphonia_core::audio::{AudioBuffer, AudioBufferRef, Signal};
+use symphonia_core::conv::ReversibleSample;
+
+/// Filters every channel of a decoded buffer in place, using one filter per channel in the
+/// buffer's channel order. Samples are converted to `T` and back, so any sample format can be
+/// filtered. Returns `false` if the number of filters does not match the number of channels, the
+/// buffer holds no frames, or a filter fails.
+pub fn filter_buffer<S, T, F>(buffer: &mut AudioBuffer<S>, filters: &mut [F]) -> bool
+where
+    S: ReversibleSample<f64>,
+    T: Float,
+    F: Filter<T>,
+{
+    if buffer.spec().channels.count() != filters.len() || buffer.frames() == 0 {
+        return false;
+    }
+    let mut scratch = vec![T::zero(); buffer.frames()];
+    let mut planes = buffer.planes_mut();
+    for (plane, filter) in planes.planes().iter_mut().zip(filters.iter_mut()) {
+        for (value, sample) in scratch.iter_mut().zip(plane.iter()) {
+            let sample: f64 = (*sample).into_sample();
+            *value = T::from(sample).unwrap_or_else(T::zero);
+        }
+        if !filter.process_block(&mut scratch) {
+            return false;
+        }
+        for (sample, value) in plane.iter_mut().zip(scratch.iter()) {
+            *sample = S::from_sample(value.to_f64().unwrap_or(0.0));
+        }
+    }
+    true
+}
+
+/// Filters a buffer as returned by a symphonia decoder, whatever its sample format. A buffer
+/// borrowed from the decoder is copied first so the decoder's own buffer is left untouched.
+/// Returns `false` under the same conditions as `filter_buffer`.
+pub fn filter_buffer_ref<T, F>(buffer: &mut AudioBufferRef<'_>, filters: &mut [F]) -> bool
+where
+    T: Float,
+    F: Filter<T>,
+{
+    match buffer {
+        AudioBufferRef::U8(buffer) => filter_buffer(buffer.to_mut(), filters),
+        AudioBufferRef::U16(buffer) => filter_buffer(buffer.to_mut(), filters),
+        AudioBufferRef::U24(buffer) => filter_buffer(buffer.to_mut(), filters),
+        AudioBufferRef::U32(buffer) => filter_buffer(buffer.to_mut(), filters),
+        AudioBufferRef::S8(buffer) => filter_buffer(buffer.to_mut(), filters),
+        AudioBufferRef::S16(buffer) => filter_buffer(buffer.to_mut(), filters),
+        AudioBufferRef::S24(buffer) => filter_buffer(buffer.to_mut(), filters),
+        AudioBufferRef::S32(buffer) => filter_buffer(buffer.to_mut(), filters),
+        AudioBufferRef::F32(buffer) => filter_buffer(buffer.to_mut(), filters),
+        AudioBufferRef::F64(buffer) => filter_buffer(buffer.to_mut(), filters),
+    }
+}
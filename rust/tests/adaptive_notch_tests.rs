@@ -0,0 +1,80 @@
+/// adaptive_notch_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::AdaptiveNotch;
+use approx::assert_relative_eq;
+
+const SAMPLE_RATE: u32 = 48000;
+
+fn hum(frequency: f64, length: usize) -> Vec<f64> {
+    (0..length)
+        .map(|n| {
+            let t = n as f64 / SAMPLE_RATE as f64;
+            (2.0 * std::f64::consts::PI * frequency * t).sin()
+        })
+        .collect()
+}
+
+fn rms(samples: &[f64]) -> f64 {
+    (samples.iter().map(|s| s * s).sum::<f64>() / samples.len() as f64).sqrt()
+}
+
+#[test]
+fn test_tracks_drifted_hum() {
+    let mut notch = AdaptiveNotch::<f64>::new(50.0_f64, 1.0_f64, SAMPLE_RATE).unwrap();
+    let mut samples = hum(50.3, SAMPLE_RATE as usize * 4);
+    assert!(notch.process_block(&mut samples));
+    assert_relative_eq!(notch.get_frequency(), 50.3, epsilon = 0.01);
+    let tail = &samples[samples.len() - SAMPLE_RATE as usize / 10..];
+    assert!(rms(tail) < 0.01);
+}
+
+#[test]
+fn test_tracks_in_single_precision() {
+    let mut notch = AdaptiveNotch::<f32>::new(60.0_f32, 1.0_f32, SAMPLE_RATE).unwrap();
+    let mut samples: Vec<f32> = hum(59.8, SAMPLE_RATE as usize * 4)
+        .into_iter()
+        .map(|s| s as f32)
+        .collect();
+    notch.process_block(&mut samples);
+    assert_relative_eq!(notch.get_frequency(), 59.8, epsilon = 0.02);
+}
+
+#[test]
+fn test_estimate_stays_within_tolerance() {
+    let mut notch = AdaptiveNotch::<f64>::new(50.0_f64, 0.5_f64, SAMPLE_RATE).unwrap();
+    let mut samples = hum(53.0, SAMPLE_RATE as usize * 2);
+    notch.process_block(&mut samples);
+    assert!(notch.get_frequency() <= 50.5 + 1e-9);
+    notch.reset();
+    assert_relative_eq!(notch.get_frequency(), 50.0, epsilon = 1e-9);
+}
+
+#[test]
+fn test_invalid_parameters() {
+    assert!(AdaptiveNotch::<f64>::new(50.0_f64, 60.0_f64, SAMPLE_RATE).is_none());
+    assert!(AdaptiveNotch::<f64>::new(50.0_f64, -1.0_f64, SAMPLE_RATE).is_none());
+    let mut notch = AdaptiveNotch::<f64>::new(50.0_f64, 0.5_f64, SAMPLE_RATE).unwrap();
+    assert!(!notch.set_bandwidth(0.0_f64));
+    assert!(!notch.set_adaptation_rate(2.0_f64));
+    assert_relative_eq!(notch.get_bandwidth(), AdaptiveNotch::<f64>::DEFAULT_BANDWIDTH);
+}
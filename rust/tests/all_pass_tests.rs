@@ -1,5 +1,4 @@
 /// all_pass_tests.rs
-
 /**
 Copyright © 2025 Alex Parisi
 
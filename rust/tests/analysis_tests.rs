@@ -0,0 +1,136 @@
+/// analysis_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::analysis::{cutoff_points, report};
+use biquad_filters::{
+    DesignMethod, Filter, FilterType, FrequencyResponse, HighPassFilter,
+    LowPassFilter, NotchFilter, PeakingEQFilter,
+};
+use approx::assert_relative_eq;
+
+#[test]
+fn report_low_pass_filter() {
+    let filter = LowPassFilter::<f64>::new(
+        1000.0_f64,
+        48000_u32,
+        std::f64::consts::FRAC_1_SQRT_2
+    ).unwrap();
+    let report = report(&filter).unwrap();
+    assert_eq!(report.filter_type, Some(FilterType::LowPass));
+    assert_relative_eq!(report.coefficients.a0, 1.0_f64);
+    assert_eq!(report.poles.len(), 2);
+    assert_eq!(report.zeros.len(), 2);
+    assert!(report.stability_margin > 0.0);
+    assert_relative_eq!(report.dc_gain, 1.0_f64, epsilon = 1e-9);
+    assert!(report.nyquist_gain < 1e-9);
+    assert_eq!(report.cutoff_points.len(), 1);
+    assert_relative_eq!(report.cutoff_points[0], 1000.0_f64, epsilon = 1e-3);
+}
+
+#[test]
+fn report_high_pass_filter() {
+    let filter = HighPassFilter::<f64>::new(
+        2000.0_f64,
+        44100_u32,
+        std::f64::consts::FRAC_1_SQRT_2
+    ).unwrap();
+    let report = report(&filter).unwrap();
+    assert_eq!(report.filter_type, Some(FilterType::HighPass));
+    assert!(report.dc_gain < 1e-9);
+    assert_relative_eq!(report.nyquist_gain, 1.0_f64, epsilon = 1e-9);
+    assert_eq!(report.cutoff_points.len(), 1);
+    assert_relative_eq!(report.cutoff_points[0], 2000.0_f64, epsilon = 1e-3);
+}
+
+#[test]
+fn report_notch_filter_edges() {
+    let filter = NotchFilter::<f64>::new(
+        1000.0_f64,
+        48000_u32,
+        2.0_f64
+    ).unwrap();
+    let report = report(&filter).unwrap();
+    assert_eq!(report.cutoff_points.len(), 2);
+    assert!(report.cutoff_points[0] < 1000.0 && report.cutoff_points[1] > 1000.0);
+    for zero in &report.zeros {
+        assert_relative_eq!(zero.norm(), 1.0_f64, epsilon = 1e-9);
+    }
+}
+
+#[test]
+fn report_peaking_filter_peak() {
+    let filter = PeakingEQFilter::<f64>::new(
+        3000.0_f64,
+        48000_u32,
+        1.0_f64,
+        6.0_f64
+    ).unwrap();
+    let report = report(&filter).unwrap();
+    assert_relative_eq!(report.peak_frequency, 3000.0_f64, epsilon = 1e-2);
+    assert_relative_eq!(20.0 * report.peak_gain.log10(), 6.0_f64, epsilon = 1e-6);
+}
+
+#[test]
+fn report_peak_gain_matches_max_gain() {
+    let peaking = PeakingEQFilter::<f64>::new(3000.0_f64, 48000_u32, 4.0_f64, 12.0_f64).unwrap();
+    let low_pass = LowPassFilter::<f64>::new(1000.0_f64, 48000_u32, 4.0_f64).unwrap();
+    let high_pass = HighPassFilter::<f64>::new(200.0_f64, 48000_u32, 0.5_f64).unwrap();
+    assert_eq!(report(&peaking).unwrap().peak_gain, peaking.max_gain());
+    assert_eq!(report(&low_pass).unwrap().peak_gain, low_pass.max_gain());
+    assert_eq!(report(&high_pass).unwrap().peak_gain, high_pass.max_gain());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn report_serializes_to_json() {
+    let filter = LowPassFilter::<f64>::new(
+        1000.0_f64,
+        48000_u32,
+        std::f64::consts::FRAC_1_SQRT_2
+    ).unwrap();
+    let report = report(&filter).unwrap();
+    let json = serde_json::to_string(&report).unwrap();
+    assert!(json.contains("\"filter_type\":\"LowPass\""));
+    let parsed: biquad_filters::analysis::DesignReport<f64> = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.cutoff_points.len(), report.cutoff_points.len());
+}
+
+#[test]
+fn cutoff_points_report_the_realized_edge() {
+    let mut filter = LowPassFilter::<f64>::new(
+        16000.0_f64,
+        44100_u32,
+        std::f64::consts::FRAC_1_SQRT_2
+    ).unwrap();
+    assert!(filter.set_design_method(DesignMethod::Matched));
+    let coefficients = FilterType::LowPass
+        .design_coefficients(&filter.get_configuration())
+        .and_then(|coefficients| coefficients.normalized())
+        .unwrap();
+    let points = cutoff_points(&coefficients, 44100).unwrap();
+    assert_eq!(points.len(), 1);
+    let level = coefficients.max_gain() / 2.0_f64.sqrt();
+    assert_relative_eq!(coefficients.magnitude_at(points[0], 44100), level, epsilon = 1e-9);
+    assert_eq!(points, report(&filter).unwrap().cutoff_points);
+
+    assert!(cutoff_points(&coefficients, 0).is_none());
+}
@@ -0,0 +1,204 @@
+/// automation_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{
+    AutomatedFilter, Filter, LowPassFilter, Parameter, ParameterBinding, ParameterChange,
+    PeakingEQFilter, RampShape,
+};
+use approx::assert_relative_eq;
+
+fn make_filter() -> LowPassFilter<f64> {
+    LowPassFilter::<f64>::new(
+        1000.0_f64,
+        48000_u32,
+        std::f64::consts::FRAC_1_SQRT_2
+    ).unwrap()
+}
+
+fn make_signal(length: usize) -> Vec<f64> {
+    (0..length).map(|n| ((n * 13) % 7) as f64 - 3.0).collect()
+}
+
+#[test]
+fn events_apply_at_exact_offsets() {
+    let mut automated = AutomatedFilter::new(make_filter());
+    automated.schedule(40, ParameterChange::Cutoff(4000.0_f64));
+    automated.schedule(10, ParameterChange::QFactor(2.0_f64));
+
+    let mut reference = make_filter();
+    let mut expected = make_signal(64);
+    reference.process_block(&mut expected[..10]);
+    reference.set_q_factor(2.0_f64);
+    reference.process_block(&mut expected[10..40]);
+    reference.set_cutoff(4000.0_f64);
+    reference.process_block(&mut expected[40..]);
+
+    let mut samples = make_signal(64);
+    assert!(automated.process_block(&mut samples));
+    for (actual, expected) in samples.iter().zip(expected.iter()) {
+        assert_relative_eq!(*actual, *expected);
+    }
+    assert!(automated.get_pending_events().is_empty());
+    assert_relative_eq!(automated.get_filter().get_cutoff(), 4000.0_f64);
+}
+
+#[test]
+fn events_beyond_block_carry_over() {
+    let mut automated = AutomatedFilter::new(make_filter());
+    automated.schedule(70, ParameterChange::Cutoff(2000.0_f64));
+
+    let mut samples = make_signal(64);
+    automated.process_block(&mut samples);
+    assert_eq!(automated.get_pending_events().len(), 1);
+    assert_eq!(automated.get_pending_events()[0].offset, 6);
+    assert_relative_eq!(automated.get_filter().get_cutoff(), 1000.0_f64);
+
+    automated.process_block(&mut samples);
+    assert!(automated.get_pending_events().is_empty());
+    assert_relative_eq!(automated.get_filter().get_cutoff(), 2000.0_f64);
+}
+
+#[test]
+fn same_offset_events_apply_in_order() {
+    let mut automated = AutomatedFilter::new(make_filter());
+    automated.schedule(5, ParameterChange::Cutoff(2000.0_f64));
+    automated.schedule(5, ParameterChange::Cutoff(3000.0_f64));
+
+    let mut samples = make_signal(16);
+    automated.process_block(&mut samples);
+    assert_relative_eq!(automated.get_filter().get_cutoff(), 3000.0_f64);
+}
+
+#[test]
+fn rejected_event_reports_failure() {
+    let mut automated = AutomatedFilter::new(make_filter());
+    automated.schedule(0, ParameterChange::Cutoff(-1.0_f64));
+
+    let mut samples = make_signal(16);
+    assert!(!automated.process_block(&mut samples));
+    assert_relative_eq!(automated.get_filter().get_cutoff(), 1000.0_f64);
+}
+
+#[test]
+fn ramp_reaches_target_across_blocks() {
+    let mut automated = AutomatedFilter::new(make_filter());
+    automated.ramp(Parameter::Cutoff, 4000.0_f64, 100, RampShape::Octaves);
+
+    let mut samples = make_signal(64);
+    automated.process_block(&mut samples);
+    assert!(automated.is_ramping(Parameter::Cutoff));
+    let cutoff = automated.get_filter().get_cutoff();
+    assert!(cutoff > 1000.0 && cutoff < 4000.0);
+
+    automated.process_block(&mut samples);
+    automated.process_block(&mut samples);
+    assert!(!automated.is_ramping(Parameter::Cutoff));
+    assert_relative_eq!(automated.get_filter().get_cutoff(), 4000.0_f64);
+}
+
+#[test]
+fn ramp_updates_at_control_interval() {
+    let mut automated = AutomatedFilter::new(make_filter());
+    assert!(automated.set_control_interval(8));
+    assert!(!automated.set_control_interval(0));
+    automated.ramp(Parameter::Cutoff, 2000.0_f64, 32, RampShape::Linear);
+
+    let mut samples = make_signal(8);
+    automated.process_block(&mut samples);
+    assert_relative_eq!(automated.get_filter().get_cutoff(), 1000.0_f64);
+    automated.process_block(&mut samples);
+    assert_relative_eq!(automated.get_filter().get_cutoff(), 1250.0_f64);
+}
+
+#[test]
+fn event_cancels_ramp() {
+    let mut automated = AutomatedFilter::new(make_filter());
+    automated.ramp(Parameter::Cutoff, 4000.0_f64, 1000, RampShape::Linear);
+    automated.schedule(10, ParameterChange::Cutoff(500.0_f64));
+
+    let mut samples = make_signal(64);
+    automated.process_block(&mut samples);
+    assert!(!automated.is_ramping(Parameter::Cutoff));
+    assert_relative_eq!(automated.get_filter().get_cutoff(), 500.0_f64);
+}
+
+#[test]
+fn ramp_shapes_for_frequency() {
+    let cutoff = Parameter::Cutoff;
+    assert_relative_eq!(RampShape::Linear.interpolate(cutoff, 100.0_f64, 1600.0, 0.5), 850.0);
+    assert_relative_eq!(RampShape::Octaves.interpolate(cutoff, 100.0_f64, 1600.0, 0.5), 400.0);
+    assert_relative_eq!(RampShape::Decibels.interpolate(cutoff, 100.0_f64, 1600.0, 0.5), 400.0);
+    let exponential = RampShape::Exponential.interpolate(cutoff, 100.0_f64, 1600.0, 0.5);
+    assert!(exponential > 850.0 && exponential < 1600.0);
+    assert_relative_eq!(RampShape::Exponential.interpolate(cutoff, 100.0_f64, 1600.0, 1.0), 1600.0);
+}
+
+#[test]
+fn ramp_shapes_for_gain() {
+    let gain = Parameter::Gain;
+    assert_relative_eq!(RampShape::Decibels.interpolate(gain, -12.0_f64, 12.0, 0.5), 0.0);
+    let linear = RampShape::Linear.interpolate(gain, -12.0_f64, 12.0, 0.5);
+    assert!(linear > 0.0 && linear < 12.0);
+}
+
+#[test]
+fn binding_maps_normalized_host_values() {
+    let mut cutoff = ParameterBinding::new(Parameter::Cutoff, 20.0, 20000.0, RampShape::Octaves)
+        .unwrap();
+    assert!(cutoff.set_normalized(0.5_f64));
+    assert_relative_eq!(cutoff.get_value(), (20.0_f64 * 20000.0).sqrt(), epsilon = 1e-9);
+    assert!(cutoff.set_normalized(1.5));
+    assert_relative_eq!(cutoff.get_value(), 20000.0);
+    assert!(!cutoff.set_value(f64::NAN));
+
+    let mut gain = ParameterBinding::new(Parameter::Gain, -12.0, 12.0, RampShape::Decibels)
+        .unwrap();
+    assert!(gain.set_normalized(0.25_f64));
+    assert_relative_eq!(gain.get_value(), -6.0);
+
+    assert!(ParameterBinding::new(Parameter::Cutoff, 0.0, 20000.0, RampShape::Octaves).is_none());
+    assert!(ParameterBinding::new(Parameter::Gain, 6.0, -6.0, RampShape::Linear).is_none());
+}
+
+#[test]
+fn binding_ramps_each_change_across_one_block() {
+    let band = PeakingEQFilter::<f64>::new(1000.0, 48000, 1.0, 0.0).unwrap();
+    let mut automated = AutomatedFilter::new(band);
+    let mut gain = ParameterBinding::new(Parameter::Gain, -12.0, 12.0, RampShape::Decibels)
+        .unwrap();
+    assert!(gain.set_value(6.0_f64));
+    assert!(gain.apply(&mut automated, 64));
+    assert!(automated.is_ramping(Parameter::Gain));
+
+    let mut block = make_signal(64);
+    assert!(automated.process_block(&mut block));
+    assert!(!gain.apply(&mut automated, 64));
+    assert!(automated.process_block(&mut block));
+    assert_relative_eq!(automated.get_filter().get_gain(), 6.0);
+    assert!(!automated.is_ramping(Parameter::Gain));
+
+    assert!(gain.set_value(-30.0));
+    assert!(gain.apply(&mut automated, 64));
+    assert!(automated.process_block(&mut block));
+    assert!(automated.process_block(&mut block));
+    assert_relative_eq!(automated.get_filter().get_gain(), -12.0);
+}
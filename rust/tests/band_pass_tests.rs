@@ -1,5 +1,4 @@
 /// band_pass_tests.rs
-
 /**
 Copyright © 2025 Alex Parisi
 
@@ -116,7 +115,7 @@ fn set_constant_skirt_gain() {
         std::f64::consts::FRAC_1_SQRT_2,
         false
     ).unwrap();
-    assert_eq!(filter.get_constant_skirt_gain(), false);
+    assert!(!filter.get_constant_skirt_gain());
     filter.set_constant_skirt_gain(true);
-    assert_eq!(filter.get_constant_skirt_gain(), true);
+    assert!(filter.get_constant_skirt_gain());
 }
\ No newline at end of file
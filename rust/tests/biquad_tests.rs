@@ -1,5 +1,4 @@
 /// biquad_tests.rs
-
 /**
 Copyright © 2025 Alex Parisi
 
@@ -21,7 +20,9 @@ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
 OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
-use biquad_filters::{Coefficients, DigitalBiquadFilter};
+use biquad_filters::{
+    Coefficients, DigitalBiquadFilter, Saturation, State, Topology, tick, tick_block,
+};
 use num_traits::Float;
 
 
@@ -241,3 +242,558 @@ fn test_process_with_negative_coefficients() {
         "Sample should be inverted due to negative coefficient"
     );
 }
+
+#[test]
+fn test_create_filter_with_non_finite_coefficients() {
+    let coefficients = Coefficients {
+        b0: f64::NAN,
+        b1: 0.0,
+        b2: 0.0,
+        a0: 1.0,
+        a1: 0.0,
+        a2: f64::INFINITY,
+    };
+
+    let filter = DigitalBiquadFilter::new(coefficients);
+    assert!(
+        filter.is_none(),
+        "Filter creation should fail with non-finite coefficients"
+    );
+}
+
+#[test]
+fn test_set_non_finite_coefficients_keeps_previous() {
+    let coefficients = Coefficients {
+        b0: 1.0,
+        b1: 0.0,
+        b2: 0.0,
+        a0: 1.0,
+        a1: 0.0,
+        a2: 0.0,
+    };
+
+    let mut filter = DigitalBiquadFilter::new(coefficients).expect("Filter creation failed");
+    let invalid = Coefficients {
+        a0: 1e-320,
+        ..coefficients
+    };
+    assert!(!filter.set_coefficients(invalid));
+    let mut sample = 1.0;
+    filter.process(&mut sample);
+    assert!(
+        (sample - 1.0).abs() < f64::EPSILON,
+        "Previous coefficients should remain in use"
+    );
+}
+
+#[test]
+fn test_update_coefficients_keeps_state() {
+    let coefficients = Coefficients {
+        b0: 0.0,
+        b1: 1.0,
+        b2: 0.0,
+        a0: 1.0,
+        a1: 0.0,
+        a2: 0.0,
+    };
+
+    let mut filter = DigitalBiquadFilter::new(coefficients).expect("Filter creation failed");
+    let mut sample = 1.0;
+    filter.process(&mut sample);
+    assert!(filter.update_coefficients(Coefficients { b1: 2.0, ..coefficients }));
+    let mut delayed = 0.0;
+    filter.process(&mut delayed);
+    assert!(
+        (delayed - 2.0).abs() < f64::EPSILON,
+        "The previous input should still be in the filter state"
+    );
+}
+
+#[test]
+fn test_saturation_leaves_small_signals_unchanged() {
+    let coefficients = Coefficients {
+        b0: 0.2,
+        b1: 0.4,
+        b2: 0.2,
+        a0: 1.0,
+        a1: -0.5,
+        a2: 0.3,
+    };
+    let mut linear = DigitalBiquadFilter::new(coefficients).expect("Filter creation failed");
+    let mut saturated = DigitalBiquadFilter::new(coefficients).expect("Filter creation failed");
+    assert!(saturated.set_saturation(Saturation::Tanh { drive: 1.0 }));
+    for n in 0..64 {
+        let mut a = 1e-6 * (n as f64 * 0.3).sin();
+        let mut b = a;
+        linear.process(&mut a);
+        saturated.process(&mut b);
+        assert!((a - b).abs() < 1e-15);
+    }
+}
+
+#[test]
+fn test_saturation_bounds_feedback() {
+    // A resonator with poles very close to the unit circle.
+    let coefficients = Coefficients {
+        b0: 1.0,
+        b1: 0.0,
+        b2: 0.0,
+        a0: 1.0,
+        a1: -1.99,
+        a2: 0.9999,
+    };
+    let mut filter = DigitalBiquadFilter::new(coefficients).expect("Filter creation failed");
+    assert!(filter.set_saturation(Saturation::SoftClip { drive: 2.0 }));
+    let mut samples = [0.0; 4096];
+    samples[0] = 100.0;
+    filter.process_block(&mut samples);
+    let peak = samples[1..].iter().fold(0.0_f64, |peak, s| peak.max(s.abs()));
+    // The fed-back state is bounded by 1 / (3 * drive), so the output is bounded by the sum of
+    // the feedback coefficient magnitudes times that bound.
+    assert!(peak <= (1.99 + 0.9999) / 3.0 + 1e-12);
+}
+
+#[test]
+fn test_invalid_saturation_is_rejected() {
+    let mut filter = DigitalBiquadFilter::<f64>::default();
+    assert!(!filter.set_saturation(Saturation::Tanh { drive: 0.0 }));
+    assert!(!filter.set_saturation(Saturation::SoftClip { drive: f64::NAN }));
+    assert_eq!(filter.get_saturation(), Saturation::Linear);
+}
+
+#[test]
+fn test_samples_processed_is_cleared_by_reset() {
+    let mut filter = DigitalBiquadFilter::<f64>::default();
+    let mut samples = [1.0, 0.5, 0.25];
+    filter.process_block(&mut samples);
+    assert_eq!(filter.samples_processed(), 3);
+    filter.reset();
+    assert_eq!(filter.samples_processed(), 0);
+}
+
+#[test]
+fn test_samples_processed_keeps_running_across_set_coefficients() {
+    let mut filter = DigitalBiquadFilter::<f64>::default();
+    let mut samples = [1.0, 0.5, 0.25];
+    filter.process_block(&mut samples);
+    let half = Coefficients { b0: 0.5, b1: 0.0, b2: 0.0, a0: 1.0, a1: 0.0, a2: 0.0 };
+    assert!(filter.set_coefficients(half));
+    assert_eq!(filter.samples_processed(), 3);
+    filter.process_block(&mut samples);
+    assert_eq!(filter.samples_processed(), 6);
+}
+
+#[test]
+fn test_coefficient_precision_conversions() {
+    let coefficients = Coefficients {
+        b0: 0.1_f64,
+        b1: 0.2,
+        b2: 0.1,
+        a0: 1.0,
+        a1: -0.9,
+        a2: 0.3,
+    };
+    let single: Coefficients<f32> = coefficients.into();
+    assert!((single.b0 - 0.1_f32).abs() < f32::EPSILON);
+    assert!((single.a1 + 0.9_f32).abs() < f32::EPSILON);
+    let double: Coefficients<f64> = single.into();
+    assert_eq!(double.b1, 0.2_f32 as f64);
+}
+
+#[test]
+fn test_filter_conversion_keeps_state() {
+    let coefficients = Coefficients {
+        b0: 0.1_f64,
+        b1: 0.2,
+        b2: 0.1,
+        a0: 1.0,
+        a1: -0.9,
+        a2: 0.3,
+    };
+    let mut double = DigitalBiquadFilter::new(coefficients).expect("Filter creation failed");
+    let mut samples = [1.0_f64; 32];
+    double.process_block(&mut samples);
+    let mut single = double.to_f32();
+    assert_eq!(single.samples_processed(), 32);
+    for _ in 0..32 {
+        let mut expected = 1.0_f64;
+        let mut sample = 1.0_f32;
+        double.process(&mut expected);
+        single.process(&mut sample);
+        assert!((sample as f64 - expected).abs() < 1e-5);
+    }
+    let widened = single.to_f64();
+    assert_eq!(widened.get_coefficients().b0, single.get_coefficients().b0 as f64);
+}
+
+#[test]
+fn test_raw_coefficients_are_preserved() {
+    let coefficients = Coefficients {
+        b0: 0.2_f64,
+        b1: 0.4,
+        b2: 0.2,
+        a0: 2.0,
+        a1: -0.6,
+        a2: 0.2,
+    };
+    let normalized = DigitalBiquadFilter::new(coefficients).expect("Filter creation failed");
+    assert!(normalized.is_normalized());
+    assert_eq!(normalized.get_coefficients().a0, 1.0);
+    assert_eq!(normalized.coefficients_raw().a0, 2.0);
+    assert_eq!(normalized.coefficients_raw().b1, 0.4);
+
+    let raw = DigitalBiquadFilter::new_raw(coefficients).expect("Filter creation failed");
+    assert!(!raw.is_normalized());
+    assert_eq!(raw.get_coefficients().a0, 2.0);
+    assert_eq!(raw.coefficients_raw().a1, -0.6);
+
+    let mut invalid = coefficients;
+    invalid.a0 = 0.0;
+    assert!(DigitalBiquadFilter::new_raw(invalid).is_none());
+}
+
+#[test]
+fn test_raw_filter_matches_normalized_filter() {
+    let coefficients = Coefficients {
+        b0: 0.2_f64,
+        b1: 0.4,
+        b2: 0.2,
+        a0: 2.0,
+        a1: -0.6,
+        a2: 0.2,
+    };
+    let mut normalized = DigitalBiquadFilter::new(coefficients).expect("Filter creation failed");
+    let mut raw = DigitalBiquadFilter::new_raw(coefficients).expect("Filter creation failed");
+    let mut expected: Vec<f64> = (0..64).map(|n| ((n * 5) % 7) as f64 - 3.0).collect();
+    let mut samples = expected.clone();
+    normalized.process_block(&mut expected);
+    raw.process_block(&mut samples);
+    for (sample, expected) in samples.iter().zip(expected.iter()) {
+        assert!((sample - expected).abs() < 1e-12);
+    }
+
+    let mut updated = coefficients;
+    updated.a0 = 4.0;
+    assert!(raw.set_coefficients(updated));
+    assert!(!raw.is_normalized());
+    assert_eq!(raw.get_coefficients().a0, 4.0);
+}
+
+#[test]
+fn test_shared_coefficients_match_filter() {
+    let raw = Coefficients {
+        b0: 0.4,
+        b1: 0.2,
+        b2: 0.1,
+        a0: 2.0,
+        a1: -0.6,
+        a2: 0.2,
+    };
+    let coefficients = raw.normalized().unwrap();
+    assert_eq!(coefficients.a0, 1.0);
+    let mut filter = DigitalBiquadFilter::new(raw).unwrap();
+    let mut states = [State::default(); 4];
+    for n in 0..64 {
+        let mut expected = ((n * 5) % 7) as f64 - 3.0;
+        let input = expected;
+        filter.process(&mut expected);
+        for state in states.iter_mut() {
+            let output = tick(&coefficients, state, input);
+            assert!((output - expected).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn test_tick_block_keeps_channels_independent() {
+    let coefficients = Coefficients {
+        b0: 0.2,
+        b1: 0.4,
+        b2: 0.2,
+        a0: 1.0,
+        a1: -0.6,
+        a2: 0.2,
+    };
+    let mut left_state = State::default();
+    let mut right_state = State::default();
+    let mut left = [1.0_f64, 0.0, 0.0, 0.0];
+    let mut right = [0.0_f64; 4];
+    tick_block(&coefficients, &mut left_state, &mut left);
+    tick_block(&coefficients, &mut right_state, &mut right);
+    assert_eq!(left[0], 0.2);
+    assert!(right.iter().all(|sample| *sample == 0.0));
+    assert!(Coefficients { a0: 0.0, ..coefficients }.normalized().is_none());
+}
+
+#[test]
+fn test_process_block_with_stats() {
+    let coefficients = Coefficients {
+        b0: 2.0,
+        b1: 0.0,
+        b2: 0.0,
+        a0: 1.0,
+        a1: 0.0,
+        a2: 0.0,
+    };
+    let mut filter = DigitalBiquadFilter::new(coefficients).unwrap();
+    let mut samples = [0.25_f64, -0.75, 1e-310, f64::NAN];
+    let stats = filter.process_block_with_stats(&mut samples).unwrap();
+    assert_eq!(stats.peak_input, 0.75);
+    assert_eq!(stats.peak_output, 1.5);
+    assert_eq!(stats.clipped, 1);
+    assert_eq!(stats.nan, 1);
+    assert!(stats.denormals_flushed);
+    assert_eq!(samples[2], 0.0);
+    assert!(filter.process_block_with_stats(&mut []).is_none());
+}
+
+#[test]
+fn transfer_function_string_is_normalized() {
+    let coefficients = Coefficients {
+        b0: 0.4,
+        b1: 0.8,
+        b2: 0.0,
+        a0: 2.0,
+        a1: -1.0,
+        a2: 0.25,
+    };
+    assert_eq!(
+        coefficients.to_transfer_function_string(),
+        "H(z) = (0.2 + 0.4 z^-1) / (1 - 0.5 z^-1 + 0.125 z^-2)"
+    );
+    assert_eq!(
+        coefficients.to_latex(),
+        "H(z) = \\frac{0.2 + 0.4 z^{-1}}{1 - 0.5 z^{-1} + 0.125 z^{-2}}"
+    );
+}
+
+#[test]
+fn transfer_function_string_uses_scientific_notation_for_small_coefficients() {
+    let coefficients = Coefficients {
+        b0: 2.5e-7,
+        b1: 5e-7,
+        b2: 2.5e-7,
+        a0: 1.0,
+        a1: -1.999,
+        a2: 0.999,
+    };
+    assert_eq!(
+        coefficients.to_transfer_function_string(),
+        "H(z) = (2.5e-7 + 5e-7 z^-1 + 2.5e-7 z^-2) / (1 - 1.999 z^-1 + 0.999 z^-2)"
+    );
+    assert!(coefficients.to_latex().starts_with("H(z) = \\frac{2.5 \\times 10^{-7} + "));
+}
+
+#[test]
+fn stability_margin_measures_pole_radius() {
+    let resonator = |radius: f64| Coefficients {
+        b0: 1.0,
+        b1: 0.0,
+        b2: 0.0,
+        a0: 1.0,
+        a1: 0.0,
+        a2: radius * radius,
+    };
+    assert!((resonator(0.9).stability_margin() - 0.1).abs() < 1e-12);
+    assert!((resonator(0.9999).stability_margin() - 1e-4).abs() < 1e-12);
+    assert!(!resonator(0.9).has_poles_near_unit_circle(1e-3));
+    assert!(resonator(0.9999).has_poles_near_unit_circle(1e-3));
+
+    // Real poles at 0.5 and 1.2.
+    let unstable = Coefficients {
+        b0: 1.0,
+        b1: 0.0,
+        b2: 0.0,
+        a0: 1.0,
+        a1: -1.7,
+        a2: 0.6,
+    };
+    assert!((unstable.stability_margin() + 0.2).abs() < 1e-12);
+    assert!(unstable.has_poles_near_unit_circle(0.0));
+}
+
+#[test]
+fn skip_matches_processing_zeros() {
+    let resonator = Coefficients {
+        b0: 0.3,
+        b1: 0.5,
+        b2: -0.2,
+        a0: 2.0,
+        a1: -3.6,
+        a2: 1.9,
+    };
+    for count in [0_u64, 1, 2, 3, 17, 1000] {
+        for raw in [false, true] {
+            let create = if raw { DigitalBiquadFilter::new_raw } else { DigitalBiquadFilter::new };
+            let mut processed = create(resonator).unwrap();
+            let mut skipped = processed.clone();
+            let mut input = [1.0, -0.5, 0.25];
+            processed.process_block(&mut input);
+            let mut input = [1.0, -0.5, 0.25];
+            skipped.process_block(&mut input);
+
+            let mut zeros = vec![0.0; count as usize];
+            processed.process_block(&mut zeros);
+            skipped.skip(count);
+            assert_eq!(skipped.samples_processed(), processed.samples_processed());
+
+            let mut expected = [0.5, 0.0, 0.0];
+            let mut actual = expected;
+            processed.process_block(&mut expected);
+            skipped.process_block(&mut actual);
+            for (expected, actual) in expected.iter().zip(actual.iter()) {
+                assert!((expected - actual).abs() < 1e-9, "count {count}: {expected} != {actual}");
+            }
+        }
+    }
+}
+
+#[test]
+fn skip_clears_the_feedback_error() {
+    let low_pass = Coefficients {
+        b0: 0.02_f32,
+        b1: 0.04,
+        b2: 0.02,
+        a0: 1.0,
+        a1: -1.6,
+        a2: 0.7,
+    };
+    let mut filter = DigitalBiquadFilter::new(low_pass).unwrap();
+    filter.set_topology(Topology::DirectForm1ErrorFeedback);
+    let mut samples = [1234.567_f32, -987.6543, 333.3333, 0.1];
+    filter.process_block(&mut samples);
+    // Long enough for the state to decay to exactly zero, so only a stale error could remain.
+    filter.skip(100_000);
+    let mut silence = [0.0_f32; 4];
+    filter.process_block(&mut silence);
+    assert_eq!(silence, [0.0; 4]);
+}
+
+#[test]
+fn settle_starts_in_the_dc_steady_state() {
+    let low_pass = Coefficients {
+        b0: 0.02,
+        b1: 0.04,
+        b2: 0.02,
+        a0: 1.0,
+        a1: -1.6,
+        a2: 0.7,
+    };
+    let mut filter = DigitalBiquadFilter::new(low_pass).unwrap();
+    let settled = filter.settle(0.5).unwrap();
+    assert!((settled - 0.5 * 0.08 / 0.1).abs() < 1e-12);
+    let mut samples = [0.5; 64];
+    filter.process_block(&mut samples);
+    assert!(samples.iter().all(|sample| (sample - settled).abs() < 1e-12));
+
+    // A pole at z = 1 has no steady state.
+    let integrator = Coefficients {
+        b0: 1.0,
+        b1: 0.0,
+        b2: 0.0,
+        a0: 1.0,
+        a1: -1.0,
+        a2: 0.0,
+    };
+    let mut filter = DigitalBiquadFilter::new(integrator).unwrap();
+    assert_eq!(filter.settle(1.0), None);
+    let mut sample = 1.0;
+    filter.process(&mut sample);
+    assert_eq!(sample, 1.0);
+}
+
+#[test]
+fn settle_includes_saturation_and_error_feedback() {
+    let low_pass = Coefficients {
+        b0: 0.02,
+        b1: 0.04,
+        b2: 0.02,
+        a0: 1.0,
+        a1: -1.6,
+        a2: 0.7,
+    };
+    for saturation in [Saturation::Tanh { drive: 1.0 }, Saturation::SoftClip { drive: 2.0 }] {
+        let mut filter = DigitalBiquadFilter::new(low_pass).unwrap();
+        assert!(filter.set_saturation(saturation));
+        filter.set_topology(Topology::DirectForm1ErrorFeedback);
+        let mut loud = [1.234_567e12, -9.876_543e11, 3.333_333e11];
+        filter.process_block(&mut loud);
+
+        let settled = filter.settle(2.0).unwrap();
+        assert!(settled < 2.0 * 0.8);
+        let mut samples = [2.0; 64];
+        filter.process_block(&mut samples);
+        for sample in samples {
+            assert!((sample - settled).abs() < 1e-12, "{sample} != {settled}");
+        }
+    }
+}
+
+/// Returns the RMS difference between an f32 filter with the given topology and the same
+/// coefficients evaluated in f64, for a 30 Hz high-pass at 192 kHz.
+fn high_pass_rounding_noise(topology: Topology) -> f64 {
+    let sample_rate = 192000.0;
+    let w = 2.0 * core::f64::consts::PI * 30.0 / sample_rate;
+    let alpha = w.sin() / (2.0 * core::f64::consts::FRAC_1_SQRT_2);
+    let a0 = 1.0 + alpha;
+    let coefficients = Coefficients {
+        b0: ((1.0 + w.cos()) / 2.0 / a0) as f32,
+        b1: (-(1.0 + w.cos()) / a0) as f32,
+        b2: ((1.0 + w.cos()) / 2.0 / a0) as f32,
+        a0: 1.0,
+        a1: (-2.0 * w.cos() / a0) as f32,
+        a2: ((1.0 - alpha) / a0) as f32,
+    };
+    let mut filter = DigitalBiquadFilter::new(coefficients).unwrap();
+    filter.set_topology(topology);
+    let mut reference = DigitalBiquadFilter::new(Coefficients::<f64>::from(coefficients)).unwrap();
+    let mut squared_error = 0.0;
+    let count = 96000;
+    for index in 0..count {
+        let mut actual = (0.5 * (index as f64 * 0.0123).sin()) as f32;
+        let mut expected = actual as f64;
+        assert!(filter.process(&mut actual));
+        assert!(reference.process(&mut expected));
+        squared_error += (actual as f64 - expected).powi(2);
+    }
+    (squared_error / count as f64).sqrt()
+}
+
+#[test]
+fn error_feedback_reduces_rounding_noise() {
+    let plain = high_pass_rounding_noise(Topology::DirectForm1);
+    let feedback = high_pass_rounding_noise(Topology::DirectForm1ErrorFeedback);
+    assert!(feedback * 10.0 < plain, "plain: {plain}, error feedback: {feedback}");
+}
+
+#[test]
+fn topology_is_reported_and_reset_clears_feedback() {
+    let coefficients = Coefficients {
+        b0: 0.5,
+        b1: 0.25,
+        b2: 0.125,
+        a0: 1.0,
+        a1: -0.5,
+        a2: 0.25,
+    };
+    let mut plain = DigitalBiquadFilter::new(coefficients).unwrap();
+    let mut feedback = DigitalBiquadFilter::new(coefficients).unwrap();
+    assert_eq!(feedback.get_topology(), Topology::DirectForm1);
+    feedback.set_topology(Topology::DirectForm1ErrorFeedback);
+    assert_eq!(feedback.get_topology(), Topology::DirectForm1ErrorFeedback);
+    for index in 0..64 {
+        let mut expected = (index as f64 * 0.3).sin();
+        let mut actual = expected;
+        assert!(plain.process(&mut expected));
+        assert!(feedback.process(&mut actual));
+        assert!((actual - expected).abs() < 1e-12);
+    }
+    feedback.reset();
+    plain.reset();
+    for _ in 0..4 {
+        let (mut expected, mut actual) = (1.0, 1.0);
+        assert!(plain.process(&mut expected));
+        assert!(feedback.process(&mut actual));
+        assert!((actual - expected).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,63 @@
+/// butterworth_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{ButterworthFilter, Filter, FilterType, FrequencyResponse, LowPassFilter};
+use approx::assert_relative_eq;
+
+#[test]
+fn create_valid_and_invalid_filters() {
+    let filter = ButterworthFilter::low_pass(1000.0_f64, 48000, 5).unwrap();
+    assert_eq!(filter.get_filter_type(), FilterType::LowPass);
+    assert_eq!(filter.get_order(), 5);
+    assert_eq!(filter.get_sections().len(), 3);
+    let filter = ButterworthFilter::band_pass(300.0_f32, 3000.0, 48000, 4).unwrap();
+    assert_eq!(filter.get_filter_type(), FilterType::BandPass);
+    assert_eq!(filter.get_sections().len(), 4);
+    assert!(ButterworthFilter::high_pass(1000.0_f64, 48000, 0).is_none());
+    assert!(ButterworthFilter::band_pass(3000.0_f64, 300.0, 48000, 2).is_none());
+}
+
+#[test]
+fn second_order_matches_the_cookbook_low_pass() {
+    let mut butterworth = ButterworthFilter::low_pass(1000.0_f64, 48000, 2).unwrap();
+    let mut cookbook = LowPassFilter::new(1000.0, 48000, core::f64::consts::FRAC_1_SQRT_2).unwrap();
+    for index in 0..128 {
+        let mut expected = (index as f64 * 0.3).sin();
+        let mut actual = expected;
+        assert!(cookbook.process(&mut expected));
+        assert!(butterworth.process(&mut actual));
+        assert_relative_eq!(actual, expected, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn high_order_filters_are_steep() {
+    let mut filter = ButterworthFilter::high_pass(1000.0_f64, 48000, 8).unwrap();
+    assert_relative_eq!(filter.magnitude_at(1000.0, 48000), core::f64::consts::FRAC_1_SQRT_2, epsilon = 1e-9);
+    // Eight poles give 48 dB per octave.
+    assert!(filter.magnitude_at(500.0, 48000) < 0.005);
+    let mut block = [1.0; 256];
+    assert!(filter.process_block(&mut block));
+    assert!(block[255].abs() < 1e-3);
+    filter.reset();
+    assert!(filter.get_sections().iter().all(|section| section.samples_processed() == 0));
+}
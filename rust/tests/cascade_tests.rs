@@ -0,0 +1,345 @@
+/// cascade_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{
+    Cascade, Coefficients, DigitalBiquadFilter, FrequencyResponse, Saturation, ScalingNorm,
+};
+use approx::assert_relative_eq;
+
+fn gain(value: f64) -> Coefficients<f64> {
+    Coefficients {
+        b0: value,
+        b1: 0.0,
+        b2: 0.0,
+        a0: 1.0,
+        a1: 0.0,
+        a2: 0.0,
+    }
+}
+
+fn resonator() -> Coefficients<f64> {
+    Coefficients {
+        b0: 0.2,
+        b1: 0.4,
+        b2: 0.2,
+        a0: 1.0,
+        a1: -0.6,
+        a2: 0.2,
+    }
+}
+
+#[test]
+fn create_valid_cascade() {
+    let cascade = Cascade::<f64, 2>::new([gain(1.0), gain(1.0)]);
+    assert!(cascade.is_some());
+    assert_eq!(Cascade::<f64, 2>::SECTIONS, 2);
+}
+
+#[test]
+fn create_invalid_cascade() {
+    let mut invalid = gain(1.0);
+    invalid.a0 = 0.0;
+    let cascade = Cascade::<f64, 2>::new([gain(1.0), invalid]);
+    assert!(cascade.is_none());
+}
+
+#[test]
+fn process_applies_every_section() {
+    let mut cascade = Cascade::<f64, 3>::new([gain(0.5), gain(0.5), gain(2.0)]).unwrap();
+    let mut sample = 1.0_f64;
+    cascade.process(&mut sample);
+    assert_relative_eq!(sample, 0.5_f64);
+}
+
+#[test]
+fn process_block_matches_individual_sections() {
+    let mut cascade = Cascade::<f64, 2>::new([resonator(), resonator()]).unwrap();
+    let mut first = DigitalBiquadFilter::new(resonator()).unwrap();
+    let mut second = DigitalBiquadFilter::new(resonator()).unwrap();
+
+    let mut samples: Vec<f64> = (0..64).map(|n| ((n * 7) % 5) as f64 - 2.0).collect();
+    let mut expected = samples.clone();
+    cascade.process_block(&mut samples);
+    for sample in expected.iter_mut() {
+        first.process(sample);
+        second.process(sample);
+    }
+    for (actual, expected) in samples.iter().zip(expected.iter()) {
+        assert_relative_eq!(*actual, *expected, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn set_section_coefficients() {
+    let mut cascade = Cascade::<f64, 2>::default();
+    assert!(cascade.set_section_coefficients(1, gain(3.0)));
+    assert!(!cascade.set_section_coefficients(2, gain(3.0)));
+    let mut sample = 1.0_f64;
+    cascade.process(&mut sample);
+    assert_relative_eq!(sample, 3.0_f64);
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn bounded_cascade_respects_capacity() {
+    use biquad_filters::BoundedCascade;
+
+    let mut cascade = BoundedCascade::<f64, 2>::new();
+    assert!(cascade.is_empty());
+    assert!(cascade.push_section(gain(2.0)));
+    assert!(cascade.push_section(gain(2.0)));
+    assert!(!cascade.push_section(gain(2.0)));
+    assert_eq!(cascade.len(), 2);
+
+    let mut sample = 1.0_f64;
+    cascade.process(&mut sample);
+    assert_relative_eq!(sample, 4.0_f64);
+
+    assert!(cascade.remove_section(0).is_some());
+    assert!(cascade.remove_section(5).is_none());
+    assert_eq!(cascade.len(), 1);
+}
+
+fn resonant_low_pass() -> Coefficients<f64> {
+    // Cookbook low-pass at 1 kHz with Q = 20.
+    let w = 2.0 * std::f64::consts::PI * 1000.0 / 48000.0;
+    let alpha = w.sin() / (2.0 * 20.0);
+    Coefficients {
+        b0: (1.0 - w.cos()) / 2.0,
+        b1: 1.0 - w.cos(),
+        b2: (1.0 - w.cos()) / 2.0,
+        a0: 1.0 + alpha,
+        a1: -2.0 * w.cos(),
+        a2: 1.0 - alpha,
+    }
+}
+
+fn resonance(amplitude: f64) -> Vec<f64> {
+    (0..48000)
+        .map(|n| amplitude * (2.0 * std::f64::consts::PI * 1000.0 * n as f64 / 48000.0).sin())
+        .collect()
+}
+
+#[test]
+fn saturation_keeps_small_signals_linear() {
+    let mut linear = Cascade::<f64, 3>::new([resonant_low_pass(); 3]).unwrap();
+    let mut saturated = linear.clone();
+    assert!(saturated.set_saturation(Saturation::Tanh { drive: 1.0 }));
+    let mut expected: Vec<f64> = (0..256).map(|n| 1e-7 * (n as f64 * 0.13).sin()).collect();
+    let mut samples = expected.clone();
+    linear.process_block(&mut expected);
+    saturated.process_block(&mut samples);
+    for (sample, expected) in samples.iter().zip(expected.iter()) {
+        assert_relative_eq!(*sample, *expected, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn saturation_is_staged_relative_to_the_input() {
+    // Each section boosts its resonance twenty-fold, so without staging the later sections would
+    // see signals far above the saturation point.
+    let mut linear = Cascade::<f64, 3>::new([resonant_low_pass(); 3]).unwrap();
+    let mut saturated = linear.clone();
+    assert!(saturated.set_saturation(Saturation::SoftClip { drive: 1.0 }));
+    let mut expected = resonance(0.05);
+    let mut samples = expected.clone();
+    linear.process_block(&mut expected);
+    saturated.process_block(&mut samples);
+    let linear_peak = expected.iter().fold(0.0_f64, |peak, s| peak.max(s.abs()));
+    for (sample, expected) in samples.iter().zip(expected.iter()) {
+        assert!((sample - expected).abs() < 0.01 * linear_peak);
+    }
+}
+
+#[test]
+fn saturation_bounds_overdriven_cascade() {
+    let mut linear = Cascade::<f64, 3>::new([resonant_low_pass(); 3]).unwrap();
+    let mut saturated = linear.clone();
+    assert!(saturated.set_saturation(Saturation::Tanh { drive: 4.0 }));
+    let mut expected = resonance(1.0);
+    let mut samples = expected.clone();
+    linear.process_block(&mut expected);
+    for sample in samples.iter_mut() {
+        saturated.process(sample);
+    }
+    let linear_peak = expected.iter().fold(0.0_f64, |peak, s| peak.max(s.abs()));
+    let saturated_peak = samples.iter().fold(0.0_f64, |peak, s| peak.max(s.abs()));
+    assert!(saturated_peak.is_finite());
+    assert!(saturated_peak < 0.5 * linear_peak);
+}
+
+#[test]
+fn invalid_cascade_saturation_is_rejected() {
+    let mut cascade = Cascade::<f64, 2>::new([gain(1.0), gain(1.0)]).unwrap();
+    assert!(!cascade.set_saturation(Saturation::Tanh { drive: -1.0 }));
+    assert_eq!(cascade.get_saturation(), Saturation::Linear);
+}
+
+#[test]
+fn block_processing_matches_chained_sections() {
+    let sections = [
+        resonator(),
+        resonant_low_pass(),
+        gain(0.5),
+        resonator(),
+        resonant_low_pass(),
+        resonator(),
+        gain(2.0),
+        resonator(),
+    ];
+    let mut cascade = Cascade::<f64, 8>::new(sections).unwrap();
+    let mut chain: Vec<DigitalBiquadFilter<f64>> = sections
+        .iter()
+        .map(|coefficients| DigitalBiquadFilter::new(*coefficients).unwrap())
+        .collect();
+    let input: Vec<f64> = (0..512).map(|n| ((n * 7) % 13) as f64 - 6.0).collect();
+    let mut samples = input.clone();
+    assert!(cascade.process_block(&mut samples));
+    for (input, output) in input.iter().zip(samples.iter()) {
+        let mut expected = *input;
+        for section in chain.iter_mut() {
+            section.process(&mut expected);
+        }
+        assert_relative_eq!(*output, expected, epsilon = 1e-9);
+    }
+}
+
+#[test]
+fn transfer_function_string_is_a_product_of_sections() {
+    let cascade = Cascade::new([gain(0.5), gain(2.0)]).unwrap();
+    assert_eq!(cascade.to_transfer_function_string(), "H(z) = [(0.5) / (1)] * [(2) / (1)]");
+    assert_eq!(cascade.to_latex(), "H(z) = \\frac{0.5}{1} \\cdot \\frac{2}{1}");
+    let empty = Cascade::<f64, 0>::new([]).unwrap();
+    assert_eq!(empty.to_latex(), "H(z) = 1");
+}
+
+#[test]
+fn stability_margin_is_the_smallest_section_margin() {
+    let cascade = Cascade::new([gain(1.0), resonant_low_pass()]).unwrap();
+    let margin = resonant_low_pass().stability_margin();
+    assert!(margin > 0.0);
+    assert_relative_eq!(cascade.stability_margin(), margin);
+    assert!(cascade.has_poles_near_unit_circle(margin * 2.0));
+    assert!(!cascade.has_poles_near_unit_circle(margin / 2.0));
+    assert_relative_eq!(Cascade::new([gain(2.0)]).unwrap().stability_margin(), 1.0);
+}
+
+#[test]
+fn scale_factors_normalize_every_node() {
+    let cascade = Cascade::new([resonant_low_pass(), gain(0.5), resonant_low_pass()]).unwrap();
+    for norm in [ScalingNorm::L1, ScalingNorm::L2, ScalingNorm::LInfinity] {
+        let factors = cascade.scale_factors(norm).unwrap();
+        // The first factor undoes the norm of the first section alone.
+        let first = Cascade::new([resonant_low_pass()]).unwrap().scale_factors(norm).unwrap();
+        assert_relative_eq!(factors[0], first[0], max_relative = 1e-9);
+        // A pure gain section only needs its gain undone.
+        assert_relative_eq!(factors[1], 2.0, max_relative = 1e-6);
+    }
+    let l1 = cascade.scale_factors(ScalingNorm::L1).unwrap();
+    let l2 = cascade.scale_factors(ScalingNorm::L2).unwrap();
+    let peak = cascade.scale_factors(ScalingNorm::LInfinity).unwrap();
+    // The L1 norm bounds the others, so it scales the most.
+    assert!(l1[0] <= peak[0] && l1[0] <= l2[0]);
+}
+
+#[test]
+fn scaled_cascade_output_stays_within_full_scale() {
+    let cascade = Cascade::new([resonant_low_pass(), resonant_low_pass()]).unwrap();
+    let factors = cascade.scale_factors(ScalingNorm::L1).unwrap();
+    let mut sections: Vec<DigitalBiquadFilter<f64>> = cascade.get_sections().to_vec();
+    // A worst-case input alternates between full scale extremes.
+    for n in 0..4096 {
+        let mut sample = if (n / 7) % 2 == 0 { 1.0 } else { -1.0 };
+        for (section, factor) in sections.iter_mut().zip(factors) {
+            sample *= factor;
+            section.process(&mut sample);
+            assert!(sample.abs() <= 1.0 + 1e-9);
+        }
+    }
+}
+
+#[test]
+fn apply_scaling_bounds_every_node_and_keeps_the_response() {
+    let reference = Cascade::new([resonant_low_pass(), gain(0.5), resonant_low_pass()]).unwrap();
+    let mut scaled = reference.clone();
+    let factors = scaled.scale_factors(ScalingNorm::L1).unwrap();
+    assert!(scaled.apply_scaling(ScalingNorm::L1));
+    assert_relative_eq!(
+        scaled.get_output_gain(),
+        factors.iter().product::<f64>().recip(),
+        max_relative = 1e-12
+    );
+
+    // Record the impulse response at the output of every section.
+    const LENGTH: usize = 8192;
+    let mut sections = scaled.get_sections().to_vec();
+    let mut impulses = vec![[0.0; LENGTH]; sections.len()];
+    for n in 0..LENGTH {
+        let mut sample = if n == 0 { 1.0 } else { 0.0 };
+        for (section, impulse) in sections.iter_mut().zip(impulses.iter_mut()) {
+            section.process(&mut sample);
+            impulse[n] = sample;
+        }
+    }
+    // The worst-case input for a node is the sign of its time-reversed impulse response, which
+    // drives that node to its L1 norm on the last sample. No node may leave full scale.
+    for (node, impulse) in impulses.iter().enumerate() {
+        let mut sections = scaled.get_sections().to_vec();
+        let mut peak = 0.0_f64;
+        let mut last = 0.0;
+        for n in 0..LENGTH {
+            let mut sample = impulse[LENGTH - 1 - n].signum();
+            for (index, section) in sections.iter_mut().enumerate() {
+                section.process(&mut sample);
+                peak = peak.max(sample.abs());
+                if index == node {
+                    last = sample;
+                }
+            }
+        }
+        assert!(peak <= 1.0 + 1e-9);
+        assert_relative_eq!(last, 1.0, max_relative = 1e-6);
+    }
+
+    // The output gain restores the original response.
+    for frequency in [0.0, 100.0, 1000.0, 5000.0, 20000.0] {
+        let expected = reference.response_at(frequency, 48000);
+        let actual = scaled.response_at(frequency, 48000);
+        assert_relative_eq!(actual.re, expected.re, epsilon = 1e-9, max_relative = 1e-9);
+        assert_relative_eq!(actual.im, expected.im, epsilon = 1e-9, max_relative = 1e-9);
+    }
+    let mut expected = reference.clone();
+    for n in 0..1024 {
+        let input = (n as f64 * 0.131).sin();
+        let (mut left, mut right) = (input, input);
+        expected.process(&mut left);
+        scaled.process(&mut right);
+        assert_relative_eq!(right, left, epsilon = 1e-9, max_relative = 1e-9);
+    }
+    let mut left: Vec<f64> = (0..256).map(|n| (n as f64 * 0.077).cos()).collect();
+    let mut right = left.clone();
+    assert!(expected.process_block(&mut left));
+    assert!(scaled.process_block(&mut right));
+    for (right, left) in right.iter().zip(left.iter()) {
+        assert_relative_eq!(*right, *left, epsilon = 1e-9, max_relative = 1e-9);
+    }
+}
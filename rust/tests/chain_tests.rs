@@ -0,0 +1,170 @@
+/// chain_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{
+    Filter, FilterChain, FrequencyResponse, HighPassFilter, LowPassFilter, OutputLimit,
+    PeakingEQFilter, UpdatePolicy, parse_chain,
+};
+
+const SAMPLE_RATE: u32 = 48000;
+
+fn signal() -> Vec<f64> {
+    (0..512).map(|n| (n as f64 * 0.05).sin() + 0.3 * (n as f64 * 0.9).sin()).collect()
+}
+
+fn chain() -> FilterChain<f64> {
+    let mut chain = FilterChain::new(SAMPLE_RATE).unwrap();
+    assert!(chain.push(Box::new(HighPassFilter::new(40.0, SAMPLE_RATE, 0.707).unwrap())));
+    assert!(chain.push(Box::new(PeakingEQFilter::new(1000.0, SAMPLE_RATE, 1.0, 6.0).unwrap())));
+    assert!(chain.push(Box::new(LowPassFilter::new(8000.0, SAMPLE_RATE, 0.707).unwrap())));
+    chain
+}
+
+#[test]
+fn processes_every_stage_in_order() {
+    let mut high_pass = HighPassFilter::new(40.0, SAMPLE_RATE, 0.707).unwrap();
+    let mut peaking = PeakingEQFilter::new(1000.0, SAMPLE_RATE, 1.0, 6.0).unwrap();
+    let mut low_pass = LowPassFilter::new(8000.0, SAMPLE_RATE, 0.707).unwrap();
+    let mut expected = signal();
+    high_pass.process_block(&mut expected);
+    peaking.process_block(&mut expected);
+    low_pass.process_block(&mut expected);
+
+    let mut chain = chain();
+    assert_eq!(chain.len(), 3);
+    let mut samples = signal();
+    assert!(chain.process_block(&mut samples[..256]));
+    for sample in samples[256..].iter_mut() {
+        assert!(chain.process(sample));
+    }
+    assert_eq!(samples, expected);
+    assert_eq!(chain.samples_processed(), 512);
+    assert!(!chain.process_block(&mut []));
+}
+
+#[test]
+fn bypass_and_reset_apply_to_the_whole_chain() {
+    let mut chain = chain();
+    assert!(chain.set_bypass(true));
+    let mut samples = signal();
+    assert!(chain.process_block(&mut samples));
+    assert_eq!(samples, signal());
+    assert!(chain.get_stage(1).is_some_and(|stage| !stage.get_bypass()));
+
+    assert!(chain.set_bypass(false));
+    let mut first = signal();
+    chain.process_block(&mut first);
+    chain.reset();
+    assert_eq!(chain.samples_processed(), 0);
+    assert!(chain.stages().all(|stage| stage.samples_processed() == 0));
+    let mut second = signal();
+    chain.process_block(&mut second);
+    assert_eq!(first, second);
+}
+
+#[test]
+fn stages_can_be_managed() {
+    let mut chain = chain();
+    let removed = chain.remove(1).unwrap();
+    assert_eq!(removed.get_gain(), 6.0);
+    assert!(chain.remove(2).is_none());
+    assert!(chain.insert(0, removed));
+    assert_eq!(chain.get_stage(0).map(|stage| stage.get_cutoff()), Some(1000.0));
+    assert!(chain.get_stage_mut(2).is_some_and(|stage| stage.set_cutoff(6000.0)));
+    assert_eq!(chain.get_stage(2).map(|stage| stage.get_cutoff()), Some(6000.0));
+    let stage = Box::new(LowPassFilter::new(100.0, SAMPLE_RATE, 0.707).unwrap());
+    assert!(!chain.insert(4, stage));
+    chain.clear();
+    assert!(chain.is_empty());
+    let mut samples = signal();
+    assert!(chain.process_block(&mut samples));
+    assert_eq!(samples, signal());
+}
+
+#[test]
+fn sample_rate_and_update_policy_reach_every_stage() {
+    let mut chain = chain();
+    let stage = Box::new(LowPassFilter::new(100.0, 44100, 0.707).unwrap());
+    assert!(chain.push(stage));
+    assert_eq!(chain.get_stage(3).map(|stage| stage.get_sample_rate()), Some(SAMPLE_RATE));
+    assert!(chain.set_sample_rate(96000));
+    assert!(chain.stages().all(|stage| stage.get_sample_rate() == 96000));
+    assert!(!chain.set_sample_rate(0));
+    assert_eq!(chain.get_sample_rate(), 96000);
+
+    chain.set_update_policy(UpdatePolicy::NextBlock);
+    assert!(chain.stages().all(|stage| stage.get_update_policy() == UpdatePolicy::NextBlock));
+    assert!(!chain.set_cutoff(1000.0));
+}
+
+#[test]
+fn output_limit_applies_after_the_last_stage() {
+    let mut chain = chain();
+    assert!(chain.set_output_limit(OutputLimit::Clip { limit: 0.5 }));
+    let mut samples = signal();
+    chain.process_block(&mut samples);
+    assert!(samples.iter().all(|sample| sample.abs() <= 0.5));
+    assert!(samples.iter().any(|sample| sample.abs() == 0.5));
+}
+
+#[test]
+fn builds_from_specs() {
+    let specs = parse_chain::<f64>("hp:40:q=0.707; peak:1k:+6dB:q=1; lp:8k:q=0.707").unwrap();
+    let mut from_specs = FilterChain::from_specs(&specs, SAMPLE_RATE).unwrap();
+    let mut expected = signal();
+    chain().process_block(&mut expected);
+    let mut samples = signal();
+    from_specs.process_block(&mut samples);
+    assert_eq!(samples, expected);
+    assert!(FilterChain::from_specs(&specs, 0).is_err());
+}
+
+#[test]
+fn chains_can_be_nested() {
+    let mut outer = FilterChain::new(SAMPLE_RATE).unwrap();
+    assert!(outer.push(Box::new(chain())));
+    let mut expected = signal();
+    chain().process_block(&mut expected);
+    let mut samples = signal();
+    outer.process_block(&mut samples);
+    assert_eq!(samples, expected);
+}
+
+#[test]
+fn response_is_the_product_of_the_stage_responses() {
+    let high_pass = HighPassFilter::new(40.0, SAMPLE_RATE, 0.707).unwrap();
+    let peaking = PeakingEQFilter::new(1000.0, SAMPLE_RATE, 1.0, 6.0).unwrap();
+    let low_pass = LowPassFilter::new(8000.0, SAMPLE_RATE, 0.707).unwrap();
+    let mut chain = chain();
+    for frequency in [20.0, 40.0, 300.0, 1000.0, 5000.0, 8000.0, 20000.0] {
+        let expected = high_pass.response_at(frequency, SAMPLE_RATE)
+            * peaking.response_at(frequency, SAMPLE_RATE)
+            * low_pass.response_at(frequency, SAMPLE_RATE);
+        let actual = chain.response_at(frequency, SAMPLE_RATE);
+        assert!((actual - expected).norm() < 1e-12, "{actual} != {expected} at {frequency} Hz");
+    }
+    assert!((chain.magnitude_at(1000.0, SAMPLE_RATE) - 10f64.powf(6.0 / 20.0)).abs() < 0.05);
+
+    assert!(chain.set_bypass(true));
+    assert_eq!(chain.magnitude_at(1000.0, SAMPLE_RATE), 1.0);
+    assert_eq!(FilterChain::<f64>::new(SAMPLE_RATE).unwrap().magnitude_at(1000.0, SAMPLE_RATE), 1.0);
+}
@@ -0,0 +1,69 @@
+/// complementary_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{BandPassFilter, ComplementaryFilter, Filter, LowPassFilter, NotchFilter};
+use approx::assert_relative_eq;
+
+fn make_signal(length: usize) -> Vec<f64> {
+    (0..length).map(|n| ((n * 11) % 9) as f64 - 4.0).collect()
+}
+
+#[test]
+fn complement_reconstructs_input() {
+    let mut filter = LowPassFilter::<f64>::new(
+        1000.0_f64,
+        48000_u32,
+        std::f64::consts::FRAC_1_SQRT_2
+    ).unwrap();
+    let input = make_signal(128);
+    let mut samples = input.clone();
+    let mut complement = vec![0.0_f64; input.len()];
+    assert!(filter.process_block_with_complement(&mut samples, &mut complement));
+    for ((input, filtered), complement) in input.iter().zip(samples.iter()).zip(complement.iter()) {
+        assert_relative_eq!(filtered + complement, *input, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn band_pass_complement_is_notch() {
+    let mut band_pass = BandPassFilter::<f64>::new(2000.0_f64, 48000_u32, 2.0_f64, false).unwrap();
+    let mut notch = NotchFilter::<f64>::new(2000.0_f64, 48000_u32, 2.0_f64).unwrap();
+    for input in make_signal(128) {
+        let mut sample = input;
+        let complement = band_pass.process_with_complement(&mut sample);
+        let mut expected = input;
+        notch.process(&mut expected);
+        assert_relative_eq!(complement, expected, epsilon = 1e-9);
+    }
+}
+
+#[test]
+fn mismatched_lengths_are_rejected() {
+    let mut filter = LowPassFilter::<f64>::new(
+        1000.0_f64,
+        48000_u32,
+        std::f64::consts::FRAC_1_SQRT_2
+    ).unwrap();
+    let mut samples = make_signal(16);
+    let mut complement = vec![0.0_f64; 8];
+    assert!(!filter.process_block_with_complement(&mut samples, &mut complement));
+}
@@ -0,0 +1,94 @@
+/// complex_biquad_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{Coefficients, ComplexBiquad, ComplexCoefficients, DigitalBiquadFilter};
+use approx::assert_relative_eq;
+use num_complex::Complex;
+
+const SAMPLE_RATE: u32 = 48000;
+
+fn low_pass() -> Coefficients<f64> {
+    // Cookbook low-pass at 1 kHz with a Butterworth Q.
+    let w = 2.0 * std::f64::consts::PI * 1000.0 / SAMPLE_RATE as f64;
+    let alpha = w.sin() / (2.0 * std::f64::consts::FRAC_1_SQRT_2);
+    Coefficients {
+        b0: (1.0 - w.cos()) / 2.0,
+        b1: 1.0 - w.cos(),
+        b2: (1.0 - w.cos()) / 2.0,
+        a0: 1.0 + alpha,
+        a1: -2.0 * w.cos(),
+        a2: 1.0 - alpha,
+    }
+}
+
+fn tone_gain(filter: &mut ComplexBiquad<f64>, frequency: f64) -> f64 {
+    filter.reset();
+    let length = SAMPLE_RATE as usize / 10;
+    let mut peak = 0.0_f64;
+    for n in 0..length {
+        let phase = 2.0 * std::f64::consts::PI * frequency * n as f64 / SAMPLE_RATE as f64;
+        let mut sample = Complex::from_polar(1.0, phase);
+        filter.process(&mut sample);
+        if n > length / 2 {
+            peak = peak.max(sample.norm());
+        }
+    }
+    peak
+}
+
+#[test]
+fn test_real_coefficients_match_real_filter() {
+    let mut complex = ComplexBiquad::new(ComplexCoefficients::from(low_pass())).unwrap();
+    // With real coefficients the real and imaginary parts are filtered independently.
+    let mut real_part = DigitalBiquadFilter::new(low_pass()).unwrap();
+    let mut imaginary_part = DigitalBiquadFilter::new(low_pass()).unwrap();
+    for n in 0..256 {
+        let mut re = (n as f64 * 0.1).sin();
+        let mut im = (n as f64 * 0.37).cos();
+        let mut sample = Complex::new(re, im);
+        assert!(complex.process(&mut sample));
+        real_part.process(&mut re);
+        imaginary_part.process(&mut im);
+        assert_relative_eq!(sample.re, re, epsilon = 1e-12);
+        assert_relative_eq!(sample.im, im, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn test_shifted_filter_separates_positive_and_negative_frequencies() {
+    let coefficients = ComplexCoefficients::frequency_shifted(&low_pass(), 5000.0, SAMPLE_RATE).unwrap();
+    let mut filter = ComplexBiquad::new(coefficients).unwrap();
+    assert_relative_eq!(tone_gain(&mut filter, 5000.0), 1.0, epsilon = 1e-3);
+    assert!(tone_gain(&mut filter, -5000.0) < 0.01);
+}
+
+#[test]
+fn test_invalid_coefficients() {
+    let mut coefficients = ComplexCoefficients::from(low_pass());
+    coefficients.a0 = Complex::new(0.0, 0.0);
+    assert!(ComplexBiquad::new(coefficients).is_none());
+    let mut filter = ComplexBiquad::new(ComplexCoefficients::from(low_pass())).unwrap();
+    let before = filter.get_coefficients();
+    assert!(!filter.set_coefficients(coefficients));
+    assert_eq!(filter.get_coefficients(), before);
+    assert!(!filter.process_block(&mut []));
+}
@@ -0,0 +1,227 @@
+/// convert_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::convert::{self, Dither, OverflowPolicy, Quantizer};
+use biquad_filters::{Filter, FilterExt, LowPassFilter};
+
+fn quiet_sine(amplitude_lsb: f64, length: usize) -> Vec<f64> {
+    (0..length)
+        .map(|n| amplitude_lsb / 32768.0 * (2.0 * std::f64::consts::PI * n as f64 / 97.0).sin())
+        .collect()
+}
+
+#[test]
+fn test_rounding_without_dither() {
+    let mut quantizer = Quantizer::<f64>::new(16, Dither::None).unwrap();
+    let input = [0.0, 0.5, -0.5, 1.0, -1.0, 2.0, f64::NAN, 1.4 / 32768.0];
+    let mut output = [0_i16; 8];
+    assert!(quantizer.quantize_block_i16(&input, &mut output));
+    assert_eq!(output, [0, 16384, -16384, 32767, -32768, 32767, 0, 1]);
+}
+
+#[test]
+fn test_dither_preserves_signals_below_one_bit() {
+    let input = quiet_sine(0.4, 97 * 400);
+    let mut plain = vec![0_i16; input.len()];
+    let mut dithered = vec![0_i16; input.len()];
+    Quantizer::<f64>::new(16, Dither::None).unwrap().quantize_block_i16(&input, &mut plain);
+    Quantizer::<f64>::new(16, Dither::Triangular).unwrap().quantize_block_i16(&input, &mut dithered);
+    assert!(plain.iter().all(|sample| *sample == 0));
+    // Correlating with the input recovers the sine's amplitude from the dithered output.
+    let correlation: f64 = input
+        .iter()
+        .zip(dithered.iter())
+        .map(|(x, y)| x * 32768.0 * *y as f64)
+        .sum::<f64>()
+        / input.iter().map(|x| (x * 32768.0).powi(2)).sum::<f64>();
+    assert!((correlation - 1.0).abs() < 0.1);
+}
+
+#[test]
+fn test_noise_shaping_moves_error_to_high_frequencies() {
+    let input = quiet_sine(1000.0, 48000);
+    let mut output = vec![0_i32; input.len()];
+    let mut quantizer = Quantizer::<f64>::new(16, Dither::Shaped).unwrap();
+    assert!(quantizer.quantize_block_i32(&input, &mut output));
+    let error: Vec<f64> = input
+        .iter()
+        .zip(output.iter())
+        .map(|(x, y)| *y as f64 - x * 32768.0)
+        .collect();
+    // Shaped noise is differentiated, so neighbouring errors are negatively correlated.
+    let lag0: f64 = error.iter().map(|e| e * e).sum();
+    let lag1: f64 = error.windows(2).map(|pair| pair[0] * pair[1]).sum();
+    assert!(lag1 / lag0 < -0.3);
+}
+
+#[test]
+fn test_twenty_four_bit_output() {
+    let mut quantizer = Quantizer::<f32>::new(24, Dither::None).unwrap();
+    let mut output = [0_i32; 3];
+    assert!(quantizer.quantize_block_i32(&[0.5_f32, -1.0, 1.0], &mut output));
+    assert_eq!(output, [4194304, -8388608, 8388607]);
+    let mut narrow = [0_i16; 3];
+    assert!(!quantizer.quantize_block_i16(&[0.5_f32, -1.0, 1.0], &mut narrow));
+    assert!(Quantizer::<f32>::new(33, Dither::None).is_none());
+}
+
+#[test]
+fn test_integer_round_trips() {
+    let integers = [0_i16, 1, -1, 16384, i16::MIN, i16::MAX];
+    let mut floats = [0.0_f32; 6];
+    assert!(convert::i16_to_f32(&integers, &mut floats));
+    assert_eq!(floats[3], 0.5);
+    assert_eq!(floats[4], -1.0);
+    let mut back = [0_i16; 6];
+    assert!(convert::f32_to_i16(&floats, &mut back));
+    assert_eq!(back, integers);
+
+    let wide = [0_i32, -1, 1 << 30, i32::MIN, i32::MAX];
+    let mut doubles = [0.0_f64; 5];
+    assert!(convert::i32_to_f64(&wide, &mut doubles));
+    assert_eq!(doubles[2], 0.5);
+    let mut wide_back = [0_i32; 5];
+    assert!(convert::f64_to_i32(&doubles, &mut wide_back));
+    assert_eq!(wide_back, wide);
+    assert!(convert::f64_to_i32(&[2.0, f64::NAN], &mut wide_back[..2]));
+    assert_eq!(&wide_back[..2], &[i32::MAX, 0]);
+    assert!(!convert::i16_to_f32(&integers, &mut floats[..2]));
+}
+
+#[test]
+fn test_interleave_round_trip() {
+    let left = [1.0_f32, 2.0, 3.0];
+    let right = [-1.0_f32, -2.0, -3.0];
+    let mut frames = [0.0_f32; 6];
+    assert!(convert::interleave(&[&left, &right], &mut frames));
+    assert_eq!(frames, [1.0, -1.0, 2.0, -2.0, 3.0, -3.0]);
+    let mut new_left = [0.0_f32; 3];
+    let mut new_right = [0.0_f32; 3];
+    assert!(convert::deinterleave(&frames, &mut [&mut new_left, &mut new_right]));
+    assert_eq!(new_left, left);
+    assert_eq!(new_right, right);
+    assert!(!convert::interleave(&[&left, &right[..2]], &mut frames));
+    assert!(!convert::deinterleave(&frames[..5], &mut [&mut new_left, &mut new_right]));
+}
+
+#[test]
+fn test_conversions_compose_with_process_block_to() {
+    let mut filter = LowPassFilter::<f32>::new(1000.0_f32, 48000_u32, 0.707_f32).unwrap();
+    let pcm = [16384_i16; 4800];
+    let mut input = [0.0_f32; 4800];
+    let mut output = [0.0_f32; 4800];
+    let mut result = [0_i16; 4800];
+    assert!(convert::i16_to_f32(&pcm, &mut input));
+    assert!(filter.process_block_to(&input, &mut output));
+    assert!(convert::f32_to_i16(&output, &mut result));
+    assert_eq!(input[0], 0.5);
+    assert!((result[4799] - 16384).abs() <= 1);
+    assert!(!filter.process_block_to(&input, &mut output[..10]));
+}
+
+#[test]
+fn sample_formats_convert_to_full_scale() {
+    use biquad_filters::convert::Sample;
+
+    assert_eq!(i16::MIN.to_float::<f32>(), -1.0);
+    assert_eq!(i16::from_float(0.5_f64), 16384);
+    assert_eq!(i16::from_float(2.0_f32), i16::MAX);
+    assert_eq!(i16::from_float(-2.0_f32), i16::MIN);
+    assert_eq!(i16::from_float(f32::NAN), 0);
+    assert_eq!(i32::from_float(1.0_f32), i32::MAX);
+    assert_eq!(i32::from_float(-0.25_f64), -536_870_912);
+    assert_eq!(f32::from_float(0.1_f64), 0.1_f32);
+}
+
+#[test]
+fn filters_process_integer_samples_directly() {
+    use biquad_filters::{Coefficients, DigitalBiquadFilter, IirFilter};
+
+    let coefficients = Coefficients {
+        b0: 0.2_f32,
+        b1: 0.4,
+        b2: 0.2,
+        a0: 1.0,
+        a1: -0.6,
+        a2: 0.2,
+    };
+    let input: Vec<i16> = (0..200).map(|n| ((n * 997) % 20000 - 10000) as i16).collect();
+    let mut float = vec![0.0_f32; input.len()];
+    assert!(convert::i16_to_f32(&input, &mut float));
+    let mut reference = DigitalBiquadFilter::new(coefficients).unwrap();
+    reference.process_block(&mut float);
+    let mut expected = vec![0_i16; input.len()];
+    assert!(convert::f32_to_i16(&float, &mut expected));
+
+    let mut samples = input.clone();
+    let mut filter = DigitalBiquadFilter::new(coefficients).unwrap();
+    assert!(filter.process_samples(&mut samples));
+    assert_eq!(samples, expected);
+
+    let mut low_pass = LowPassFilter::new(1000.0_f64, 48000, 0.707).unwrap();
+    let mut wide: Vec<i32> = vec![i32::MAX / 2; 300];
+    assert!(low_pass.process_samples(&mut wide));
+    assert!((wide[299] - i32::MAX / 2).abs() < 1000);
+
+    let mut iir = IirFilter::new(&[0.5_f32], &[1.0], 48000).unwrap();
+    let mut halved = vec![1000_i16; 150];
+    assert!(iir.process_samples(&mut halved));
+    assert!(halved.iter().all(|&sample| sample == 500));
+}
+
+#[test]
+fn overflow_saturates_by_default_and_is_flagged() {
+    let mut quantizer = Quantizer::<f64>::new(16, Dither::None).unwrap();
+    assert_eq!(quantizer.get_overflow_policy(), OverflowPolicy::Saturate);
+    assert_eq!(quantizer.quantize(0.5), 16384);
+    assert!(!quantizer.take_overflow());
+    assert_eq!(quantizer.quantize(1.5), 32767);
+    assert_eq!(quantizer.quantize(-0.25), -8192);
+    assert!(quantizer.take_overflow());
+    assert!(!quantizer.take_overflow());
+}
+
+#[test]
+fn overflow_wraps_in_twos_complement() {
+    let mut quantizer = Quantizer::<f64>::new(16, Dither::None).unwrap();
+    quantizer.set_overflow_policy(OverflowPolicy::Wrap);
+    // 1.25 of full scale is 40960, which wraps to 40960 - 65536.
+    assert_eq!(quantizer.quantize(1.25), -24576);
+    assert_eq!(quantizer.quantize(-1.25), 24576);
+    assert!(quantizer.take_overflow());
+
+    let mut quantizer = Quantizer::<f64>::new(24, Dither::None).unwrap();
+    quantizer.set_overflow_policy(OverflowPolicy::Wrap);
+    assert_eq!(quantizer.quantize(1.0), -8_388_608);
+}
+
+#[test]
+fn overflow_error_stops_block_conversion() {
+    let mut quantizer = Quantizer::<f64>::new(16, Dither::None).unwrap();
+    quantizer.set_overflow_policy(OverflowPolicy::Error);
+    let input = [0.5, 2.0, 0.25];
+    let mut output = [0_i16; 3];
+    assert!(!quantizer.quantize_block_i16(&input, &mut output));
+    assert_eq!(output, [16384, 0, 0]);
+    assert!(quantizer.take_overflow());
+    assert!(quantizer.quantize_block_i16(&[0.25], &mut output[..1]));
+}
@@ -0,0 +1,116 @@
+/// convolution_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{Cascade, Coefficients, PartitionedConvolver};
+
+/// A deterministic pseudo-random signal in [-1, 1).
+fn noise(len: usize, mut seed: u32) -> Vec<f64> {
+    (0..len)
+        .map(|_| {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            seed as f64 / u32::MAX as f64 * 2.0 - 1.0
+        })
+        .collect()
+}
+
+fn direct_convolution(taps: &[f64], input: &[f64]) -> Vec<f64> {
+    (0..input.len())
+        .map(|n| {
+            taps.iter()
+                .enumerate()
+                .take(n + 1)
+                .map(|(k, tap)| tap * input[n - k])
+                .sum()
+        })
+        .collect()
+}
+
+#[test]
+fn create_invalid_convolver() {
+    assert!(PartitionedConvolver::<f64>::new(&[], 64).is_none());
+    assert!(PartitionedConvolver::new(&[1.0], 48).is_none());
+    assert!(PartitionedConvolver::new(&[1.0, f64::NAN], 64).is_none());
+    let convolver = PartitionedConvolver::new(&[1.0; 100], 32).unwrap();
+    assert_eq!(convolver.get_block_len(), 32);
+    assert_eq!(convolver.get_tap_count(), 100);
+}
+
+#[test]
+fn matches_direct_convolution_for_any_call_length() {
+    let taps = noise(300, 7);
+    let input = noise(2000, 11);
+    let expected = direct_convolution(&taps, &input);
+
+    let mut convolver = PartitionedConvolver::new(&taps, 64).unwrap();
+    let mut output = input.clone();
+    let mut start = 0;
+    for (call, length) in [1, 63, 64, 200, 5, 1000].iter().cycle().enumerate() {
+        if start == output.len() {
+            break;
+        }
+        let end = (start + length + call % 3).min(output.len());
+        assert!(convolver.process_block(&mut output[start..end]));
+        start = end;
+    }
+    for (expected, actual) in expected.iter().zip(output.iter()) {
+        assert!((expected - actual).abs() < 1e-9, "{expected} != {actual}");
+    }
+}
+
+#[test]
+fn reset_clears_history() {
+    let taps = noise(100, 3);
+    let mut convolver = PartitionedConvolver::new(&taps, 16).unwrap();
+    let mut first = noise(50, 5);
+    convolver.process_block(&mut first);
+    convolver.reset();
+    let mut impulse = vec![0.0; 100];
+    impulse[0] = 1.0;
+    convolver.process_block(&mut impulse);
+    for (tap, sample) in taps.iter().zip(impulse.iter()) {
+        assert!((tap - sample).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn cascade_convolver_matches_recursive_processing() {
+    let section = Coefficients {
+        b0: 0.0675,
+        b1: 0.135,
+        b2: 0.0675,
+        a0: 1.0,
+        a1: -1.143,
+        a2: 0.4128,
+    };
+    let mut cascade = Cascade::new([section; 8]).unwrap();
+    let mut convolver = cascade.to_convolver(512, 128).unwrap();
+    let mut expected = noise(4096, 13);
+    let mut actual = expected.clone();
+    cascade.process_block(&mut expected);
+    convolver.process_block(&mut actual);
+    for (expected, actual) in expected.iter().zip(actual.iter()) {
+        assert!((expected - actual).abs() < 1e-9);
+    }
+    assert!(cascade.to_convolver(0, 128).is_none());
+}
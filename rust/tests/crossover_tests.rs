@@ -0,0 +1,167 @@
+/// crossover_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{Crossover, CrossoverMode, CrossoverNetwork};
+use approx::assert_relative_eq;
+use std::f64::consts::PI;
+
+const SAMPLE_RATE: u32 = 48000;
+
+/// Evaluates the magnitude of an impulse response at the given frequency.
+fn magnitude(impulse: &[f64], frequency: f64) -> f64 {
+    let w = 2.0 * PI * frequency / SAMPLE_RATE as f64;
+    let (re, im) = impulse.iter().enumerate().fold((0.0, 0.0), |(re, im), (n, h)| {
+        (re + h * (w * n as f64).cos(), im - h * (w * n as f64).sin())
+    });
+    (re * re + im * im).sqrt()
+}
+
+fn impulse_responses(crossover: &mut Crossover<f64>) -> (Vec<f64>, Vec<f64>) {
+    let mut input = vec![0.0_f64; 8192];
+    input[0] = 1.0;
+    let mut low = vec![0.0_f64; input.len()];
+    let mut high = vec![0.0_f64; input.len()];
+    assert!(crossover.process_block(&input, &mut low, &mut high));
+    (low, high)
+}
+
+#[test]
+fn create_invalid_crossover() {
+    let mode = CrossoverMode::AllpassComplementary { order: 4 };
+    assert!(Crossover::<f64>::new(mode, 1000.0, SAMPLE_RATE).is_none());
+    assert!(Crossover::<f64>::new(CrossoverMode::LinkwitzRiley4, 30000.0, SAMPLE_RATE).is_none());
+}
+
+#[test]
+fn allpass_complementary_sum_is_flat() {
+    for order in [1, 3, 5, 7] {
+        let mode = CrossoverMode::AllpassComplementary { order };
+        let mut crossover = Crossover::<f64>::new(mode, 1000.0, SAMPLE_RATE).unwrap();
+        let (low, high) = impulse_responses(&mut crossover);
+        let sum: Vec<f64> = low.iter().zip(high.iter()).map(|(l, h)| l + h).collect();
+        for frequency in [20.0, 300.0, 1000.0, 3000.0, 15000.0] {
+            assert_relative_eq!(magnitude(&sum, frequency), 1.0, epsilon = 1e-6);
+            let power = magnitude(&low, frequency).powi(2) + magnitude(&high, frequency).powi(2);
+            assert_relative_eq!(power, 1.0, epsilon = 1e-6);
+        }
+        assert_relative_eq!(magnitude(&low, 1000.0), std::f64::consts::FRAC_1_SQRT_2, epsilon = 1e-6);
+        assert_relative_eq!(magnitude(&low, 0.0), 1.0, epsilon = 1e-6);
+        assert!(magnitude(&high, 0.0) < 1e-6);
+    }
+}
+
+#[test]
+fn allpass_complementary_slope_increases_with_order() {
+    let third = CrossoverMode::AllpassComplementary { order: 3 };
+    let fifth = CrossoverMode::AllpassComplementary { order: 5 };
+    let (low_third, _) = impulse_responses(&mut Crossover::new(third, 1000.0, SAMPLE_RATE).unwrap());
+    let (low_fifth, _) = impulse_responses(&mut Crossover::new(fifth, 1000.0, SAMPLE_RATE).unwrap());
+    assert!(magnitude(&low_fifth, 4000.0) < magnitude(&low_third, 4000.0));
+}
+
+#[test]
+fn linkwitz_riley_sum_is_flat() {
+    let mut crossover = Crossover::<f64>::new(CrossoverMode::LinkwitzRiley4, 2000.0, SAMPLE_RATE).unwrap();
+    let (low, high) = impulse_responses(&mut crossover);
+    let sum: Vec<f64> = low.iter().zip(high.iter()).map(|(l, h)| l + h).collect();
+    for frequency in [50.0, 1000.0, 2000.0, 8000.0] {
+        assert_relative_eq!(magnitude(&sum, frequency), 1.0, epsilon = 1e-6);
+    }
+    assert_relative_eq!(magnitude(&low, 2000.0), 0.5, epsilon = 1e-6);
+}
+
+#[test]
+fn set_frequency_redesigns() {
+    let mode = CrossoverMode::AllpassComplementary { order: 3 };
+    let mut crossover = Crossover::<f64>::new(mode, 1000.0, SAMPLE_RATE).unwrap();
+    assert!(crossover.set_frequency(4000.0));
+    assert!(!crossover.set_frequency(-1.0));
+    assert_relative_eq!(crossover.get_frequency(), 4000.0);
+    let (low, _) = impulse_responses(&mut crossover);
+    assert_relative_eq!(magnitude(&low, 4000.0), std::f64::consts::FRAC_1_SQRT_2, epsilon = 1e-6);
+}
+
+#[test]
+fn linkwitz_riley_bands_are_in_phase_at_crossover() {
+    let crossover = Crossover::new(CrossoverMode::LinkwitzRiley4, 1000.0, SAMPLE_RATE).unwrap();
+    let (low, high) = crossover.group_delays(1000.0).unwrap();
+    assert!(low > 0.0);
+    assert_relative_eq!(low, high, epsilon = 1e-3);
+    assert!(crossover.group_delays(30000.0).is_none());
+}
+
+#[test]
+fn network_bands_sum_to_all_pass() {
+    let mut network =
+        CrossoverNetwork::new(CrossoverMode::LinkwitzRiley4, &[200.0, 2000.0], SAMPLE_RATE).unwrap();
+    assert_eq!(network.bands(), 3);
+    let mut sum = vec![0.0; 8192];
+    let mut bands = [0.0; 3];
+    for (n, output) in sum.iter_mut().enumerate() {
+        let sample = if n == 0 { 1.0 } else { 0.0 };
+        assert!(network.process(sample, &mut bands));
+        *output = bands.iter().sum();
+    }
+    for frequency in [50.0, 200.0, 700.0, 2000.0, 8000.0] {
+        assert_relative_eq!(magnitude(&sum, frequency), 1.0, epsilon = 1e-3);
+    }
+    assert!(!network.process(0.0, &mut [0.0; 2]));
+}
+
+#[test]
+fn aligned_network_delays_bands_to_match() {
+    let mut network =
+        CrossoverNetwork::new(CrossoverMode::LinkwitzRiley4, &[200.0, 2000.0], SAMPLE_RATE).unwrap();
+    let group_delays = network.group_delays().unwrap();
+    let alignment = network.alignment_delays().unwrap();
+    let longest = group_delays.iter().cloned().fold(0.0, f64::max);
+    for (delay, extra) in group_delays.iter().zip(alignment.iter()) {
+        assert!(*extra >= 0.0);
+        assert_relative_eq!(delay + extra, longest, epsilon = 1e-9);
+    }
+    // The lowest band passes through the steepest low-pass, so it is delayed the most.
+    assert_relative_eq!(alignment[0], 0.0);
+
+    network.set_aligned(true);
+    assert!(network.is_aligned());
+    let mut highest = vec![0.0; 4096];
+    let mut bands = [0.0; 3];
+    for (n, output) in highest.iter_mut().enumerate() {
+        let sample = if n == 0 { 1.0 } else { 0.0 };
+        network.process(sample, &mut bands);
+        *output = bands[2];
+    }
+    let peak = highest
+        .iter()
+        .enumerate()
+        .fold((0, 0.0), |best, (n, h)| if h.abs() > best.1 { (n, h.abs()) } else { best })
+        .0;
+    assert!(peak as f64 >= alignment[2].floor());
+}
+
+#[test]
+fn invalid_network_is_rejected() {
+    assert!(CrossoverNetwork::<f64>::new(CrossoverMode::LinkwitzRiley4, &[], SAMPLE_RATE).is_none());
+    assert!(
+        CrossoverNetwork::new(CrossoverMode::LinkwitzRiley4, &[2000.0, 200.0], SAMPLE_RATE).is_none()
+    );
+}
@@ -0,0 +1,87 @@
+/// deesser_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{DeEsser, DeEsserBand};
+use approx::assert_relative_eq;
+
+fn tone(frequency: f64, amplitude: f64, len: usize) -> Vec<f64> {
+    (0..len)
+        .map(|n| amplitude * (2.0 * std::f64::consts::PI * frequency * n as f64 / 48000.0).sin())
+        .collect()
+}
+
+fn peak(samples: &[f64]) -> f64 {
+    samples.iter().fold(0.0_f64, |peak, sample| peak.max(sample.abs()))
+}
+
+#[test]
+fn cuts_loud_sibilance() {
+    let mut de_esser = DeEsser::<f64>::new(6000.0, 48000).unwrap();
+    let mut samples = tone(6000.0, 0.5, 9600);
+    assert!(de_esser.process_block(&mut samples));
+    assert_relative_eq!(de_esser.get_reduction(), 6.0);
+    // Six decibels of cut at the centre halves the level.
+    assert!((peak(&samples[4800..]) - 0.25).abs() < 0.01);
+}
+
+#[test]
+fn leaves_low_frequencies_alone() {
+    let mut de_esser = DeEsser::<f64>::new(6000.0, 48000).unwrap();
+    let input = tone(200.0, 0.5, 9600);
+    let mut samples = input.clone();
+    de_esser.process_block(&mut samples);
+    assert_eq!(de_esser.get_reduction(), 0.0);
+    assert!((peak(&samples[4800..]) - 0.5).abs() < 0.01);
+}
+
+#[test]
+fn quiet_sibilance_is_below_threshold() {
+    let mut de_esser = DeEsser::<f64>::new(7000.0, 48000).unwrap();
+    assert!(de_esser.set_threshold(-20.0));
+    let mut samples = tone(7000.0, 0.05, 4800);
+    de_esser.process_block(&mut samples);
+    assert_eq!(de_esser.get_reduction(), 0.0);
+}
+
+#[test]
+fn high_shelf_band_and_range() {
+    let mut de_esser = DeEsser::<f64>::new(5000.0, 48000).unwrap();
+    de_esser.set_band(DeEsserBand::HighShelf);
+    assert!(de_esser.set_range(3.0));
+    let mut samples = tone(10000.0, 0.9, 9600);
+    de_esser.process_block(&mut samples);
+    assert!(de_esser.get_reduction() > 0.0);
+    assert!(de_esser.get_reduction() <= 3.0);
+    de_esser.reset();
+    assert_eq!(de_esser.get_reduction(), 0.0);
+}
+
+#[test]
+fn invalid_settings_are_rejected() {
+    assert!(DeEsser::<f64>::new(2000.0, 48000).is_none());
+    assert!(DeEsser::<f64>::new(8000.0, 16000).is_none());
+    let mut de_esser = DeEsser::<f32>::new(6000.0, 44100).unwrap();
+    assert!(!de_esser.set_frequency(12000.0));
+    assert_eq!(de_esser.get_frequency(), 6000.0);
+    assert!(!de_esser.set_range(-1.0));
+    assert!(!de_esser.set_threshold(f32::NAN));
+}
@@ -0,0 +1,61 @@
+/// descriptor_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{FilterType, ParameterDescriptor, ParameterScale};
+use approx::assert_relative_eq;
+
+#[test]
+fn filter_types_list_their_parameters() {
+    let ids = |filter_type: FilterType| -> Vec<&'static str> {
+        filter_type.parameters().iter().map(|parameter| parameter.id).collect()
+    };
+    assert_eq!(ids(FilterType::LowPass), ["cutoff", "q_factor"]);
+    assert_eq!(ids(FilterType::Notch), ["cutoff", "q_factor"]);
+    assert_eq!(ids(FilterType::PeakingEQ), ["cutoff", "q_factor", "gain"]);
+    assert_eq!(ids(FilterType::HighShelf), ["cutoff", "q_factor", "gain"]);
+    for parameter in FilterType::LowShelf.parameters() {
+        assert!(parameter.min < parameter.max);
+        assert!(parameter.default >= parameter.min && parameter.default <= parameter.max);
+    }
+}
+
+#[test]
+fn logarithmic_controls_cover_equal_ratios() {
+    let cutoff = ParameterDescriptor::CUTOFF;
+    assert_eq!(cutoff.scale, ParameterScale::Logarithmic);
+    assert_eq!(cutoff.unit, "Hz");
+    assert_relative_eq!(cutoff.denormalize(0.0), 20.0);
+    assert_relative_eq!(cutoff.denormalize(1.0), 20000.0);
+    // The midpoint of 20 Hz to 20 kHz on a logarithmic scale is their geometric mean.
+    assert_relative_eq!(cutoff.denormalize(0.5), (20.0_f64 * 20000.0).sqrt(), epsilon = 1e-9);
+    assert_relative_eq!(cutoff.normalize(cutoff.denormalize(0.3)), 0.3, epsilon = 1e-12);
+    assert_eq!(cutoff.normalize(5.0), 0.0);
+}
+
+#[test]
+fn linear_controls_clamp() {
+    let gain = ParameterDescriptor::GAIN;
+    assert_relative_eq!(gain.normalize(0.0), 0.5);
+    assert_relative_eq!(gain.denormalize(0.25), -12.0);
+    assert_eq!(gain.normalize(100.0), 1.0);
+    assert_eq!(gain.denormalize(f64::NAN), -24.0);
+}
@@ -0,0 +1,157 @@
+/// design_method_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{
+    BandPassFilter, DesignMethod, Filter, FilterConfiguration, FrequencyResponse, HighPassFilter,
+    LowPassFilter, NotchFilter, PeakingEQFilter,
+};
+use approx::assert_relative_eq;
+use num_complex::Complex;
+
+const SAMPLE_RATE: u32 = 44100;
+
+/// Returns the analog prototype response of a filter type at `frequency`.
+fn analog(filter_type: &str, cutoff: f64, q: f64, gain: f64, frequency: f64) -> f64 {
+    let s = Complex::new(0.0, frequency / cutoff);
+    let a = 10.0_f64.powf(gain / 40.0);
+    let response = match filter_type {
+        "low_pass" => 1.0 / (s * s + s / q + 1.0),
+        "high_pass" => s * s / (s * s + s / q + 1.0),
+        "band_pass" => (s / q) / (s * s + s / q + 1.0),
+        _ => (s * s + s * a / q + 1.0) / (s * s + s / (a * q) + 1.0),
+    };
+    20.0 * response.norm().log10()
+}
+
+/// Returns the largest difference in decibels between a filter and its analog prototype up to
+/// 20 kHz.
+fn worst_error<F: Filter<f64> + FrequencyResponse<f64>>(filter: &F, filter_type: &str) -> f64 {
+    let config = filter.get_configuration();
+    let (cutoff, q, gain) = (config.get_cutoff(), config.get_q_factor(), config.get_gain());
+    let mut worst: f64 = 0.0;
+    let mut frequency = 20.0;
+    while frequency <= 20000.0 {
+        let digital = 20.0 * filter.magnitude_at(frequency, SAMPLE_RATE).log10();
+        let expected = analog(filter_type, cutoff, q, gain, frequency);
+        if expected > -30.0 {
+            worst = worst.max((digital - expected).abs());
+        }
+        frequency *= 1.05;
+    }
+    worst
+}
+
+/// Designs a filter with both methods and returns the worst errors, bilinear first.
+fn errors<F: Filter<f64> + FrequencyResponse<f64>>(mut filter: F, filter_type: &str) -> (f64, f64) {
+    let bilinear = worst_error(&filter, filter_type);
+    assert!(filter.set_design_method(DesignMethod::Matched));
+    assert_eq!(filter.get_design_method(), DesignMethod::Matched);
+    (bilinear, worst_error(&filter, filter_type))
+}
+
+#[test]
+fn matched_designs_follow_the_analog_prototype_near_nyquist() {
+    let q = std::f64::consts::FRAC_1_SQRT_2;
+    for cutoff in [1000.0, 10000.0, 16000.0] {
+        let cases = [
+            ("low_pass", errors(LowPassFilter::new(cutoff, SAMPLE_RATE, q).unwrap(), "low_pass")),
+            ("high_pass", errors(HighPassFilter::new(cutoff, SAMPLE_RATE, q).unwrap(), "high_pass")),
+            ("band_pass", errors(BandPassFilter::new(cutoff, SAMPLE_RATE, 2.0, false).unwrap(), "band_pass")),
+            ("peak", errors(PeakingEQFilter::new(cutoff, SAMPLE_RATE, 2.0, 9.0).unwrap(), "peak")),
+            ("cut", errors(PeakingEQFilter::new(cutoff, SAMPLE_RATE, 1.0, -12.0).unwrap(), "cut")),
+        ];
+        for (name, (bilinear, matched)) in cases {
+            assert!(matched < 1.3, "{name} at {cutoff} Hz: {matched} dB");
+            if cutoff > 5000.0 {
+                assert!(matched < bilinear / 2.5, "{name} at {cutoff} Hz: {matched} dB");
+            }
+        }
+    }
+}
+
+#[test]
+fn matched_cut_is_the_inverse_of_the_boost() {
+    let mut boost = PeakingEQFilter::<f64>::new(12000.0, SAMPLE_RATE, 1.5, 8.0).unwrap();
+    let mut cut = PeakingEQFilter::<f64>::new(12000.0, SAMPLE_RATE, 1.5, -8.0).unwrap();
+    assert!(boost.set_design_method(DesignMethod::Matched));
+    assert!(cut.set_design_method(DesignMethod::Matched));
+    for frequency in [100.0, 6000.0, 12000.0, 21000.0] {
+        let product =
+            boost.response_at(frequency, SAMPLE_RATE) * cut.response_at(frequency, SAMPLE_RATE);
+        assert_relative_eq!(product.re, 1.0, epsilon = 1e-9);
+        assert_relative_eq!(product.im, 0.0, epsilon = 1e-9);
+    }
+}
+
+#[test]
+fn design_method_is_part_of_the_configuration() {
+    let config = FilterConfiguration::<f32>::builder()
+        .cutoff(15000.0)
+        .sample_rate(SAMPLE_RATE)
+        .design_method(DesignMethod::Matched)
+        .build()
+        .unwrap();
+    assert_eq!(config.get_design_method(), DesignMethod::Matched);
+    assert_eq!(FilterConfiguration::<f32>::default().get_design_method(), DesignMethod::Bilinear);
+
+    let mut filter = LowPassFilter::<f32>::new(15000.0, SAMPLE_RATE, 0.707).unwrap();
+    let snapshot = filter.snapshot();
+    assert!(filter.set_configuration(config));
+    assert!(filter.diff(&snapshot).design_method);
+    let mut samples = [1.0_f32; 256];
+    assert!(filter.process_block(&mut samples));
+    assert_relative_eq!(samples[255], 1.0, epsilon = 1e-4);
+}
+
+#[test]
+fn types_without_a_matched_design_use_the_bilinear_transform() {
+    let mut notch = NotchFilter::<f64>::new(15000.0, SAMPLE_RATE, 2.0).unwrap();
+    let bilinear = notch.response_at(12000.0, SAMPLE_RATE);
+    assert!(notch.set_design_method(DesignMethod::Matched));
+    assert_eq!(notch.response_at(12000.0, SAMPLE_RATE), bilinear);
+}
+
+#[test]
+fn orfanidis_peaking_matches_the_analog_gain_at_nyquist() {
+    let nyquist = f64::from(SAMPLE_RATE) / 2.0;
+    for (cutoff, q, gain) in [(1000.0, 1.0, 6.0), (12000.0, 2.0, 9.0), (16000.0, 1.5, -12.0)] {
+        let mut filter = PeakingEQFilter::<f64>::new(cutoff, SAMPLE_RATE, q, gain).unwrap();
+        let bilinear = worst_error(&filter, "peak");
+        assert!(filter.set_design_method(DesignMethod::Orfanidis));
+        let expected = analog("peak", cutoff, q, gain, nyquist);
+        let digital = 20.0 * filter.magnitude_at(nyquist, SAMPLE_RATE).log10();
+        assert_relative_eq!(digital, expected, epsilon = 1e-9);
+        let centre = 20.0 * filter.magnitude_at(cutoff, SAMPLE_RATE).log10();
+        assert_relative_eq!(centre, gain, epsilon = 1e-9);
+        let orfanidis = worst_error(&filter, "peak");
+        assert!(orfanidis < 1.5 && orfanidis <= bilinear, "{cutoff} Hz: {orfanidis} dB");
+    }
+}
+
+#[test]
+fn orfanidis_rejects_bands_wider_than_nyquist() {
+    let mut filter = PeakingEQFilter::<f64>::new(18000.0, SAMPLE_RATE, 0.5, 6.0).unwrap();
+    assert!(!filter.set_design_method(DesignMethod::Orfanidis));
+    assert_eq!(filter.get_design_method(), DesignMethod::Bilinear);
+    let mut low_pass = LowPassFilter::<f64>::new(18000.0, SAMPLE_RATE, 0.5).unwrap();
+    assert!(low_pass.set_design_method(DesignMethod::Orfanidis));
+}
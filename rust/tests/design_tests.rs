@@ -0,0 +1,150 @@
+/// design_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::design::{
+    BesselNormalization, MAX_BESSEL_ORDER, bessel_sections, butterworth_band_pass,
+    butterworth_high_pass, butterworth_low_pass, butterworth_q_values,
+};
+use biquad_filters::{Coefficients, FrequencyResponse};
+use approx::assert_relative_eq;
+
+#[test]
+fn second_order_is_a_single_section() {
+    let q = butterworth_q_values::<f64>(2);
+    assert_eq!(q.len(), 1);
+    assert_relative_eq!(q[0], std::f64::consts::FRAC_1_SQRT_2, epsilon = 1e-12);
+}
+
+#[test]
+fn fourth_order_matches_published_values() {
+    let q = butterworth_q_values::<f64>(4);
+    assert_eq!(q.len(), 2);
+    assert_relative_eq!(q[0], 0.541_196_100_146, epsilon = 1e-9);
+    assert_relative_eq!(q[1], 1.306_562_964_876, epsilon = 1e-9);
+}
+
+#[test]
+fn odd_orders_leave_out_the_first_order_section() {
+    let q = butterworth_q_values::<f32>(5);
+    assert_eq!(q.len(), 2);
+    assert_relative_eq!(q[0], 0.618_034, epsilon = 1e-5);
+    assert_relative_eq!(q[1], 1.618_034, epsilon = 1e-5);
+    assert!(butterworth_q_values::<f32>(1).is_empty());
+    assert!(butterworth_q_values::<f32>(0).is_empty());
+}
+
+#[test]
+fn q_values_are_ascending() {
+    let q = butterworth_q_values::<f64>(8);
+    assert_eq!(q.len(), 4);
+    assert!(q.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn bessel_second_order_normalizations() {
+    let delay = bessel_sections::<f64>(2, BesselNormalization::Delay).unwrap();
+    assert_eq!(delay.len(), 1);
+    assert_relative_eq!(delay[0].frequency, 3.0_f64.sqrt(), epsilon = 1e-9);
+    assert_relative_eq!(delay[0].q.unwrap(), 1.0 / 3.0_f64.sqrt(), epsilon = 1e-9);
+
+    let magnitude = bessel_sections::<f64>(2, BesselNormalization::Magnitude).unwrap();
+    assert_relative_eq!(magnitude[0].frequency, 1.272_019_649_5, epsilon = 1e-6);
+    assert_relative_eq!(magnitude[0].q.unwrap(), delay[0].q.unwrap(), epsilon = 1e-9);
+}
+
+#[test]
+fn bessel_fourth_order_matches_published_values() {
+    let sections = bessel_sections::<f64>(4, BesselNormalization::Magnitude).unwrap();
+    assert_eq!(sections.len(), 2);
+    assert_relative_eq!(sections[0].frequency, 1.4302, epsilon = 1e-4);
+    assert_relative_eq!(sections[0].q.unwrap(), 0.5219, epsilon = 1e-4);
+    assert_relative_eq!(sections[1].frequency, 1.6034, epsilon = 1e-4);
+    assert_relative_eq!(sections[1].q.unwrap(), 0.8055, epsilon = 1e-4);
+}
+
+#[test]
+fn bessel_odd_orders_start_with_a_first_order_section() {
+    let sections = bessel_sections::<f32>(3, BesselNormalization::Delay).unwrap();
+    assert_eq!(sections.len(), 2);
+    assert_eq!(sections[0].q, None);
+    assert_relative_eq!(sections[0].frequency, 2.322_185, epsilon = 1e-5);
+    assert_relative_eq!(sections[1].q.unwrap(), 0.691_047, epsilon = 1e-5);
+}
+
+#[test]
+fn bessel_rejects_unsupported_orders() {
+    assert!(bessel_sections::<f64>(0, BesselNormalization::Magnitude).is_none());
+    assert!(bessel_sections::<f64>(MAX_BESSEL_ORDER + 1, BesselNormalization::Delay).is_none());
+    let highest = bessel_sections::<f64>(MAX_BESSEL_ORDER, BesselNormalization::Magnitude).unwrap();
+    assert_eq!(highest.len(), MAX_BESSEL_ORDER / 2);
+    assert!(highest.iter().all(|section| section.frequency.is_finite()));
+}
+
+fn cascade_magnitude(sections: &[Coefficients<f64>], frequency: f64) -> f64 {
+    sections.iter().map(|section| section.magnitude_at(frequency, 48000)).product()
+}
+
+/// The squared magnitude of an analog Butterworth response at the prewarped frequency ratio.
+fn butterworth_power(order: usize, frequency: f64, cutoff: f64) -> f64 {
+    let prewarp = |f: f64| (core::f64::consts::PI * f / 48000.0).tan();
+    1.0 / (1.0 + (prewarp(frequency) / prewarp(cutoff)).powi(2 * order as i32))
+}
+
+#[test]
+fn butterworth_low_and_high_pass_follow_the_prototype() {
+    for order in 1..=9 {
+        let low = butterworth_low_pass::<f64>(order, 1000.0, 48000).unwrap();
+        let high = butterworth_high_pass::<f64>(order, 1000.0, 48000).unwrap();
+        assert_eq!(low.len(), order.div_ceil(2));
+        for frequency in [100.0, 1000.0, 3000.0] {
+            let power = butterworth_power(order, frequency, 1000.0);
+            assert_relative_eq!(cascade_magnitude(&low, frequency).powi(2), power, epsilon = 1e-9);
+            assert_relative_eq!(
+                cascade_magnitude(&high, frequency).powi(2),
+                1.0 - power,
+                epsilon = 1e-9
+            );
+        }
+    }
+    assert!(butterworth_low_pass::<f64>(0, 1000.0, 48000).is_none());
+    assert!(butterworth_high_pass::<f64>(4, 24000.0, 48000).is_none());
+}
+
+#[test]
+fn butterworth_band_pass_has_half_power_edges() {
+    for order in 1..=6 {
+        let sections = butterworth_band_pass::<f64>(order, 500.0, 2000.0, 48000).unwrap();
+        assert_eq!(sections.len(), order);
+        let half_power = core::f64::consts::FRAC_1_SQRT_2;
+        assert_relative_eq!(cascade_magnitude(&sections, 500.0), half_power, epsilon = 1e-9);
+        assert_relative_eq!(cascade_magnitude(&sections, 2000.0), half_power, epsilon = 1e-9);
+        // Unity at the centre, which is the geometric mean of the prewarped edges.
+        let prewarp = |f: f64| (core::f64::consts::PI * f / 48000.0).tan();
+        let center = (prewarp(500.0) * prewarp(2000.0)).sqrt().atan() * 48000.0 / core::f64::consts::PI;
+        assert_relative_eq!(cascade_magnitude(&sections, center), 1.0, epsilon = 1e-9);
+        assert!(cascade_magnitude(&sections, 50.0) < half_power);
+    }
+    // A band wide enough that the real prototype pole splits into two real poles.
+    let wide = butterworth_band_pass::<f64>(3, 20.0, 20000.0, 48000).unwrap();
+    assert_relative_eq!(cascade_magnitude(&wide, 20.0), core::f64::consts::FRAC_1_SQRT_2, epsilon = 1e-9);
+    assert!(butterworth_band_pass::<f64>(2, 2000.0, 500.0, 48000).is_none());
+}
@@ -0,0 +1,115 @@
+/// dyn_filter_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{
+    ChainStage, Filter, FilterChain, FilterExt, FrequencyResponse, HighPassFilter, IirFilter,
+    LowPassFilter, PeakingEQFilter,
+};
+
+fn chain() -> Vec<Box<dyn Filter<f32> + Send>> {
+    vec![
+        Box::new(HighPassFilter::new(40.0, 48000, 0.707).unwrap()),
+        Box::new(PeakingEQFilter::new(1000.0, 48000, 1.0, 6.0).unwrap()),
+        Box::new(LowPassFilter::new(12000.0, 48000, 0.707).unwrap()),
+        Box::new(IirFilter::new(&[0.5, 0.5], &[1.0], 48000).unwrap()),
+    ]
+}
+
+fn run<F: Filter<f32>>(filter: &mut F, samples: &mut [f32]) -> bool {
+    filter.process_block(samples)
+}
+
+#[test]
+fn heterogeneous_filters_can_be_boxed() {
+    let mut filters = chain();
+    let mut samples: Vec<f32> = (0..256).map(|n| (n as f32 * 0.05).sin()).collect();
+    for filter in filters.iter_mut() {
+        assert!(run(filter, &mut samples));
+        assert_eq!(filter.samples_processed(), 256);
+    }
+    assert!(samples.iter().all(|sample| sample.is_finite()));
+}
+
+#[test]
+fn boxed_filters_can_be_reconfigured() {
+    let mut filters = chain();
+    assert!(filters[2].set_cutoff(8000.0));
+    assert_eq!(filters[2].get_cutoff(), 8000.0);
+    assert!(!filters[3].set_cutoff(8000.0));
+    assert!(filters[1].set_bypass(true));
+    let mut sample = 0.25_f32;
+    assert!(filters[1].process(&mut sample));
+    assert_eq!(sample, 0.25);
+    let snapshot = filters[0].snapshot();
+    assert!(filters[0].set_q_factor(2.0));
+    assert!(filters[0].restore(&snapshot));
+    assert_eq!(filters[0].get_q_factor(), 0.707);
+}
+
+#[test]
+fn boxed_responses_can_be_evaluated() {
+    let responses: Vec<Box<dyn FrequencyResponse<f64>>> = vec![
+        Box::new(LowPassFilter::new(1000.0, 48000, 0.707).unwrap()),
+        Box::new(HighPassFilter::new(1000.0, 48000, 0.707).unwrap()),
+    ];
+    assert!(responses[0].magnitude_at(100.0, 48000) > 0.99);
+    assert!(responses[1].magnitude_at(100.0, 48000) < 0.02);
+}
+
+#[test]
+fn generic_methods_work_through_references() {
+    let mut filters = chain();
+    let filter: &mut (dyn Filter<f32> + Send) = filters[1].as_mut();
+    assert!(filter.update(|config| {
+        config.set_cutoff(2000.0);
+        config.set_gain(-3.0);
+    }));
+    assert_eq!((filter.get_cutoff(), filter.get_gain()), (2000.0, -3.0));
+    assert!(!filter.update(|config| config.set_q_factor(-1.0)));
+    let mut samples = [0_i16, 1000, -1000, i16::MAX];
+    assert!(filter.process_samples(&mut samples));
+    assert_eq!(filter.samples_processed(), 4);
+
+    let mut plain = LowPassFilter::<f32>::new(1000.0, 48000, 0.707).unwrap();
+    let plain: &mut dyn Filter<f32> = &mut plain;
+    assert!(plain.update(|config| config.set_q_factor(2.0)));
+    assert_eq!(plain.get_q_factor(), 2.0);
+}
+
+#[test]
+fn mixed_filters_can_be_chained() {
+    let stages: Vec<ChainStage<f32>> = vec![
+        Box::new(HighPassFilter::new(40.0, 48000, 0.707).unwrap()),
+        Box::new(PeakingEQFilter::new(1000.0, 48000, 1.0, 6.0).unwrap()),
+        Box::new(LowPassFilter::new(12000.0, 48000, 0.707).unwrap()),
+        Box::new(IirFilter::new(&[0.5, 0.5], &[1.0], 48000).unwrap()),
+    ];
+    let mut filter_chain = FilterChain::new(48000).unwrap();
+    for stage in stages {
+        assert!(filter_chain.push(stage));
+    }
+    let stage = filter_chain.get_stage_mut(2).unwrap();
+    assert!(stage.update(|config| config.set_cutoff(6000.0)));
+    let mut samples: Vec<f32> = (0..256).map(|n| (n as f32 * 0.05).sin()).collect();
+    assert!(filter_chain.process_block(&mut samples));
+    assert!(samples.iter().all(|sample| sample.is_finite()));
+}
@@ -0,0 +1,84 @@
+/// emphasis_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{EmphasisFilter, EmphasisTimeConstant, FrequencyResponse};
+use approx::assert_relative_eq;
+
+fn decibels(filter: &EmphasisFilter<f64>, frequency: f64) -> f64 {
+    20.0 * filter.magnitude_at(frequency, filter.get_sample_rate()).log10()
+}
+
+#[test]
+fn time_constants_have_standard_corners() {
+    assert_relative_eq!(EmphasisTimeConstant::Microseconds50.corner_frequency(), 3183.1, epsilon = 0.1);
+    assert_relative_eq!(EmphasisTimeConstant::Microseconds75.corner_frequency(), 2122.1, epsilon = 0.1);
+}
+
+#[test]
+fn de_emphasis_is_a_first_order_low_pass() {
+    for time_constant in [EmphasisTimeConstant::Microseconds50, EmphasisTimeConstant::Microseconds75] {
+        for rate in [32000, 48000, 192000] {
+            let filter = EmphasisFilter::de_emphasis(time_constant, rate).unwrap();
+            assert!(!filter.is_pre_emphasis());
+            assert_eq!(filter.get_time_constant(), time_constant);
+            assert_relative_eq!(decibels(&filter, 0.0), 0.0, epsilon = 1e-9);
+            assert_relative_eq!(
+                decibels(&filter, time_constant.corner_frequency()),
+                -10.0 * 2.0_f64.log10(),
+                epsilon = 1e-9
+            );
+        }
+    }
+}
+
+#[test]
+fn pre_emphasis_undoes_de_emphasis_in_the_audio_band() {
+    let time_constant = EmphasisTimeConstant::Microseconds75;
+    let pre = EmphasisFilter::pre_emphasis(time_constant, 48000).unwrap();
+    let de = EmphasisFilter::de_emphasis(time_constant, 48000).unwrap();
+    assert!(pre.is_pre_emphasis());
+    for frequency in [50.0, 1000.0, 5000.0, 10000.0] {
+        assert!((decibels(&pre, frequency) + decibels(&de, frequency)).abs() < 0.1);
+    }
+}
+
+#[test]
+fn pre_emphasis_follows_the_analog_curve_at_high_rates() {
+    let time_constant = EmphasisTimeConstant::Microseconds75;
+    let pre = EmphasisFilter::pre_emphasis(time_constant, 192000).unwrap();
+    for frequency in [1000.0, 5000.0, 15000.0] {
+        let analog = 10.0 * (1.0 + (frequency / time_constant.corner_frequency()).powi(2)).log10();
+        assert!((decibels(&pre, frequency) - analog).abs() < 0.5);
+    }
+}
+
+#[test]
+fn processes_and_rejects_low_rates() {
+    let mut filter =
+        EmphasisFilter::<f32>::de_emphasis(EmphasisTimeConstant::Microseconds50, 44100).unwrap();
+    let mut block = [1.0; 512];
+    assert!(filter.process_block(&mut block));
+    assert_relative_eq!(block[511], 1.0, epsilon = 1e-4);
+    filter.reset();
+    assert!(EmphasisFilter::<f64>::de_emphasis(EmphasisTimeConstant::Microseconds50, 6000).is_none());
+    assert!(EmphasisFilter::<f64>::pre_emphasis(EmphasisTimeConstant::Microseconds50, 6800).is_none());
+}
@@ -0,0 +1,116 @@
+/// envelope_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{AutoWah, EnvelopeFollower, SvfMode};
+use approx::assert_relative_eq;
+
+const SAMPLE_RATE: u32 = 48000;
+
+fn tone(frequency: f64, amplitude: f64, length: usize) -> Vec<f64> {
+    (0..length)
+        .map(|n| {
+            let time = n as f64 / SAMPLE_RATE as f64;
+            amplitude * (2.0 * std::f64::consts::PI * frequency * time).sin()
+        })
+        .collect()
+}
+
+#[test]
+fn follower_rises_with_the_attack_and_falls_with_the_release() {
+    let mut follower = EnvelopeFollower::<f64>::new(0.001, 0.01, SAMPLE_RATE).unwrap();
+    let step = vec![-1.0; 48];
+    let mut envelope = vec![0.0; 48];
+    assert!(follower.process_block(&step, &mut envelope));
+    assert_relative_eq!(envelope[47], 1.0 - (-1.0_f64).exp(), epsilon = 1e-12);
+    assert!(envelope.windows(2).all(|pair| pair[1] > pair[0]));
+
+    let level = follower.get_envelope();
+    for _ in 0..480 {
+        follower.process(0.0);
+    }
+    assert_relative_eq!(follower.get_envelope(), level * (-1.0_f64).exp(), epsilon = 1e-12);
+
+    assert!(follower.set_attack(0.0));
+    assert_relative_eq!(follower.process(0.5), 0.5);
+    follower.reset();
+    assert_eq!(follower.get_envelope(), 0.0);
+}
+
+#[test]
+fn follower_rejects_invalid_settings() {
+    assert!(EnvelopeFollower::<f64>::new(-0.001, 0.01, SAMPLE_RATE).is_none());
+    assert!(EnvelopeFollower::<f64>::new(0.001, f64::NAN, SAMPLE_RATE).is_none());
+    assert!(EnvelopeFollower::<f64>::new(0.001, 0.01, 0).is_none());
+
+    let mut follower = EnvelopeFollower::<f64>::new(0.001, 0.01, SAMPLE_RATE).unwrap();
+    assert!(!follower.set_release(f64::INFINITY));
+    assert!(!follower.set_sample_rate(0));
+    assert!(follower.set_sample_rate(96000));
+    assert_relative_eq!(follower.get_attack(), 0.001);
+    assert!(!follower.process_block(&[1.0, 1.0], &mut [0.0]));
+}
+
+#[test]
+fn louder_input_opens_the_filter() {
+    let mut wah = AutoWah::<f64>::new(SAMPLE_RATE).unwrap();
+    let mut silence = vec![0.0; 4800];
+    assert!(wah.process_block(&mut silence));
+    assert_relative_eq!(wah.get_cutoff(), AutoWah::<f64>::DEFAULT_MIN_FREQUENCY);
+
+    let mut quiet = tone(220.0, 0.05, 4800);
+    assert!(wah.process_block(&mut quiet));
+    let partly_open = wah.get_cutoff();
+    assert!(partly_open > AutoWah::<f64>::DEFAULT_MIN_FREQUENCY);
+    assert!(partly_open < AutoWah::<f64>::DEFAULT_MAX_FREQUENCY);
+
+    let mut loud = tone(220.0, 0.8, 4800);
+    assert!(wah.process_block(&mut loud));
+    assert_relative_eq!(wah.get_cutoff(), AutoWah::<f64>::DEFAULT_MAX_FREQUENCY, epsilon = 1e-9);
+    assert!(loud.iter().all(|sample| sample.is_finite()));
+
+    wah.reset();
+    assert_relative_eq!(wah.get_cutoff(), AutoWah::<f64>::DEFAULT_MIN_FREQUENCY);
+    assert_eq!(wah.get_follower().get_envelope(), 0.0);
+}
+
+#[test]
+fn range_mode_and_sensitivity_are_validated() {
+    assert!(AutoWah::<f64>::new(4000).is_none());
+
+    let mut wah = AutoWah::<f64>::new(SAMPLE_RATE).unwrap();
+    assert_eq!(wah.get_mode(), SvfMode::BandPass);
+    wah.set_mode(SvfMode::LowPass);
+    assert_eq!(wah.get_mode(), SvfMode::LowPass);
+
+    assert!(!wah.set_range(2000.0, 1000.0));
+    assert!(!wah.set_range(0.0, 1000.0));
+    assert!(!wah.set_range(200.0, 24000.0));
+    assert!(wah.set_range(200.0, 4000.0));
+    assert_eq!(wah.get_range(), (200.0, 4000.0));
+
+    assert!(!wah.set_sensitivity(-1.0));
+    assert!(wah.set_sensitivity(0.0));
+    assert!(!wah.set_q_factor(0.0));
+    let mut loud = tone(220.0, 0.8, 960);
+    assert!(wah.process_block(&mut loud));
+    assert_relative_eq!(wah.get_cutoff(), 200.0);
+}
@@ -0,0 +1,78 @@
+/// fault_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{Coefficients, DigitalBiquadFilter, Fault, Filter, LowPassFilter};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+fn coefficients(a1: f64, a2: f64) -> Coefficients<f64> {
+    Coefficients {
+        b0: 1.0,
+        b1: 0.0,
+        b2: 0.0,
+        a0: 1.0,
+        a1,
+        a2,
+    }
+}
+
+#[test]
+fn test_stability() {
+    assert!(coefficients(-1.8, 0.81).is_stable());
+    assert!(!coefficients(-2.0, 1.0).is_stable());
+    assert!(!coefficients(0.0, 1.2).is_stable());
+    assert!(!coefficients(-1.5, 0.4).is_stable());
+}
+
+#[test]
+fn test_unstable_coefficients_are_flagged() {
+    let mut filter = DigitalBiquadFilter::new(coefficients(-1.8, 0.81)).unwrap();
+    assert!(filter.get_faults().is_empty());
+    assert!(filter.update_coefficients(coefficients(0.0, 1.2)));
+    assert!(filter.get_faults().unstable_coefficients);
+    let faults = filter.take_faults();
+    assert!(faults.unstable_coefficients);
+    assert!(!faults.non_finite_output);
+    assert!(filter.get_faults().is_empty());
+    let unstable = DigitalBiquadFilter::new(coefficients(-2.0, 1.0)).unwrap();
+    assert!(unstable.get_faults().unstable_coefficients);
+}
+
+static NON_FINITE: AtomicUsize = AtomicUsize::new(0);
+
+fn count_non_finite(fault: Fault) {
+    if fault == Fault::NonFiniteOutput {
+        NON_FINITE.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn test_handler_is_called_once_per_fault() {
+    let mut filter = LowPassFilter::<f64>::new(1000.0_f64, 48000_u32, 0.707_f64).unwrap();
+    filter.set_fault_handler(Some(count_non_finite));
+    let mut samples = [0.5_f64; 16];
+    samples[3] = f64::INFINITY;
+    filter.process_block(&mut samples);
+    assert_eq!(NON_FINITE.load(Ordering::Relaxed), 1);
+    assert!(filter.take_faults().non_finite_output);
+    filter.process_block(&mut samples);
+    assert_eq!(NON_FINITE.load(Ordering::Relaxed), 2);
+}
@@ -0,0 +1,96 @@
+/// filter_bank_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{Filter, FilterBank, FrequencyScale};
+use approx::assert_relative_eq;
+
+fn rms(samples: &[f64]) -> f64 {
+    (samples.iter().map(|sample| sample * sample).sum::<f64>() / samples.len() as f64).sqrt()
+}
+
+#[test]
+fn scale_conversions_round_trip() {
+    for scale in [FrequencyScale::Mel, FrequencyScale::Bark] {
+        for frequency in [100.0_f64, 1000.0, 8000.0] {
+            let value = scale.from_hz(frequency).unwrap();
+            assert_relative_eq!(scale.to_hz(value).unwrap(), frequency, epsilon = 1e-6);
+        }
+    }
+    assert_relative_eq!(FrequencyScale::Mel.from_hz(1000.0_f64).unwrap(), 1000.0, epsilon = 0.1);
+}
+
+#[test]
+fn create_mel_bank() {
+    let bank = FilterBank::<f64>::mel(20, 100.0, 8000.0, 16000).unwrap();
+    assert_eq!(bank.len(), 20);
+    let centers = bank.get_center_frequencies();
+    assert!(centers.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(centers[0] > 100.0 && centers[19] < 8000.0);
+    let low_width = centers[1] - centers[0];
+    let high_width = centers[19] - centers[18];
+    assert!(high_width > low_width);
+}
+
+#[test]
+fn create_bark_bank() {
+    let bank = FilterBank::<f32>::bark(24, 50.0, 15000.0, 44100).unwrap();
+    assert_eq!(bank.len(), 24);
+    assert!(bank.get_band(23).unwrap().get_q_factor() > 0.0);
+}
+
+#[test]
+fn create_invalid_bank() {
+    assert!(FilterBank::<f64>::mel(0, 100.0, 8000.0, 16000).is_none());
+    assert!(FilterBank::<f64>::mel(10, 8000.0, 100.0, 16000).is_none());
+    assert!(FilterBank::<f64>::mel(10, 100.0, 9000.0, 16000).is_none());
+}
+
+#[test]
+fn tone_excites_matching_band() {
+    let sample_rate = 16000_u32;
+    let mut bank = FilterBank::<f64>::mel(16, 100.0, 7000.0, sample_rate).unwrap();
+    let target = 9;
+    let frequency = bank.get_center_frequencies()[target];
+    let input: Vec<f64> = (0..4096)
+        .map(|n| (2.0 * std::f64::consts::PI * frequency * n as f64 / sample_rate as f64).sin())
+        .collect();
+
+    let mut buffers = vec![vec![0.0_f64; input.len()]; bank.len()];
+    let mut outputs: Vec<&mut [f64]> = buffers.iter_mut().map(|b| b.as_mut_slice()).collect();
+    assert!(bank.process_block(&input, &mut outputs));
+
+    let levels: Vec<f64> = buffers.iter().map(|b| rms(&b[2048..])).collect();
+    let loudest = levels
+        .iter()
+        .enumerate()
+        .fold(0, |best, (index, level)| if *level > levels[best] { index } else { best });
+    assert_eq!(loudest, target);
+}
+
+#[test]
+fn process_requires_one_output_per_band() {
+    let mut bank = FilterBank::<f64>::mel(4, 100.0, 4000.0, 16000).unwrap();
+    let mut outputs = [0.0_f64; 3];
+    assert!(!bank.process(1.0, &mut outputs));
+    let mut outputs = [0.0_f64; 4];
+    assert!(bank.process(1.0, &mut outputs));
+}
@@ -0,0 +1,112 @@
+/// filter_configuration_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{DesignMethod, Filter, FilterConfiguration, FilterError, PeakingEQFilter};
+use approx::assert_relative_eq;
+
+#[test]
+fn builder_creates_valid_configuration() {
+    let configuration = FilterConfiguration::<f64>::builder()
+        .cutoff(1000.0)
+        .sample_rate(48000)
+        .q_factor(2.0)
+        .gain(-6.0)
+        .build()
+        .unwrap();
+    assert_eq!(configuration.get_cutoff(), 1000.0);
+    assert_eq!(configuration.get_q_factor(), 2.0);
+    assert_eq!(configuration.get_gain(), -6.0);
+    assert!(!configuration.get_bypass());
+
+    let mut filter = PeakingEQFilter::<f64>::new(500.0, 48000, 1.0, 0.0).unwrap();
+    assert!(filter.set_configuration(configuration));
+    assert_eq!(filter.get_gain(), -6.0);
+}
+
+#[test]
+fn builder_defaults_q_factor() {
+    let configuration = FilterConfiguration::<f32>::builder()
+        .cutoff(100.0)
+        .sample_rate(44100)
+        .build()
+        .unwrap();
+    assert!((configuration.get_q_factor() - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-6);
+}
+
+#[test]
+fn builder_reports_each_invalid_setting() {
+    let valid = FilterConfiguration::<f64>::builder().cutoff(1000.0).sample_rate(48000);
+    assert_eq!(
+        FilterConfiguration::<f64>::builder().sample_rate(48000).build().unwrap_err(),
+        FilterError::InvalidCutoff
+    );
+    assert_eq!(
+        FilterConfiguration::<f64>::builder().cutoff(1000.0).build().unwrap_err(),
+        FilterError::InvalidSampleRate
+    );
+    assert_eq!(valid.cutoff(24000.0).build().unwrap_err(), FilterError::CutoffAboveNyquist);
+    assert_eq!(valid.q_factor(0.0).build().unwrap_err(), FilterError::InvalidQFactor);
+    assert_eq!(valid.gain(100.0).build().unwrap_err(), FilterError::InvalidGain);
+    assert_eq!(valid.gain(f64::NAN).build().unwrap_err(), FilterError::InvalidGain);
+    assert_eq!(valid.notch_gain(1.5).build().unwrap_err(), FilterError::InvalidNotchGain);
+    assert_eq!(valid.cut_q_factor(-1.0).build().unwrap_err(), FilterError::InvalidCutQFactor);
+    assert_eq!(
+        valid.gain(12.0).shelf_slope(6.0).build().unwrap_err(),
+        FilterError::InvalidShelfSlope
+    );
+    assert_eq!(
+        FilterError::CutoffAboveNyquist.to_string(),
+        "cutoff frequency must be below the Nyquist frequency"
+    );
+}
+
+#[test]
+fn lerp_moves_in_octaves_and_decibels() {
+    let a = FilterConfiguration::builder()
+        .cutoff(100.0)
+        .sample_rate(48000)
+        .q_factor(0.5)
+        .gain(-6.0)
+        .build()
+        .unwrap();
+    let b = FilterConfiguration::builder()
+        .cutoff(6400.0)
+        .sample_rate(48000)
+        .q_factor(8.0)
+        .gain(12.0)
+        .bypass(true)
+        .design_method(DesignMethod::Matched)
+        .build()
+        .unwrap();
+    let middle = FilterConfiguration::lerp(&a, &b, 0.5);
+    assert_relative_eq!(middle.get_cutoff(), 800.0, epsilon = 1e-9);
+    assert_relative_eq!(middle.get_q_factor(), 2.0, epsilon = 1e-12);
+    assert_relative_eq!(middle.get_gain(), 3.0, epsilon = 1e-12);
+    assert!(middle.get_bypass());
+    assert_eq!(middle.get_design_method(), DesignMethod::Matched);
+
+    let early = FilterConfiguration::lerp(&a, &b, 1.0 / 6.0);
+    assert_relative_eq!(early.get_cutoff(), 200.0, epsilon = 1e-9);
+    assert!(!early.get_bypass());
+    assert_eq!(FilterConfiguration::lerp(&a, &b, -1.0).get_cutoff(), 100.0);
+    assert_relative_eq!(FilterConfiguration::lerp(&a, &b, 2.0).get_cutoff(), 6400.0, epsilon = 1e-9);
+}
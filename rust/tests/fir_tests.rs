@@ -0,0 +1,106 @@
+/// fir_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use approx::assert_relative_eq;
+use biquad_filters::{Cascade, Coefficients, DigitalBiquadFilter, Window};
+
+fn resonator() -> Coefficients<f64> {
+    Coefficients {
+        b0: 0.2,
+        b1: 0.4,
+        b2: 0.2,
+        a0: 1.0,
+        a1: -0.6,
+        a2: 0.2,
+    }
+}
+
+fn impulse_response(filter: &mut DigitalBiquadFilter<f64>, len: usize) -> Vec<f64> {
+    (0..len)
+        .map(|n| {
+            let mut sample = if n == 0 { 1.0 } else { 0.0 };
+            filter.process(&mut sample);
+            sample
+        })
+        .collect()
+}
+
+#[test]
+fn rectangular_taps_match_impulse_response() {
+    let filter = DigitalBiquadFilter::new(resonator()).unwrap();
+    let fir = filter.to_fir(32, Window::Rectangular).unwrap();
+    let expected = impulse_response(&mut filter.clone(), 32);
+    assert_eq!(fir.taps.len(), 32);
+    for (tap, expected) in fir.taps.iter().zip(expected.iter()) {
+        assert_relative_eq!(*tap, *expected, epsilon = 1e-15);
+    }
+    assert!(fir.error < 1e-6);
+}
+
+#[test]
+fn error_shrinks_with_length() {
+    let mut coefficients = resonator();
+    coefficients.a1 = -1.8;
+    coefficients.a2 = 0.9;
+    let filter = DigitalBiquadFilter::new(coefficients).unwrap();
+    let short = filter.to_fir(8, Window::Rectangular).unwrap();
+    let long = filter.to_fir(128, Window::Rectangular).unwrap();
+    assert!(short.error > 0.1);
+    assert!(long.error < short.error);
+    assert!(long.error < 1e-2);
+}
+
+#[test]
+fn window_tapers_the_tail() {
+    let filter = DigitalBiquadFilter::new(resonator()).unwrap();
+    let plain = filter.to_fir(16, Window::Rectangular).unwrap();
+    for window in [Window::Hann, Window::Hamming, Window::Blackman] {
+        let tapered = filter.to_fir(16, window).unwrap();
+        assert_relative_eq!(tapered.taps[0], plain.taps[0], epsilon = 1e-12);
+        assert!(tapered.taps[15].abs() < plain.taps[15].abs());
+        assert!(tapered.error >= plain.error);
+    }
+}
+
+#[test]
+fn approximation_leaves_filter_untouched() {
+    let mut filter = DigitalBiquadFilter::new(resonator()).unwrap();
+    let mut sample = 1.0;
+    filter.process(&mut sample);
+    assert!(filter.to_fir(0, Window::Hann).is_none());
+    assert!(filter.to_fir(4, Window::Hann).is_some());
+    assert_eq!(filter.samples_processed(), 1);
+}
+
+#[test]
+fn cascade_taps_match_chained_sections() {
+    let cascade = Cascade::<f64, 2>::new([resonator(), resonator()]).unwrap();
+    let fir = cascade.to_fir(24, Window::Rectangular).unwrap();
+    let mut first = DigitalBiquadFilter::new(resonator()).unwrap();
+    let mut second = DigitalBiquadFilter::new(resonator()).unwrap();
+    for (n, tap) in fir.taps.iter().enumerate() {
+        let mut sample = if n == 0 { 1.0 } else { 0.0 };
+        first.process(&mut sample);
+        second.process(&mut sample);
+        assert_relative_eq!(*tap, sample, epsilon = 1e-15);
+    }
+}
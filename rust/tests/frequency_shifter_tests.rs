@@ -0,0 +1,108 @@
+/// frequency_shifter_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{FrequencyShifter, HilbertTransformer};
+use approx::assert_relative_eq;
+
+const SAMPLE_RATE: u32 = 48000;
+
+fn sine(frequency: f64, length: usize) -> Vec<f64> {
+    (0..length)
+        .map(|n| (2.0 * std::f64::consts::PI * frequency * n as f64 / SAMPLE_RATE as f64).sin())
+        .collect()
+}
+
+/// Returns the amplitude of `frequency` in the second half of `samples`.
+fn amplitude(samples: &[f64], frequency: f64) -> f64 {
+    let start = samples.len() / 2;
+    let (mut re, mut im) = (0.0, 0.0);
+    for (n, sample) in samples.iter().enumerate().skip(start) {
+        let phase = 2.0 * std::f64::consts::PI * frequency * n as f64 / SAMPLE_RATE as f64;
+        re += sample * phase.cos();
+        im += sample * phase.sin();
+    }
+    2.0 * (re * re + im * im).sqrt() / (samples.len() - start) as f64
+}
+
+#[test]
+fn test_hilbert_outputs_are_in_quadrature() {
+    for frequency in [100.0, 1000.0, 10000.0] {
+        let mut hilbert = HilbertTransformer::<f64>::new().unwrap();
+        let input = sine(frequency, SAMPLE_RATE as usize);
+        let mut in_phase = vec![0.0_f64; input.len()];
+        let mut quadrature = vec![0.0_f64; input.len()];
+        assert!(hilbert.process_block(&input, &mut in_phase, &mut quadrature));
+        // The analytic signal has a constant envelope for a sine.
+        for (i, q) in in_phase.iter().zip(quadrature.iter()).skip(input.len() / 2) {
+            assert_relative_eq!((i * i + q * q).sqrt(), 1.0, epsilon = 0.03);
+        }
+    }
+}
+
+#[test]
+fn test_hilbert_envelope_follows_amplitude_modulation() {
+    let mut hilbert = HilbertTransformer::<f64>::new().unwrap();
+    let modulation = |n: usize| {
+        0.6 + 0.3 * (2.0 * std::f64::consts::PI * 5.0 * n as f64 / SAMPLE_RATE as f64).sin()
+    };
+    let carrier = sine(2000.0, SAMPLE_RATE as usize);
+    let input: Vec<f64> = carrier.iter().enumerate().map(|(n, c)| c * modulation(n)).collect();
+    let mut envelope = vec![0.0_f64; input.len()];
+    assert!(!hilbert.envelope_block(&input, &mut envelope[1..]));
+    assert!(hilbert.envelope_block(&input, &mut envelope));
+    for (n, value) in envelope.iter().enumerate().skip(input.len() / 2) {
+        assert_relative_eq!(*value, modulation(n), epsilon = 0.03);
+    }
+}
+
+#[test]
+fn test_shifts_up_and_down() {
+    let input = sine(3000.0, SAMPLE_RATE as usize / 2);
+    for (shift, expected, image) in [(1000.0, 4000.0, 2000.0), (-1000.0, 2000.0, 4000.0)] {
+        let mut shifter = FrequencyShifter::<f64>::new(shift, SAMPLE_RATE).unwrap();
+        let mut samples = input.clone();
+        assert!(shifter.process_block(&mut samples));
+        assert_relative_eq!(amplitude(&samples, expected), 1.0, epsilon = 0.02);
+        assert!(amplitude(&samples, image) < 0.02);
+        assert!(amplitude(&samples, 3000.0) < 0.02);
+    }
+}
+
+#[test]
+fn test_mix() {
+    let mut shifter = FrequencyShifter::<f64>::new(500.0_f64, SAMPLE_RATE).unwrap();
+    assert!(shifter.set_mix(0.5_f64));
+    let mut samples = sine(2000.0, SAMPLE_RATE as usize / 2);
+    shifter.process_block(&mut samples);
+    assert_relative_eq!(amplitude(&samples, 2000.0), 0.5, epsilon = 0.02);
+    assert_relative_eq!(amplitude(&samples, 2500.0), 0.5, epsilon = 0.02);
+}
+
+#[test]
+fn test_invalid_parameters() {
+    assert!(FrequencyShifter::<f64>::new(30000.0_f64, SAMPLE_RATE).is_none());
+    let mut shifter = FrequencyShifter::<f32>::new(-200.0_f32, SAMPLE_RATE).unwrap();
+    assert!(!shifter.set_mix(1.5_f32));
+    assert!(!shifter.set_shift(f32::NAN));
+    assert_relative_eq!(shifter.get_shift(), -200.0_f32);
+    assert_relative_eq!(shifter.get_mix(), 1.0_f32);
+}
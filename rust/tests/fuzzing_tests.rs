@@ -0,0 +1,86 @@
+#![cfg(feature = "arbitrary")]
+/// fuzzing_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use arbitrary::{Arbitrary, Unstructured};
+use biquad_filters::{
+    AllPassFilter, BandPassFilter, Coefficients, DigitalBiquadFilter, Filter, FilterConfiguration,
+    FilterType, HighPassFilter, HighShelfFilter, LowPassFilter, LowShelfFilter, NotchFilter,
+    PeakingEQFilter, TiltFilter,
+};
+
+/// Deterministic bytes standing in for fuzzer input.
+fn bytes(len: usize) -> Vec<u8> {
+    let mut state = 0x2545_F491_u32;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state >> 24) as u8
+        })
+        .collect()
+}
+
+fn build(filter_type: FilterType, configuration: &FilterConfiguration<f64>) -> Option<Box<dyn Filter<f64>>> {
+    let cutoff = configuration.get_cutoff();
+    let rate = configuration.get_sample_rate();
+    let q = configuration.get_q_factor();
+    let gain = configuration.get_gain();
+    Some(match filter_type {
+        FilterType::LowPass => Box::new(LowPassFilter::new(cutoff, rate, q)?),
+        FilterType::HighPass => Box::new(HighPassFilter::new(cutoff, rate, q)?),
+        FilterType::BandPass => Box::new(BandPassFilter::new(cutoff, rate, q, false)?),
+        FilterType::Notch => Box::new(NotchFilter::new(cutoff, rate, q)?),
+        FilterType::AllPass => Box::new(AllPassFilter::new(cutoff, rate, q)?),
+        FilterType::PeakingEQ => Box::new(PeakingEQFilter::new(cutoff, rate, q, gain)?),
+        FilterType::LowShelf => Box::new(LowShelfFilter::new(cutoff, rate, q, gain)?),
+        FilterType::HighShelf => Box::new(HighShelfFilter::new(cutoff, rate, q, gain)?),
+        FilterType::Tilt => Box::new(TiltFilter::new(cutoff, rate, gain)?),
+    })
+}
+
+#[test]
+fn arbitrary_configurations_are_accepted() {
+    let data = bytes(8192);
+    let mut u = Unstructured::new(&data);
+    for _ in 0..100 {
+        let filter_type = FilterType::arbitrary(&mut u).unwrap();
+        let configuration = FilterConfiguration::<f64>::arbitrary(&mut u).unwrap();
+        assert!(configuration.is_valid());
+        let mut filter = build(filter_type, &configuration).expect("configuration rejected");
+        let mut samples = [1.0, 0.0, -1.0, 0.5];
+        assert!(filter.process_block(&mut samples));
+        assert!(samples.iter().all(|sample| sample.is_finite()));
+    }
+}
+
+#[test]
+fn arbitrary_coefficients_are_stable() {
+    let data = bytes(4096);
+    let mut u = Unstructured::new(&data);
+    for _ in 0..100 {
+        let coefficients = Coefficients::<f32>::arbitrary(&mut u).unwrap();
+        assert!(coefficients.is_stable());
+        assert!(DigitalBiquadFilter::new(coefficients).is_some());
+    }
+}
@@ -0,0 +1,73 @@
+/// gammatone_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{GammatoneBank, equivalent_rectangular_bandwidth};
+use approx::assert_relative_eq;
+
+fn steady_state_gain(bank: &mut GammatoneBank<f64>, frequency: f64, sample_rate: u32) -> Vec<f64> {
+    bank.reset();
+    let mut outputs = vec![0.0_f64; bank.len()];
+    let mut peaks = vec![0.0_f64; bank.len()];
+    for n in 0..8192 {
+        let sample = (2.0 * std::f64::consts::PI * frequency * n as f64 / sample_rate as f64).sin();
+        bank.process(sample, &mut outputs);
+        if n >= 4096 {
+            for (peak, output) in peaks.iter_mut().zip(outputs.iter()) {
+                *peak = peak.max(output.abs());
+            }
+        }
+    }
+    peaks
+}
+
+#[test]
+fn erb_matches_glasberg_moore() {
+    assert_relative_eq!(equivalent_rectangular_bandwidth(1000.0_f64).unwrap(), 132.639, epsilon = 1e-3);
+}
+
+#[test]
+fn create_erb_spaced_bank() {
+    let bank = GammatoneBank::<f64>::new(32, 100.0, 8000.0, 22050).unwrap();
+    assert_eq!(bank.len(), 32);
+    let centers = bank.get_center_frequencies();
+    assert_relative_eq!(centers[0], 100.0, epsilon = 1e-9);
+    assert_relative_eq!(centers[31], 8000.0, epsilon = 1e-6);
+    assert!(centers.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn create_invalid_bank() {
+    assert!(GammatoneBank::<f64>::new(0, 100.0, 8000.0, 22050).is_none());
+    assert!(GammatoneBank::<f64>::new(8, 100.0, 12000.0, 22050).is_none());
+    assert!(GammatoneBank::<f64>::with_center_frequencies(&[-5.0], 22050).is_none());
+}
+
+#[test]
+fn unity_gain_at_center_frequency() {
+    let sample_rate = 16000_u32;
+    let mut bank = GammatoneBank::<f64>::with_center_frequencies(&[500.0, 2000.0], sample_rate).unwrap();
+    let peaks = steady_state_gain(&mut bank, 500.0, sample_rate);
+    assert_relative_eq!(peaks[0], 1.0, epsilon = 1e-2);
+    assert!(peaks[1] < 0.01);
+    let peaks = steady_state_gain(&mut bank, 2000.0, sample_rate);
+    assert_relative_eq!(peaks[1], 1.0, epsilon = 1e-2);
+}
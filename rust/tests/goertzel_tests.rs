@@ -0,0 +1,106 @@
+/// goertzel_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::GoertzelDetector;
+use approx::assert_relative_eq;
+use num_complex::Complex;
+use std::f64::consts::PI;
+
+const SAMPLE_RATE: u32 = 8000;
+const ROWS: [f64; 4] = [697.0, 770.0, 852.0, 941.0];
+const COLUMNS: [f64; 4] = [1209.0, 1336.0, 1477.0, 1633.0];
+
+fn tone(frequencies: &[(f64, f64, f64)], length: usize) -> Vec<f64> {
+    (0..length)
+        .map(|n| {
+            let t = n as f64 / SAMPLE_RATE as f64;
+            frequencies.iter().map(|(f, a, phase)| a * (2.0 * PI * f * t + phase).cos()).sum()
+        })
+        .collect()
+}
+
+#[test]
+fn measures_amplitude_and_phase_on_a_bin() {
+    // 1000 Hz falls exactly on a bin of a 400-sample block at 8 kHz.
+    let samples = tone(&[(1000.0, 0.5, 0.3), (2500.0, 1.0, 0.0)], 400);
+    let mut detector = GoertzelDetector::<f64>::new(1000.0, SAMPLE_RATE).unwrap();
+    assert!(detector.process_block(&samples));
+    assert_eq!(detector.samples_processed(), 400);
+    assert_relative_eq!(detector.magnitude(), 0.5, epsilon = 1e-9);
+    assert_relative_eq!(detector.phase(), 0.3, epsilon = 1e-9);
+    assert_relative_eq!(detector.magnitude_db(), 20.0 * 0.5_f64.log10(), epsilon = 1e-9);
+
+    // The response is the DFT term of the block.
+    let dft: Complex<f64> = samples
+        .iter()
+        .enumerate()
+        .map(|(n, x)| Complex::from_polar(*x, -2.0 * PI * 1000.0 * n as f64 / 8000.0))
+        .sum();
+    assert_relative_eq!(detector.get_response().re, dft.re, epsilon = 1e-8);
+    assert_relative_eq!(detector.get_response().im, dft.im, epsilon = 1e-8);
+}
+
+#[test]
+fn decodes_dtmf_digits() {
+    let keys = ["123A", "456B", "789C", "*0#D"];
+    let mut detectors: Vec<GoertzelDetector<f32>> = ROWS
+        .iter()
+        .chain(COLUMNS.iter())
+        .map(|frequency| GoertzelDetector::new(*frequency as f32, SAMPLE_RATE).unwrap())
+        .collect();
+    for (row, row_frequency) in ROWS.iter().enumerate() {
+        for (column, column_frequency) in COLUMNS.iter().enumerate() {
+            let pair = [(*row_frequency, 0.4, 0.0), (*column_frequency, 0.4, 1.0)];
+            let samples: Vec<f32> = tone(&pair, 205)
+                .iter()
+                .map(|sample| *sample as f32)
+                .collect();
+            let levels: Vec<f32> = detectors
+                .iter_mut()
+                .map(|detector| {
+                    detector.reset();
+                    detector.process_block(&samples);
+                    detector.magnitude()
+                })
+                .collect();
+            let loudest = |range: std::ops::Range<usize>| {
+                range.max_by(|a, b| levels[*a].total_cmp(&levels[*b])).unwrap()
+            };
+            let digit = keys[loudest(0..4)].as_bytes()[loudest(4..8) - 4];
+            assert_eq!(digit, keys[row].as_bytes()[column]);
+        }
+    }
+}
+
+#[test]
+fn rejects_invalid_frequencies() {
+    assert!(GoertzelDetector::<f64>::new(4000.0, SAMPLE_RATE).is_none());
+    assert!(GoertzelDetector::<f64>::new(-1.0, SAMPLE_RATE).is_none());
+    assert!(GoertzelDetector::<f64>::new(100.0, 0).is_none());
+    let mut detector = GoertzelDetector::<f64>::new(0.0, SAMPLE_RATE).unwrap();
+    assert_eq!(detector.magnitude(), 0.0);
+    assert!(!detector.process_block(&[]));
+    detector.process_block(&[0.25; 64]);
+    assert_relative_eq!(detector.magnitude(), 0.25, epsilon = 1e-12);
+    detector.reset();
+    assert_eq!(detector.samples_processed(), 0);
+}
@@ -0,0 +1,102 @@
+/// graphic_eq_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{FrequencyResponse, GraphicEq, GraphicEqLayout};
+use approx::assert_relative_eq;
+
+const SAMPLE_RATE: u32 = 48000;
+
+fn gain_db(eq: &GraphicEq<f64>, frequency: f64) -> f64 {
+    20.0 * eq.magnitude_at(frequency, SAMPLE_RATE).log10()
+}
+
+#[test]
+fn layouts_use_the_iso_center_frequencies() {
+    assert_eq!(GraphicEqLayout::Octave.center_frequencies().len(), 10);
+    assert_eq!(GraphicEqLayout::TwoThirdsOctave.center_frequencies().len(), 15);
+    assert_eq!(GraphicEqLayout::ThirdOctave.center_frequencies().len(), 31);
+    assert_eq!(GraphicEqLayout::Octave.center_frequencies()[0], 31.5);
+    assert_eq!(GraphicEqLayout::ThirdOctave.center_frequencies()[30], 20000.0);
+    let octave_q = GraphicEqLayout::Octave.q_factor();
+    assert_relative_eq!(octave_q, core::f64::consts::SQRT_2, epsilon = 1e-12);
+    assert_relative_eq!(GraphicEqLayout::ThirdOctave.q_factor(), 4.318, epsilon = 1e-3);
+}
+
+#[test]
+fn new_equalizer_is_flat() {
+    let eq = GraphicEq::<f64>::new(GraphicEqLayout::ThirdOctave, SAMPLE_RATE).unwrap();
+    assert_eq!(eq.len(), 31);
+    assert!(eq.get_gains().iter().all(|gain| *gain == 0.0));
+    for frequency in [20.0, 440.0, 1000.0, 15000.0] {
+        assert_relative_eq!(gain_db(&eq, frequency), 0.0, epsilon = 1e-9);
+    }
+}
+
+#[test]
+fn rejects_bands_above_nyquist() {
+    assert!(GraphicEq::<f64>::new(GraphicEqLayout::ThirdOctave, 32000).is_none());
+    assert!(GraphicEq::<f64>::new(GraphicEqLayout::Octave, 32000).is_none());
+    assert!(GraphicEq::<f64>::new(GraphicEqLayout::Octave, 44100).is_some());
+}
+
+#[test]
+fn band_gain_sets_the_response_at_its_center() {
+    let mut eq = GraphicEq::<f64>::new(GraphicEqLayout::Octave, SAMPLE_RATE).unwrap();
+    assert!(eq.set_gain(5, 6.0));
+    assert_eq!(eq.get_center_frequency(5), Some(1000.0));
+    assert_eq!(eq.get_gain(5), Some(6.0));
+    assert_relative_eq!(gain_db(&eq, 1000.0), 6.0, epsilon = 1e-9);
+    assert!(gain_db(&eq, 62.5).abs() < 0.1);
+    assert!(!eq.set_gain(10, 3.0));
+    assert!(!eq.set_gain(0, f64::NAN));
+    assert!(!eq.set_gain(0, 100.0));
+    assert_eq!(eq.get_gain(0), Some(0.0));
+}
+
+#[test]
+fn set_gains_is_all_or_nothing() {
+    let mut eq = GraphicEq::<f64>::new(GraphicEqLayout::Octave, SAMPLE_RATE).unwrap();
+    let gains = [1.0, 2.0, 3.0, 4.0, 5.0, -5.0, -4.0, -3.0, -2.0, -1.0];
+    assert!(eq.set_gains(&gains));
+    assert_eq!(eq.get_gains(), gains.to_vec());
+    assert!(!eq.set_gains(&[0.0; 9]));
+    let mut invalid = [0.0; 10];
+    invalid[9] = f64::INFINITY;
+    assert!(!eq.set_gains(&invalid));
+    assert_eq!(eq.get_gains(), gains.to_vec());
+    eq.flatten();
+    assert!(eq.get_gains().iter().all(|gain| *gain == 0.0));
+}
+
+#[test]
+fn processing_matches_the_response() {
+    let mut eq = GraphicEq::<f64>::new(GraphicEqLayout::TwoThirdsOctave, SAMPLE_RATE).unwrap();
+    assert!(eq.set_gain(8, -12.0));
+    let frequency = 1000.0;
+    let mut samples: Vec<f64> = (0..48000)
+        .map(|n| (2.0 * core::f64::consts::PI * frequency * n as f64 / SAMPLE_RATE as f64).sin())
+        .collect();
+    assert!(eq.process_block(&mut samples));
+    let peak = samples[24000..].iter().fold(0.0_f64, |peak, sample| peak.max(sample.abs()));
+    assert_relative_eq!(peak, eq.magnitude_at(frequency, SAMPLE_RATE), epsilon = 1e-3);
+    assert!(!eq.process_block(&mut []));
+}
@@ -1,5 +1,4 @@
 /// high_pass_tests.rs
-
 /**
 Copyright © 2025 Alex Parisi
 
@@ -100,3 +99,27 @@ fn set_quality_factor() {
     filter.set_q_factor(1.0_f64);
     assert_relative_eq!(filter.get_q_factor(), 1.0_f64);
 }
+
+#[test]
+fn resonance_round_trips() {
+    let mut filter = HighPassFilter::<f64>::new(1000.0, 44100, 0.3).unwrap();
+    assert_relative_eq!(filter.get_resonance(), 0.0);
+    assert!(filter.set_resonance(0.75));
+    assert_relative_eq!(filter.get_q_factor(), 2.0);
+    assert_relative_eq!(biquad_filters::q_to_resonance(2.0_f64).unwrap(), 0.75);
+    assert!(biquad_filters::resonance_to_q(f64::NAN).is_none());
+}
+
+#[test]
+fn bypass_and_gain_accessors_behave_like_other_filters() {
+    let mut filter = HighPassFilter::<f64>::new(1000.0, 44100, 0.707).unwrap();
+    assert_relative_eq!(filter.get_gain(), 0.0);
+    assert!(filter.set_gain(3.0));
+    assert_relative_eq!(filter.get_gain(), 3.0);
+
+    assert!(filter.set_bypass(true));
+    let mut sample = 0.5_f64;
+    assert!(filter.process(&mut sample));
+    assert_relative_eq!(sample, 0.5);
+    assert_eq!(filter.samples_processed(), 1);
+}
@@ -1,5 +1,4 @@
 /// high_shelf_tests.rs
-
 /**
 Copyright © 2025 Alex Parisi
 
@@ -21,7 +20,9 @@ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
 OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
-use biquad_filters::{Filter, HighShelfFilter};
+use biquad_filters::{
+    Filter, FrequencyResponse, HighShelfFilter, LowShelfFilter, q_to_shelf_slope, shelf_slope_to_q,
+};
 use approx::assert_relative_eq;
 
 #[test]
@@ -106,3 +107,50 @@ fn set_quality_factor() {
     filter.set_q_factor(1.0_f64);
     assert_relative_eq!(filter.get_q_factor(), 1.0_f64);
 }
+
+#[test]
+fn shelf_slope_converts_to_q() {
+    // A slope of one is a Q of 1/sqrt(2) at any gain.
+    for gain in [-12.0_f64, 3.0, 15.0] {
+        assert_relative_eq!(shelf_slope_to_q(1.0, gain).unwrap(), std::f64::consts::FRAC_1_SQRT_2);
+        for slope in [0.3, 0.8, 1.5] {
+            let q = shelf_slope_to_q(slope, gain).unwrap();
+            assert_relative_eq!(q_to_shelf_slope(q, gain).unwrap(), slope, epsilon = 1e-12);
+        }
+    }
+    assert!(shelf_slope_to_q(6.0_f64, 12.0).is_none());
+    assert!(shelf_slope_to_q(0.0_f64, 12.0).is_none());
+    assert!(q_to_shelf_slope(-1.0_f64, 12.0).is_none());
+}
+
+#[test]
+fn shelf_slope_sets_the_steepness() {
+    let mut filter = HighShelfFilter::<f64>::new(2000.0, 48000, 3.0, 12.0).unwrap();
+    assert!(filter.get_shelf_slope().is_none());
+    assert!(filter.set_shelf_slope(Some(1.0)));
+    assert_eq!(filter.get_shelf_slope(), Some(1.0));
+    let reference =
+        HighShelfFilter::<f64>::new(2000.0, 48000, std::f64::consts::FRAC_1_SQRT_2, 12.0).unwrap();
+    // With a slope of one the response rises monotonically to the shelf gain.
+    let mut previous = 0.0;
+    let mut frequency = 20.0;
+    while frequency < 20000.0 {
+        let gain = 20.0 * filter.magnitude_at(frequency, 48000).log10();
+        let expected = 20.0 * reference.magnitude_at(frequency, 48000).log10();
+        assert_relative_eq!(gain, expected, epsilon = 1e-9);
+        assert!(gain >= previous - 1e-9);
+        previous = gain;
+        frequency *= 1.1;
+    }
+    // The slope is converted again when the gain changes, and rejected if it becomes too steep.
+    assert!(filter.set_shelf_slope(Some(4.0)));
+    assert!(!filter.set_gain(15.0));
+    assert_relative_eq!(filter.get_gain(), 12.0);
+    assert!(!filter.set_shelf_slope(Some(f64::NAN)));
+    assert!(filter.set_shelf_slope(None));
+
+    let mut low_shelf = LowShelfFilter::<f32>::new(200.0, 48000, 0.5, -6.0).unwrap();
+    let snapshot = low_shelf.snapshot();
+    assert!(low_shelf.set_shelf_slope(Some(0.5)));
+    assert!(low_shelf.diff(&snapshot).shelf_slope);
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "state-history")]
+/// history_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{
+    Coefficients, DigitalBiquadFilter, Filter, LowPassFilter, StateVariableFilter, SvfMode,
+};
+
+fn accumulator() -> DigitalBiquadFilter<f64> {
+    DigitalBiquadFilter::new(Coefficients {
+        b0: 1.0,
+        b1: 0.0,
+        b2: 0.0,
+        a0: 1.0,
+        a1: -1.0,
+        a2: 0.0,
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_history_keeps_most_recent_samples() {
+    let mut filter = accumulator();
+    filter.enable_history(4);
+    let mut samples = [1.0_f64; 10];
+    filter.process_block(&mut samples);
+    let history = filter.get_history();
+    assert_eq!(history.len(), 4);
+    let indices: Vec<u64> = history.iter().map(|entry| entry.sample_index).collect();
+    assert_eq!(indices, vec![6, 7, 8, 9]);
+    for entry in history.iter() {
+        assert_eq!(entry.input, 1.0);
+        assert_eq!(entry.output, entry.state.y1 + 1.0);
+    }
+    assert_eq!(history[3].output, 10.0);
+}
+
+#[test]
+fn test_history_locates_first_nan() {
+    let mut filter = LowPassFilter::<f64>::new(1000.0_f64, 48000_u32, 0.707_f64).unwrap();
+    filter.enable_history(16);
+    let mut samples = [0.5_f64; 8];
+    samples[5] = f64::NAN;
+    filter.process_block(&mut samples);
+    let first = filter
+        .get_history()
+        .into_iter()
+        .find(|entry| entry.output.is_nan())
+        .unwrap();
+    assert_eq!(first.sample_index, 5);
+    assert!(first.input.is_nan());
+    assert!(first.state.y1.is_finite());
+}
+
+#[test]
+fn test_history_is_cleared_by_reset() {
+    let mut filter = accumulator();
+    assert!(filter.get_history().is_empty());
+    filter.enable_history(4);
+    let mut samples = [1.0_f64; 2];
+    filter.process_block(&mut samples);
+    assert_eq!(filter.get_history().len(), 2);
+    filter.reset();
+    assert!(filter.get_history().is_empty());
+    filter.disable_history();
+    filter.process_block(&mut samples);
+    assert!(filter.get_history().is_empty());
+}
+
+#[test]
+fn test_filters_without_history_use_the_default_methods() {
+    let mut filter = StateVariableFilter::<f64>::new(1000.0, 48000, 0.707, SvfMode::LowPass)
+        .unwrap();
+    filter.enable_history(8);
+    let mut samples = [1.0, 0.5, 0.25];
+    filter.process_block(&mut samples);
+    assert!(filter.get_history().is_empty());
+    filter.disable_history();
+}
@@ -0,0 +1,139 @@
+/// iir_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use approx::assert_relative_eq;
+use biquad_filters::{Coefficients, DigitalBiquadFilter, Filter, IirFilter};
+
+fn section(a1: f64, a2: f64) -> Coefficients<f64> {
+    Coefficients {
+        b0: 0.2,
+        b1: 0.4,
+        b2: 0.2,
+        a0: 1.0,
+        a1,
+        a2,
+    }
+}
+
+fn multiply(left: &[f64], right: &[f64]) -> Vec<f64> {
+    let mut product = vec![0.0; left.len() + right.len() - 1];
+    for (i, l) in left.iter().enumerate() {
+        for (j, r) in right.iter().enumerate() {
+            product[i + j] += l * r;
+        }
+    }
+    product
+}
+
+fn input() -> Vec<f64> {
+    (0..256).map(|n| ((n * 7) % 11) as f64 - 5.0).collect()
+}
+
+#[test]
+fn second_order_matches_biquad() {
+    let coefficients = section(-0.6, 0.2);
+    let mut biquad = DigitalBiquadFilter::new(coefficients).unwrap();
+    let mut iir = IirFilter::new(&[0.4, 0.8, 0.4], &[2.0, -1.2, 0.4], 48000).unwrap();
+    assert_eq!(iir.get_order(), 2);
+    assert_relative_eq!(iir.get_denominator()[0], 1.0);
+    let mut expected = input();
+    let mut samples = expected.clone();
+    biquad.process_block(&mut expected);
+    assert!(iir.process_block(&mut samples));
+    for (sample, expected) in samples.iter().zip(expected.iter()) {
+        assert_relative_eq!(*sample, *expected, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn fourth_order_matches_chained_sections() {
+    let first = section(-0.6, 0.2);
+    let second = section(-1.1, 0.5);
+    let numerator = multiply(&[first.b0, first.b1, first.b2], &[second.b0, second.b1, second.b2]);
+    let denominator = multiply(&[1.0, first.a1, first.a2], &[1.0, second.a1, second.a2]);
+    let mut iir = IirFilter::new(&numerator, &denominator, 48000).unwrap();
+    assert_eq!(iir.get_order(), 4);
+    assert!(iir.is_stable());
+    let mut first = DigitalBiquadFilter::new(first).unwrap();
+    let mut second = DigitalBiquadFilter::new(second).unwrap();
+    for x in input() {
+        let mut expected = x;
+        first.process(&mut expected);
+        second.process(&mut expected);
+        let mut sample = x;
+        assert!(Filter::process(&mut iir, &mut sample));
+        assert_relative_eq!(sample, expected, epsilon = 1e-9);
+    }
+    assert_eq!(iir.samples_processed(), 256);
+}
+
+#[test]
+fn mismatched_orders_are_padded() {
+    // A third-order all-pole filter with a pure gain numerator.
+    let mut iir = IirFilter::new(&[0.5], &[1.0, -0.5, 0.1, 0.05], 44100).unwrap();
+    assert_eq!(iir.get_order(), 3);
+    assert_eq!(iir.get_numerator(), &[0.5, 0.0, 0.0, 0.0]);
+    let mut impulse = vec![0.0; 4];
+    impulse[0] = 1.0;
+    iir.process_block(&mut impulse);
+    assert_relative_eq!(impulse[0], 0.5);
+    assert_relative_eq!(impulse[1], 0.25);
+    assert_relative_eq!(impulse[2], 0.075);
+}
+
+#[test]
+fn invalid_transfer_functions_are_rejected() {
+    assert!(IirFilter::<f64>::new(&[], &[1.0], 48000).is_none());
+    assert!(IirFilter::new(&[1.0], &[0.0, 1.0], 48000).is_none());
+    assert!(IirFilter::new(&[f64::NAN], &[1.0], 48000).is_none());
+    assert!(IirFilter::new(&[1.0], &[1.0], 0).is_none());
+    let mut iir = IirFilter::new(&[1.0], &[1.0, -0.5], 48000).unwrap();
+    assert!(!iir.set_coefficients(&[1.0], &[]));
+    assert_eq!(iir.get_order(), 1);
+}
+
+#[test]
+fn unstable_denominator_is_reported() {
+    let iir = IirFilter::new(&[1.0], &[1.0, -2.5, 2.0, -0.5], 48000).unwrap();
+    assert!(!iir.is_stable());
+    assert!(iir.get_faults().unstable_coefficients);
+    let stable = IirFilter::new(&[1.0], &[1.0, -0.9], 48000).unwrap();
+    assert!(stable.is_stable());
+    assert!(stable.get_faults().is_empty());
+}
+
+#[test]
+fn design_parameters_cannot_be_changed() {
+    let mut iir = IirFilter::new(&[1.0, 1.0], &[1.0, -0.5], 48000).unwrap();
+    assert!(!iir.set_cutoff(1000.0));
+    assert!(!iir.set_q_factor(0.7));
+    assert!(!iir.set_gain(6.0));
+    assert!(iir.set_sample_rate(96000));
+    assert_eq!(iir.get_sample_rate(), 96000);
+    let snapshot = iir.snapshot();
+    assert!(iir.set_bypass(true));
+    let mut sample = 0.25;
+    assert!(Filter::process(&mut iir, &mut sample));
+    assert_relative_eq!(sample, 0.25);
+    assert!(iir.restore(&snapshot));
+    assert!(!iir.get_bypass());
+}
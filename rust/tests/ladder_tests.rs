@@ -0,0 +1,117 @@
+/// ladder_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{LadderFilter, Saturation};
+use approx::assert_relative_eq;
+
+const SAMPLE_RATE: u32 = 48000;
+
+fn sine_amplitude(filter: &mut LadderFilter<f64>, frequency: f64) -> f64 {
+    filter.reset();
+    let length = SAMPLE_RATE as usize;
+    let mut peak = 0.0_f64;
+    for n in 0..length {
+        let mut sample = (2.0 * std::f64::consts::PI * frequency * n as f64 / SAMPLE_RATE as f64).sin();
+        filter.process(&mut sample);
+        if n > length / 2 {
+            peak = peak.max(sample.abs());
+        }
+    }
+    peak
+}
+
+#[test]
+fn test_unity_passband_gain() {
+    for resonance in [0.0_f64, 0.5, 0.9] {
+        let mut filter = LadderFilter::<f64>::new(1000.0_f64, resonance, SAMPLE_RATE).unwrap();
+        let mut samples = vec![1.0_f64; 4800];
+        assert!(filter.process_block(&mut samples));
+        assert_relative_eq!(*samples.last().unwrap(), 1.0, epsilon = 1e-6);
+    }
+}
+
+#[test]
+fn test_four_pole_slope() {
+    let mut filter = LadderFilter::<f64>::new(500.0_f64, 0.0_f64, SAMPLE_RATE).unwrap();
+    // Without resonance every pole sits at the cutoff, so each is -3 dB there.
+    assert_relative_eq!(sine_amplitude(&mut filter, 500.0), 0.25, epsilon = 1e-3);
+    // Well above the cutoff the response falls by close to 24 dB per octave.
+    let lower = sine_amplitude(&mut filter, 4000.0);
+    let upper = sine_amplitude(&mut filter, 8000.0);
+    assert!(lower / upper > 10.0);
+}
+
+#[test]
+fn test_resonance_boosts_cutoff() {
+    let mut flat = LadderFilter::<f64>::new(1000.0_f64, 0.0_f64, SAMPLE_RATE).unwrap();
+    let mut resonant = LadderFilter::<f64>::new(1000.0_f64, 0.9_f64, SAMPLE_RATE).unwrap();
+    let flat_gain = sine_amplitude(&mut flat, 1000.0);
+    let resonant_gain = sine_amplitude(&mut resonant, 1000.0);
+    assert!(resonant_gain > 4.0 * flat_gain);
+}
+
+#[test]
+fn test_saturation_limits_level() {
+    let mut filter = LadderFilter::<f64>::new(1000.0_f64, 0.9_f64, SAMPLE_RATE).unwrap();
+    let linear = sine_amplitude(&mut filter, 1000.0);
+    assert!(filter.set_saturation(Saturation::Tanh { drive: 2.0 }));
+    let saturated = sine_amplitude(&mut filter, 1000.0);
+    assert!(saturated < linear);
+}
+
+#[test]
+fn test_invalid_parameters() {
+    assert!(LadderFilter::<f64>::new(0.0_f64, 0.5_f64, SAMPLE_RATE).is_none());
+    assert!(LadderFilter::<f64>::new(30000.0_f64, 0.5_f64, SAMPLE_RATE).is_none());
+    assert!(LadderFilter::<f64>::new(1000.0_f64, 2.0_f64, SAMPLE_RATE).is_none());
+    let mut filter = LadderFilter::<f32>::new(1000.0_f32, 0.5_f32, SAMPLE_RATE).unwrap();
+    assert!(!filter.set_resonance(-0.1_f32));
+    assert!(!filter.set_cutoff(f32::NAN));
+    assert_relative_eq!(filter.get_resonance(), 0.5_f32);
+    assert_relative_eq!(filter.get_cutoff(), 1000.0_f32);
+}
+
+#[test]
+fn test_self_oscillation_is_sustained_and_bounded() {
+    let mut filter = LadderFilter::<f64>::new(1000.0_f64, 1.1_f64, SAMPLE_RATE).unwrap();
+    assert!(filter.is_self_oscillating());
+    let mut samples = vec![0.0_f64; SAMPLE_RATE as usize];
+    samples[0] = 1e-3;
+    filter.process_block(&mut samples);
+    let tail = &samples[samples.len() - 4800..];
+    let peak = tail.iter().fold(0.0_f64, |peak, s| peak.max(s.abs()));
+    assert!(peak > 0.1);
+    assert!(peak <= 1.0);
+    // One hundred milliseconds at 1 kHz holds one hundred rising zero crossings.
+    let crossings = tail.windows(2).filter(|pair| pair[0] < 0.0 && pair[1] >= 0.0).count();
+    assert!((95..=105).contains(&crossings));
+}
+
+#[test]
+fn test_below_threshold_decays() {
+    let mut filter = LadderFilter::<f64>::new(1000.0_f64, 0.95_f64, SAMPLE_RATE).unwrap();
+    assert!(!filter.is_self_oscillating());
+    let mut samples = vec![0.0_f64; SAMPLE_RATE as usize];
+    samples[0] = 1.0;
+    filter.process_block(&mut samples);
+    assert!(samples[samples.len() - 1].abs() < 1e-6);
+}
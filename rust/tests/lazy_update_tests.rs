@@ -0,0 +1,70 @@
+/// lazy_update_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{Filter, FrequencyResponse, LowPassFilter, PeakingEQFilter};
+use approx::assert_relative_eq;
+
+fn impulse_response<F: Filter<f64>>(filter: &mut F) -> Vec<f64> {
+    let mut samples = vec![0.0_f64; 32];
+    samples[0] = 1.0;
+    filter.process_block(&mut samples);
+    samples
+}
+
+#[test]
+fn lazy_changes_apply_on_next_block() {
+    let mut lazy = PeakingEQFilter::<f64>::new(1000.0, 48000, 0.707, 0.0).unwrap();
+    assert!(!lazy.get_lazy_updates());
+    assert!(lazy.set_lazy_updates(true));
+    assert!(lazy.get_lazy_updates());
+    assert!(lazy.set_cutoff(2000.0));
+    assert!(lazy.set_q_factor(2.0));
+    assert!(lazy.set_gain(6.0));
+    assert_relative_eq!(lazy.get_gain(), 6.0);
+
+    let mut eager = PeakingEQFilter::<f64>::new(2000.0, 48000, 2.0, 6.0).unwrap();
+    let expected = impulse_response(&mut eager);
+    let actual = impulse_response(&mut lazy);
+    for (actual, expected) in actual.iter().zip(expected.iter()) {
+        assert_relative_eq!(*actual, *expected, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn lazy_updates_still_validate() {
+    let mut filter = LowPassFilter::<f64>::new(1000.0, 48000, 0.707).unwrap();
+    assert!(filter.set_lazy_updates(true));
+    assert!(!filter.set_cutoff(-5.0));
+    assert!(!filter.set_q_factor(f64::NAN));
+    assert_relative_eq!(filter.get_cutoff(), 1000.0);
+}
+
+#[test]
+fn disabling_lazy_updates_recomputes() {
+    let mut filter = LowPassFilter::<f64>::new(1000.0, 48000, 0.707).unwrap();
+    let before = filter.magnitude_at(4000.0, 48000);
+    assert!(filter.set_lazy_updates(true));
+    assert!(filter.set_cutoff(4000.0));
+    assert_relative_eq!(filter.magnitude_at(4000.0, 48000), before);
+    assert!(filter.set_lazy_updates(false));
+    assert_relative_eq!(filter.magnitude_at(4000.0, 48000), std::f64::consts::FRAC_1_SQRT_2, epsilon = 1e-3);
+}
@@ -0,0 +1,88 @@
+/// limiter_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{Cascade, Coefficients, Filter, LowPassFilter, OutputLimit};
+use approx::assert_relative_eq;
+
+#[test]
+fn test_clip_and_soft_limits() {
+    let clip = OutputLimit::Clip { limit: 1.0_f64 };
+    assert_relative_eq!(clip.apply(0.5), 0.5);
+    assert_relative_eq!(clip.apply(-3.0), -1.0);
+    assert_relative_eq!(clip.apply(f64::INFINITY), 1.0);
+    assert_eq!(clip.apply(f64::NAN), 0.0);
+
+    let soft = OutputLimit::Soft { limit: 1.0_f64 };
+    assert_relative_eq!(soft.apply(0.4), 0.4);
+    assert_relative_eq!(soft.apply(-0.5), -0.5);
+    assert!(soft.apply(0.9) < 0.9);
+    assert!(soft.apply(100.0) <= 1.0);
+    assert!(soft.apply(-100.0) >= -1.0);
+    assert!(soft.apply(2.0) > soft.apply(1.0));
+}
+
+#[test]
+fn test_filter_output_is_limited() {
+    let mut limited = LowPassFilter::<f64>::new(1000.0_f64, 48000_u32, 10.0_f64).unwrap();
+    let mut reference = limited.clone();
+    assert!(limited.set_output_limit(OutputLimit::Clip { limit: 1.0_f64 }));
+    let mut exceeded = false;
+    for n in 0..4800 {
+        let input = (2.0 * std::f64::consts::PI * 1000.0 * n as f64 / 48000.0).sin();
+        let mut sample = input;
+        let mut expected = input;
+        limited.process(&mut sample);
+        reference.process(&mut expected);
+        assert!(sample.abs() <= 1.0);
+        // The limit does not touch the fed-back state, so the filter keeps tracking the
+        // unlimited response.
+        assert_relative_eq!(sample, expected.clamp(-1.0, 1.0), epsilon = 1e-9);
+        exceeded |= expected.abs() > 1.0;
+    }
+    assert!(exceeded);
+}
+
+#[test]
+fn test_cascade_output_is_limited() {
+    let gain = Coefficients {
+        b0: 4.0,
+        b1: 0.0,
+        b2: 0.0,
+        a0: 1.0,
+        a1: 0.0,
+        a2: 0.0,
+    };
+    let mut cascade = Cascade::<f64, 2>::new([gain, gain]).unwrap();
+    assert!(cascade.set_output_limit(OutputLimit::Soft { limit: 2.0_f64 }));
+    let mut samples = [0.01_f64, 1.0];
+    cascade.process_block(&mut samples);
+    assert_relative_eq!(samples[0], 0.16);
+    assert!(samples[1] < 2.0);
+}
+
+#[test]
+fn test_invalid_limits_are_rejected() {
+    let mut filter = LowPassFilter::<f64>::new(1000.0_f64, 48000_u32, 0.707_f64).unwrap();
+    assert!(!filter.set_output_limit(OutputLimit::Clip { limit: 0.0_f64 }));
+    assert!(!filter.set_output_limit(OutputLimit::Soft { limit: f64::INFINITY }));
+    assert_eq!(filter.get_output_limit(), OutputLimit::Unlimited);
+}
@@ -0,0 +1,87 @@
+/// linkwitz_riley_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{
+    Crossover, CrossoverMode, FrequencyResponse, LinkwitzRileyFilter,
+};
+use approx::assert_relative_eq;
+
+const SAMPLE_RATE: u32 = 48000;
+
+#[test]
+fn create_valid_and_invalid_filters() {
+    for (order, sections) in [(2, 1), (4, 2), (6, 3), (8, 4)] {
+        let filter = LinkwitzRileyFilter::low_pass(1000.0_f64, SAMPLE_RATE, order).unwrap();
+        assert_eq!(filter.get_sections().len(), sections);
+        assert_eq!(filter.get_order(), order);
+        assert!(!filter.is_high_pass());
+    }
+    assert!(LinkwitzRileyFilter::high_pass(1000.0_f32, SAMPLE_RATE, 4).unwrap().is_high_pass());
+    assert!(LinkwitzRileyFilter::low_pass(1000.0_f64, SAMPLE_RATE, 0).is_none());
+    assert!(LinkwitzRileyFilter::low_pass(1000.0_f64, SAMPLE_RATE, 3).is_none());
+    assert!(LinkwitzRileyFilter::low_pass(24000.0_f64, SAMPLE_RATE, 4).is_none());
+    assert!(LinkwitzRileyFilter::high_pass(0.0_f64, SAMPLE_RATE, 4).is_none());
+}
+
+#[test]
+fn bands_are_half_amplitude_and_sum_flat() {
+    for order in [2, 4, 6, 8] {
+        let low = LinkwitzRileyFilter::low_pass(1000.0_f64, SAMPLE_RATE, order).unwrap();
+        let high = LinkwitzRileyFilter::high_pass(1000.0_f64, SAMPLE_RATE, order).unwrap();
+        assert_relative_eq!(low.magnitude_at(1000.0, SAMPLE_RATE), 0.5, epsilon = 1e-9);
+        assert_relative_eq!(high.magnitude_at(1000.0, SAMPLE_RATE), 0.5, epsilon = 1e-9);
+        for frequency in [20.0, 300.0, 1000.0, 2500.0, 15000.0] {
+            let sum = low.response_at(frequency, SAMPLE_RATE) + high.response_at(frequency, SAMPLE_RATE);
+            assert_relative_eq!(sum.norm(), 1.0, epsilon = 1e-9);
+        }
+    }
+}
+
+#[test]
+fn lr4_matches_the_crossover() {
+    let mut crossover = Crossover::new(CrossoverMode::LinkwitzRiley4, 800.0_f64, SAMPLE_RATE).unwrap();
+    let mut low = LinkwitzRileyFilter::low_pass(800.0, SAMPLE_RATE, 4).unwrap();
+    let mut high = LinkwitzRileyFilter::high_pass(800.0, SAMPLE_RATE, 4).unwrap();
+    for index in 0..128 {
+        let input = (index as f64 * 0.21).sin();
+        let (expected_low, expected_high) = crossover.process(input);
+        let (mut actual_low, mut actual_high) = (input, input);
+        assert!(low.process(&mut actual_low));
+        assert!(high.process(&mut actual_high));
+        assert_relative_eq!(actual_low, expected_low, epsilon = 1e-12);
+        assert_relative_eq!(actual_high, expected_high, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn frequency_can_move_while_running() {
+    let mut filter = LinkwitzRileyFilter::low_pass(1000.0_f64, SAMPLE_RATE, 8).unwrap();
+    let mut block = [1.0; 64];
+    assert!(filter.process_block(&mut block));
+    assert!(filter.set_frequency(2000.0));
+    assert_eq!(filter.get_frequency(), 2000.0);
+    assert_relative_eq!(filter.magnitude_at(2000.0, SAMPLE_RATE), 0.5, epsilon = 1e-9);
+    assert!(!filter.set_frequency(30000.0));
+    assert_eq!(filter.get_frequency(), 2000.0);
+    filter.reset();
+    assert!(filter.get_sections().iter().all(|section| section.samples_processed() == 0));
+}
@@ -1,5 +1,4 @@
 /// low_pass_tests.rs
-
 /**
 Copyright © 2025 Alex Parisi
 
@@ -21,8 +20,9 @@ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
 OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
-use biquad_filters::{Filter, LowPassFilter};
+use biquad_filters::{Cascade, DigitalBiquadFilter, Filter, FrequencyResponse, LowPassFilter};
 use approx::assert_relative_eq;
+use num_traits::Float;
 
 #[test]
 fn create_valid_double_filter() {
@@ -99,3 +99,117 @@ fn set_quality_factor() {
     filter.set_q_factor(1.0_f64);
     assert_relative_eq!(filter.get_q_factor(), 1.0_f64);
 }
+
+#[test]
+fn create_filter_with_non_finite_parameters() {
+    assert!(LowPassFilter::<f64>::new(f64::NAN, 44100_u32, 0.707_f64).is_none());
+    assert!(LowPassFilter::<f64>::new(1000.0_f64, 44100_u32, f64::INFINITY).is_none());
+}
+
+#[test]
+fn set_non_finite_cutoff_frequency() {
+    let mut filter = LowPassFilter::<f64>::new(
+        1000.0_f64,
+        44100_u32,
+        std::f64::consts::FRAC_1_SQRT_2
+    ).unwrap();
+    assert!(!filter.set_cutoff(f64::NAN));
+    let mut samples = [1.0_f64; 16];
+    assert!(filter.process_block(&mut samples));
+    assert!(samples.iter().all(|sample| sample.is_finite()));
+}
+
+#[test]
+fn failed_setter_keeps_previous_configuration() {
+    let mut filter = LowPassFilter::<f64>::new(
+        1000.0_f64,
+        44100_u32,
+        std::f64::consts::FRAC_1_SQRT_2
+    ).unwrap();
+    assert!(!filter.set_q_factor(-1.0_f64));
+    assert_relative_eq!(filter.get_q_factor(), std::f64::consts::FRAC_1_SQRT_2);
+    assert!(filter.set_cutoff(2000.0_f64));
+}
+
+#[test]
+fn test_samples_processed_tracks_time() {
+    let mut filter = LowPassFilter::<f64>::new(1000.0_f64, 48000_u32, 0.707_f64).unwrap();
+    let mut samples = vec![0.5_f64; 480];
+    filter.process_block(&mut samples);
+    assert_eq!(filter.samples_processed(), 480);
+    assert!(filter.set_cutoff(2000.0_f64));
+    filter.set_bypass(true);
+    filter.process_block(&mut samples);
+    filter.process(&mut samples[0]);
+    assert_eq!(filter.samples_processed(), 961);
+    assert!((filter.seconds_processed() - 961.0 / 48000.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_precision_conversion_keeps_configuration() {
+    let filter = LowPassFilter::<f64>::new(1000.0_f64, 48000_u32, 0.707_f64).unwrap();
+    let single = filter.to_f32();
+    assert_relative_eq!(single.get_cutoff(), 1000.0_f32);
+    assert_eq!(single.get_sample_rate(), 48000);
+    assert_relative_eq!(single.to_f64().get_q_factor(), 0.707_f64, epsilon = 1e-7);
+}
+
+#[test]
+fn process_block_with_stats_reports_bypassed_block() {
+    let mut filter = LowPassFilter::<f64>::new(1000.0, 44100, std::f64::consts::FRAC_1_SQRT_2)
+        .unwrap();
+    filter.set_bypass(true);
+    let mut samples = [0.5_f64, -1.25, 0.0];
+    let stats = filter.process_block_with_stats(&mut samples).unwrap();
+    assert_relative_eq!(stats.peak_input, 1.25);
+    assert_relative_eq!(stats.peak_output, 1.25);
+    assert_eq!(stats.clipped, 1);
+    assert_eq!(stats.nan, 0);
+    assert_eq!(filter.samples_processed(), 3);
+}
+
+#[test]
+fn resonance_maps_to_q_factor() {
+    let mut filter = LowPassFilter::<f64>::new(1000.0, 44100, 0.707).unwrap();
+    assert!(filter.set_resonance(0.0));
+    assert_relative_eq!(filter.get_q_factor(), 0.5);
+    assert!(filter.set_resonance(0.5));
+    assert_relative_eq!(filter.get_q_factor(), 1.0);
+    assert_relative_eq!(filter.get_resonance(), 0.5);
+    assert!(filter.set_resonance(0.99));
+    assert_relative_eq!(filter.get_q_factor(), 50.0, epsilon = 1e-9);
+    assert!(!filter.set_resonance(1.0));
+    assert!(!filter.set_resonance(-0.1));
+    assert_relative_eq!(filter.get_q_factor(), 50.0, epsilon = 1e-9);
+}
+
+/// Generic code only needs `Float` to build and run filters.
+fn smooth<T: Float>(samples: &mut [T], sample_rate: u32) -> Option<()> {
+    let mut filter = LowPassFilter::new(T::from(1000.0)?, sample_rate, T::from(0.707)?)?;
+    filter.process_block(samples);
+    let mut cascade = Cascade::<T, 2>::default();
+    cascade.process_block(samples);
+    let mut section = DigitalBiquadFilter::<T>::default();
+    section.process_block(samples);
+    Some(())
+}
+
+#[test]
+fn filters_only_require_float() {
+    let mut samples = [1.0_f32, 0.0, 0.0, 0.0];
+    assert!(smooth(&mut samples, 48000).is_some());
+    assert!(samples[0] > 0.0 && samples[0] < 1.0);
+}
+
+#[test]
+fn single_precision_design_matches_double_precision() {
+    let single = LowPassFilter::<f32>::new(40.0, 192000, core::f32::consts::FRAC_1_SQRT_2).unwrap();
+    let double = LowPassFilter::<f64>::new(40.0, 192000, core::f64::consts::FRAC_1_SQRT_2).unwrap();
+    // Evaluate both designs in double precision so only the coefficients differ.
+    let single = single.to_f64();
+    for frequency in [40.0, 100.0, 1000.0] {
+        let expected = double.magnitude_at(frequency, 192000);
+        let actual = single.magnitude_at(frequency, 192000);
+        assert_relative_eq!(actual, expected, max_relative = 1e-2);
+    }
+}
@@ -1,5 +1,4 @@
 /// low_shelf_tests.rs
-
 /**
 Copyright © 2025 Alex Parisi
 
@@ -21,7 +20,7 @@ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
 OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
-use biquad_filters::{Filter, LowShelfFilter};
+use biquad_filters::{Filter, FrequencyResponse, LowShelfFilter};
 use approx::assert_relative_eq;
 
 #[test]
@@ -106,3 +105,13 @@ fn set_quality_factor() {
     filter.set_q_factor(1.0_f64);
     assert_relative_eq!(filter.get_q_factor(), 1.0_f64);
 }
+
+#[test]
+fn response_follows_the_cookbook_shelf() {
+    let filter = LowShelfFilter::new(200.0_f64, 48000, core::f64::consts::FRAC_1_SQRT_2, 6.0).unwrap();
+    // The full gain below the shelf, half of it in decibels at the corner, and none above.
+    let decibels = |frequency: f64| 20.0 * filter.magnitude_at(frequency, 48000).log10();
+    assert_relative_eq!(decibels(0.0), 6.0, epsilon = 1e-9);
+    assert_relative_eq!(decibels(200.0), 3.0, epsilon = 1e-9);
+    assert_relative_eq!(decibels(24000.0), 0.0, epsilon = 1e-9);
+}
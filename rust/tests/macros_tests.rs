@@ -0,0 +1,166 @@
+#![cfg(feature = "macros")]
+/// macros_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{
+    BiquadFilterWrapper, Coefficients, DigitalBiquadFilter, Filter, FilterChain,
+    FilterConfiguration, FilterType, FrequencyResponse, HighShelfFilter, LowPassFilter,
+    NotchFilter, PeakingEQFilter, analysis, biquad,
+};
+use num_traits::Float;
+
+const LOW_PASS: Coefficients<f64> = biquad!(lowpass, 1000.0 Hz, fs = 48000, q = 0.707);
+
+/// Checks that a compile-time design responds like the runtime filter.
+fn assert_matches(design: Coefficients<f64>, mut filter: impl Filter<f64>) {
+    let mut actual = DigitalBiquadFilter::new(design).unwrap();
+    let mut expected_samples = [1.0, 0.0, 0.0, 0.0, 0.5, -0.25, 0.0, 0.0];
+    let mut actual_samples = expected_samples;
+    filter.process_block(&mut expected_samples);
+    actual.process_block(&mut actual_samples);
+    for (expected, actual) in expected_samples.iter().zip(actual_samples.iter()) {
+        assert!((expected - actual).abs() < 1e-12, "{expected} != {actual}");
+    }
+}
+
+#[test]
+fn const_design_matches_runtime_low_pass() {
+    assert_matches(LOW_PASS, LowPassFilter::new(1000.0, 48000, 0.707).unwrap());
+}
+
+#[test]
+fn units_and_defaults() {
+    let design = biquad!(notch, 2.5 kHz, fs = 44100);
+    assert_matches(design, NotchFilter::new(2500.0, 44100, std::f64::consts::FRAC_1_SQRT_2).unwrap());
+}
+
+#[test]
+fn gain_accepts_negative_decibels() {
+    let design = biquad!(peaking, 3.5 kHz, fs = 48000, q = 1.2, gain = -3.0 dB);
+    assert_matches(design, PeakingEQFilter::new(3500.0, 48000, 1.2, -3.0).unwrap());
+    let design = biquad!(highshelf, 8000 Hz, fs = 96000, q = 0.5, gain = 6);
+    assert_matches(design, HighShelfFilter::new(8000.0, 96000, 0.5, 6.0).unwrap());
+}
+
+/// A first-order low-pass written outside the crate, with only its design supplied by hand.
+#[derive(BiquadFilterWrapper)]
+struct OnePoleLowPass<T: Float> {
+    #[biquad(filter)]
+    section: DigitalBiquadFilter<T>,
+    #[biquad(config)]
+    settings: FilterConfiguration<T>,
+}
+
+impl<T: Float> OnePoleLowPass<T> {
+    fn new(cutoff: T, sample_rate: u32) -> Option<Self> {
+        let settings = FilterConfiguration::new(cutoff, sample_rate, T::one(), T::zero(), false, false);
+        let section = DigitalBiquadFilter::new(Self::calculate_coefficients(&settings)?)?;
+        Some(Self { section, settings })
+    }
+
+    fn design_coefficients(config: &FilterConfiguration<T>) -> Option<Coefficients<T>> {
+        let pi = T::from(std::f64::consts::PI)?;
+        let k = (pi * config.get_cutoff() / T::from(config.get_sample_rate())?).tan();
+        Some(Coefficients {
+            b0: k,
+            b1: k,
+            b2: T::zero(),
+            a0: k + T::one(),
+            a1: k - T::one(),
+            a2: T::zero(),
+        })
+    }
+}
+
+/// A wrapper around the cookbook high-pass design that reports the built-in response type.
+#[derive(BiquadFilterWrapper)]
+#[biquad(filter_type = HighPass)]
+struct CookbookHighPass<T: Float> {
+    #[biquad(filter)]
+    filter: DigitalBiquadFilter<T>,
+    #[biquad(config)]
+    config: FilterConfiguration<T>,
+}
+
+impl<T: Float> CookbookHighPass<T> {
+    fn design_coefficients(config: &FilterConfiguration<T>) -> Option<Coefficients<T>> {
+        FilterType::HighPass.design_coefficients(config)
+    }
+}
+
+#[test]
+fn derived_wrapper_supports_analysis() {
+    let filter = OnePoleLowPass::<f64>::new(1000.0, 48000).unwrap();
+    assert_eq!(filter.filter_type(), None);
+    assert_eq!(filter.get_config().get_cutoff(), 1000.0);
+
+    let report = analysis::report(&filter).unwrap();
+    assert_eq!(report.filter_type, None);
+    assert!((report.dc_gain - 1.0).abs() < 1e-12);
+    assert_eq!(report.cutoff_points.len(), 1);
+    assert!((report.cutoff_points[0] - 1000.0).abs() < 1e-6);
+}
+
+#[test]
+fn derived_wrapper_reports_a_named_filter_type() {
+    let config = FilterConfiguration::new(200.0, 48000, 0.707, 0.0, false, false);
+    let coefficients = FilterType::HighPass.design_coefficients(&config).unwrap();
+    let filter = CookbookHighPass { filter: DigitalBiquadFilter::new(coefficients).unwrap(), config };
+    assert_eq!(filter.filter_type(), Some(FilterType::HighPass));
+    assert_eq!(analysis::report(&filter).unwrap().filter_type, Some(FilterType::HighPass));
+}
+
+#[test]
+fn derived_wrapper_is_a_filter() {
+    let mut filter = OnePoleLowPass::<f64>::new(1000.0, 48000).unwrap();
+    let mut samples = [1.0; 512];
+    assert!(Filter::process_block(&mut filter, &mut samples));
+    assert!((samples[511] - 1.0).abs() < 1e-9);
+
+    assert!(filter.set_cutoff(4000.0));
+    assert_eq!(filter.get_cutoff(), 4000.0);
+    assert!((filter.magnitude_at(4000.0, 48000) - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-12);
+
+    assert!(filter.set_bypass(true));
+    let mut bypassed = [0.25, -0.5];
+    assert!(filter.process_block(&mut bypassed));
+    assert_eq!(bypassed, [0.25, -0.5]);
+}
+
+#[test]
+fn derived_wrapper_runs_in_a_filter_chain() {
+    let mut chain = FilterChain::new(48000).unwrap();
+    assert!(chain.push(Box::new(OnePoleLowPass::<f64>::new(2000.0, 48000).unwrap())));
+    assert!(chain.push(Box::new(LowPassFilter::new(5000.0, 48000, 0.707).unwrap())));
+
+    let mut first = OnePoleLowPass::<f64>::new(2000.0, 48000).unwrap();
+    let mut second = LowPassFilter::new(5000.0, 48000, 0.707).unwrap();
+    let input: Vec<f64> = (0..256).map(|n| ((n * 37 % 19) as f64 - 9.0) / 9.0).collect();
+    let mut expected = input.clone();
+    first.process_block(&mut expected);
+    second.process_block(&mut expected);
+    let mut actual = input;
+    assert!(chain.process_block(&mut actual));
+    for (expected, actual) in expected.iter().zip(actual.iter()) {
+        assert!((expected - actual).abs() < 1e-12, "{expected} != {actual}");
+    }
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "metering")]
+/// metering_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{Coefficients, DigitalBiquadFilter, Filter, LowPassFilter, Metered};
+use approx::assert_relative_eq;
+
+#[test]
+fn test_meters_track_input_and_output() {
+    let mut filter = DigitalBiquadFilter::new(Coefficients {
+        b0: 0.5,
+        b1: 0.0,
+        b2: 0.0,
+        a0: 1.0,
+        a1: 0.0,
+        a2: 0.0,
+    })
+    .unwrap();
+    let meters = filter.enable_metering(64);
+    let mut samples = vec![0.8_f64; 1024];
+    samples[10] = -1.0;
+    filter.process_block(&mut samples);
+    assert_relative_eq!(meters.input.get_peak(), 1.0);
+    assert_relative_eq!(meters.output.get_peak(), 0.5);
+    assert_relative_eq!(meters.input.get_rms(), 0.8, epsilon = 1e-4);
+    assert_relative_eq!(meters.output.get_rms(), 0.4, epsilon = 1e-4);
+}
+
+#[test]
+fn test_take_peak_clears_the_peak() {
+    let mut filter = LowPassFilter::<f32>::new(1000.0_f32, 48000_u32, 0.707_f32).unwrap();
+    let meters = filter.enable_metering(480);
+    let mut samples = vec![0.0_f32; 256];
+    samples[0] = 0.9;
+    filter.process_block(&mut samples);
+    assert_relative_eq!(meters.input.take_peak(), 0.9);
+    assert_relative_eq!(meters.input.get_peak(), 0.0);
+    let mut sample = 0.25_f32;
+    filter.process(&mut sample);
+    assert_relative_eq!(meters.input.get_peak(), 0.25);
+}
+
+#[test]
+fn test_meters_are_readable_from_another_thread() {
+    let mut filter = LowPassFilter::<f64>::new(1000.0_f64, 48000_u32, 0.707_f64).unwrap();
+    let meters = filter.enable_metering(480);
+    let reader = std::thread::spawn(move || {
+        while meters.input.get_peak() == 0.0 {
+            std::thread::yield_now();
+        }
+        meters.input.get_peak()
+    });
+    let mut samples = vec![0.5_f64; 64];
+    filter.process_block(&mut samples);
+    assert_relative_eq!(reader.join().unwrap(), 0.5);
+    assert!(filter.get_meters().is_some());
+    filter.disable_metering();
+    assert!(filter.get_meters().is_none());
+}
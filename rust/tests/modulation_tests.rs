@@ -0,0 +1,121 @@
+/// modulation_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{Filter, Lfo, LfoShape, LowPassFilter, ModulatedFilter, Parameter};
+use approx::assert_relative_eq;
+
+const SAMPLE_RATE: u32 = 48000;
+
+fn make_filter() -> LowPassFilter<f64> {
+    LowPassFilter::<f64>::new(1000.0, SAMPLE_RATE, std::f64::consts::FRAC_1_SQRT_2).unwrap()
+}
+
+fn make_signal(length: usize) -> Vec<f64> {
+    (0..length).map(|n| ((n * 13) % 7) as f64 - 3.0).collect()
+}
+
+#[test]
+fn lfo_shapes_swing_between_plus_and_minus_depth() {
+    let mut sine = Lfo::<f64>::new(LfoShape::Sine, 1000.0, 2.0).unwrap();
+    let mut triangle = Lfo::<f64>::new(LfoShape::Triangle, 1000.0, 2.0).unwrap();
+    assert_relative_eq!(sine.value(), 0.0);
+    assert_relative_eq!(triangle.value(), 0.0);
+    sine.advance(12, SAMPLE_RATE);
+    triangle.advance(12, SAMPLE_RATE);
+    assert_relative_eq!(sine.value(), 2.0);
+    assert_relative_eq!(triangle.value(), 2.0);
+    triangle.advance(12, SAMPLE_RATE);
+    assert_relative_eq!(triangle.value(), 0.0, epsilon = 1e-12);
+    triangle.advance(12, SAMPLE_RATE);
+    assert_relative_eq!(triangle.value(), -2.0);
+    triangle.advance(24, SAMPLE_RATE);
+    assert_relative_eq!(triangle.get_phase(), 0.25, epsilon = 1e-12);
+
+    assert!(Lfo::<f64>::new(LfoShape::Sine, -1.0, 1.0).is_none());
+    assert!(Lfo::<f64>::new(LfoShape::Sine, 1.0, f64::NAN).is_none());
+    assert!(!sine.set_rate(f64::INFINITY));
+}
+
+#[test]
+fn random_lfo_holds_a_value_for_each_cycle() {
+    let mut lfo = Lfo::<f64>::with_seed(LfoShape::Random, 1000.0, 1.5, 7).unwrap();
+    let mut values = Vec::new();
+    for _ in 0..16 {
+        let held = lfo.value();
+        assert!(held.abs() <= 1.5);
+        lfo.advance(20, SAMPLE_RATE);
+        assert_eq!(lfo.value(), held);
+        lfo.advance(28, SAMPLE_RATE);
+        values.push(held);
+    }
+    assert!(values.windows(2).any(|pair| pair[0] != pair[1]));
+
+    lfo.reset();
+    assert_eq!(lfo.value(), values[0]);
+}
+
+#[test]
+fn sweeps_the_cutoff_every_control_interval() {
+    let lfo = Lfo::new(LfoShape::Sine, 100.0, 1.0).unwrap();
+    let mut modulated = ModulatedFilter::new(make_filter(), Parameter::Cutoff, lfo);
+    assert!(modulated.set_control_interval(16));
+
+    let mut reference = make_filter();
+    let mut expected = make_signal(480);
+    for (chunk, block) in expected.chunks_mut(16).enumerate() {
+        let time = (chunk * 16) as f64 / SAMPLE_RATE as f64;
+        let offset = (2.0 * std::f64::consts::PI * 100.0 * time).sin();
+        assert!(reference.set_cutoff(1000.0 * offset.exp2()));
+        reference.process_block(block);
+    }
+
+    let mut samples = make_signal(480);
+    for block in samples.chunks_mut(100) {
+        assert!(modulated.process_block(block));
+    }
+    for (actual, expected) in samples.iter().zip(expected.iter()) {
+        assert_relative_eq!(*actual, *expected, epsilon = 1e-9);
+    }
+    assert_relative_eq!(modulated.get_center(), 1000.0);
+    assert!(!modulated.set_control_interval(0));
+}
+
+#[test]
+fn keeps_the_cutoff_below_nyquist_and_restores_the_center() {
+    let lfo = Lfo::new(LfoShape::Triangle, 10.0, 8.0).unwrap();
+    let mut modulated = ModulatedFilter::new(make_filter(), Parameter::Cutoff, lfo);
+    assert!(modulated.set_center(4000.0));
+    assert!(!modulated.set_center(0.0));
+    modulated.get_lfo_mut().set_phase(0.25);
+    assert_relative_eq!(modulated.modulated_value(), 0.49 * SAMPLE_RATE as f64);
+
+    let mut samples = make_signal(64);
+    assert!(modulated.process_block(&mut samples));
+    assert!(samples.iter().all(|sample| sample.is_finite()));
+
+    assert!(modulated.set_parameter(Parameter::Gain));
+    assert_relative_eq!(modulated.get_filter().get_cutoff(), 4000.0);
+    assert!(modulated.set_center(-6.0));
+    assert!(modulated.process_block(&mut samples));
+    let filter = modulated.into_inner();
+    assert_relative_eq!(filter.get_gain(), -6.0);
+}
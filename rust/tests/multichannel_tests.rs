@@ -0,0 +1,132 @@
+/// multichannel_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{
+    Filter, FilterConfiguration, FilterType, FrequencyResponse, LowPassFilter,
+    MultiChannelFilter,
+};
+use approx::assert_relative_eq;
+
+const SAMPLE_RATE: u32 = 48000;
+
+fn config(cutoff: f64) -> FilterConfiguration<f64> {
+    FilterConfiguration::builder()
+        .cutoff(cutoff)
+        .sample_rate(SAMPLE_RATE)
+        .q_factor(0.707)
+        .build()
+        .unwrap()
+}
+
+fn channel(index: usize) -> Vec<f64> {
+    (0..256).map(|n| ((n * (index + 1)) as f64 * 0.07).sin()).collect()
+}
+
+#[test]
+fn each_channel_matches_a_separate_filter() {
+    let mut filter = MultiChannelFilter::new(FilterType::LowPass, config(1000.0), 3).unwrap();
+    let mut planar: Vec<Vec<f64>> = (0..3).map(channel).collect();
+    let mut slices: Vec<&mut [f64]> = planar.iter_mut().map(|c| c.as_mut_slice()).collect();
+    assert!(filter.process_planar(&mut slices));
+    for (index, processed) in planar.iter().enumerate() {
+        let mut single = LowPassFilter::new(1000.0, SAMPLE_RATE, 0.707).unwrap();
+        let mut expected = channel(index);
+        single.process_block(&mut expected);
+        for (sample, expected) in processed.iter().zip(&expected) {
+            assert_relative_eq!(*sample, *expected, epsilon = 1e-12);
+        }
+    }
+}
+
+#[test]
+fn interleaved_matches_planar() {
+    let mut planar_filter =
+        MultiChannelFilter::new(FilterType::HighPass, config(200.0), 2).unwrap();
+    let mut interleaved_filter = planar_filter.clone();
+    let mut planar: Vec<Vec<f64>> = (0..2).map(channel).collect();
+    let mut interleaved: Vec<f64> =
+        (0..256).flat_map(|n| [planar[0][n], planar[1][n]]).collect();
+
+    let mut slices: Vec<&mut [f64]> = planar.iter_mut().map(|c| c.as_mut_slice()).collect();
+    assert!(planar_filter.process_planar(&mut slices));
+    assert!(interleaved_filter.process_interleaved(&mut interleaved, 2));
+    for n in 0..256 {
+        assert_eq!(interleaved[2 * n], planar[0][n]);
+        assert_eq!(interleaved[2 * n + 1], planar[1][n]);
+    }
+}
+
+#[test]
+fn rejects_mismatched_layouts() {
+    assert!(MultiChannelFilter::new(FilterType::LowPass, config(1000.0), 0).is_none());
+    let mut filter = MultiChannelFilter::new(FilterType::LowPass, config(1000.0), 2).unwrap();
+    let mut samples = vec![1.0; 7];
+    assert!(!filter.process_interleaved(&mut samples, 2));
+    assert!(!filter.process_interleaved(&mut samples[..6], 3));
+    assert!(samples.iter().all(|sample| *sample == 1.0));
+    let mut left = vec![1.0; 4];
+    assert!(!filter.process_planar(&mut [left.as_mut_slice()]));
+    assert_eq!(left, vec![1.0; 4]);
+}
+
+#[test]
+fn settings_apply_to_every_channel() {
+    let mut filter = MultiChannelFilter::new(FilterType::LowPass, config(1000.0), 2).unwrap();
+    assert!(filter.update(|config| config.set_cutoff(4000.0)));
+    assert_eq!(filter.get_configuration().get_cutoff(), 4000.0);
+    let single = LowPassFilter::new(4000.0, SAMPLE_RATE, 0.707).unwrap();
+    assert_relative_eq!(
+        filter.magnitude_at(3000.0, SAMPLE_RATE),
+        single.magnitude_at(3000.0, SAMPLE_RATE),
+        epsilon = 1e-12
+    );
+    assert!(!filter.update(|config| config.set_q_factor(-1.0)));
+    assert_eq!(filter.get_configuration().get_q_factor(), 0.707);
+    assert!(filter.set_filter(FilterType::HighPass, config(4000.0)));
+    assert_eq!(filter.get_filter_type(), FilterType::HighPass);
+
+    let mut bypassed = config(4000.0);
+    bypassed.set_bypass(true);
+    assert!(filter.set_configuration(bypassed));
+    let mut samples = vec![0.5; 8];
+    assert!(filter.process_interleaved(&mut samples, 2));
+    assert_eq!(samples, vec![0.5; 8]);
+}
+
+#[test]
+fn channels_can_be_added_and_reset() {
+    let mut filter = MultiChannelFilter::new(FilterType::LowPass, config(1000.0), 1).unwrap();
+    let mut first = channel(0);
+    filter.process_planar(&mut [first.as_mut_slice()]);
+    assert!(filter.set_channels(2));
+    assert!(!filter.set_channels(0));
+    assert_eq!(filter.get_channels(), 2);
+
+    filter.reset();
+    let mut again = channel(0);
+    let mut silent = vec![0.0; 256];
+    filter.process_planar(&mut [again.as_mut_slice(), silent.as_mut_slice()]);
+    assert_eq!(again, first);
+    assert!(silent.iter().all(|sample| *sample == 0.0));
+    assert!(filter.reset_channel(1));
+    assert!(!filter.reset_channel(2));
+}
@@ -1,5 +1,4 @@
 /// notch_tests.rs
-
 /**
 Copyright © 2025 Alex Parisi
 
@@ -21,7 +20,7 @@ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
 OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
-use biquad_filters::{Filter, NotchFilter};
+use biquad_filters::{Filter, FrequencyResponse, NotchFilter};
 use approx::assert_relative_eq;
 
 #[test]
@@ -99,3 +98,22 @@ fn set_quality_factor() {
     filter.set_q_factor(1.0_f64);
     assert_relative_eq!(filter.get_q_factor(), 1.0_f64);
 }
+
+#[test]
+fn depth_sets_center_attenuation() {
+    let mut filter = NotchFilter::<f64>::new(1000.0, 48000, 2.0).unwrap();
+    assert_eq!(filter.get_depth(), f64::NEG_INFINITY);
+    assert!(filter.magnitude_at(1000.0, 48000) < 1e-9);
+
+    assert!(filter.set_depth(-6.0));
+    assert_relative_eq!(filter.get_depth(), -6.0, epsilon = 1e-9);
+    let center = 20.0 * filter.magnitude_at(1000.0, 48000).log10();
+    assert_relative_eq!(center, -6.0, epsilon = 1e-9);
+    // Far from the center the response is still close to unity.
+    assert_relative_eq!(filter.magnitude_at(20.0, 48000), 1.0, epsilon = 1e-3);
+
+    assert!(filter.set_depth(f64::NEG_INFINITY));
+    assert!(filter.magnitude_at(1000.0, 48000) < 1e-9);
+    assert!(!filter.set_depth(3.0));
+    assert!(!filter.set_depth(f64::NAN));
+}
@@ -0,0 +1,152 @@
+/// parallel_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{
+    BandPassFilter, Filter, FrequencyResponse, IirFilter, LowPassFilter, NotchFilter,
+    ParallelBank, PeakingEQFilter,
+};
+use approx::assert_relative_eq;
+
+const SAMPLE_RATE: u32 = 48000;
+
+fn signal() -> Vec<f64> {
+    (0..200).map(|n| (n as f64 * 0.05).sin() + 0.3 * (n as f64 * 0.9).sin()).collect()
+}
+
+#[test]
+fn sums_scaled_branch_outputs() {
+    let mut low_pass = LowPassFilter::new(500.0, SAMPLE_RATE, 0.707).unwrap();
+    let mut peaking = PeakingEQFilter::new(2000.0, SAMPLE_RATE, 2.0, 6.0).unwrap();
+    let mut low_passed = signal();
+    low_pass.process_block(&mut low_passed);
+    let mut peaked = signal();
+    peaking.process_block(&mut peaked);
+
+    let mut bank = ParallelBank::new(SAMPLE_RATE).unwrap();
+    let low_pass = LowPassFilter::new(500.0, SAMPLE_RATE, 0.707).unwrap();
+    let peaking = PeakingEQFilter::new(2000.0, SAMPLE_RATE, 2.0, 6.0).unwrap();
+    assert_eq!(bank.add_branch(Box::new(low_pass), 0.5), Some(0));
+    assert_eq!(bank.add_branch(Box::new(peaking), -0.25), Some(1));
+    assert!(bank.set_direct_gain(0.1));
+
+    let mut samples = signal();
+    assert!(bank.process_block(&mut samples[..150]));
+    for sample in samples[150..].iter_mut() {
+        assert!(bank.process(sample));
+    }
+    for (n, sample) in samples.iter().enumerate() {
+        let expected = 0.1 * signal()[n] + 0.5 * low_passed[n] - 0.25 * peaked[n];
+        assert_relative_eq!(*sample, expected, epsilon = 1e-12);
+    }
+    assert!(!bank.process_block(&mut []));
+}
+
+#[test]
+fn direct_path_minus_band_pass_is_a_notch() {
+    let mut bank = ParallelBank::new(SAMPLE_RATE).unwrap();
+    let band_pass = BandPassFilter::new(1000.0, SAMPLE_RATE, 2.0, false).unwrap();
+    bank.add_branch(Box::new(band_pass), -1.0).unwrap();
+    assert!(bank.set_direct_gain(1.0));
+    let mut notch = NotchFilter::new(1000.0, SAMPLE_RATE, 2.0).unwrap();
+    let mut expected = signal();
+    notch.process_block(&mut expected);
+    let mut samples = signal();
+    bank.process_block(&mut samples);
+    for (sample, expected) in samples.iter().zip(&expected) {
+        assert_relative_eq!(*sample, *expected, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn empty_bank_outputs_the_direct_path() {
+    let mut bank = ParallelBank::<f64>::new(SAMPLE_RATE).unwrap();
+    let mut samples = signal();
+    bank.process_block(&mut samples);
+    assert!(samples.iter().all(|sample| *sample == 0.0));
+    assert!(bank.set_direct_gain(2.0));
+    assert!(!bank.set_direct_gain(f64::NAN));
+    let mut samples = signal();
+    bank.process_block(&mut samples);
+    assert_eq!(samples, signal().iter().map(|sample| 2.0 * sample).collect::<Vec<_>>());
+}
+
+#[test]
+fn branches_can_be_managed() {
+    assert!(ParallelBank::<f64>::new(0).is_none());
+    let mut bank = ParallelBank::new(SAMPLE_RATE).unwrap();
+    let low_pass = LowPassFilter::new(500.0, 44100, 0.707).unwrap();
+    assert!(bank.add_branch(Box::new(low_pass.clone()), f64::INFINITY).is_none());
+    assert_eq!(bank.add_branch(Box::new(low_pass.clone()), 1.0), Some(0));
+    assert_eq!(bank.add_branch(Box::new(low_pass), 1.0), Some(1));
+    assert_eq!(bank.get_branch(0).map(|branch| branch.get_sample_rate()), Some(SAMPLE_RATE));
+
+    assert!(bank.set_gain(1, 0.5));
+    assert!(!bank.set_gain(2, 0.5));
+    assert_eq!(bank.get_gain(1), Some(0.5));
+    assert!(bank.get_branch_mut(1).is_some_and(|branch| branch.set_cutoff(800.0)));
+
+    assert!(bank.set_sample_rate(96000));
+    let rates: Vec<u32> = (0..bank.len()).map(|i| bank.get_branch(i).unwrap().get_sample_rate()).collect();
+    assert_eq!(rates, vec![96000, 96000]);
+    assert!(!bank.set_sample_rate(0));
+
+    let mut samples = signal();
+    bank.process_block(&mut samples);
+    bank.reset();
+    assert_eq!(bank.get_branch(0).unwrap().samples_processed(), 0);
+
+    let (branch, gain) = bank.remove_branch(0).unwrap();
+    assert_eq!((branch.get_cutoff(), gain), (500.0, 1.0));
+    assert_eq!(bank.get_gain(0), Some(0.5));
+    assert!(bank.remove_branch(1).is_none());
+    bank.clear();
+    assert!(bank.is_empty());
+}
+
+#[test]
+fn response_matches_the_equivalent_series_design() {
+    // Two real poles in series, 1 / ((1 - p1 z^-1)(1 - p2 z^-1)), split into partial fractions.
+    let (p1, p2) = (0.9, 0.5);
+    let series = IirFilter::new(&[1.0], &[1.0, -(p1 + p2), p1 * p2], SAMPLE_RATE).unwrap();
+    let mut bank = ParallelBank::new(SAMPLE_RATE).unwrap();
+    let first = IirFilter::new(&[1.0], &[1.0, -p1], SAMPLE_RATE).unwrap();
+    let second = IirFilter::new(&[1.0], &[1.0, -p2], SAMPLE_RATE).unwrap();
+    bank.add_branch(Box::new(first), p1 / (p1 - p2)).unwrap();
+    bank.add_branch(Box::new(second), -p2 / (p1 - p2)).unwrap();
+    for frequency in [0.0, 50.0, 500.0, 3000.0, 12000.0, 24000.0] {
+        let expected = series.response_at(frequency, SAMPLE_RATE);
+        let actual = bank.response_at(frequency, SAMPLE_RATE);
+        assert!((actual - expected).norm() < 1e-12, "{actual} != {expected} at {frequency} Hz");
+    }
+
+    // The direct path minus a band pass has the response of the matching notch.
+    let notch = NotchFilter::new(1000.0, SAMPLE_RATE, 2.0).unwrap();
+    let mut bank = ParallelBank::new(SAMPLE_RATE).unwrap();
+    bank.add_branch(Box::new(BandPassFilter::new(1000.0, SAMPLE_RATE, 2.0, false).unwrap()), -1.0)
+        .unwrap();
+    assert!(bank.set_direct_gain(1.0));
+    for frequency in [100.0, 900.0, 1000.0, 1100.0, 10000.0] {
+        let expected = notch.response_at(frequency, SAMPLE_RATE);
+        let actual = bank.response_at(frequency, SAMPLE_RATE);
+        assert!((actual - expected).norm() < 1e-12, "{actual} != {expected} at {frequency} Hz");
+    }
+}
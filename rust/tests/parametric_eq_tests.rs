@@ -0,0 +1,140 @@
+/// parametric_eq_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{
+    Filter, FilterConfiguration, FilterType, FrequencyResponse, HighPassFilter, HighShelfFilter,
+    ParametricEq, PeakingEQFilter,
+};
+use approx::assert_relative_eq;
+
+const SAMPLE_RATE: u32 = 48000;
+
+fn config(cutoff: f64, q_factor: f64, gain: f64) -> FilterConfiguration<f64> {
+    FilterConfiguration::builder()
+        .cutoff(cutoff)
+        .sample_rate(SAMPLE_RATE)
+        .q_factor(q_factor)
+        .gain(gain)
+        .build()
+        .unwrap()
+}
+
+fn equalizer() -> ParametricEq<f64> {
+    let mut eq = ParametricEq::new(SAMPLE_RATE);
+    assert_eq!(eq.add_band(FilterType::HighPass, config(80.0, 0.707, 0.0)), Some(0));
+    assert_eq!(eq.add_band(FilterType::PeakingEQ, config(1000.0, 2.0, 6.0)), Some(1));
+    assert_eq!(eq.add_band(FilterType::HighShelf, config(8000.0, 0.707, -3.0)), Some(2));
+    eq
+}
+
+#[test]
+fn runs_the_bands_in_series() {
+    let mut eq = equalizer();
+    let mut high_pass = HighPassFilter::new(80.0, SAMPLE_RATE, 0.707).unwrap();
+    let mut peak = PeakingEQFilter::new(1000.0, SAMPLE_RATE, 2.0, 6.0).unwrap();
+    let mut shelf = HighShelfFilter::new(8000.0, SAMPLE_RATE, 0.707, -3.0).unwrap();
+    let input: Vec<f64> = (0..512).map(|n| ((n * 7919) % 127) as f64 / 63.0 - 1.0).collect();
+    let mut expected = input.clone();
+    high_pass.process_block(&mut expected);
+    peak.process_block(&mut expected);
+    shelf.process_block(&mut expected);
+    let mut samples = input.clone();
+    assert!(eq.process_block(&mut samples));
+    for (sample, expected) in samples.iter().zip(expected.iter()) {
+        assert_relative_eq!(*sample, *expected, epsilon = 1e-12);
+    }
+    for frequency in [50.0, 1000.0, 12000.0] {
+        let product = high_pass.magnitude_at(frequency, SAMPLE_RATE)
+            * peak.magnitude_at(frequency, SAMPLE_RATE)
+            * shelf.magnitude_at(frequency, SAMPLE_RATE);
+        assert_relative_eq!(eq.magnitude_at(frequency, SAMPLE_RATE), product, epsilon = 1e-12);
+    }
+    assert!(!eq.process_block(&mut []));
+}
+
+#[test]
+fn bands_can_be_added_changed_and_removed() {
+    let mut eq = equalizer();
+    assert!(eq.insert_band(1, FilterType::Notch, config(60.0, 10.0, 0.0)));
+    assert!(!eq.insert_band(9, FilterType::Notch, config(60.0, 10.0, 0.0)));
+    assert_eq!(eq.len(), 4);
+    assert_eq!(eq.get_band(1).unwrap().0, FilterType::Notch);
+
+    // An invalid change is rejected and the band keeps its settings.
+    assert!(!eq.set_band(2, FilterType::PeakingEQ, FilterConfiguration::default()));
+    assert!(eq.update_band(2, |config| config.set_gain(-4.0)));
+    assert_relative_eq!(eq.get_band(2).unwrap().1.get_gain(), -4.0);
+    assert!(!eq.update_band(2, |config| config.set_q_factor(0.0)));
+    assert!(!eq.update_band(7, |config| config.set_gain(1.0)));
+    assert!(eq.set_band(2, FilterType::LowShelf, config(200.0, 0.707, 3.0)));
+    assert_eq!(eq.get_band(2).unwrap().0, FilterType::LowShelf);
+
+    let (filter_type, removed) = eq.remove_band(1).unwrap();
+    assert_eq!(filter_type, FilterType::Notch);
+    assert_relative_eq!(removed.get_cutoff(), 60.0);
+    assert!(eq.remove_band(3).is_none());
+    eq.clear();
+    assert!(eq.is_empty());
+    assert_relative_eq!(eq.magnitude_at(1000.0, SAMPLE_RATE), 1.0);
+}
+
+#[test]
+fn bands_follow_the_equalizer_sample_rate() {
+    let mut eq = ParametricEq::<f32>::new(44100);
+    let band = FilterConfiguration::builder().cutoff(1000.0).sample_rate(96000).gain(6.0).build();
+    eq.add_band(FilterType::PeakingEQ, band.unwrap()).unwrap();
+    assert_eq!(eq.get_band(0).unwrap().1.get_sample_rate(), 44100);
+    assert_relative_eq!(eq.magnitude_at(1000.0, 44100), 10.0_f32.powf(0.3), epsilon = 1e-4);
+    assert!(eq.set_sample_rate(96000));
+    assert_relative_eq!(eq.magnitude_at(1000.0, 96000), 10.0_f32.powf(0.3), epsilon = 1e-4);
+    // No band can be designed at a sample rate of zero, so nothing changes.
+    assert!(!eq.set_sample_rate(0));
+    assert_eq!(eq.get_sample_rate(), 96000);
+    assert_eq!(eq.get_band(0).unwrap().1.get_sample_rate(), 96000);
+}
+
+#[test]
+fn bypassed_bands_pass_the_signal() {
+    let mut eq = equalizer();
+    assert!(eq.update_band(1, |config| config.set_bypass(true)));
+    let mut high_pass = HighPassFilter::new(80.0, SAMPLE_RATE, 0.707).unwrap();
+    let mut shelf = HighShelfFilter::new(8000.0, SAMPLE_RATE, 0.707, -3.0).unwrap();
+    let mut expected = [1.0; 64];
+    high_pass.process_block(&mut expected);
+    shelf.process_block(&mut expected);
+    let mut samples = [1.0; 64];
+    eq.process_block(&mut samples);
+    assert_relative_eq!(samples[63], expected[63], epsilon = 1e-12);
+    eq.reset();
+}
+
+#[test]
+fn filter_types_design_like_their_filters() {
+    let peak = PeakingEQFilter::new(1000.0, SAMPLE_RATE, 2.0, 6.0).unwrap();
+    let designed = FilterType::PeakingEQ.design_coefficients(&peak.get_configuration()).unwrap();
+    assert_relative_eq!(
+        designed.magnitude_at(1000.0, SAMPLE_RATE),
+        peak.magnitude_at(1000.0, SAMPLE_RATE),
+        epsilon = 1e-12
+    );
+    assert!(FilterType::LowPass.design_coefficients(&FilterConfiguration::<f64>::default()).is_none());
+}
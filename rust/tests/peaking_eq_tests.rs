@@ -1,5 +1,4 @@
 /// peaking_eq_tests.rs
-
 /**
 Copyright © 2025 Alex Parisi
 
@@ -21,7 +20,7 @@ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
 OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 SOFTWARE.
 */
-use biquad_filters::{Filter, PeakingEQFilter};
+use biquad_filters::{DesignMethod, Filter, FilterExt, FrequencyResponse, PeakingEQFilter};
 use approx::assert_relative_eq;
 
 #[test]
@@ -106,3 +105,73 @@ fn set_quality_factor() {
     filter.set_q_factor(1.0_f64);
     assert_relative_eq!(filter.get_q_factor(), 1.0_f64);
 }
+
+#[test]
+fn create_filter_with_non_finite_gain() {
+    let filter = PeakingEQFilter::<f64>::new(
+        1000.0_f64,
+        44100_u32,
+        std::f64::consts::FRAC_1_SQRT_2,
+        f64::INFINITY
+    );
+    assert!(filter.is_none());
+}
+
+#[test]
+fn set_parameters_matches_individual_setters() {
+    let mut batched = PeakingEQFilter::<f64>::new(1000.0, 44100, 0.707, 0.0).unwrap();
+    let mut individual = batched.clone();
+    assert!(batched.set_parameters(2500.0, 2.0, 6.0));
+    assert!(individual.set_cutoff(2500.0));
+    assert!(individual.set_q_factor(2.0));
+    assert!(individual.set_gain(6.0));
+    let mut a = 1.0_f64;
+    let mut b = 1.0_f64;
+    batched.process(&mut a);
+    individual.process(&mut b);
+    assert_relative_eq!(a, b);
+
+    assert!(!batched.set_parameters(3000.0, -1.0, 3.0));
+    assert_relative_eq!(batched.get_cutoff(), 2500.0);
+    assert_relative_eq!(batched.get_gain(), 6.0);
+
+    assert!(batched.update(|config| config.set_gain(-3.0)));
+    assert_relative_eq!(batched.get_gain(), -3.0);
+}
+
+#[test]
+fn cut_q_factor_applies_only_to_cuts() {
+    let mut band = PeakingEQFilter::<f64>::new(1000.0, 48000, 0.5, 6.0).unwrap();
+    assert_eq!(band.get_cut_q_factor(), None);
+    assert!(band.set_cut_q_factor(Some(4.0)));
+    let wide = PeakingEQFilter::<f64>::new(1000.0, 48000, 0.5, 6.0).unwrap();
+    assert_relative_eq!(band.magnitude_at(2000.0, 48000), wide.magnitude_at(2000.0, 48000));
+
+    assert!(band.set_gain(-6.0));
+    let narrow = PeakingEQFilter::<f64>::new(1000.0, 48000, 4.0, -6.0).unwrap();
+    assert_relative_eq!(band.magnitude_at(2000.0, 48000), narrow.magnitude_at(2000.0, 48000));
+    assert_relative_eq!(band.get_q_factor(), 0.5);
+
+    assert!(!band.set_cut_q_factor(Some(0.0)));
+    assert_eq!(band.get_cut_q_factor(), Some(4.0));
+    assert!(band.set_cut_q_factor(None));
+}
+
+#[test]
+fn cut_is_the_inverse_of_boost() {
+    for method in [DesignMethod::Bilinear, DesignMethod::Matched, DesignMethod::Orfanidis] {
+        for (cutoff, q, gain) in [(100.0, 0.7, 15.0), (3000.0, 4.0, 6.0), (16000.0, 1.5, 12.0)] {
+            let mut boost = PeakingEQFilter::<f64>::new(cutoff, 44100, q, gain).unwrap();
+            let mut cut = PeakingEQFilter::<f64>::new(cutoff, 44100, q, -gain).unwrap();
+            assert!(boost.set_design_method(method));
+            assert!(cut.set_design_method(method));
+            let mut frequency = 20.0;
+            while frequency < 22050.0 {
+                let product = boost.response_at(frequency, 44100) * cut.response_at(frequency, 44100);
+                assert_relative_eq!(product.re, 1.0, epsilon = 1e-9);
+                assert_relative_eq!(product.im, 0.0, epsilon = 1e-9);
+                frequency *= 1.1;
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+/// pinking_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{ButterworthFilter, FrequencyResponse, PinkingFilter};
+use approx::assert_relative_eq;
+
+#[test]
+fn slope_is_three_decibels_per_octave() {
+    for rate in [22050, 44100, 48000, 96000, 192000] {
+        let filter = PinkingFilter::<f64>::new(rate).unwrap();
+        let top = (0.45 * f64::from(rate)).min(20000.0);
+        let mut frequency = 20.0;
+        while frequency <= top {
+            let gain = 20.0 * filter.magnitude_at(frequency, rate).log10();
+            let ideal = -10.0 * (frequency / 1000.0).log10();
+            assert!((gain - ideal).abs() < 0.4, "{} Hz at {}: {} dB", frequency, rate, gain);
+            frequency *= 1.1;
+        }
+        assert_relative_eq!(filter.magnitude_at(1000.0, rate), 1.0, epsilon = 1e-9);
+    }
+}
+
+#[test]
+fn pinks_white_noise() {
+    // A one-octave band holds the same power at any frequency in pink noise.
+    let rate = 48000;
+    let mut filter = PinkingFilter::<f64>::new(rate).unwrap();
+    let mut seed: u32 = 1;
+    let mut samples: Vec<f64> = (0..1 << 16)
+        .map(|_| {
+            seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+            f64::from(seed) / f64::from(u32::MAX) - 0.5
+        })
+        .collect();
+    assert!(filter.process_block(&mut samples));
+    assert!(samples.iter().all(|sample| sample.is_finite()));
+    let band_power = |low: f64| {
+        let mut band = ButterworthFilter::band_pass(low, 2.0 * low, rate, 4).unwrap();
+        let mut filtered = samples.clone();
+        band.process_block(&mut filtered);
+        filtered[4096..].iter().map(|x| x * x).sum::<f64>()
+    };
+    let ratio = band_power(250.0) / band_power(4000.0);
+    assert!((10.0 * ratio.log10()).abs() < 1.5, "{}", ratio);
+}
+
+#[test]
+fn rejects_low_rates_and_resets() {
+    assert!(PinkingFilter::<f32>::new(2000).is_none());
+    let mut filter = PinkingFilter::<f32>::new(44100).unwrap();
+    let mut sample = 1.0;
+    assert!(filter.process(&mut sample));
+    filter.reset();
+    assert_eq!(filter.get_sections().len(), 3);
+    assert_eq!(filter.get_sample_rate(), 44100);
+}
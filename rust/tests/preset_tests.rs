@@ -0,0 +1,175 @@
+/// preset_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use approx::assert_relative_eq;
+use biquad_filters::{
+    FilterConfiguration, FrequencyResponse, FilterSpec, FilterType, ParametricEq, Preset, PresetError, SpecError,
+};
+
+const SAMPLE_RATE: u32 = 48000;
+
+fn eq() -> ParametricEq<f64> {
+    let mut eq = ParametricEq::new(SAMPLE_RATE);
+    let band = |cutoff: f64, q_factor: f64, gain: f64| {
+        FilterConfiguration::builder()
+            .cutoff(cutoff)
+            .sample_rate(SAMPLE_RATE)
+            .q_factor(q_factor)
+            .gain(gain)
+    };
+    eq.add_band(FilterType::HighPass, band(80.0, 0.75, 0.0).build().unwrap()).unwrap();
+    eq.add_band(FilterType::PeakingEQ, band(2512.3, 2.0, -3.25).bypass(true).build().unwrap())
+        .unwrap();
+    eq.add_band(FilterType::HighShelf, band(9000.0, 0.6, 1.5).build().unwrap()).unwrap();
+    eq
+}
+
+#[test]
+fn saves_and_loads_every_band() {
+    let preset = Preset::from_parametric_eq("Vocal", &eq()).unwrap();
+    let mut saved = Vec::new();
+    preset.save(&mut saved).unwrap();
+    let text = String::from_utf8(saved.clone()).unwrap();
+    assert!(text.starts_with("version = 1\nname = Vocal\n"));
+    assert_eq!(text.lines().filter(|line| line.starts_with("band = ")).count(), 3);
+
+    let loaded = Preset::<f64>::load(saved.as_slice()).unwrap();
+    assert_eq!(loaded, preset);
+    assert_eq!(loaded.get_name(), "Vocal");
+    let band = loaded.get_bands()[1];
+    assert_eq!(band.get_filter_type(), FilterType::PeakingEQ);
+    assert_eq!((band.get_cutoff(), band.get_q_factor(), band.get_gain()), (2512.3, 2.0, -3.25));
+    assert!(band.get_bypass());
+}
+
+#[test]
+fn restores_an_eq_at_any_sample_rate() {
+    let preset = Preset::from_parametric_eq("", &eq()).unwrap();
+    let restored = preset.to_parametric_eq(96000).unwrap();
+    assert_eq!(restored.len(), 3);
+    assert_eq!(restored.get_sample_rate(), 96000);
+    for index in 0..3 {
+        let (original_type, original) = eq().get_band(index).unwrap();
+        let (filter_type, config) = restored.get_band(index).unwrap();
+        assert_eq!(filter_type, original_type);
+        assert_eq!(config.get_cutoff(), original.get_cutoff());
+        assert_eq!(config.get_gain(), original.get_gain());
+        assert_eq!(config.get_bypass(), original.get_bypass());
+        assert_eq!(config.get_sample_rate(), 96000);
+    }
+
+    let chain = preset.to_chain(44100).unwrap();
+    assert_eq!(chain.len(), 3);
+    assert!(chain.get_stage(1).unwrap().get_bypass());
+    assert_eq!(chain.get_stage(2).unwrap().get_sample_rate(), 44100);
+}
+
+#[test]
+fn ignores_comments_and_blank_lines() {
+    let text = "# exported EQ\nversion = 1\n\nband = lp:8k\n  # tail\nband=ls:100:+2dB\n";
+    let preset: Preset<f32> = text.parse().unwrap();
+    assert_eq!(preset.get_name(), "");
+    assert_eq!(preset.get_bands().len(), 2);
+    assert_eq!(preset.get_bands()[0].get_cutoff(), 8000.0);
+}
+
+#[test]
+fn rejects_unknown_versions_and_malformed_lines() {
+    assert_eq!("band = lp:8k".parse::<Preset<f64>>(), Err(PresetError::MissingVersion));
+    assert_eq!("".parse::<Preset<f64>>(), Err(PresetError::MissingVersion));
+    assert_eq!("version = 2".parse::<Preset<f64>>(), Err(PresetError::UnsupportedVersion(2)));
+    assert_eq!(
+        "version = 1\ncolor = red".parse::<Preset<f64>>(),
+        Err(PresetError::InvalidLine(2))
+    );
+    assert_eq!(
+        "version = 1\n\nband = wah:8k".parse::<Preset<f64>>(),
+        Err(PresetError::InvalidBand { line: 3, error: SpecError::UnknownFilterType })
+    );
+}
+
+#[test]
+fn bands_can_be_edited() {
+    assert!(Preset::<f64>::new("two\nlines").is_none());
+    let mut preset = Preset::new("Bright").unwrap();
+    assert!(!preset.set_name("a\rb"));
+    preset.add_band("hs:8k:+3dB".parse::<FilterSpec<f64>>().unwrap());
+    preset.add_band("lp:18k".parse().unwrap());
+    assert_eq!(preset.remove_band(1).map(|band| band.get_cutoff()), Some(18000.0));
+    assert!(preset.remove_band(1).is_none());
+    let saved = "version = 1\nname = Bright\nband = hs:8000:q=0.7071067811865476:+3dB\n";
+    assert_eq!(preset.to_string(), saved);
+}
+
+fn scene_a() -> Preset<f64> {
+    "version = 1\nband = peak:250:q=1:-6dB\nband = lp:4k".parse().unwrap()
+}
+
+fn scene_b() -> Preset<f64> {
+    "version = 1\nband = peak:1k:q=4:+6dB\nband = lp:16k".parse().unwrap()
+}
+
+#[test]
+fn lerp_blends_matching_bands() {
+    let mut a = scene_a();
+    let mut b = scene_b();
+    a.set_name("A");
+    b.set_name("B");
+    let middle = Preset::lerp(&a, &b, 0.5).unwrap();
+    assert_eq!(middle.get_name(), "B");
+    let peak = middle.get_bands()[0];
+    assert_relative_eq!(peak.get_cutoff(), 500.0, epsilon = 1e-9);
+    assert_relative_eq!(peak.get_q_factor(), 2.0, epsilon = 1e-12);
+    assert_relative_eq!(peak.get_gain(), 0.0, epsilon = 1e-12);
+    assert_relative_eq!(middle.get_bands()[1].get_cutoff(), 8000.0, epsilon = 1e-9);
+    assert_eq!(Preset::lerp(&a, &b, 0.0).unwrap(), a);
+
+    let mut shorter = b.clone();
+    shorter.remove_band(1);
+    assert!(Preset::lerp(&a, &shorter, 0.5).is_none());
+}
+
+#[test]
+fn apply_to_retunes_an_eq_in_place() {
+    let (a, b) = (scene_a(), scene_b());
+    let mut eq = a.to_parametric_eq(SAMPLE_RATE).unwrap();
+    let mut samples = vec![0.5; 64];
+    eq.process_block(&mut samples);
+
+    let middle = Preset::lerp(&a, &b, 0.5).unwrap();
+    assert!(middle.apply_to(&mut eq));
+    let expected = middle.to_parametric_eq(SAMPLE_RATE).unwrap();
+    assert_relative_eq!(
+        eq.magnitude_at(500.0, SAMPLE_RATE),
+        expected.magnitude_at(500.0, SAMPLE_RATE),
+        epsilon = 1e-12
+    );
+    // The bands kept their state, so the output carries on from the previous block.
+    assert!(eq.get_band_filter(0).unwrap().samples_processed() > 0);
+
+    let invalid: Preset<f64> = "version = 1\nband = peak:1k:q=0\nband = lp:8k".parse().unwrap();
+    assert!(!invalid.apply_to(&mut eq));
+    let mut single = Preset::new("").unwrap();
+    single.add_band(b.get_bands()[0]);
+    assert!(!single.apply_to(&mut eq));
+    assert_eq!(eq.get_band(1).unwrap().1.get_cutoff(), 8000.0);
+}
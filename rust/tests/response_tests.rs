@@ -0,0 +1,145 @@
+/// response_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use approx::assert_relative_eq;
+use biquad_filters::{
+    AllPassFilter, Cascade, Coefficients, DigitalBiquadFilter, Filter, FrequencyResponse, IirFilter,
+    LowPassFilter, PeakingEQFilter,
+};
+use std::f64::consts::{FRAC_1_SQRT_2, PI};
+
+const SAMPLE_RATE: u32 = 48000;
+
+fn cookbook_low_pass(cutoff: f64, q: f64) -> Coefficients<f64> {
+    let w = 2.0 * PI * cutoff / SAMPLE_RATE as f64;
+    let alpha = w.sin() / (2.0 * q);
+    Coefficients {
+        b0: (1.0 - w.cos()) / 2.0,
+        b1: 1.0 - w.cos(),
+        b2: (1.0 - w.cos()) / 2.0,
+        a0: 1.0 + alpha,
+        a1: -2.0 * w.cos(),
+        a2: 1.0 - alpha,
+    }
+}
+
+#[test]
+fn low_pass_magnitude_matches_design() {
+    let filter = LowPassFilter::new(1000.0, SAMPLE_RATE, FRAC_1_SQRT_2).unwrap();
+    assert_relative_eq!(filter.magnitude_at(0.0, SAMPLE_RATE), 1.0, epsilon = 1e-12);
+    assert_relative_eq!(filter.magnitude_at(1000.0, SAMPLE_RATE), FRAC_1_SQRT_2, epsilon = 1e-9);
+    assert!(filter.magnitude_at(20000.0, SAMPLE_RATE) < 1e-2);
+    assert_relative_eq!(filter.phase_at(1000.0, SAMPLE_RATE), -PI / 2.0, epsilon = 1e-9);
+}
+
+#[test]
+fn containers_can_be_used_as_trait_objects() {
+    let low_pass = LowPassFilter::new(1000.0, SAMPLE_RATE, FRAC_1_SQRT_2).unwrap();
+    let coefficients = cookbook_low_pass(1000.0, FRAC_1_SQRT_2);
+    let biquad = DigitalBiquadFilter::new(coefficients).unwrap();
+    let cascade = Cascade::<f64, 2>::new([coefficients, coefficients]).unwrap();
+    let iir = IirFilter::new(
+        &[coefficients.b0, coefficients.b1, coefficients.b2],
+        &[coefficients.a0, coefficients.a1, coefficients.a2],
+        SAMPLE_RATE,
+    )
+    .unwrap();
+    let responses: [&dyn FrequencyResponse<f64>; 5] =
+        [&coefficients, &low_pass, &biquad, &cascade, &iir];
+    let frequencies = [100.0, 1000.0, 5000.0];
+    let expected = low_pass.response_grid(&frequencies, SAMPLE_RATE);
+    for (index, response) in responses.iter().enumerate() {
+        let grid = response.response_grid(&frequencies, SAMPLE_RATE);
+        assert_eq!(grid.len(), 3);
+        for (actual, expected) in grid.iter().zip(expected.iter()) {
+            // The cascade applies the low-pass twice.
+            let expected = if index == 3 { expected * expected } else { *expected };
+            assert_relative_eq!(actual.re, expected.re, epsilon = 1e-9);
+            assert_relative_eq!(actual.im, expected.im, epsilon = 1e-9);
+        }
+    }
+}
+
+#[test]
+fn all_pass_is_flat_and_bypass_is_neutral() {
+    let mut all_pass = AllPassFilter::new(2000.0_f64, SAMPLE_RATE, 0.7).unwrap();
+    for frequency in [10.0, 500.0, 2000.0, 15000.0] {
+        assert_relative_eq!(all_pass.magnitude_at(frequency, SAMPLE_RATE), 1.0, epsilon = 1e-9);
+    }
+    assert!(all_pass.phase_at(2000.0, SAMPLE_RATE).abs() > 1.0);
+    assert!(all_pass.set_bypass(true));
+    assert_relative_eq!(all_pass.phase_at(2000.0, SAMPLE_RATE), 0.0);
+}
+
+#[test]
+fn max_gain_finds_resonant_peak() {
+    let resonant = cookbook_low_pass(1000.0, 10.0);
+    let exact = resonant.max_gain();
+    let expected = 10.0 / (1.0 - 1.0 / 400.0_f64).sqrt();
+    assert_relative_eq!(exact, expected, max_relative = 1e-3);
+
+    // The cascade is searched numerically, so it should land on the same peak.
+    let unity = Coefficients {
+        b0: 1.0,
+        b1: 0.0,
+        b2: 0.0,
+        a0: 1.0,
+        a1: 0.0,
+        a2: 0.0,
+    };
+    let cascade = Cascade::new([unity, resonant]).unwrap();
+    assert_relative_eq!(cascade.max_gain(), exact, max_relative = 1e-6);
+    assert_relative_eq!(cascade.pre_attenuation(), 1.0 / exact, max_relative = 1e-6);
+
+    let filter = LowPassFilter::new(1000.0, SAMPLE_RATE, 10.0).unwrap();
+    assert_relative_eq!(filter.max_gain(), exact, max_relative = 1e-9);
+}
+
+#[test]
+fn pre_attenuation_is_unity_without_gain() {
+    let filter = LowPassFilter::new(1000.0, SAMPLE_RATE, 0.5).unwrap();
+    assert_relative_eq!(filter.max_gain(), 1.0, max_relative = 1e-9);
+    assert_eq!(filter.pre_attenuation(), 1.0);
+    let all_pass = AllPassFilter::new(1000.0, SAMPLE_RATE, FRAC_1_SQRT_2).unwrap();
+    assert_eq!(all_pass.pre_attenuation(), 1.0);
+}
+
+#[test]
+fn magnitude_response_matches_measured_tones() {
+    let frequencies = [100.0, 1000.0, 2500.0, 8000.0];
+    let design = PeakingEQFilter::<f64>::new(1000.0, SAMPLE_RATE, 2.0, 9.0).unwrap();
+    let magnitudes = design.magnitude_response(&frequencies, SAMPLE_RATE);
+    assert_eq!(magnitudes.len(), frequencies.len());
+    assert_relative_eq!(magnitudes[1], 10.0_f64.powf(9.0 / 20.0), epsilon = 1e-9);
+
+    for (&frequency, &magnitude) in frequencies.iter().zip(magnitudes.iter()) {
+        assert_relative_eq!(magnitude, design.magnitude_at(frequency, SAMPLE_RATE));
+        let mut filter = design.clone();
+        let mut samples: Vec<f64> = (0..SAMPLE_RATE as usize)
+            .map(|n| (2.0 * PI * frequency * n as f64 / SAMPLE_RATE as f64).sin())
+            .collect();
+        filter.process_block(&mut samples);
+        let settled = &samples[SAMPLE_RATE as usize / 2..];
+        let power = settled.iter().map(|sample| sample * sample).sum::<f64>() / settled.len() as f64;
+        assert_relative_eq!((2.0 * power).sqrt(), magnitude, max_relative = 1e-3);
+    }
+}
@@ -0,0 +1,73 @@
+/// snapshot_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{Filter, PeakingEQFilter};
+use approx::assert_relative_eq;
+
+fn make_filter() -> PeakingEQFilter<f64> {
+    PeakingEQFilter::<f64>::new(
+        1000.0_f64,
+        48000_u32,
+        std::f64::consts::FRAC_1_SQRT_2,
+        3.0_f64
+    ).unwrap()
+}
+
+#[test]
+fn snapshot_matches_current_settings() {
+    let filter = make_filter();
+    let snapshot = filter.snapshot();
+    assert!(filter.diff(&snapshot).is_empty());
+}
+
+#[test]
+fn diff_reports_changed_settings() {
+    let mut filter = make_filter();
+    let snapshot = filter.snapshot();
+    filter.set_cutoff(2000.0_f64);
+    filter.set_gain(-3.0_f64);
+
+    let diff = filter.diff(&snapshot);
+    assert!(diff.cutoff);
+    assert!(diff.gain);
+    assert!(!diff.q_factor);
+    assert!(!diff.sample_rate);
+    assert_eq!(diff.count(), 2);
+    assert_eq!(diff, snapshot.diff(&filter.snapshot()));
+}
+
+#[test]
+fn restore_returns_to_snapshot() {
+    let mut filter = make_filter();
+    let a = filter.snapshot();
+    filter.set_cutoff(4000.0_f64);
+    filter.set_q_factor(2.0_f64);
+    let b = filter.snapshot();
+
+    assert!(filter.restore(&a));
+    assert_relative_eq!(filter.get_cutoff(), 1000.0_f64);
+    assert_relative_eq!(filter.get_q_factor(), std::f64::consts::FRAC_1_SQRT_2);
+    assert!(filter.diff(&a).is_empty());
+
+    assert!(filter.restore(&b));
+    assert_relative_eq!(filter.get_cutoff(), 4000.0_f64);
+}
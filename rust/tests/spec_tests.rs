@@ -0,0 +1,103 @@
+/// spec_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use approx::assert_relative_eq;
+use biquad_filters::{
+    Filter, FilterError, FilterSpec, FilterType, PeakingEQFilter, SpecError, parse_chain,
+};
+
+#[test]
+fn parses_single_specs() {
+    let spec: FilterSpec<f64> = "lp:1000:q=0.707".parse().unwrap();
+    assert_eq!(spec.get_filter_type(), FilterType::LowPass);
+    assert_eq!(spec.get_cutoff(), 1000.0);
+    assert_eq!(spec.get_q_factor(), 0.707);
+    assert_eq!(spec.get_gain(), 0.0);
+
+    let spec: FilterSpec<f64> = "peak:2500:+3dB:q=2".parse().unwrap();
+    assert_eq!(spec.get_filter_type(), FilterType::PeakingEQ);
+    assert_eq!(spec.get_gain(), 3.0);
+    assert_eq!(spec.get_q_factor(), 2.0);
+
+    let spec: FilterSpec<f32> = "HS:8kHz:-4dB".parse().unwrap();
+    assert_eq!(spec.get_filter_type(), FilterType::HighShelf);
+    assert_eq!(spec.get_cutoff(), 8000.0);
+    assert_eq!(spec.get_gain(), -4.0);
+    assert_relative_eq!(spec.get_q_factor(), core::f32::consts::FRAC_1_SQRT_2);
+
+    let spec: FilterSpec<f64> = "bp:440Hz:skirt:bypass".parse().unwrap();
+    assert!(spec.get_constant_skirt_gain());
+    assert!(spec.get_bypass());
+}
+
+#[test]
+fn rejects_malformed_specs() {
+    let parse = |spec: &str| spec.parse::<FilterSpec<f64>>().unwrap_err();
+    assert_eq!(parse(""), SpecError::Empty);
+    assert_eq!(parse("lp"), SpecError::Empty);
+    assert_eq!(parse("comb:1000"), SpecError::UnknownFilterType);
+    assert_eq!(parse("lp:fast"), SpecError::InvalidNumber);
+    assert_eq!(parse("lp:1000:q=wide"), SpecError::InvalidNumber);
+    assert_eq!(parse("lp:1000:slope=2"), SpecError::InvalidField);
+    assert_eq!(parse("lp:1000:q=1:q=2"), SpecError::InvalidField);
+}
+
+#[test]
+fn builds_the_described_filter() {
+    let spec: FilterSpec<f64> = "peak:2500:+3dB:q=2".parse().unwrap();
+    let mut filter = spec.build(48000).unwrap();
+    let mut expected = PeakingEQFilter::new(2500.0, 48000, 2.0, 3.0).unwrap();
+    assert_eq!(filter.get_configuration().get_gain(), 3.0);
+    for index in 0..64 {
+        let mut actual = (index as f64 * 0.1).sin();
+        let mut reference = actual;
+        assert!(filter.process(&mut actual));
+        assert!(expected.process(&mut reference));
+        assert_relative_eq!(actual, reference, epsilon = 1e-12);
+    }
+
+    let spec: FilterSpec<f64> = "lp:30k".parse().unwrap();
+    assert_eq!(
+        spec.build(48000).err(),
+        Some(SpecError::Filter(FilterError::CutoffAboveNyquist))
+    );
+    assert!(spec.build(96000).is_ok());
+}
+
+#[test]
+fn parses_chains_and_round_trips() {
+    let chain = parse_chain::<f32>("hp:80; peak:2500:+3dB:q=2; hs:8k:-4dB;").unwrap();
+    let types: Vec<_> = chain.iter().map(FilterSpec::get_filter_type).collect();
+    assert_eq!(
+        types,
+        [FilterType::HighPass, FilterType::PeakingEQ, FilterType::HighShelf]
+    );
+    for spec in &chain {
+        assert_eq!(spec.to_string().parse::<FilterSpec<f32>>().unwrap(), *spec);
+    }
+    let mut filters: Vec<_> = chain.iter().map(|spec| spec.build(48000).unwrap()).collect();
+    let mut sample = 1.0;
+    assert!(filters.iter_mut().all(|filter| filter.process(&mut sample)));
+
+    assert_eq!(parse_chain::<f32>(" ; ").unwrap_err(), SpecError::Empty);
+    assert_eq!(parse_chain::<f32>("lp:100;;hp:10").unwrap_err(), SpecError::Empty);
+}
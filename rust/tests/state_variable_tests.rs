@@ -0,0 +1,131 @@
+/// state_variable_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{
+    AllPassFilter, BandPassFilter, Filter, FrequencyResponse, HighPassFilter, LowPassFilter,
+    NotchFilter, StateVariableFilter, SvfMode, UpdatePolicy,
+};
+use approx::assert_relative_eq;
+
+const SAMPLE_RATE: u32 = 48000;
+
+fn input(index: usize) -> f64 {
+    (index as f64 * 0.37).sin() + 0.5 * (index as f64 * 2.1).cos()
+}
+
+fn assert_matches(mode: SvfMode, mut reference: Box<dyn Filter<f64>>, constant_skirt_gain: bool) {
+    let mut svf = StateVariableFilter::new(1200.0, SAMPLE_RATE, 2.0, mode).unwrap();
+    assert!(svf.set_constant_skirt_gain(constant_skirt_gain));
+    for index in 0..256 {
+        let mut expected = input(index);
+        let mut actual = expected;
+        assert!(reference.process(&mut expected));
+        assert!(svf.process(&mut actual));
+        assert_relative_eq!(actual, expected, epsilon = 1e-9);
+    }
+}
+
+#[test]
+fn create_valid_and_invalid_filters() {
+    assert!(StateVariableFilter::new(1000.0_f64, SAMPLE_RATE, 0.707, SvfMode::LowPass).is_some());
+    assert!(StateVariableFilter::new(1000.0_f32, SAMPLE_RATE, 0.707, SvfMode::Notch).is_some());
+    assert!(StateVariableFilter::new(0.0_f64, SAMPLE_RATE, 0.707, SvfMode::LowPass).is_none());
+    assert!(StateVariableFilter::new(24000.0_f64, SAMPLE_RATE, 0.707, SvfMode::LowPass).is_none());
+    assert!(StateVariableFilter::new(1000.0_f64, SAMPLE_RATE, 0.0, SvfMode::LowPass).is_none());
+}
+
+#[test]
+fn outputs_match_cookbook_biquads() {
+    let (cutoff, rate, q) = (1200.0, SAMPLE_RATE, 2.0);
+    assert_matches(SvfMode::LowPass, Box::new(LowPassFilter::new(cutoff, rate, q).unwrap()), false);
+    assert_matches(SvfMode::HighPass, Box::new(HighPassFilter::new(cutoff, rate, q).unwrap()), false);
+    assert_matches(SvfMode::Notch, Box::new(NotchFilter::new(cutoff, rate, q).unwrap()), false);
+    assert_matches(SvfMode::AllPass, Box::new(AllPassFilter::new(cutoff, rate, q).unwrap()), false);
+    for skirt in [false, true] {
+        let band_pass = BandPassFilter::new(cutoff, rate, q, skirt).unwrap();
+        assert_matches(SvfMode::BandPass, Box::new(band_pass), skirt);
+    }
+}
+
+#[test]
+fn process_all_returns_every_output() {
+    let mut all = StateVariableFilter::new(800.0, SAMPLE_RATE, 0.9, SvfMode::LowPass).unwrap();
+    let mut low = all.clone();
+    let mut high = all.clone();
+    high.set_mode(SvfMode::HighPass);
+    assert_eq!(high.get_mode(), SvfMode::HighPass);
+    for index in 0..64 {
+        let outputs = all.process_all(input(index));
+        let (mut low_pass, mut high_pass) = (input(index), input(index));
+        assert!(low.process(&mut low_pass));
+        assert!(high.process(&mut high_pass));
+        assert_eq!(outputs.low_pass, low_pass);
+        assert_eq!(outputs.high_pass, high_pass);
+        assert_relative_eq!(outputs.notch, outputs.low_pass + outputs.high_pass, epsilon = 1e-12);
+    }
+    assert_eq!(all.samples_processed(), 64);
+}
+
+#[test]
+fn response_matches_cookbook_biquads() {
+    let svf = StateVariableFilter::new(1200.0, SAMPLE_RATE, 2.0, SvfMode::HighPass).unwrap();
+    let biquad = HighPassFilter::new(1200.0, SAMPLE_RATE, 2.0).unwrap();
+    for frequency in [50.0, 1200.0, 5000.0, 20000.0] {
+        let expected = biquad.response_at(frequency, SAMPLE_RATE);
+        let actual = svf.response_at(frequency, SAMPLE_RATE);
+        assert_relative_eq!(actual.re, expected.re, epsilon = 1e-9);
+        assert_relative_eq!(actual.im, expected.im, epsilon = 1e-9);
+    }
+}
+
+#[test]
+fn stays_bounded_under_audio_rate_modulation() {
+    let mut svf = StateVariableFilter::new(1000.0_f32, SAMPLE_RATE, 8.0, SvfMode::BandPass).unwrap();
+    for index in 0..48000 {
+        // Sweep the cutoff between 100 Hz and 10 kHz at 200 Hz.
+        let phase = index as f32 * 200.0 / SAMPLE_RATE as f32 * core::f32::consts::TAU;
+        assert!(svf.set_cutoff(100.0 * 100.0_f32.powf(0.5 + 0.5 * phase.sin())));
+        let mut sample = if index % 2 == 0 { 1.0 } else { -1.0 };
+        assert!(svf.process(&mut sample));
+        assert!(sample.abs() < 10.0);
+    }
+    assert!(svf.get_faults().is_empty());
+}
+
+#[test]
+fn smoothed_updates_reach_the_new_settings() {
+    let mut svf = StateVariableFilter::new(1000.0, SAMPLE_RATE, 0.707, SvfMode::LowPass).unwrap();
+    let mut target = svf.clone();
+    assert!(target.set_cutoff(4000.0));
+    svf.set_update_policy(UpdatePolicy::Smoothed);
+    assert!(svf.set_cutoff(4000.0));
+    assert!(svf.has_pending_coefficients());
+    assert_eq!(svf.get_cutoff(), 4000.0);
+    let mut block = [0.0; 32];
+    assert!(svf.process_block(&mut block));
+    assert!(!svf.has_pending_coefficients());
+    assert_relative_eq!(
+        svf.magnitude_at(4000.0, SAMPLE_RATE),
+        target.magnitude_at(4000.0, SAMPLE_RATE),
+        epsilon = 1e-12
+    );
+}
@@ -0,0 +1,120 @@
+/// stereo_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{
+    ChannelOffset, Filter, FilterConfiguration, FilterType, FrequencyResponse, LowPassFilter,
+    PeakingEQFilter, StereoFilter,
+};
+use approx::assert_relative_eq;
+
+const SAMPLE_RATE: u32 = 48000;
+
+fn config(cutoff: f64, gain: f64) -> FilterConfiguration<f64> {
+    FilterConfiguration::builder()
+        .cutoff(cutoff)
+        .sample_rate(SAMPLE_RATE)
+        .q_factor(1.0)
+        .gain(gain)
+        .build()
+        .unwrap()
+}
+
+fn channel(index: usize) -> Vec<f64> {
+    (0..128).map(|n| ((n * (index + 1)) as f64 * 0.11).sin()).collect()
+}
+
+#[test]
+fn channels_match_separate_filters() {
+    let mut stereo = StereoFilter::new(FilterType::LowPass, config(2000.0, 0.0)).unwrap();
+    let mut interleaved: Vec<f64> =
+        (0..128).flat_map(|n| [channel(0)[n], channel(1)[n]]).collect();
+    assert!(stereo.process_interleaved(&mut interleaved));
+    for index in 0..2 {
+        let mut single = LowPassFilter::new(2000.0, SAMPLE_RATE, 1.0).unwrap();
+        let mut expected = channel(index);
+        single.process_block(&mut expected);
+        for (n, expected) in expected.iter().enumerate() {
+            assert_relative_eq!(interleaved[2 * n + index], *expected, epsilon = 1e-12);
+        }
+    }
+}
+
+#[test]
+fn frame_interleaved_and_planar_processing_agree() {
+    let stereo = StereoFilter::new(FilterType::HighPass, config(300.0, 0.0)).unwrap();
+    let (mut by_frame, mut interleaved, mut planar) = (stereo.clone(), stereo.clone(), stereo);
+    let (mut left, mut right) = (channel(0), channel(1));
+    let mut frames: Vec<f64> = left.iter().zip(&right).flat_map(|(l, r)| [*l, *r]).collect();
+    assert!(planar.process_planar(&mut left, &mut right));
+    assert!(interleaved.process_interleaved(&mut frames));
+    for n in 0..128 {
+        let mut frame = (channel(0)[n], channel(1)[n]);
+        assert!(by_frame.process(&mut frame));
+        assert_eq!(frame, (left[n], right[n]));
+        assert_eq!((frames[2 * n], frames[2 * n + 1]), (left[n], right[n]));
+    }
+}
+
+#[test]
+fn offsets_follow_the_shared_configuration() {
+    let mut stereo = StereoFilter::new(FilterType::PeakingEQ, config(1000.0, 6.0)).unwrap();
+    let right = ChannelOffset { cutoff: 1.0, gain: -2.0 };
+    assert!(stereo.set_offsets(ChannelOffset::default(), right));
+    assert!(stereo.update(|config| config.set_cutoff(500.0)));
+    let (left_config, right_config) = stereo.get_channel_configurations();
+    assert_eq!((left_config.get_cutoff(), left_config.get_gain()), (500.0, 6.0));
+    assert_eq!((right_config.get_cutoff(), right_config.get_gain()), (1000.0, 4.0));
+    let expected = PeakingEQFilter::new(1000.0, SAMPLE_RATE, 1.0, 4.0).unwrap();
+    assert_relative_eq!(
+        stereo.get_coefficients().1.magnitude_at(1000.0, SAMPLE_RATE),
+        expected.magnitude_at(1000.0, SAMPLE_RATE),
+        epsilon = 1e-12
+    );
+
+    let invalid = ChannelOffset { cutoff: f64::NAN, gain: 0.0 };
+    assert!(!stereo.set_offsets(invalid, right));
+    assert_eq!(stereo.get_offsets(), (ChannelOffset::default(), right));
+    assert!(!stereo.update(|config| config.set_q_factor(0.0)));
+    assert_eq!(stereo.get_configuration().get_cutoff(), 500.0);
+}
+
+#[test]
+fn rejects_malformed_blocks_and_honours_bypass() {
+    let mut stereo = StereoFilter::new(FilterType::LowPass, config(2000.0, 0.0)).unwrap();
+    let mut samples = vec![1.0; 5];
+    assert!(!stereo.process_interleaved(&mut samples));
+    assert!(!stereo.process_planar(&mut samples[..2], &mut [1.0; 3]));
+    assert!(samples.iter().all(|sample| *sample == 1.0));
+
+    assert!(stereo.update(|config| config.set_bypass(true)));
+    let mut frame = (0.25, -0.5);
+    assert!(stereo.process(&mut frame));
+    assert_eq!(frame, (0.25, -0.5));
+
+    assert!(stereo.update(|config| config.set_bypass(false)));
+    let mut first = (1.0, 1.0);
+    stereo.process(&mut first);
+    stereo.reset();
+    let mut second = (1.0, 1.0);
+    stereo.process(&mut second);
+    assert_eq!(first, second);
+}
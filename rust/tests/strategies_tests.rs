@@ -0,0 +1,72 @@
+#![cfg(feature = "proptest")]
+/// strategies_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::strategies::{configuration, filter_type, samples, stable_coefficients};
+use biquad_filters::{
+    DigitalBiquadFilter, Filter, FilterType, HighShelfFilter, LowPassFilter, PeakingEQFilter,
+};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn generated_configurations_are_valid(configuration in configuration::<f64>(), kind in filter_type()) {
+        prop_assert!(configuration.is_valid());
+        let cutoff = configuration.get_cutoff();
+        let rate = configuration.get_sample_rate();
+        let q = configuration.get_q_factor();
+        let gain = configuration.get_gain();
+        let created = match kind {
+            FilterType::PeakingEQ => PeakingEQFilter::new(cutoff, rate, q, gain).is_some(),
+            FilterType::HighShelf => HighShelfFilter::new(cutoff, rate, q, gain).is_some(),
+            _ => LowPassFilter::new(cutoff, rate, q).is_some(),
+        };
+        prop_assert!(created);
+    }
+
+    #[test]
+    fn f32_tracks_f64(coefficients in stable_coefficients::<f64>(), input in samples::<f64>(1..64)) {
+        let mut wide = DigitalBiquadFilter::new(coefficients).unwrap();
+        let mut narrow = wide.to_f32();
+        for sample in input {
+            let mut expected = sample;
+            let mut actual = sample as f32;
+            wide.process(&mut expected);
+            narrow.process(&mut actual);
+            prop_assert!((expected - actual as f64).abs() < 1e-2 * (1.0 + expected.abs()));
+        }
+    }
+
+    #[test]
+    fn batched_and_individual_setters_agree(configuration in configuration::<f64>()) {
+        let mut batched = PeakingEQFilter::new(1000.0, configuration.get_sample_rate(), 1.0, 0.0).unwrap();
+        let mut individual = batched.clone();
+        prop_assert!(batched.set_configuration(configuration));
+        prop_assert!(individual.set_cutoff(configuration.get_cutoff()));
+        prop_assert!(individual.set_q_factor(configuration.get_q_factor()));
+        prop_assert!(individual.set_gain(configuration.get_gain()));
+        let (mut a, mut b) = (1.0_f64, 1.0_f64);
+        batched.process(&mut a);
+        individual.process(&mut b);
+        prop_assert!((a - b).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,124 @@
+#![cfg(feature = "std")]
+/// stream_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::stream::{FilteredReader, FilteredWriter, Interleaving, SampleFormat};
+use biquad_filters::{Filter, LowPassFilter};
+use std::io::{ErrorKind, Read, Write};
+
+fn low_pass(cutoff: f32) -> LowPassFilter<f32> {
+    LowPassFilter::new(cutoff, 48000, std::f32::consts::FRAC_1_SQRT_2).unwrap()
+}
+
+fn channel(seed: usize, len: usize) -> Vec<i16> {
+    (0..len).map(|n| (((n * 37 + seed * 11) % 101) as i16 - 50) * 300).collect()
+}
+
+fn expected(cutoff: f32, input: &[i16]) -> Vec<i16> {
+    let mut samples = input.to_vec();
+    low_pass(cutoff).process_samples(&mut samples);
+    samples
+}
+
+#[test]
+fn reader_filters_interleaved_channels() {
+    let left = channel(1, 500);
+    let right = channel(2, 500);
+    let bytes: Vec<u8> = left
+        .iter()
+        .zip(right.iter())
+        .flat_map(|(l, r)| [l.to_le_bytes(), r.to_le_bytes()])
+        .flatten()
+        .collect();
+    let filters = vec![low_pass(1000.0), low_pass(5000.0)];
+    let mut reader =
+        FilteredReader::new(bytes.as_slice(), SampleFormat::I16, Interleaving::Interleaved, filters)
+            .unwrap();
+    let mut output = Vec::new();
+    reader.read_to_end(&mut output).unwrap();
+    assert_eq!(output.len(), bytes.len());
+    let samples: Vec<i16> = output
+        .chunks_exact(2)
+        .map(|bytes| i16::from_le_bytes([bytes[0], bytes[1]]))
+        .collect();
+    let left_out: Vec<i16> = samples.iter().step_by(2).copied().collect();
+    let right_out: Vec<i16> = samples.iter().skip(1).step_by(2).copied().collect();
+    assert_eq!(left_out, expected(1000.0, &left));
+    assert_eq!(right_out, expected(5000.0, &right));
+}
+
+#[test]
+fn writer_filters_planar_blocks() {
+    let left: Vec<f32> = (0..10).map(|n| (n as f32 * 0.7).sin() * 0.5).collect();
+    let right: Vec<f32> = (0..10).map(|n| (n as f32 * 0.3).cos() * 0.5).collect();
+    // Blocks of four frames, with a final block of two.
+    let mut bytes = Vec::new();
+    for block in 0..3 {
+        let frames = (block * 4)..((block * 4 + 4).min(10));
+        for sample in &left[frames.clone()] {
+            bytes.extend_from_slice(&sample.to_le_bytes());
+        }
+        for sample in &right[frames] {
+            bytes.extend_from_slice(&sample.to_le_bytes());
+        }
+    }
+    let filters = vec![low_pass(2000.0), low_pass(2000.0)];
+    let planar = Interleaving::Planar { block_frames: 4 };
+    let mut writer = FilteredWriter::new(Vec::new(), SampleFormat::F32, planar, filters).unwrap();
+    // Write in uneven pieces so frames and blocks arrive split.
+    for piece in bytes.chunks(7) {
+        writer.write_all(piece).unwrap();
+    }
+    let output = writer.finish().unwrap();
+    assert_eq!(output.len(), bytes.len());
+    let samples: Vec<f32> = output
+        .chunks_exact(4)
+        .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .collect();
+
+    let mut filter = low_pass(2000.0);
+    let mut expected_left = left.clone();
+    filter.process_block(&mut expected_left);
+    assert_eq!(&samples[0..4], &expected_left[0..4]);
+    assert_eq!(&samples[8..12], &expected_left[4..8]);
+    assert_eq!(&samples[16..18], &expected_left[8..10]);
+}
+
+#[test]
+fn partial_frame_is_an_error() {
+    let bytes = [0_u8; 6];
+    let filters = vec![low_pass(1000.0), low_pass(1000.0)];
+    let mut reader =
+        FilteredReader::new(&bytes[..], SampleFormat::F32, Interleaving::Interleaved, filters)
+            .unwrap();
+    let mut output = Vec::new();
+    let error = reader.read_to_end(&mut output).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn invalid_layouts_are_rejected() {
+    let filters: Vec<LowPassFilter<f32>> = Vec::new();
+    assert!(FilteredReader::new(&[][..], SampleFormat::F32, Interleaving::Interleaved, filters).is_none());
+    let planar = Interleaving::Planar { block_frames: 0 };
+    assert!(FilteredWriter::new(Vec::new(), SampleFormat::I16, planar, vec![low_pass(1000.0)]).is_none());
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "symphonia")]
+/// symphonia_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::symphonia::{filter_buffer, filter_buffer_ref};
+use biquad_filters::{Filter, LowPassFilter};
+use symphonia_core::audio::{AudioBuffer, AudioBufferRef, Channels, Signal, SignalSpec};
+use std::borrow::Cow;
+
+fn low_pass(cutoff: f64) -> LowPassFilter<f64> {
+    LowPassFilter::new(cutoff, 48000, std::f64::consts::FRAC_1_SQRT_2).unwrap()
+}
+
+fn stereo<S: symphonia_core::sample::Sample>(frames: usize) -> AudioBuffer<S> {
+    let spec = SignalSpec::new(48000, Channels::FRONT_LEFT | Channels::FRONT_RIGHT);
+    let mut buffer = AudioBuffer::new(frames as u64, spec);
+    buffer.render_reserved(Some(frames));
+    buffer
+}
+
+#[test]
+fn filters_each_channel_of_a_float_buffer() {
+    let mut buffer = stereo::<f32>(256);
+    for (n, sample) in buffer.chan_mut(0).iter_mut().enumerate() {
+        *sample = if n % 2 == 0 { 0.5 } else { -0.5 };
+    }
+    buffer.chan_mut(1).fill(0.25);
+    let mut filters = [low_pass(1000.0), low_pass(1000.0)];
+    assert!(filter_buffer(&mut buffer, &mut filters));
+    // The Nyquist-rate signal is removed and the DC level passes.
+    assert!(buffer.chan(0)[255].abs() < 0.01);
+    assert!((buffer.chan(1)[255] - 0.25).abs() < 0.01);
+}
+
+#[test]
+fn filters_integer_buffer_ref() {
+    let mut buffer = stereo::<i16>(64);
+    buffer.chan_mut(0).fill(8000);
+    buffer.chan_mut(1).fill(-8000);
+    let mut decoded = AudioBufferRef::S16(Cow::Owned(buffer));
+    let mut filters = [low_pass(5000.0), low_pass(5000.0)];
+    assert!(filter_buffer_ref(&mut decoded, &mut filters));
+    let AudioBufferRef::S16(buffer) = decoded else {
+        panic!("sample format changed");
+    };
+    assert!(buffer.chan(0)[0] < 8000);
+    assert!((buffer.chan(1)[63] + 8000).abs() < 100);
+}
+
+#[test]
+fn channel_count_must_match() {
+    let mut buffer = stereo::<f32>(16);
+    let mut filters = [low_pass(1000.0)];
+    assert!(!filter_buffer(&mut buffer, &mut filters));
+    assert_eq!(filters[0].samples_processed(), 0);
+}
@@ -0,0 +1,104 @@
+/// thiran_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use approx::assert_relative_eq;
+use biquad_filters::{Coefficients, FrequencyResponse, ThiranDelay};
+use std::f64::consts::PI;
+
+fn delayed_sine(delay: f64, frequency: f64) -> (Vec<f64>, Vec<f64>) {
+    let sine = |n: f64| (2.0 * PI * frequency * n / 48000.0).sin();
+    let mut thiran = ThiranDelay::new(delay).unwrap();
+    let mut samples: Vec<f64> = (0..4800).map(|n| sine(n as f64)).collect();
+    assert!(thiran.process_block(&mut samples));
+    let expected = (0..4800).map(|n| sine(n as f64 - delay)).collect();
+    (samples, expected)
+}
+
+#[test]
+fn low_frequencies_are_delayed_by_fraction() {
+    for delay in [0.0, 0.3, 1.0, 1.7, 2.4, 5.25, 12.9] {
+        let (samples, expected) = delayed_sine(delay, 200.0);
+        for (sample, expected) in samples.iter().zip(expected.iter()).skip(1000) {
+            assert!((sample - expected).abs() < 1e-3, "delay {delay}");
+        }
+    }
+}
+
+#[test]
+fn integer_delays_are_exact() {
+    let mut thiran = ThiranDelay::new(4.0).unwrap();
+    let mut impulse = [0.0_f64; 8];
+    impulse[0] = 1.0;
+    thiran.process_block(&mut impulse);
+    assert_relative_eq!(impulse[4], 1.0, epsilon = 1e-12);
+    assert_relative_eq!(impulse.iter().map(|s: &f64| s.abs()).sum::<f64>(), 1.0, epsilon = 1e-12);
+}
+
+#[test]
+fn set_delay_validates_and_resets() {
+    let mut thiran = ThiranDelay::new(3.5).unwrap();
+    assert!(ThiranDelay::new(-1.0).is_none());
+    assert!(!thiran.set_delay(f64::NAN));
+    assert_relative_eq!(thiran.get_delay(), 3.5);
+    assert!(thiran.set_delay(0.5));
+    assert_relative_eq!(thiran.get_delay(), 0.5);
+}
+
+#[test]
+fn group_delay_of_coefficients() {
+    let delay = Coefficients {
+        b0: 0.0,
+        b1: 1.0,
+        b2: 0.0,
+        a0: 1.0,
+        a1: 0.0,
+        a2: 0.0,
+    };
+    assert_relative_eq!(delay.group_delay(1000.0, 48000), 1.0, epsilon = 1e-12);
+    let one_pole = Coefficients {
+        b0: 0.5,
+        b1: 0.0,
+        b2: 0.0,
+        a0: 1.0,
+        a1: -0.5,
+        a2: 0.0,
+    };
+    // At DC the delay of 1 / (1 - p z^-1) is p / (1 - p).
+    assert_relative_eq!(one_pole.group_delay(0.0, 48000), 1.0, epsilon = 1e-12);
+}
+
+#[test]
+fn response_is_all_pass_with_the_requested_delay() {
+    let mut thiran = ThiranDelay::new(6.3).unwrap();
+    for delay in [6.3_f64, 0.7, 2.0] {
+        assert!(thiran.set_delay(delay));
+        assert_relative_eq!(thiran.group_delay(0.0, 48000), delay, epsilon = 1e-9);
+        assert!((thiran.group_delay(2000.0, 48000) - delay).abs() < 1e-2);
+        for frequency in [100.0, 5000.0, 20000.0] {
+            assert_relative_eq!(thiran.magnitude_at(frequency, 48000), 1.0, epsilon = 1e-12);
+        }
+        // Below a few kilohertz the phase is that of a pure delay.
+        let expected = -2.0 * PI * 500.0 / 48000.0 * delay;
+        let phase = thiran.response_at(500.0, 48000) / num_complex::Complex::from_polar(1.0, expected);
+        assert!(phase.arg().abs() < 1e-4);
+    }
+}
@@ -0,0 +1,71 @@
+/// tilt_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{Filter, FilterSpec, FilterType, FrequencyResponse, TiltFilter};
+use approx::assert_relative_eq;
+
+fn decibels(filter: &TiltFilter<f64>, frequency: f64) -> f64 {
+    20.0 * filter.magnitude_at(frequency, 48000).log10()
+}
+
+#[test]
+fn create_valid_filters() {
+    assert!(TiltFilter::new(1000.0_f64, 48000, 6.0).is_some());
+    assert!(TiltFilter::new(1000.0_f32, 48000, -6.0).is_some());
+    assert!(TiltFilter::new(0.0_f64, 48000, 6.0).is_none());
+    assert!(TiltFilter::new(1000.0_f64, 0, 6.0).is_none());
+}
+
+#[test]
+fn tilts_around_the_pivot() {
+    let filter = TiltFilter::new(1000.0_f64, 48000, 6.0).unwrap();
+    assert_relative_eq!(decibels(&filter, 0.0), -6.0, epsilon = 1e-9);
+    assert_relative_eq!(decibels(&filter, 1000.0), 0.0, epsilon = 1e-9);
+    assert_relative_eq!(decibels(&filter, 24000.0), 6.0, epsilon = 1e-9);
+    assert!(decibels(&filter, 500.0) < 0.0 && decibels(&filter, 2000.0) > 0.0);
+
+    let filter = TiltFilter::new(1000.0_f64, 48000, -4.0).unwrap();
+    assert_relative_eq!(decibels(&filter, 0.0), 4.0, epsilon = 1e-9);
+    assert_relative_eq!(decibels(&filter, 24000.0), -4.0, epsilon = 1e-9);
+}
+
+#[test]
+fn pivot_and_tilt_use_the_configuration() {
+    let mut filter = TiltFilter::new(1000.0_f64, 48000, 0.0).unwrap();
+    assert_relative_eq!(decibels(&filter, 100.0), 0.0, epsilon = 1e-9);
+    assert!(filter.set_gain(3.0));
+    assert!(filter.set_cutoff(300.0));
+    assert_eq!(filter.get_configuration().get_cutoff(), 300.0);
+    assert_relative_eq!(decibels(&filter, 300.0), 0.0, epsilon = 1e-9);
+    assert_relative_eq!(decibels(&filter, 0.0), -3.0, epsilon = 1e-9);
+
+    let spec: FilterSpec<f64> = "tilt:300:+3dB".parse().unwrap();
+    assert_eq!(spec.get_filter_type(), FilterType::Tilt);
+    let mut built = spec.build(48000).unwrap();
+    for index in 0..32 {
+        let mut expected = (index as f64 * 0.2).sin();
+        let mut actual = expected;
+        assert!(filter.process(&mut expected));
+        assert!(built.process(&mut actual));
+        assert_relative_eq!(actual, expected, epsilon = 1e-12);
+    }
+}
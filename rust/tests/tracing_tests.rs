@@ -0,0 +1,92 @@
+#![cfg(feature = "tracing")]
+/// tracing_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{Filter, FilterExt, LowPassFilter};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+/// Counts debug and warning events.
+#[derive(Default)]
+struct Counter {
+    debug: AtomicUsize,
+    warn: AtomicUsize,
+}
+
+struct CountingSubscriber(Arc<Counter>);
+
+impl Subscriber for CountingSubscriber {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        match *event.metadata().level() {
+            Level::DEBUG => self.0.debug.fetch_add(1, Ordering::Relaxed),
+            Level::WARN => self.0.warn.fetch_add(1, Ordering::Relaxed),
+            _ => 0,
+        };
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn test_parameter_changes_emit_events() {
+    let counter = Arc::new(Counter::default());
+    let subscriber = CountingSubscriber(counter.clone());
+    tracing::subscriber::with_default(subscriber, || {
+        let mut filter = LowPassFilter::<f64>::new(1000.0_f64, 48000_u32, 0.707_f64).unwrap();
+        assert!(filter.set_cutoff(2000.0_f64));
+        assert!(filter.set_q_factor(1.0_f64));
+        assert!(!filter.set_cutoff(-1.0_f64));
+    });
+    assert_eq!(counter.debug.load(Ordering::Relaxed), 2);
+    assert_eq!(counter.warn.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn test_batched_changes_recompute_once() {
+    let counter = Arc::new(Counter::default());
+    let subscriber = CountingSubscriber(counter.clone());
+    tracing::subscriber::with_default(subscriber, || {
+        let mut filter = LowPassFilter::<f64>::new(1000.0_f64, 48000_u32, 0.707_f64).unwrap();
+        assert!(filter.set_parameters(2000.0_f64, 1.0_f64, 0.0_f64));
+        assert!(filter.update(|config| {
+            config.set_cutoff(3000.0_f64);
+            config.set_q_factor(2.0_f64);
+        }));
+    });
+    assert_eq!(counter.debug.load(Ordering::Relaxed), 2);
+}
@@ -0,0 +1,141 @@
+/// update_policy_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use approx::assert_relative_eq;
+use biquad_filters::{Coefficients, DigitalBiquadFilter, Filter, IirFilter, LowPassFilter, UpdatePolicy};
+
+fn gain(value: f64) -> Coefficients<f64> {
+    Coefficients {
+        b0: value,
+        b1: 0.0,
+        b2: 0.0,
+        a0: 1.0,
+        a1: -0.5,
+        a2: 0.0,
+    }
+}
+
+#[test]
+fn next_block_holds_coefficients_until_processing() {
+    let mut filter = DigitalBiquadFilter::new(gain(1.0)).unwrap();
+    filter.set_update_policy(UpdatePolicy::NextBlock);
+    assert_eq!(filter.get_update_policy(), UpdatePolicy::NextBlock);
+    assert!(filter.update_coefficients(gain(2.0)));
+    assert!(filter.update_coefficients(gain(3.0)));
+    assert!(filter.has_pending_coefficients());
+    assert_relative_eq!(filter.get_coefficients().b0, 1.0);
+
+    let mut samples = [1.0, 0.0];
+    assert!(filter.process_block(&mut samples));
+    assert!(!filter.has_pending_coefficients());
+    assert_relative_eq!(filter.get_coefficients().b0, 3.0);
+    assert_relative_eq!(samples[0], 3.0);
+    assert_relative_eq!(samples[1], 1.5);
+}
+
+#[test]
+fn deferred_reset_applies_with_the_coefficients() {
+    let mut filter = DigitalBiquadFilter::new(gain(1.0)).unwrap();
+    filter.set_update_policy(UpdatePolicy::NextBlock);
+    let mut sample = 1.0;
+    filter.process(&mut sample);
+    assert!(filter.set_coefficients(gain(1.0)));
+    assert!(filter.update_coefficients(gain(1.0)));
+    assert_eq!(filter.samples_processed(), 1);
+    let mut sample = 0.0;
+    filter.process(&mut sample);
+    // The state was cleared before the block, so the earlier impulse no longer rings.
+    assert_relative_eq!(sample, 0.0);
+    assert!(!filter.update_coefficients(Coefficients { a0: 0.0, ..gain(1.0) }));
+}
+
+#[test]
+fn switching_to_immediate_applies_pending_coefficients() {
+    let mut filter = DigitalBiquadFilter::new(gain(1.0)).unwrap();
+    filter.set_update_policy(UpdatePolicy::NextBlock);
+    assert!(filter.update_coefficients(gain(2.0)));
+    filter.set_update_policy(UpdatePolicy::Immediate);
+    assert!(!filter.has_pending_coefficients());
+    assert_relative_eq!(filter.get_coefficients().b0, 2.0);
+}
+
+#[test]
+fn wrapped_filters_render_the_same_blocks() {
+    let mut immediate = LowPassFilter::new(1000.0, 48000, 0.707).unwrap();
+    let mut deferred = immediate.clone();
+    deferred.set_update_policy(UpdatePolicy::NextBlock);
+    let input: Vec<f64> = (0..512).map(|n| ((n * 3) % 7) as f64 - 3.0).collect();
+    for (index, block) in input.chunks(64).enumerate() {
+        let cutoff = 500.0 + 200.0 * index as f64;
+        assert!(immediate.set_cutoff(cutoff));
+        assert!(deferred.set_cutoff(cutoff));
+        let mut expected = block.to_vec();
+        let mut samples = block.to_vec();
+        immediate.process_block(&mut expected);
+        deferred.process_block(&mut samples);
+        assert_eq!(samples, expected);
+    }
+}
+
+#[test]
+fn iir_filter_defers_transfer_function() {
+    let mut iir = IirFilter::new(&[1.0], &[1.0], 48000).unwrap();
+    iir.set_update_policy(UpdatePolicy::NextBlock);
+    assert!(iir.set_coefficients(&[0.5, 0.5], &[1.0]));
+    assert!(iir.has_pending_coefficients());
+    assert_eq!(iir.get_order(), 0);
+    let mut samples = [1.0, 0.0];
+    assert!(Filter::process_block(&mut iir, &mut samples));
+    assert_eq!(iir.get_order(), 1);
+    assert_eq!(samples, [0.5, 0.5]);
+}
+
+#[test]
+fn smoothed_policy_ramps_across_the_block() {
+    let mut filter = DigitalBiquadFilter::new(gain(1.0)).unwrap();
+    filter.set_update_policy(UpdatePolicy::Smoothed);
+    assert!(filter.update_coefficients(gain(5.0)));
+    let mut samples = [1.0, 0.0, 0.0, 0.0];
+    let mut expected = samples;
+    let mut reference = DigitalBiquadFilter::new(gain(1.0)).unwrap();
+    for (n, sample) in expected.iter_mut().enumerate() {
+        assert!(reference.update_coefficients(gain(1.0 + (n + 1) as f64)));
+        reference.process(sample);
+    }
+    assert!(filter.process_block(&mut samples));
+    for (sample, expected) in samples.iter().zip(expected.iter()) {
+        assert_relative_eq!(*sample, *expected, epsilon = 1e-12);
+    }
+    assert!(!filter.has_pending_coefficients());
+    assert_relative_eq!(filter.get_coefficients().b0, 5.0);
+}
+
+#[test]
+fn smoothed_policy_switches_on_reset() {
+    let mut filter = DigitalBiquadFilter::new(gain(1.0)).unwrap();
+    filter.set_update_policy(UpdatePolicy::Smoothed);
+    assert!(filter.set_coefficients(gain(4.0)));
+    let mut samples = [1.0, 0.0];
+    assert!(filter.process_block(&mut samples));
+    assert_relative_eq!(samples[0], 4.0);
+    assert_relative_eq!(samples[1], 2.0);
+}
@@ -0,0 +1,116 @@
+/// weighting_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use biquad_filters::{CWeightingFilter, FrequencyResponse, KWeightingFilter};
+use approx::assert_relative_eq;
+
+/// C-weighting values in decibels from IEC 61672-1, by the exponent of the exact frequency
+/// `1000 * 10^(n / 10)` Hz behind each nominal frequency.
+const C_WEIGHTING: [(i32, f64); 13] = [
+    (-20, -14.3), // 10 Hz
+    (-17, -6.2),  // 20 Hz
+    (-15, -3.0),  // 31.5 Hz
+    (-12, -0.8),  // 63 Hz
+    (-9, -0.2),   // 125 Hz
+    (-6, 0.0),    // 250 Hz
+    (-3, 0.0),    // 500 Hz
+    (0, 0.0),     // 1 kHz
+    (3, -0.2),    // 2 kHz
+    (6, -0.8),    // 4 kHz
+    (9, -3.0),    // 8 kHz
+    (10, -4.4),   // 10 kHz
+    (12, -8.5),   // 16 kHz
+];
+
+fn exact_frequency(exponent: i32) -> f64 {
+    1000.0 * 10.0_f64.powf(exponent as f64 / 10.0)
+}
+
+fn decibels(filter: &CWeightingFilter<f64>, frequency: f64) -> f64 {
+    20.0 * filter.magnitude_at(frequency, filter.get_sample_rate()).log10()
+}
+
+/// The IEC 61672-1 class 1 tolerances in decibels above 4 kHz, as (exponent, upper, lower).
+const CLASS_1_TOLERANCES: [(i32, f64, f64); 3] = [(9, 1.5, -2.5), (10, 2.0, -3.0), (12, 3.5, -16.0)];
+
+#[test]
+fn c_weighting_follows_the_standard_curve() {
+    for rate in [44100, 48000, 96000] {
+        let filter = CWeightingFilter::new(rate).unwrap();
+        assert_relative_eq!(decibels(&filter, 1000.0), 0.0, epsilon = 1e-9);
+        for (exponent, expected) in C_WEIGHTING.iter().filter(|(exponent, _)| *exponent <= 6) {
+            let frequency = exact_frequency(*exponent);
+            let actual = decibels(&filter, frequency);
+            assert!((actual - expected).abs() <= 0.1, "{frequency} Hz at {rate} Hz: {actual} dB");
+        }
+        for (exponent, upper, lower) in CLASS_1_TOLERANCES {
+            let frequency = exact_frequency(exponent);
+            let expected = C_WEIGHTING.iter().find(|(e, _)| *e == exponent).unwrap().1;
+            let error = decibels(&filter, frequency) - expected;
+            assert!(error <= upper && error >= lower, "{frequency} Hz at {rate} Hz: {error} dB off");
+        }
+    }
+}
+
+#[test]
+fn c_weighting_processes_and_rejects_low_rates() {
+    assert!(CWeightingFilter::<f64>::new(2000).is_none());
+    let mut filter = CWeightingFilter::<f32>::new(44100).unwrap();
+    assert_eq!(filter.get_sample_rate(), 44100);
+    assert_eq!(filter.get_sections().len(), 2);
+    // A DC input decays to nothing.
+    let mut block = [1.0; 44100];
+    assert!(filter.process_block(&mut block));
+    assert!(block[44099].abs() < 1e-3);
+    filter.reset();
+}
+
+#[test]
+fn k_weighting_reproduces_the_published_coefficients() {
+    let filter = KWeightingFilter::<f64>::new(48000).unwrap();
+    let [shelf, high_pass] = filter.get_sections().each_ref().map(|section| section.get_coefficients());
+    let expected_shelf = [1.53512485958697, -2.69169618940638, 1.19839281085285, -1.69065929318241, 0.73248077421585];
+    let expected_high_pass = [1.0, -2.0, 1.0, -1.99004745483398, 0.99007225036621];
+    for (actual, expected) in [(shelf, expected_shelf), (high_pass, expected_high_pass)] {
+        let actual = [actual.b0, actual.b1, actual.b2, actual.a1, actual.a2];
+        for (actual, expected) in actual.iter().zip(expected) {
+            assert_relative_eq!(*actual, expected, epsilon = 1e-8);
+        }
+    }
+}
+
+#[test]
+fn k_weighting_response_is_independent_of_the_sample_rate() {
+    let reference = KWeightingFilter::<f64>::new(48000).unwrap();
+    for rate in [44100, 88200, 96000, 192000] {
+        let filter = KWeightingFilter::<f64>::new(rate).unwrap();
+        for frequency in [20.0, 38.0, 100.0, 1000.0, 1682.0, 4000.0] {
+            let expected = 20.0 * reference.magnitude_at(frequency, 48000).log10();
+            let actual = 20.0 * filter.magnitude_at(frequency, rate).log10();
+            assert!((actual - expected).abs() < 0.05, "{frequency} Hz at {rate} Hz");
+        }
+    }
+    // The shelf adds about 4 dB at high frequencies.
+    let high = 20.0 * reference.magnitude_at(10000.0, 48000).log10();
+    assert!((high - 4.0).abs() < 0.1);
+    assert!(KWeightingFilter::<f32>::new(3000).is_none());
+}
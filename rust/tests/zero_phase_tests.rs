@@ -0,0 +1,140 @@
+/// zero_phase_tests.rs
+/**
+Copyright © 2025 Alex Parisi
+
+Permission is hereby granted, free of charge, to any person obtaining a copy of
+this software and associated documentation files (the "Software"), to deal in
+the Software without restriction, including without limitation the rights to
+use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies
+of the Software, and to permit persons to whom the Software is furnished to do
+so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+*/
+use approx::assert_relative_eq;
+use biquad_filters::{Coefficients, DigitalBiquadFilter, filtfilt};
+use std::f64::consts::PI;
+
+fn low_pass(cutoff: f64) -> Coefficients<f64> {
+    // Cookbook low-pass at 48 kHz with Q = 0.707.
+    let w = 2.0 * PI * cutoff / 48000.0;
+    let alpha = w.sin() / (2.0 * 0.707);
+    Coefficients {
+        b0: (1.0 - w.cos()) / 2.0,
+        b1: 1.0 - w.cos(),
+        b2: (1.0 - w.cos()) / 2.0,
+        a0: 1.0 + alpha,
+        a1: -2.0 * w.cos(),
+        a2: 1.0 - alpha,
+    }
+}
+
+fn sine(frequency: f64, len: usize) -> Vec<f64> {
+    (0..len).map(|n| (2.0 * PI * frequency * n as f64 / 48000.0).sin()).collect()
+}
+
+#[test]
+fn constant_signal_has_no_edge_transients() {
+    let mut samples = vec![0.75; 200];
+    assert!(filtfilt(&[low_pass(1000.0)], &mut samples));
+    for sample in samples {
+        assert_relative_eq!(sample, 0.75, epsilon = 1e-9);
+    }
+}
+
+#[test]
+fn passband_sine_has_no_phase_shift() {
+    let input = sine(200.0, 4800);
+    let mut samples = input.clone();
+    assert!(filtfilt(&[low_pass(4000.0), low_pass(4000.0)], &mut samples));
+    // The sine is far below the cutoff, so it passes almost unchanged and in phase.
+    for (sample, input) in samples.iter().zip(input.iter()).skip(100).take(4600) {
+        assert!((sample - input).abs() < 1e-3);
+    }
+}
+
+#[test]
+fn magnitude_is_squared() {
+    let coefficients = low_pass(1000.0);
+    let mut samples = sine(1000.0, 48000);
+    assert!(filtfilt(&[coefficients], &mut samples));
+    let peak = samples[12000..36000].iter().fold(0.0_f64, |peak, s| peak.max(s.abs()));
+    // A Q of 0.707 puts the cutoff 3 dB down, so two passes give half amplitude.
+    assert_relative_eq!(peak, 0.5, epsilon = 1e-2);
+}
+
+#[test]
+fn result_is_symmetric_in_time_away_from_the_edges() {
+    let input: Vec<f64> = (0..300).map(|n| ((n * 7) % 11) as f64 - 5.0).collect();
+    let mut forward = input.clone();
+    let mut reversed: Vec<f64> = input.iter().rev().copied().collect();
+    assert!(filtfilt(&[low_pass(3000.0)], &mut forward));
+    assert!(filtfilt(&[low_pass(3000.0)], &mut reversed));
+    for (a, b) in forward.iter().zip(reversed.iter().rev()).skip(50).take(200) {
+        assert_relative_eq!(*a, *b, epsilon = 1e-6);
+    }
+}
+
+#[test]
+fn invalid_input_is_rejected() {
+    let mut empty: [f64; 0] = [];
+    assert!(!filtfilt(&[low_pass(1000.0)], &mut empty));
+    let mut invalid = low_pass(1000.0);
+    invalid.a0 = 0.0;
+    let mut samples = [1.0, 2.0];
+    assert!(!filtfilt(&[invalid], &mut samples));
+    let mut single = [0.5];
+    let filter = DigitalBiquadFilter::new(low_pass(1000.0)).unwrap();
+    assert!(filtfilt(&[filter.get_coefficients()], &mut single));
+    assert_relative_eq!(single[0], 0.5, epsilon = 1e-12);
+}
+
+#[test]
+fn stream_matches_whole_signal() {
+    use biquad_filters::ZeroPhaseStream;
+
+    let sections = [low_pass(3000.0), low_pass(3000.0)];
+    let input: Vec<f64> = (0..10000)
+        .map(|n| ((n * 7) % 11) as f64 - 5.0 + (n as f64 * 0.001).sin())
+        .collect();
+    let mut expected = input.clone();
+    assert!(filtfilt(&sections, &mut expected));
+
+    let mut stream = ZeroPhaseStream::new(&sections, 256, 1024).unwrap();
+    let mut output = Vec::new();
+    for piece in input.chunks(333) {
+        assert!(stream.process(piece, &mut output));
+        assert!(output.len() <= input.len());
+    }
+    assert!(stream.finish(&mut output));
+    assert_eq!(output.len(), input.len());
+    for (sample, expected) in output.iter().zip(expected.iter()) {
+        assert_relative_eq!(*sample, *expected, epsilon = 1e-9);
+    }
+}
+
+#[test]
+fn stream_handles_short_signals() {
+    use biquad_filters::ZeroPhaseStream;
+
+    let sections = [low_pass(1000.0)];
+    let mut stream = ZeroPhaseStream::new(&sections, 64, 256).unwrap();
+    let mut output = Vec::new();
+    assert!(!stream.finish(&mut output));
+    assert!(stream.process(&[1.0, 2.0, 3.0], &mut output));
+    assert!(output.is_empty());
+    assert!(stream.finish(&mut output));
+    let mut expected = [1.0, 2.0, 3.0];
+    assert!(filtfilt(&sections, &mut expected));
+    assert_eq!(output, expected);
+    assert!(ZeroPhaseStream::new(&sections, 0, 256).is_none());
+}